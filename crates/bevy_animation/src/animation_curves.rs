@@ -101,8 +101,9 @@ use bevy_math::curve::{
     Curve, Interval,
 };
 use bevy_platform_support::hash::Hashed;
-use bevy_reflect::{FromReflect, Reflect, Reflectable, TypeInfo, Typed};
+use bevy_reflect::{FromReflect, NamedField, Reflect, Reflectable, TypeInfo, Typed};
 use bevy_render::mesh::morph::MorphWeights;
+use bevy_transform::components::Transform;
 use downcast_rs::{impl_downcast, Downcast};
 
 /// A value on a component that Bevy can animate.
@@ -398,6 +399,10 @@ where
             });
         Ok(())
     }
+
+    fn sample_value(&self, t: f32) -> Option<Box<dyn Reflect>> {
+        Some(Box::new(self.curve.sample_clamped(t)))
+    }
 }
 
 impl<A: Animatable> AnimationCurveEvaluator for AnimatableCurveEvaluator<A> {
@@ -527,6 +532,12 @@ where
             .push((weight, graph_node));
         Ok(())
     }
+
+    fn sample_value(&self, t: f32) -> Option<Box<dyn Reflect>> {
+        Some(Box::new(
+            self.0.sample_iter_clamped(t).collect::<Vec<f32>>(),
+        ))
+    }
 }
 
 impl WeightsCurveEvaluator {
@@ -814,6 +825,21 @@ pub trait AnimationCurve: Debug + Send + Sync + 'static {
         weight: f32,
         graph_node: AnimationNodeIndex,
     ) -> Result<(), AnimationEvaluationError>;
+
+    /// Samples the curve at the given time `t` outside of the evaluation of an
+    /// animation graph, such as to extract root motion.
+    ///
+    /// Returns `None` if the curve doesn't support being sampled on its own,
+    /// which is the default. Of the curves in this crate, [`AnimatableCurve`]
+    /// returns the value of its property, such as a [`Vec3`] for
+    /// `Transform::translation`, and [`WeightsCurve`] returns the morph target
+    /// weights as a `Vec<f32>`.
+    ///
+    /// [`Vec3`]: bevy_math::Vec3
+    fn sample_value(&self, t: f32) -> Option<Box<dyn Reflect>> {
+        let _ = t;
+        None
+    }
 }
 
 /// The [`EvaluatorId`] is used to look up the [`AnimationCurveEvaluator`] for an [`AnimatableProperty`].
@@ -832,6 +858,30 @@ pub enum EvaluatorId<'a> {
     Type(TypeId),
 }
 
+/// Returns the name of the field of [`Transform`] that `curve` animates, if
+/// any.
+pub(crate) fn transform_field(curve: &dyn AnimationCurve) -> Option<&'static str> {
+    let EvaluatorId::ComponentField(field) = curve.evaluator_id() else {
+        return None;
+    };
+    let (type_id, index) = **field;
+    let TypeInfo::Struct(transform_info) = Transform::type_info() else {
+        return None;
+    };
+    if type_id != TypeId::of::<Transform>() {
+        return None;
+    }
+    transform_info.field_at(index).map(NamedField::name)
+}
+
+/// Returns true if `curve` animates [`MorphWeights`], like a [`WeightsCurve`].
+pub(crate) fn animates_morph_weights(curve: &dyn AnimationCurve) -> bool {
+    matches!(
+        curve.evaluator_id(),
+        EvaluatorId::Type(type_id) if type_id == TypeId::of::<WeightsCurveEvaluator>()
+    )
+}
+
 /// A low-level trait for use in [`crate::VariableCurve`] that provides fine
 /// control over how animations are evaluated.
 ///
@@ -0,0 +1,340 @@
+//! Blend spaces, which compute the weights of the clips of a node of an
+//! [`AnimationGraph`] from an input value, such as the velocity of a
+//! character.
+//!
+//! A blend space places each of its child clips at a coordinate in a one- or
+//! two-dimensional parameter space. Every frame, the input of the blend space
+//! is read from the [`AnimationParameters`] of the player, and the clips
+//! closest to it are given the largest weights. For example, a locomotion
+//! blend space can place an idle clip at a speed of 0, a walk at 1.5 and a
+//! run at 4, and blend them smoothly as the character speeds up.
+//!
+//! Blend spaces blend their children like [blend nodes]. They're usually
+//! combined with a [sync group], so that the steps of their clips line up.
+//!
+//! [blend nodes]: AnimationNodeType::Blend
+//! [sync group]: crate::sync::AnimationSyncMode
+
+use bevy_asset::Assets;
+use bevy_ecs::system::{Query, Res};
+use bevy_math::Vec2;
+use bevy_reflect::{prelude::ReflectDefault, Reflect, ReflectDeserialize, ReflectSerialize};
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+
+use crate::{
+    graph::{AnimationGraph, AnimationGraphHandle, AnimationNodeIndex, AnimationNodeType},
+    state_machine::AnimationParameters,
+    AnimationPlayer,
+};
+
+/// The blend weights of the children of a blend space.
+pub type BlendSpaceWeights = SmallVec<[(AnimationNodeIndex, f32); 8]>;
+
+/// A one-dimensional blend space, which places its child clips along a line.
+///
+/// The two clips surrounding the input are blended linearly. Inputs beyond
+/// the first or the last clip play that clip alone.
+#[derive(Clone, Debug, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Debug, Default, Serialize, Deserialize)]
+pub struct BlendSpace1D {
+    /// The name of the float [`AnimationParameters`] used as the input.
+    pub parameter: String,
+    /// The clips of the blend space.
+    pub points: Vec<BlendSpace1DPoint>,
+}
+
+/// A clip placed in a [`BlendSpace1D`].
+#[derive(Clone, Copy, Debug, Reflect, Serialize, Deserialize)]
+#[reflect(Debug, Serialize, Deserialize)]
+pub struct BlendSpace1DPoint {
+    /// The clip node, which should be a child of the blend space node.
+    pub node: AnimationNodeIndex,
+    /// The coordinate of the clip.
+    pub position: f32,
+}
+
+impl BlendSpace1D {
+    /// Creates a new empty [`BlendSpace1D`] driven by the float parameter
+    /// `parameter`.
+    pub fn new(parameter: impl Into<String>) -> Self {
+        Self {
+            parameter: parameter.into(),
+            points: vec![],
+        }
+    }
+
+    /// Returns the blend weights of the clips for the given `input`.
+    ///
+    /// The weights sum to 1, unless the blend space is empty.
+    pub fn weights(&self, input: f32) -> BlendSpaceWeights {
+        let below = self
+            .points
+            .iter()
+            .filter(|point| point.position <= input)
+            .max_by(|a, b| a.position.total_cmp(&b.position));
+        let above = self
+            .points
+            .iter()
+            .filter(|point| point.position >= input)
+            .min_by(|a, b| a.position.total_cmp(&b.position));
+
+        let mut weights: BlendSpaceWeights =
+            self.points.iter().map(|point| (point.node, 0.0)).collect();
+        let mut set_weight = |node: AnimationNodeIndex, weight: f32| {
+            if let Some((_, slot)) = weights.iter_mut().find(|(other, _)| *other == node) {
+                *slot += weight;
+            }
+        };
+        match (below, above) {
+            (Some(below), Some(above)) if above.position > below.position => {
+                let s = (input - below.position) / (above.position - below.position);
+                set_weight(below.node, 1.0 - s);
+                set_weight(above.node, s);
+            }
+            (Some(point), _) | (None, Some(point)) => set_weight(point.node, 1.0),
+            (None, None) => {}
+        }
+        weights
+    }
+}
+
+/// A two-dimensional blend space, which places its child clips on a plane.
+///
+/// Weights are computed with the gradient band method: each clip is weighted
+/// by how close the input is to it relative to every other clip, which blends
+/// smoothly between arbitrarily placed clips without a triangulation.
+#[derive(Clone, Debug, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Debug, Default, Serialize, Deserialize)]
+pub struct BlendSpace2D {
+    /// The names of the float [`AnimationParameters`] used as the x and y
+    /// coordinates of the input.
+    pub parameters: [String; 2],
+    /// The clips of the blend space.
+    pub points: Vec<BlendSpace2DPoint>,
+}
+
+/// A clip placed in a [`BlendSpace2D`].
+#[derive(Clone, Copy, Debug, Reflect, Serialize, Deserialize)]
+#[reflect(Debug, Serialize, Deserialize)]
+pub struct BlendSpace2DPoint {
+    /// The clip node, which should be a child of the blend space node.
+    pub node: AnimationNodeIndex,
+    /// The coordinates of the clip.
+    pub position: Vec2,
+}
+
+impl BlendSpace2D {
+    /// Creates a new empty [`BlendSpace2D`] driven by the float parameters `x`
+    /// and `y`.
+    pub fn new(x: impl Into<String>, y: impl Into<String>) -> Self {
+        Self {
+            parameters: [x.into(), y.into()],
+            points: vec![],
+        }
+    }
+
+    /// Returns the blend weights of the clips for the given `input`.
+    ///
+    /// The weights sum to 1, unless the blend space is empty.
+    pub fn weights(&self, input: Vec2) -> BlendSpaceWeights {
+        let mut weights: BlendSpaceWeights = self
+            .points
+            .iter()
+            .map(|point| {
+                let offset = input - point.position;
+                let weight = self
+                    .points
+                    .iter()
+                    .filter(|other| other.position != point.position)
+                    .map(|other| {
+                        let edge = other.position - point.position;
+                        (1.0 - offset.dot(edge) / edge.length_squared()).clamp(0.0, 1.0)
+                    })
+                    .fold(1.0, f32::min);
+                (point.node, weight)
+            })
+            .collect();
+
+        let total: f32 = weights.iter().map(|(_, weight)| weight).sum();
+        if total > 0.0 {
+            for (_, weight) in &mut weights {
+                *weight /= total;
+            }
+        }
+        weights
+    }
+}
+
+/// A system that plays the children of the blend spaces being played by each
+/// [`AnimationPlayer`], with the weights computed from its
+/// [`AnimationParameters`].
+///
+/// The weight of each child is the weight computed by the blend space
+/// multiplied by the weight of the blend space node in the player, so that
+/// transitions into and out of blend spaces fade their clips. Children start
+/// with the repeat mode and speed of their blend space, and are stopped when it
+/// stops.
+pub fn update_blend_spaces(
+    animation_graphs: Res<Assets<AnimationGraph>>,
+    mut players: Query<(
+        &mut AnimationPlayer,
+        &AnimationGraphHandle,
+        Option<&AnimationParameters>,
+    )>,
+) {
+    for (mut player, graph_handle, parameters) in &mut players {
+        let Some(animation_graph) = animation_graphs.get(graph_handle) else {
+            continue;
+        };
+        let float = |name: &str| parameters.map_or(0.0, |parameters| parameters.float(name));
+
+        for node_index in animation_graph.nodes() {
+            let weights = match animation_graph[node_index].node_type {
+                AnimationNodeType::BlendSpace1D(ref blend_space) => {
+                    blend_space.weights(float(&blend_space.parameter))
+                }
+                AnimationNodeType::BlendSpace2D(ref blend_space) => {
+                    let [x, y] = &blend_space.parameters;
+                    blend_space.weights(Vec2::new(float(x), float(y)))
+                }
+                _ => continue,
+            };
+
+            let Some(blend_space) = player.active_animations.get(&node_index).copied() else {
+                for (child, _) in weights {
+                    if player.is_playing_animation(child) {
+                        player.stop(child);
+                    }
+                }
+                continue;
+            };
+            for (child, weight) in weights {
+                let started = !player.is_playing_animation(child);
+                let active_animation = player.play(child);
+                if started {
+                    active_animation
+                        .set_repeat(blend_space.repeat_mode())
+                        .set_speed(blend_space.speed());
+                }
+                active_animation.set_weight(blend_space.weight() * weight);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::weak_handle;
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+
+    use super::*;
+    use crate::RepeatAnimation;
+
+    fn node(index: u32) -> AnimationNodeIndex {
+        AnimationNodeIndex::new(index as usize)
+    }
+
+    fn weight_of(weights: &BlendSpaceWeights, index: u32) -> f32 {
+        weights
+            .iter()
+            .find(|(other, _)| *other == node(index))
+            .map_or(0.0, |(_, weight)| *weight)
+    }
+
+    #[test]
+    fn blend_space_1d_weights() {
+        let mut blend_space = BlendSpace1D::new("speed");
+        for (index, position) in [(1, 0.0), (2, 1.5), (3, 4.0)] {
+            blend_space.points.push(BlendSpace1DPoint {
+                node: node(index),
+                position,
+            });
+        }
+
+        let weights = blend_space.weights(2.75);
+        assert_eq!(weight_of(&weights, 1), 0.0);
+        assert!((weight_of(&weights, 2) - 0.5).abs() < 1e-5);
+        assert!((weight_of(&weights, 3) - 0.5).abs() < 1e-5);
+
+        assert_eq!(weight_of(&blend_space.weights(-1.0), 1), 1.0);
+        assert_eq!(weight_of(&blend_space.weights(1.5), 2), 1.0);
+        assert_eq!(weight_of(&blend_space.weights(10.0), 3), 1.0);
+    }
+
+    #[test]
+    fn blend_space_2d_weights() {
+        let mut blend_space = BlendSpace2D::new("x", "y");
+        for (index, position) in [
+            (1, Vec2::ZERO),
+            (2, Vec2::X),
+            (3, Vec2::NEG_X),
+            (4, Vec2::Y),
+        ] {
+            blend_space.points.push(BlendSpace2DPoint {
+                node: node(index),
+                position,
+            });
+        }
+
+        // An input on a clip plays that clip alone.
+        let weights = blend_space.weights(Vec2::X);
+        assert!((weight_of(&weights, 2) - 1.0).abs() < 1e-5);
+
+        // Weights always sum to 1 and favor the nearest clips.
+        let weights = blend_space.weights(Vec2::new(0.5, 0.25));
+        let total: f32 = weights.iter().map(|(_, weight)| weight).sum();
+        assert!((total - 1.0).abs() < 1e-5);
+        assert!(weight_of(&weights, 2) > weight_of(&weights, 4));
+        assert_eq!(weight_of(&weights, 3), 0.0);
+    }
+
+    #[test]
+    fn update_blend_space_children() {
+        let mut graph = AnimationGraph::new();
+        let locomotion = graph.add_blend_space_1d("speed", 1.0, graph.root);
+        let walk = graph
+            .add_blend_space_clip_1d(
+                weak_handle!("00000000-0000-0000-0000-000000000001"),
+                0.0,
+                locomotion,
+            )
+            .unwrap();
+        let run = graph
+            .add_blend_space_clip_1d(
+                weak_handle!("00000000-0000-0000-0000-000000000002"),
+                2.0,
+                locomotion,
+            )
+            .unwrap();
+        let mut graphs = Assets::<AnimationGraph>::default();
+        let graph = graphs.add(graph);
+        let mut world = World::new();
+        world.insert_resource(graphs);
+
+        let mut player = AnimationPlayer::default();
+        player.play(locomotion).set_weight(0.5).repeat();
+        let mut parameters = AnimationParameters::default();
+        parameters.set_float("speed", 0.5);
+        let entity = world
+            .spawn((player, AnimationGraphHandle(graph), parameters))
+            .id();
+
+        world.run_system_once(update_blend_spaces).unwrap();
+        let player = world.get::<AnimationPlayer>(entity).unwrap();
+        let walk_animation = player.animation(walk).unwrap();
+        assert!((walk_animation.weight() - 0.375).abs() < 1e-5);
+        assert_eq!(walk_animation.repeat_mode(), RepeatAnimation::Forever);
+        assert!((player.animation(run).unwrap().weight() - 0.125).abs() < 1e-5);
+
+        // Stopping the blend space stops its children.
+        world
+            .get_mut::<AnimationPlayer>(entity)
+            .unwrap()
+            .stop(locomotion);
+        world.run_system_once(update_blend_spaces).unwrap();
+        let player = world.get::<AnimationPlayer>(entity).unwrap();
+        assert!(!player.is_playing_animation(walk));
+        assert!(!player.is_playing_animation(run));
+    }
+}
@@ -0,0 +1,1170 @@
+//! Animation compression, which reduces the memory used by the curves of
+//! [`AnimationClip`]s.
+//!
+//! Clips imported from glTF store every keyframe at full `f32` precision.
+//! Compression resamples the curves animating the [`Transform`] and the
+//! [`MorphWeights`] of each target, and then:
+//!
+//! * Replaces tracks whose value never changes by more than the tolerance with
+//!   a single constant value.
+//!
+//! * Removes the keyframes that can be recovered by interpolating their
+//!   neighbors, keeping the error below the tolerance of the track.
+//!
+//! * Quantizes the remaining keyframes: times to the indices of the samples
+//!   they were taken from, translations and scales to 16 or 32 bits per
+//!   component within the range of their track, and rotations to 64 bits
+//!   using the "smallest three" encoding. Morph target weights are kept at
+//!   full precision.
+//!
+//! Keyframe reduction measures the error of the quantized keyframes, so the
+//! compressed curves stay within the tolerances at every sample.
+//!
+//! Clips can be compressed at runtime with
+//! [`AnimationCompressionSettings::compress`], or ahead of time by the asset
+//! processor with the [`AnimationClipCompressor`] transformer, whose output is
+//! saved by the [`CompressedAnimationClipSaver`] and loaded back by the
+//! [`CompressedAnimationClipLoader`].
+//!
+//! [`MorphWeights`]: bevy_render::mesh::morph::MorphWeights
+
+use core::fmt::Debug;
+use std::io;
+
+use bevy_asset::{
+    io::{Reader, Writer},
+    saver::{AssetSaver, SavedAsset},
+    transformer::{AssetTransformer, TransformedAsset},
+    Asset, AssetLoader, AsyncWriteExt, LoadContext,
+};
+use bevy_math::{
+    curve::{iterable::IterableCurve, ConstantCurve, Curve, Interval},
+    ops, Quat, Vec3,
+};
+use bevy_reflect::{Reflect, TypePath};
+use bevy_transform::components::Transform;
+use ron::de::SpannedError;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    animatable::Animatable,
+    animated_field,
+    animation_curves::{
+        animates_morph_weights, transform_field, AnimatableCurve, AnimatableProperty,
+        AnimatedField, AnimationCompatibleCurve, AnimationCurve, WeightsCurve,
+    },
+    AnimationClip, AnimationTargetId, VariableCurve,
+};
+
+/// The largest number of steps a component of a quantized [`Vec3`] is divided
+/// into, beyond which the steps are finer than the precision of an `f32`.
+const MAX_VEC3_STEPS: u32 = 1 << 24;
+
+/// The number of bits of each component of a quantized [`Quat`].
+const QUAT_COMPONENT_BITS: u32 = 20;
+
+/// The largest value of a quantized component of a [`Quat`].
+const MAX_QUANTIZED_QUAT_COMPONENT: u64 = (1 << QUAT_COMPONENT_BITS) - 1;
+
+/// The largest magnitude of the three smallest components of a unit
+/// quaternion.
+const QUAT_COMPONENT_RANGE: f32 = core::f32::consts::FRAC_1_SQRT_2;
+
+/// The settings of animation compression.
+///
+/// The tolerances bound the error of the compressed curves at each sample,
+/// including the error of quantization. Translations and scales are quantized
+/// finely enough for any tolerance above the precision of an `f32`, while
+/// quantized rotations are only precise to about `1e-5` radians, so smaller
+/// rotation tolerances can't be met.
+#[derive(Clone, Copy, Debug, Reflect, Serialize, Deserialize)]
+pub struct AnimationCompressionSettings {
+    /// The number of samples per second at which curves are resampled before
+    /// keyframe reduction.
+    pub sample_rate: f32,
+    /// The maximum distance between a compressed and an original translation.
+    pub translation_tolerance: f32,
+    /// The maximum angle, in radians, between a compressed and an original
+    /// rotation.
+    pub rotation_tolerance: f32,
+    /// The maximum distance between a compressed and an original scale.
+    pub scale_tolerance: f32,
+    /// The maximum difference between a compressed and an original morph
+    /// target weight.
+    pub weight_tolerance: f32,
+}
+
+impl Default for AnimationCompressionSettings {
+    fn default() -> Self {
+        Self {
+            sample_rate: 60.0,
+            translation_tolerance: 1e-4,
+            rotation_tolerance: 1e-3,
+            scale_tolerance: 1e-4,
+            weight_tolerance: 1e-3,
+        }
+    }
+}
+
+impl AnimationCompressionSettings {
+    /// Returns a compressed copy of `clip`.
+    ///
+    /// Curves animating the translation, rotation and scale of a
+    /// [`Transform`], or morph target weights, are compressed. Other curves,
+    /// and the events of the clip, are copied unchanged.
+    pub fn compress(&self, clip: &AnimationClip) -> AnimationClip {
+        let mut compressed = AnimationClip {
+            duration: clip.duration,
+            sync_markers: clip.sync_markers.clone(),
+            events: clip.events.clone(),
+            ..AnimationClip::default()
+        };
+        for (target, curves) in &clip.curves {
+            let curves = curves
+                .iter()
+                .map(|curve| {
+                    self.compress_curve(&*curve.0, clip.duration)
+                        .map_or_else(|| curve.clone(), |track| track.to_curve())
+                })
+                .collect();
+            compressed.curves.insert(*target, curves);
+        }
+        compressed
+    }
+
+    /// Compresses `clip` into a [`CompressedAnimationClip`], which can be
+    /// saved by the asset processor.
+    ///
+    /// Curves animating the translation, rotation and scale of a
+    /// [`Transform`], or morph target weights, are serialized, which covers
+    /// the clips imported from glTF. Other curves and events hold arbitrary
+    /// code that can't be serialized, so this fails if `clip` has any.
+    pub fn compress_to_serializable(
+        &self,
+        clip: &AnimationClip,
+    ) -> Result<CompressedAnimationClip, AnimationCompressionError> {
+        if clip.events.values().any(|events| !events.is_empty()) {
+            return Err(AnimationCompressionError::Events);
+        }
+        let mut tracks = clip
+            .curves
+            .iter()
+            .map(|(target, curves)| {
+                let curves = curves
+                    .iter()
+                    .map(|curve| {
+                        self.compress_curve(&*curve.0, clip.duration)
+                            .ok_or(AnimationCompressionError::UnsupportedCurve(*target))
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok((*target, curves))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        tracks.sort_by_key(|(target, _)| *target);
+        Ok(CompressedAnimationClip {
+            duration: clip.duration,
+            sync_markers: clip.sync_markers.clone(),
+            tracks,
+        })
+    }
+
+    /// Returns a compressed track of `curve`, or `None` if it doesn't animate
+    /// a [`Transform`] or morph target weights, or can't be sampled.
+    fn compress_curve(&self, curve: &dyn AnimationCurve, duration: f32) -> Option<TargetTrack> {
+        if animates_morph_weights(curve) {
+            return Some(TargetTrack::Weights(self.compress_track(
+                curve,
+                duration,
+                self.weight_tolerance,
+            )?));
+        }
+        Some(match transform_field(curve)? {
+            "translation" => TargetTrack::Translation(self.compress_track(
+                curve,
+                duration,
+                self.translation_tolerance,
+            )?),
+            "rotation" => TargetTrack::Rotation(self.compress_track(
+                curve,
+                duration,
+                self.rotation_tolerance,
+            )?),
+            "scale" => {
+                TargetTrack::Scale(self.compress_track(curve, duration, self.scale_tolerance)?)
+            }
+            _ => return None,
+        })
+    }
+
+    /// Resamples `curve` over the clip, and detects constant tracks or reduces
+    /// and quantizes the keyframes of the samples.
+    fn compress_track<T: Reflect + Clone, C: QuantizedCurve<T>>(
+        &self,
+        curve: &dyn AnimationCurve,
+        duration: f32,
+        tolerance: f32,
+    ) -> Option<CompressedTrack<T, C>> {
+        let sample = |t: f32| -> Option<T> {
+            curve
+                .sample_value(t)?
+                .downcast::<T>()
+                .ok()
+                .map(|value| *value)
+        };
+
+        let domain = curve.domain();
+        let start = domain.start().max(0.0);
+        let end = domain.end().min(duration);
+        let domain = match Interval::new(start, end) {
+            Ok(domain) if domain.length() > 0.0 => domain,
+            _ => return Some(CompressedTrack::Constant(sample(start.min(end))?)),
+        };
+
+        let count = ((domain.length() * self.sample_rate).ceil() as usize).max(1) + 1;
+        let time = |index: usize| start + domain.length() * index as f32 / (count - 1) as f32;
+        let samples = (0..count)
+            .map(|index| sample(time(index)))
+            .collect::<Option<Vec<T>>>()?;
+
+        if samples
+            .iter()
+            .all(|value| C::error(&samples[0], value) <= tolerance)
+        {
+            return Some(CompressedTrack::Constant(samples[0].clone()));
+        }
+
+        // Interpolate between the keyframes as they are decoded after
+        // quantization, so its error is part of the error of the reduction.
+        let quantizer = C::quantizer(&samples, tolerance);
+        let indices = reduce_keyframes(
+            &samples,
+            tolerance,
+            |a, b, s| {
+                C::interpolate(
+                    &C::round_trip(&quantizer, a),
+                    &C::round_trip(&quantizer, b),
+                    s,
+                )
+            },
+            C::error,
+        );
+        let keyframes = ReducedKeyframes {
+            times: KeyframeTimes::new(domain, count - 1, &indices),
+            values: indices
+                .iter()
+                .map(|&index| samples[index].clone())
+                .collect(),
+        };
+        Some(CompressedTrack::Keyframes(C::new(quantizer, keyframes)))
+    }
+}
+
+/// The result of compressing a track, before or after quantization.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+enum CompressedTrack<T, K> {
+    /// The track holds a single value.
+    Constant(T),
+    /// The track is defined by the remaining keyframes.
+    Keyframes(K),
+}
+
+impl<T, C> CompressedTrack<T, C>
+where
+    T: Animatable + Clone + Debug,
+    ConstantCurve<T>: AnimationCompatibleCurve<T>,
+    C: AnimationCompatibleCurve<T> + Clone,
+{
+    /// Returns a curve animating `property` with the track.
+    fn to_curve<P>(&self, property: P) -> VariableCurve
+    where
+        P: AnimatableProperty<Property = T> + Clone,
+    {
+        match self {
+            CompressedTrack::Constant(value) => VariableCurve::new(AnimatableCurve::new(
+                property,
+                ConstantCurve::new(Interval::EVERYWHERE, value.clone()),
+            )),
+            CompressedTrack::Keyframes(curve) => {
+                VariableCurve::new(AnimatableCurve::new(property, curve.clone()))
+            }
+        }
+    }
+}
+
+/// A compressed track animating a field of the [`Transform`] of a target, or
+/// its morph target weights.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+enum TargetTrack {
+    Translation(CompressedTrack<Vec3, QuantizedVec3Curve>),
+    Rotation(CompressedTrack<Quat, QuantizedQuatCurve>),
+    Scale(CompressedTrack<Vec3, QuantizedVec3Curve>),
+    Weights(CompressedTrack<Vec<f32>, CompressedWeightsCurve>),
+}
+
+impl TargetTrack {
+    fn to_curve(&self) -> VariableCurve {
+        match self {
+            TargetTrack::Translation(track) => {
+                track.to_curve(animated_field!(Transform::translation))
+            }
+            TargetTrack::Rotation(track) => track.to_curve(animated_field!(Transform::rotation)),
+            TargetTrack::Scale(track) => track.to_curve(animated_field!(Transform::scale)),
+            TargetTrack::Weights(CompressedTrack::Constant(weights)) => VariableCurve::new(
+                WeightsCurve(ConstantCurve::new(Interval::EVERYWHERE, weights.clone())),
+            ),
+            TargetTrack::Weights(CompressedTrack::Keyframes(curve)) => {
+                VariableCurve::new(WeightsCurve(curve.clone()))
+            }
+        }
+    }
+}
+
+/// The keyframes of a track that remain after keyframe reduction.
+struct ReducedKeyframes<T> {
+    times: KeyframeTimes,
+    values: Vec<T>,
+}
+
+/// A curve of quantized keyframes, built from the keyframes of a track that
+/// remain after keyframe reduction.
+trait QuantizedCurve<T>: Sized {
+    /// The parameters used to quantize the values of a track.
+    type Quantizer;
+
+    /// Returns the parameters used to quantize the values of a track with the
+    /// given `samples`, whose error should be at most half of `tolerance`.
+    fn quantizer(samples: &[T], tolerance: f32) -> Self::Quantizer;
+
+    /// Returns `value` as it is decoded after being quantized with `quantizer`.
+    fn round_trip(quantizer: &Self::Quantizer, value: &T) -> T;
+
+    /// Returns a curve of the given `keyframes`, quantized with `quantizer`.
+    fn new(quantizer: Self::Quantizer, keyframes: ReducedKeyframes<T>) -> Self;
+
+    /// Interpolates between the values `a` and `b` like the curve does.
+    fn interpolate(a: &T, b: &T, s: f32) -> T;
+
+    /// Returns the error between the values `a` and `b`.
+    fn error(a: &T, b: &T) -> f32;
+}
+
+/// An [`AnimationClip`] whose curves were compressed by
+/// [`AnimationCompressionSettings::compress_to_serializable`], in a form that
+/// can be serialized.
+///
+/// This is the output of the [`AnimationClipCompressor`] transformer, which is
+/// saved by the [`CompressedAnimationClipSaver`] and loaded back as an
+/// [`AnimationClip`] by the [`CompressedAnimationClipLoader`].
+#[derive(Asset, TypePath, Clone, Debug, Serialize, Deserialize)]
+pub struct CompressedAnimationClip {
+    duration: f32,
+    sync_markers: Vec<f32>,
+    tracks: Vec<(AnimationTargetId, Vec<TargetTrack>)>,
+}
+
+impl CompressedAnimationClip {
+    /// Returns an [`AnimationClip`] that plays the compressed curves.
+    pub fn to_clip(&self) -> AnimationClip {
+        let mut clip = AnimationClip {
+            duration: self.duration,
+            sync_markers: self.sync_markers.clone(),
+            ..AnimationClip::default()
+        };
+        for (target, tracks) in &self.tracks {
+            clip.curves
+                .insert(*target, tracks.iter().map(TargetTrack::to_curve).collect());
+        }
+        clip
+    }
+}
+
+/// An error that prevents an [`AnimationClip`] from being compressed into a
+/// [`CompressedAnimationClip`].
+#[derive(Error, Debug)]
+pub enum AnimationCompressionError {
+    /// A curve of the target doesn't animate the translation, rotation or
+    /// scale of a [`Transform`], or morph target weights, so it can't be
+    /// serialized.
+    #[error("a curve of the target {0:?} doesn't animate a `Transform` or morph target weights, so it can't be serialized")]
+    UnsupportedCurve(AnimationTargetId),
+    /// The clip has events, which can't be serialized.
+    #[error("the clip has events, which can't be serialized")]
+    Events,
+}
+
+/// Returns the indices of the evenly spaced `samples` that must be kept as
+/// keyframes, so that interpolating between them never differs from the
+/// removed samples by more than `tolerance`.
+///
+/// The first and last samples are always kept.
+fn reduce_keyframes<T>(
+    samples: &[T],
+    tolerance: f32,
+    interpolate: impl Fn(&T, &T, f32) -> T,
+    error: impl Fn(&T, &T) -> f32,
+) -> Vec<usize> {
+    let mut keyframes = vec![0];
+    let mut anchor = 0;
+    for end in 2..samples.len() {
+        let fits = (anchor + 1..end).all(|index| {
+            let s = (index - anchor) as f32 / (end - anchor) as f32;
+            error(
+                &interpolate(&samples[anchor], &samples[end], s),
+                &samples[index],
+            ) <= tolerance
+        });
+        if !fits {
+            anchor = end - 1;
+            keyframes.push(anchor);
+        }
+    }
+    if samples.len() > 1 {
+        keyframes.push(samples.len() - 1);
+    }
+    keyframes
+}
+
+/// Unsigned integers stored in 16 bits each if they all fit, and in 32 bits
+/// otherwise.
+#[derive(Clone, Debug, Reflect, Serialize, Deserialize)]
+enum PackedInts {
+    U16(Vec<u16>),
+    U32(Vec<u32>),
+}
+
+impl PackedInts {
+    fn new(values: Vec<u32>) -> Self {
+        match values.iter().map(|&value| u16::try_from(value)).collect() {
+            Ok(values) => PackedInts::U16(values),
+            Err(_) => PackedInts::U32(values),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            PackedInts::U16(values) => values.len(),
+            PackedInts::U32(values) => values.len(),
+        }
+    }
+
+    fn get(&self, index: usize) -> u32 {
+        match self {
+            PackedInts::U16(values) => u32::from(values[index]),
+            PackedInts::U32(values) => values[index],
+        }
+    }
+
+    fn partition_point(&self, pred: impl Fn(u32) -> bool) -> usize {
+        match self {
+            PackedInts::U16(values) => values.partition_point(|&value| pred(u32::from(value))),
+            PackedInts::U32(values) => values.partition_point(|&value| pred(value)),
+        }
+    }
+}
+
+/// The times of the keyframes of a curve, stored as the indices of the evenly
+/// spaced samples they were taken from, so they are exact.
+#[derive(Clone, Debug, Reflect, Serialize, Deserialize)]
+struct KeyframeTimes {
+    domain: Interval,
+    /// The number of intervals between the samples.
+    intervals: u32,
+    indices: PackedInts,
+}
+
+impl KeyframeTimes {
+    fn new(domain: Interval, intervals: usize, indices: &[usize]) -> Self {
+        Self {
+            domain,
+            intervals: intervals as u32,
+            indices: PackedInts::new(indices.iter().map(|&index| index as u32).collect()),
+        }
+    }
+
+    /// Returns the indices of the keyframes surrounding `t`, and the
+    /// interpolation parameter between them.
+    fn segment(&self, t: f32) -> (usize, usize, f32) {
+        // Long clips have more samples than an `f32` can locate precisely.
+        let s = (f64::from(t) - f64::from(self.domain.start())) / f64::from(self.domain.length());
+        let position = s.clamp(0.0, 1.0) * f64::from(self.intervals);
+        let next = self
+            .indices
+            .partition_point(|index| f64::from(index) <= position);
+        if next == 0 {
+            return (0, 0, 0.0);
+        }
+        if next == self.indices.len() {
+            return (next - 1, next - 1, 0.0);
+        }
+        let start = f64::from(self.indices.get(next - 1));
+        let end = f64::from(self.indices.get(next));
+        (next - 1, next, ((position - start) / (end - start)) as f32)
+    }
+}
+
+/// A compressed curve of [`Vec3`] keyframes, such as translations or scales.
+///
+/// Keyframe times are stored as the indices of the samples they were taken
+/// from, and each component of the values is quantized within the range of
+/// the values, to 16 bits or to 32 bits when the tolerance requires it. Values
+/// are linearly interpolated.
+///
+/// These curves are produced by [`AnimationCompressionSettings::compress`],
+/// and are wrapped in an [`AnimatableCurve`] to animate a property.
+#[derive(Clone, Debug, Reflect, Serialize, Deserialize)]
+pub struct QuantizedVec3Curve {
+    times: KeyframeTimes,
+    quantizer: Vec3Quantizer,
+    /// The three quantized components of each value.
+    values: PackedInts,
+}
+
+/// The quantization of the [`Vec3`] values of a track.
+#[derive(Clone, Copy, Debug, Reflect, Serialize, Deserialize)]
+struct Vec3Quantizer {
+    min: Vec3,
+    extent: Vec3,
+    /// The number of steps the range of each component is divided into.
+    steps: u32,
+}
+
+impl Vec3Quantizer {
+    fn quantize(&self, value: Vec3) -> [u32; 3] {
+        let s = Vec3::select(
+            self.extent.cmpgt(Vec3::ZERO),
+            (value - self.min) / self.extent,
+            Vec3::ZERO,
+        );
+        (s.clamp(Vec3::ZERO, Vec3::ONE) * self.steps as f32)
+            .round()
+            .as_uvec3()
+            .to_array()
+    }
+
+    fn dequantize(&self, [x, y, z]: [u32; 3]) -> Vec3 {
+        let s = Vec3::new(x as f32, y as f32, z as f32) / self.steps as f32;
+        self.min + s * self.extent
+    }
+}
+
+impl QuantizedCurve<Vec3> for QuantizedVec3Curve {
+    type Quantizer = Vec3Quantizer;
+
+    fn quantizer(samples: &[Vec3], tolerance: f32) -> Vec3Quantizer {
+        let (min, max) = samples
+            .iter()
+            .fold((Vec3::INFINITY, Vec3::NEG_INFINITY), |(min, max), value| {
+                (min.min(*value), max.max(*value))
+            });
+        let extent = max - min;
+        // Each component is off by at most half a step, so a value is off by
+        // at most half the length of a step along every axis.
+        let steps = (extent.length() / tolerance)
+            .ceil()
+            .clamp(1.0, MAX_VEC3_STEPS as f32);
+        Vec3Quantizer {
+            min,
+            extent,
+            steps: steps as u32,
+        }
+    }
+
+    fn round_trip(quantizer: &Vec3Quantizer, value: &Vec3) -> Vec3 {
+        quantizer.dequantize(quantizer.quantize(*value))
+    }
+
+    fn new(quantizer: Vec3Quantizer, keyframes: ReducedKeyframes<Vec3>) -> Self {
+        let values = keyframes
+            .values
+            .iter()
+            .flat_map(|value| quantizer.quantize(*value))
+            .collect();
+        Self {
+            times: keyframes.times,
+            quantizer,
+            values: PackedInts::new(values),
+        }
+    }
+
+    fn interpolate(a: &Vec3, b: &Vec3, s: f32) -> Vec3 {
+        a.lerp(*b, s)
+    }
+
+    fn error(a: &Vec3, b: &Vec3) -> f32 {
+        a.distance(*b)
+    }
+}
+
+impl QuantizedVec3Curve {
+    fn value(&self, index: usize) -> Vec3 {
+        self.quantizer.dequantize([
+            self.values.get(3 * index),
+            self.values.get(3 * index + 1),
+            self.values.get(3 * index + 2),
+        ])
+    }
+}
+
+impl Curve<Vec3> for QuantizedVec3Curve {
+    #[inline]
+    fn domain(&self) -> Interval {
+        self.times.domain
+    }
+
+    #[inline]
+    fn sample_clamped(&self, t: f32) -> Vec3 {
+        let (start, end, s) = self.times.segment(t);
+        self.value(start).lerp(self.value(end), s)
+    }
+
+    #[inline]
+    fn sample_unchecked(&self, t: f32) -> Vec3 {
+        self.sample_clamped(t)
+    }
+}
+
+/// A compressed curve of [`Quat`] keyframes.
+///
+/// Keyframe times are stored as the indices of the samples they were taken
+/// from, and rotations are quantized to 64 bits using the "smallest three"
+/// encoding: the index of the largest component, and the three others
+/// quantized to 20 bits each.
+/// Rotations are spherically interpolated.
+///
+/// These curves are produced by [`AnimationCompressionSettings::compress`],
+/// and are wrapped in an [`AnimatableCurve`] to animate a property.
+#[derive(Clone, Debug, Reflect, Serialize, Deserialize)]
+pub struct QuantizedQuatCurve {
+    times: KeyframeTimes,
+    values: Vec<u64>,
+}
+
+impl QuantizedCurve<Quat> for QuantizedQuatCurve {
+    /// Rotations are always quantized with the same precision.
+    type Quantizer = ();
+
+    fn quantizer(_: &[Quat], _: f32) {}
+
+    fn round_trip(_: &(), value: &Quat) -> Quat {
+        dequantize_quat(quantize_quat(*value))
+    }
+
+    fn new(_: (), keyframes: ReducedKeyframes<Quat>) -> Self {
+        Self {
+            times: keyframes.times,
+            values: keyframes
+                .values
+                .iter()
+                .copied()
+                .map(quantize_quat)
+                .collect(),
+        }
+    }
+
+    fn interpolate(a: &Quat, b: &Quat, s: f32) -> Quat {
+        a.slerp(*b, s)
+    }
+
+    fn error(a: &Quat, b: &Quat) -> f32 {
+        a.angle_between(*b)
+    }
+}
+
+impl Curve<Quat> for QuantizedQuatCurve {
+    #[inline]
+    fn domain(&self) -> Interval {
+        self.times.domain
+    }
+
+    #[inline]
+    fn sample_clamped(&self, t: f32) -> Quat {
+        let (start, end, s) = self.times.segment(t);
+        dequantize_quat(self.values[start]).slerp(dequantize_quat(self.values[end]), s)
+    }
+
+    #[inline]
+    fn sample_unchecked(&self, t: f32) -> Quat {
+        self.sample_clamped(t)
+    }
+}
+
+/// A compressed curve of morph target weights.
+///
+/// Keyframe times are stored as the indices of the samples they were taken
+/// from, and the weights at full precision. Weights are linearly interpolated.
+///
+/// These curves are produced by [`AnimationCompressionSettings::compress`],
+/// and are wrapped in a [`WeightsCurve`] to animate morph targets.
+#[derive(Clone, Debug, Reflect, Serialize, Deserialize)]
+pub struct CompressedWeightsCurve {
+    times: KeyframeTimes,
+    /// The number of weights of each keyframe.
+    width: usize,
+    values: Vec<f32>,
+}
+
+impl CompressedWeightsCurve {
+    fn weights(&self, index: usize) -> &[f32] {
+        &self.values[index * self.width..(index + 1) * self.width]
+    }
+}
+
+impl QuantizedCurve<Vec<f32>> for CompressedWeightsCurve {
+    /// Weights aren't quantized.
+    type Quantizer = ();
+
+    fn quantizer(_: &[Vec<f32>], _: f32) {}
+
+    fn round_trip(_: &(), value: &Vec<f32>) -> Vec<f32> {
+        value.clone()
+    }
+
+    fn new(_: (), keyframes: ReducedKeyframes<Vec<f32>>) -> Self {
+        Self {
+            times: keyframes.times,
+            width: keyframes.values.first().map_or(0, Vec::len),
+            values: keyframes.values.concat(),
+        }
+    }
+
+    fn interpolate(a: &Vec<f32>, b: &Vec<f32>, s: f32) -> Vec<f32> {
+        a.iter().zip(b).map(|(a, b)| a + (b - a) * s).collect()
+    }
+
+    fn error(a: &Vec<f32>, b: &Vec<f32>) -> f32 {
+        a.iter()
+            .zip(b)
+            .map(|(a, b)| (a - b).abs())
+            .fold(0.0, f32::max)
+    }
+}
+
+impl IterableCurve<f32> for CompressedWeightsCurve {
+    #[inline]
+    fn domain(&self) -> Interval {
+        self.times.domain
+    }
+
+    #[inline]
+    fn sample_iter_unchecked(&self, t: f32) -> impl Iterator<Item = f32> {
+        let (start, end, s) = self.times.segment(t);
+        self.weights(start)
+            .iter()
+            .zip(self.weights(end))
+            .map(move |(a, b)| a + (b - a) * s)
+    }
+}
+
+/// Packs a rotation into 64 bits with the "smallest three" encoding.
+fn quantize_quat(rotation: Quat) -> u64 {
+    let components = rotation.normalize().to_array();
+    let largest = (0..4)
+        .max_by(|&a, &b| components[a].abs().total_cmp(&components[b].abs()))
+        .unwrap_or(3);
+    // `q` and `-q` are the same rotation, so the largest component can be
+    // made positive and recovered from the others.
+    let sign = components[largest].signum();
+    (0..4)
+        .filter(|&index| index != largest)
+        .fold(largest as u64, |packed, index| {
+            let s = (components[index] * sign / QUAT_COMPONENT_RANGE).clamp(-1.0, 1.0) * 0.5 + 0.5;
+            let quantized = ops::round(s * MAX_QUANTIZED_QUAT_COMPONENT as f32) as u64;
+            (packed << QUAT_COMPONENT_BITS) | quantized
+        })
+}
+
+/// Unpacks a rotation packed by [`quantize_quat`].
+fn dequantize_quat(packed: u64) -> Quat {
+    let largest = (packed >> (3 * QUAT_COMPONENT_BITS)) as usize;
+    let mut components = [0.0; 4];
+    let mut sum_of_squares = 0.0;
+    let mut shift = 3 * QUAT_COMPONENT_BITS;
+    for (index, component) in components.iter_mut().enumerate() {
+        if index == largest {
+            continue;
+        }
+        shift -= QUAT_COMPONENT_BITS;
+        let quantized = (packed >> shift) & MAX_QUANTIZED_QUAT_COMPONENT;
+        let s = quantized as f32 / MAX_QUANTIZED_QUAT_COMPONENT as f32;
+        *component = (s * 2.0 - 1.0) * QUAT_COMPONENT_RANGE;
+        sum_of_squares += *component * *component;
+    }
+    components[largest] = ops::sqrt((1.0 - sum_of_squares).max(0.0));
+    Quat::from_array(components).normalize()
+}
+
+/// An [`AssetTransformer`] that compresses [`AnimationClip`]s in the asset
+/// processor, using [`AnimationCompressionSettings`] as its settings.
+///
+/// The compressed clips are saved with the [`CompressedAnimationClipSaver`],
+/// for example with a [`LoadTransformAndSave`] processor:
+///
+/// ```
+/// # use bevy_animation::compression::*;
+/// # use bevy_asset::{processor::LoadTransformAndSave, AssetLoader};
+/// # fn processor<L: AssetLoader<Asset = bevy_animation::AnimationClip>>() {
+/// type CompressAnimationClips<L> =
+///     LoadTransformAndSave<L, AnimationClipCompressor, CompressedAnimationClipSaver>;
+/// # }
+/// ```
+///
+/// [`LoadTransformAndSave`]: bevy_asset::processor::LoadTransformAndSave
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AnimationClipCompressor;
+
+impl AssetTransformer for AnimationClipCompressor {
+    type AssetInput = AnimationClip;
+    type AssetOutput = CompressedAnimationClip;
+    type Settings = AnimationCompressionSettings;
+    type Error = AnimationCompressionError;
+
+    async fn transform<'a>(
+        &'a self,
+        asset: TransformedAsset<Self::AssetInput>,
+        settings: &'a Self::Settings,
+    ) -> Result<TransformedAsset<Self::AssetOutput>, Self::Error> {
+        let compressed = settings.compress_to_serializable(asset.get())?;
+        Ok(asset.replace_asset(compressed))
+    }
+}
+
+/// An [`AssetSaver`] that saves [`CompressedAnimationClip`]s in RON format,
+/// so that they load back as [`AnimationClip`]s with the
+/// [`CompressedAnimationClipLoader`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CompressedAnimationClipSaver;
+
+impl AssetSaver for CompressedAnimationClipSaver {
+    type Asset = CompressedAnimationClip;
+    type Settings = ();
+    type OutputLoader = CompressedAnimationClipLoader;
+    type Error = CompressedAnimationClipLoadError;
+
+    async fn save(
+        &self,
+        writer: &mut Writer,
+        asset: SavedAsset<'_, Self::Asset>,
+        _: &Self::Settings,
+    ) -> Result<(), Self::Error> {
+        let bytes = ron::ser::to_string(&*asset)?;
+        writer.write_all(bytes.as_bytes()).await?;
+        Ok(())
+    }
+}
+
+/// An [`AssetLoader`] that loads the [`CompressedAnimationClip`]s saved by the
+/// [`CompressedAnimationClipSaver`] as [`AnimationClip`]s.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CompressedAnimationClipLoader;
+
+/// Various errors that can occur when serializing or deserializing compressed
+/// animation clips to and from RON, respectively.
+#[derive(Error, Debug)]
+pub enum CompressedAnimationClipLoadError {
+    /// An I/O error occurred.
+    #[error("I/O")]
+    Io(#[from] io::Error),
+    /// An error occurred in RON serialization or deserialization.
+    #[error("RON serialization")]
+    Ron(#[from] ron::Error),
+    /// An error occurred in RON deserialization, and the location of the error
+    /// is supplied.
+    #[error("RON serialization")]
+    SpannedRon(#[from] SpannedError),
+}
+
+impl AssetLoader for CompressedAnimationClipLoader {
+    type Asset = AnimationClip;
+
+    type Settings = ();
+
+    type Error = CompressedAnimationClipLoadError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _: &Self::Settings,
+        _: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let compressed: CompressedAnimationClip = ron::de::from_bytes(&bytes)?;
+        Ok(compressed.to_clip())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["animclip", "animclip.ron"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gltf_curves::WideLinearKeyframeCurve;
+    use bevy_app::{App, TaskPoolPlugin};
+    use bevy_asset::{
+        io::{
+            memory::{Dir, MemoryAssetReader, MemoryAssetWriter},
+            AssetReader, AssetSource, AssetSourceId,
+        },
+        AssetApp, AssetPlugin, AssetServer, Assets, Handle, LoadState,
+    };
+    use bevy_math::curve::FunctionCurve;
+    use futures_lite::future::block_on;
+    use std::path::Path;
+
+    /// Returns a clip animating the translation, rotation and scale of the
+    /// `bone` target, and the morph target weights of the `face` target.
+    fn test_clip() -> AnimationClip {
+        let target = AnimationTargetId::from_name(&"bone".into());
+        let domain = Interval::new(0.0, 2.0).unwrap();
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_target(
+            target,
+            AnimatableCurve::new(
+                animated_field!(Transform::translation),
+                FunctionCurve::new(domain, |t| Vec3::new(t, ops::sin(t * 3.0), -2.0)),
+            ),
+        );
+        clip.add_curve_to_target(
+            target,
+            AnimatableCurve::new(
+                animated_field!(Transform::rotation),
+                FunctionCurve::new(domain, |t| Quat::from_rotation_y(t * 1.5)),
+            ),
+        );
+        clip.add_curve_to_target(
+            target,
+            AnimatableCurve::new(
+                animated_field!(Transform::scale),
+                FunctionCurve::new(domain, |_| Vec3::splat(2.0)),
+            ),
+        );
+        clip.add_curve_to_target(
+            AnimationTargetId::from_name(&"face".into()),
+            WeightsCurve(
+                WideLinearKeyframeCurve::new([0.0, 0.5, 2.0], [0.0, 1.0, 1.0, 0.25, 0.5, 0.0])
+                    .unwrap(),
+            ),
+        );
+        clip
+    }
+
+    /// Asserts that the compressed `curves` sample within `tolerance` of the
+    /// `original` ones.
+    fn assert_curves_match(original: &[VariableCurve], curves: &[VariableCurve], tolerance: f32) {
+        assert_eq!(curves.len(), original.len());
+        for (original, curve) in original.iter().zip(curves) {
+            for index in 0..=40 {
+                let t = index as f32 * 0.05;
+                let expected = original.0.sample_value(t).unwrap();
+                let actual = curve.0.sample_value(t).unwrap();
+                if animates_morph_weights(&*curve.0) {
+                    let expected = expected.downcast::<Vec<f32>>().unwrap();
+                    let actual = actual.downcast::<Vec<f32>>().unwrap();
+                    assert!(CompressedWeightsCurve::error(&expected, &actual) < tolerance);
+                    continue;
+                }
+                match transform_field(&*curve.0) {
+                    Some("rotation") => {
+                        let expected = expected.downcast::<Quat>().unwrap();
+                        let actual = actual.downcast::<Quat>().unwrap();
+                        assert!(expected.angle_between(*actual) < tolerance);
+                    }
+                    _ => {
+                        let expected = expected.downcast::<Vec3>().unwrap();
+                        let actual = actual.downcast::<Vec3>().unwrap();
+                        assert!(expected.distance(*actual) < tolerance);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reduce_linear_keyframes() {
+        let samples = [0.0, 1.0, 2.0, 3.0, 2.0, 1.0, 1.0];
+        let keyframes = reduce_keyframes(
+            &samples,
+            1e-3,
+            |a: &f32, b: &f32, s| a + (b - a) * s,
+            |a, b| (a - b).abs(),
+        );
+        assert_eq!(keyframes, vec![0, 3, 5, 6]);
+    }
+
+    #[test]
+    fn quantized_quat_round_trip() {
+        for rotation in [
+            Quat::IDENTITY,
+            Quat::from_rotation_x(2.5),
+            -Quat::from_rotation_y(-1.0),
+            Quat::from_euler(bevy_math::EulerRot::XYZ, 0.3, -1.2, 2.9),
+        ] {
+            let decoded = dequantize_quat(quantize_quat(rotation));
+            // `q` and `-q` are the same rotation.
+            assert!(decoded.abs_diff_eq(rotation, 1e-5) || decoded.abs_diff_eq(-rotation, 1e-5));
+        }
+    }
+
+    #[test]
+    fn compress_clip_within_tolerance() {
+        let target = AnimationTargetId::from_name(&"bone".into());
+        let clip = test_clip();
+        let settings = AnimationCompressionSettings {
+            translation_tolerance: 1e-3,
+            ..AnimationCompressionSettings::default()
+        };
+        let compressed = settings.compress(&clip);
+        assert_eq!(compressed.duration(), clip.duration());
+
+        let curves = compressed.curves_for_target(target).unwrap();
+        assert_curves_match(clip.curves_for_target(target).unwrap(), curves, 2e-3);
+
+        // The constant scale track is detected and holds its value everywhere.
+        let scale = &curves[2].0;
+        assert_eq!(scale.domain(), Interval::EVERYWHERE);
+
+        let face = AnimationTargetId::from_name(&"face".into());
+        assert_curves_match(
+            clip.curves_for_target(face).unwrap(),
+            compressed.curves_for_target(face).unwrap(),
+            2e-3,
+        );
+    }
+
+    #[test]
+    fn quantized_keyframes_within_tolerance() {
+        // A long track with a wide range needs 32 bits for both the keyframe
+        // times and the components of the values.
+        let domain = Interval::new(0.0, 20.0).unwrap();
+        let curve = AnimatableCurve::new(
+            animated_field!(Transform::translation),
+            FunctionCurve::new(domain, |t| {
+                Vec3::new(
+                    500.0 * ops::sin(t * 0.3),
+                    200.0 * ops::cos(t * 0.7),
+                    10.0 * t,
+                )
+            }),
+        );
+        let settings = AnimationCompressionSettings {
+            sample_rate: 4000.0,
+            ..AnimationCompressionSettings::default()
+        };
+        let tolerance = 1e-3;
+        let CompressedTrack::Keyframes(compressed) = settings
+            .compress_track::<Vec3, QuantizedVec3Curve>(&curve, 20.0, tolerance)
+            .unwrap()
+        else {
+            panic!("the track isn't constant");
+        };
+        assert!(matches!(compressed.times.indices, PackedInts::U32(_)));
+        assert!(matches!(compressed.values, PackedInts::U32(_)));
+
+        for index in 0..=80_000 {
+            let t = 20.0 * index as f32 / 80_000.0;
+            let expected = curve.sample_value(t).unwrap().downcast::<Vec3>().unwrap();
+            // Allow for the rounding of the sample times, which moves the
+            // fastest components by up to `1e-4`.
+            assert!(compressed.sample_clamped(t).distance(*expected) <= tolerance + 1e-4);
+        }
+    }
+
+    /// Loads the clip of [`test_clip`] from any file.
+    #[derive(Default)]
+    struct TestClipLoader;
+
+    impl AssetLoader for TestClipLoader {
+        type Asset = AnimationClip;
+        type Settings = ();
+        type Error = io::Error;
+
+        async fn load(
+            &self,
+            _: &mut dyn Reader,
+            _: &(),
+            _: &mut LoadContext<'_>,
+        ) -> Result<AnimationClip, io::Error> {
+            Ok(test_clip())
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["testclip"]
+        }
+    }
+
+    #[test]
+    fn compress_clip_for_processor() {
+        // The asset processor keeps its transaction log on the file system, so this runs the
+        // steps of a `LoadTransformAndSave` processor on an in-memory source instead.
+        let dir = Dir::default();
+        dir.insert_asset_text(Path::new("walk.testclip"), "");
+        let reader_dir = dir.clone();
+        let writer_dir = dir.clone();
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || {
+                    Box::new(MemoryAssetReader {
+                        root: reader_dir.clone(),
+                    })
+                })
+                .with_writer(move |_| {
+                    Some(Box::new(MemoryAssetWriter {
+                        root: writer_dir.clone(),
+                    }))
+                }),
+        )
+        .add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+        .init_asset::<AnimationClip>()
+        .init_asset_loader::<TestClipLoader>()
+        .init_asset_loader::<CompressedAnimationClipLoader>()
+        .register_asset_saver(CompressedAnimationClipSaver);
+        let asset_server = app.world().resource::<AssetServer>().clone();
+
+        let clip = load_clip(&mut app, "walk.testclip");
+        let compressed = AnimationCompressionSettings::default()
+            .compress_to_serializable(&clip)
+            .unwrap();
+        block_on(asset_server.save("walk.animclip", &compressed)).unwrap();
+
+        // The saved clip is loaded back by the compressed clip loader.
+        let meta =
+            block_on(MemoryAssetReader { root: dir }.read_meta_bytes(Path::new("walk.animclip")))
+                .unwrap();
+        assert!(String::from_utf8(meta)
+            .unwrap()
+            .contains(core::any::type_name::<CompressedAnimationClipLoader>()));
+        let loaded = load_clip(&mut app, "walk.animclip");
+        assert_eq!(loaded.duration(), clip.duration());
+        for name in ["bone", "face"] {
+            let target = AnimationTargetId::from_name(&name.into());
+            assert_curves_match(
+                clip.curves_for_target(target).unwrap(),
+                loaded.curves_for_target(target).unwrap(),
+                2e-3,
+            );
+        }
+    }
+
+    /// Loads the clip at `path`, updating `app` until it is loaded.
+    fn load_clip(app: &mut App, path: &'static str) -> AnimationClip {
+        let handle: Handle<AnimationClip> = app.world().resource::<AssetServer>().load(path);
+        for _ in 0..10_000 {
+            app.update();
+            match app.world().resource::<AssetServer>().load_state(&handle) {
+                LoadState::Loaded => break,
+                LoadState::Failed(error) => panic!("failed to load {path}: {error}"),
+                _ => {}
+            }
+        }
+        app.world()
+            .resource::<Assets<AnimationClip>>()
+            .get(&handle)
+            .cloned()
+            .unwrap_or_else(|| panic!("{path} should load"))
+    }
+}
@@ -0,0 +1,560 @@
+//! Curve assets, which let curves such as damage falloffs or camera shakes be
+//! authored in data files instead of code.
+//!
+//! A [`CurveAsset`] is a list of keyframes, each with its own interpolation
+//! mode and tangents, and wrap modes that describe how the curve continues
+//! before its first and after its last keyframe. It implements [`Curve`], so
+//! that it can be sampled directly, used with the curve adaptors of
+//! `bevy_math`, or animate a property in an [`AnimationClip`] by wrapping it
+//! in an [`AnimatableCurve`]:
+//!
+//! ```
+//! # use bevy_animation::{animated_field, prelude::*, curve_asset::*, AnimationTargetId};
+//! # use bevy_math::{curve::Curve, Vec3};
+//! # use bevy_transform::components::Transform;
+//! # let shake = CurveAsset::new([
+//! #     CurveKeyframe::cubic(0.0, Vec3::ZERO),
+//! #     CurveKeyframe::cubic(0.5, Vec3::X),
+//! # ]);
+//! let mut clip = AnimationClip::default();
+//! clip.add_curve_to_target(
+//!     AnimationTargetId::from_name(&"camera".into()),
+//!     AnimatableCurve::new(animated_field!(Transform::translation), shake.clone()),
+//! );
+//! ```
+//!
+//! Curve assets can be serialized to and loaded from [RON] files. Loaders are
+//! registered for curves of `f32`, [`Vec2`], [`Vec3`] and [`Vec4`] values, each
+//! with its own extension, so that the type of a curve is known from its path:
+//!
+//! | Value type | Extension         |
+//! |------------|-------------------|
+//! | `f32`      | `.f32.curve.ron`  |
+//! | [`Vec2`]   | `.vec2.curve.ron` |
+//! | [`Vec3`]   | `.vec3.curve.ron` |
+//! | [`Vec4`]   | `.vec4.curve.ron` |
+//!
+//! [RON]: https://github.com/ron-rs/ron
+//! [`AnimationClip`]: crate::AnimationClip
+//! [`AnimatableCurve`]: crate::animation_curves::AnimatableCurve
+//! [`Vec2`]: bevy_math::Vec2
+//! [`Vec3`]: bevy_math::Vec3
+//! [`Vec4`]: bevy_math::Vec4
+
+use core::marker::PhantomData;
+use std::io::{self, Write};
+
+use bevy_asset::{io::Reader, Asset, AssetLoader, LoadContext};
+use bevy_math::{
+    cubic_splines::{CubicCurve, CubicSegment},
+    curve::{
+        cores::{EvenCoreError, UnevenCoreError},
+        Curve, Interval, SampleCurve, UnevenSampleAutoCurve,
+    },
+    ops, VectorSpace,
+};
+use bevy_reflect::{prelude::ReflectDefault, FromReflect, Reflect, Reflectable};
+use ron::de::SpannedError;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use thiserror::Error;
+
+/// A value type that [`CurveAsset`]s can hold.
+///
+/// This is implemented for every [`VectorSpace`] that can be reflected and
+/// serialized, such as `f32` and the vector types.
+pub trait CurveAssetValue:
+    VectorSpace + Reflectable + FromReflect + Serialize + DeserializeOwned
+{
+}
+
+impl<T> CurveAssetValue for T where
+    T: VectorSpace + Reflectable + FromReflect + Serialize + DeserializeOwned
+{
+}
+
+/// A curve defined by keyframes, which can be loaded as an asset.
+///
+/// See the [module-level documentation](self) for more information.
+#[derive(Asset, Reflect, Clone, Debug, Serialize, Deserialize)]
+#[reflect(Debug)]
+#[serde(bound = "T: CurveAssetValue")]
+pub struct CurveAsset<T: CurveAssetValue> {
+    /// The keyframes of the curve, sorted by time.
+    pub keyframes: Vec<CurveKeyframe<T>>,
+    /// How the curve continues before its first keyframe.
+    #[serde(default)]
+    pub pre_wrap: CurveWrapMode,
+    /// How the curve continues after its last keyframe.
+    #[serde(default)]
+    pub post_wrap: CurveWrapMode,
+}
+
+/// A keyframe of a [`CurveAsset`].
+#[derive(Reflect, Clone, Copy, Debug, Serialize, Deserialize)]
+#[reflect(Debug)]
+#[serde(bound = "T: CurveAssetValue")]
+pub struct CurveKeyframe<T: CurveAssetValue> {
+    /// The time of the keyframe.
+    pub time: f32,
+    /// The value of the curve at the keyframe.
+    pub value: T,
+    /// How the curve is interpolated between this keyframe and the next.
+    #[serde(default)]
+    pub interpolation: KeyframeInterpolation,
+    /// The rate of change of the curve arriving at this keyframe, per unit of
+    /// time, for [cubic] interpolation.
+    ///
+    /// If `None`, the tangent is computed from the neighboring keyframes.
+    ///
+    /// [cubic]: KeyframeInterpolation::Cubic
+    #[serde(default)]
+    pub in_tangent: Option<T>,
+    /// The rate of change of the curve leaving this keyframe, per unit of
+    /// time, for [cubic] interpolation.
+    ///
+    /// If `None`, the tangent is computed from the neighboring keyframes.
+    ///
+    /// [cubic]: KeyframeInterpolation::Cubic
+    #[serde(default)]
+    pub out_tangent: Option<T>,
+}
+
+/// How a [`CurveAsset`] is interpolated between a keyframe and the next.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[reflect(Debug, Default, PartialEq)]
+pub enum KeyframeInterpolation {
+    /// The value of the keyframe is held until the next keyframe.
+    Constant,
+    /// The values of both keyframes are interpolated linearly.
+    #[default]
+    Linear,
+    /// The values of both keyframes are interpolated with a cubic Hermite
+    /// spline, following their tangents.
+    Cubic,
+}
+
+/// How a [`CurveAsset`] continues beyond its keyframes.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[reflect(Debug, Default, PartialEq)]
+pub enum CurveWrapMode {
+    /// The value of the nearest keyframe is held.
+    #[default]
+    Clamp,
+    /// The keyframes repeat.
+    Repeat,
+    /// The keyframes repeat, alternately forwards and backwards.
+    PingPong,
+}
+
+impl<T: CurveAssetValue> CurveKeyframe<T> {
+    /// Creates a keyframe holding its value until the next keyframe.
+    pub fn constant(time: f32, value: T) -> Self {
+        Self::new(time, value, KeyframeInterpolation::Constant)
+    }
+
+    /// Creates a keyframe interpolated linearly to the next keyframe.
+    pub fn linear(time: f32, value: T) -> Self {
+        Self::new(time, value, KeyframeInterpolation::Linear)
+    }
+
+    /// Creates a keyframe interpolated with a cubic spline to the next
+    /// keyframe, with tangents computed from the neighboring keyframes.
+    pub fn cubic(time: f32, value: T) -> Self {
+        Self::new(time, value, KeyframeInterpolation::Cubic)
+    }
+
+    fn new(time: f32, value: T, interpolation: KeyframeInterpolation) -> Self {
+        Self {
+            time,
+            value,
+            interpolation,
+            in_tangent: None,
+            out_tangent: None,
+        }
+    }
+
+    /// Sets the incoming and outgoing tangents of the keyframe.
+    pub fn with_tangents(mut self, in_tangent: T, out_tangent: T) -> Self {
+        self.in_tangent = Some(in_tangent);
+        self.out_tangent = Some(out_tangent);
+        self
+    }
+}
+
+impl<T: CurveAssetValue> CurveAsset<T> {
+    /// Creates a new [`CurveAsset`] from the given keyframes, clamped at both
+    /// ends.
+    pub fn new(keyframes: impl IntoIterator<Item = CurveKeyframe<T>>) -> Self {
+        let mut curve = Self {
+            keyframes: keyframes.into_iter().collect(),
+            pre_wrap: CurveWrapMode::Clamp,
+            post_wrap: CurveWrapMode::Clamp,
+        };
+        curve.sort_keyframes();
+        curve
+    }
+
+    /// Sets how the curve continues before its first and after its last
+    /// keyframe.
+    pub fn with_wrap(mut self, pre_wrap: CurveWrapMode, post_wrap: CurveWrapMode) -> Self {
+        self.pre_wrap = pre_wrap;
+        self.post_wrap = post_wrap;
+        self
+    }
+
+    /// Sorts the keyframes by time.
+    ///
+    /// This should be called after editing the times of the keyframes.
+    pub fn sort_keyframes(&mut self) {
+        self.keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+    }
+
+    /// Serializes the curve to the given [`Write`]r in RON format.
+    ///
+    /// If writing to a file, it can later be loaded with the
+    /// [`CurveAssetLoader`] to reconstruct the curve.
+    pub fn save<W>(&self, writer: &mut W) -> Result<(), CurveAssetLoadError>
+    where
+        W: Write,
+    {
+        let mut ron_serializer = ron::ser::Serializer::new(writer, None)?;
+        Ok(self.serialize(&mut ron_serializer)?)
+    }
+
+    /// Returns an [`UnevenSampleAutoCurve`] through the values of the
+    /// keyframes, ignoring their interpolation modes and the wrap modes.
+    pub fn to_uneven_sample_auto_curve(&self) -> Result<UnevenSampleAutoCurve<T>, UnevenCoreError> {
+        UnevenSampleAutoCurve::new(
+            self.keyframes
+                .iter()
+                .map(|keyframe| (keyframe.time, keyframe.value)),
+        )
+    }
+
+    /// Returns a [`SampleCurve`] of `samples` evenly spaced samples of this
+    /// curve, from its first to its last keyframe, interpolated linearly.
+    pub fn to_sample_curve(
+        &self,
+        samples: usize,
+    ) -> Result<SampleCurve<T, fn(&T, &T, f32) -> T>, EvenCoreError> {
+        let domain = self
+            .keyframe_domain()
+            .ok_or(EvenCoreError::NotEnoughSamples {
+                samples: self.keyframes.len(),
+            })?;
+        let last = samples.max(2) - 1;
+        let samples = (0..=last).map(|index| {
+            self.sample_unchecked(domain.start() + domain.length() * index as f32 / last as f32)
+        });
+        SampleCurve::new(domain, samples, |a: &T, b: &T, t| a.lerp(*b, t))
+    }
+
+    /// Returns a [`CubicCurve`] equivalent to this curve between its first and
+    /// last keyframe, or `None` if it has fewer than two keyframes.
+    ///
+    /// Cubic curves are parametrized by segment: the segment between keyframe
+    /// `i` and keyframe `i + 1` covers the parameters `[i, i + 1]`, regardless
+    /// of the times of the keyframes.
+    pub fn to_cubic_curve(&self) -> Option<CubicCurve<T>> {
+        CubicCurve::from_segments((1..self.keyframes.len()).map(|index| {
+            let (start, end) = (&self.keyframes[index - 1], &self.keyframes[index]);
+            let (p0, p1) = (start.value, end.value);
+            // Hermite tangents are per unit of the segment parameter.
+            let (m0, m1) = match start.interpolation {
+                KeyframeInterpolation::Constant => {
+                    return CubicSegment {
+                        coeff: [p0, T::ZERO, T::ZERO, T::ZERO],
+                    };
+                }
+                KeyframeInterpolation::Linear => (p1 - p0, p1 - p0),
+                KeyframeInterpolation::Cubic => {
+                    let duration = end.time - start.time;
+                    (
+                        self.out_tangent(index - 1) * duration,
+                        self.in_tangent(index) * duration,
+                    )
+                }
+            };
+            CubicSegment {
+                coeff: [
+                    p0,
+                    m0,
+                    (p1 - p0) * 3.0 - m0 * 2.0 - m1,
+                    (p0 - p1) * 2.0 + m0 + m1,
+                ],
+            }
+        }))
+    }
+
+    /// The interval between the first and the last keyframe, if it isn't
+    /// empty.
+    fn keyframe_domain(&self) -> Option<Interval> {
+        let first = self.keyframes.first()?.time;
+        let last = self.keyframes.last()?.time;
+        Interval::new(first, last)
+            .ok()
+            .filter(|domain| domain.length() > 0.0)
+    }
+
+    /// The tangent computed from the keyframes surrounding keyframe `index`.
+    fn auto_tangent(&self, index: usize) -> T {
+        let previous = &self.keyframes[index.saturating_sub(1)];
+        let next = &self.keyframes[(index + 1).min(self.keyframes.len() - 1)];
+        match next.time > previous.time {
+            true => (next.value - previous.value) / (next.time - previous.time),
+            false => T::ZERO,
+        }
+    }
+
+    fn in_tangent(&self, index: usize) -> T {
+        self.keyframes[index]
+            .in_tangent
+            .unwrap_or_else(|| self.auto_tangent(index))
+    }
+
+    fn out_tangent(&self, index: usize) -> T {
+        self.keyframes[index]
+            .out_tangent
+            .unwrap_or_else(|| self.auto_tangent(index))
+    }
+
+    /// Maps `t` into the interval of the keyframes, according to the wrap
+    /// modes.
+    fn wrap(&self, t: f32, domain: Interval) -> f32 {
+        let wrap_mode = match t {
+            t if t < domain.start() => self.pre_wrap,
+            t if t > domain.end() => self.post_wrap,
+            _ => return t,
+        };
+        let length = domain.length();
+        let offset = t - domain.start();
+        match wrap_mode {
+            CurveWrapMode::Clamp => t.clamp(domain.start(), domain.end()),
+            CurveWrapMode::Repeat => domain.start() + ops::rem_euclid(offset, length),
+            CurveWrapMode::PingPong => {
+                let offset = ops::rem_euclid(offset, 2.0 * length);
+                match offset > length {
+                    true => domain.start() + 2.0 * length - offset,
+                    false => domain.start() + offset,
+                }
+            }
+        }
+    }
+}
+
+impl<T: CurveAssetValue> Curve<T> for CurveAsset<T> {
+    #[inline]
+    fn domain(&self) -> Interval {
+        let Some(domain) = self.keyframe_domain() else {
+            return Interval::EVERYWHERE;
+        };
+        let start = match self.pre_wrap {
+            CurveWrapMode::Clamp => domain.start(),
+            CurveWrapMode::Repeat | CurveWrapMode::PingPong => f32::NEG_INFINITY,
+        };
+        let end = match self.post_wrap {
+            CurveWrapMode::Clamp => domain.end(),
+            CurveWrapMode::Repeat | CurveWrapMode::PingPong => f32::INFINITY,
+        };
+        Interval::new(start, end).unwrap_or(Interval::EVERYWHERE)
+    }
+
+    fn sample_unchecked(&self, t: f32) -> T {
+        let Some(domain) = self.keyframe_domain() else {
+            return self
+                .keyframes
+                .first()
+                .map_or(T::ZERO, |keyframe| keyframe.value);
+        };
+        let t = self.wrap(t, domain);
+
+        let next = self
+            .keyframes
+            .partition_point(|keyframe| keyframe.time <= t);
+        if next == 0 {
+            return self.keyframes[0].value;
+        }
+        if next == self.keyframes.len() {
+            return self.keyframes[next - 1].value;
+        }
+        let (start, end) = (&self.keyframes[next - 1], &self.keyframes[next]);
+        let duration = end.time - start.time;
+        let s = (t - start.time) / duration;
+        match start.interpolation {
+            KeyframeInterpolation::Constant => start.value,
+            KeyframeInterpolation::Linear => start.value.lerp(end.value, s),
+            KeyframeInterpolation::Cubic => {
+                let m0 = self.out_tangent(next - 1) * duration;
+                let m1 = self.in_tangent(next) * duration;
+                let (s2, s3) = (s * s, s * s * s);
+                start.value * (2.0 * s3 - 3.0 * s2 + 1.0)
+                    + m0 * (s3 - 2.0 * s2 + s)
+                    + end.value * (-2.0 * s3 + 3.0 * s2)
+                    + m1 * (s3 - s2)
+            }
+        }
+    }
+}
+
+/// An [`AssetLoader`] that can load [`CurveAsset`]s of `T` values from files
+/// with the given extensions.
+///
+/// Every value type needs extensions of its own, since an extension can only
+/// be loaded by one loader. See the [module-level documentation](self) for the
+/// extensions registered by the [`AnimationPlugin`](crate::AnimationPlugin).
+pub struct CurveAssetLoader<T> {
+    extensions: &'static [&'static str],
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> CurveAssetLoader<T> {
+    /// Creates a loader for curves stored in files with the given
+    /// `extensions`, without the leading dot.
+    pub const fn new(extensions: &'static [&'static str]) -> Self {
+        Self {
+            extensions,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Various errors that can occur when serializing or deserializing curve
+/// assets to and from RON, respectively.
+#[derive(Error, Debug)]
+pub enum CurveAssetLoadError {
+    /// An I/O error occurred.
+    #[error("I/O")]
+    Io(#[from] io::Error),
+    /// An error occurred in RON serialization or deserialization.
+    #[error("RON serialization")]
+    Ron(#[from] ron::Error),
+    /// An error occurred in RON deserialization, and the location of the error
+    /// is supplied.
+    #[error("RON serialization")]
+    SpannedRon(#[from] SpannedError),
+    /// The curve has no keyframes.
+    #[error("curve has no keyframes")]
+    NoKeyframes,
+}
+
+impl<T: CurveAssetValue> AssetLoader for CurveAssetLoader<T> {
+    type Asset = CurveAsset<T>;
+
+    type Settings = ();
+
+    type Error = CurveAssetLoadError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _: &Self::Settings,
+        _: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+        let mut curve = CurveAsset::<T>::deserialize(&mut deserializer)
+            .map_err(|err| deserializer.span_error(err))?;
+        if curve.keyframes.is_empty() {
+            return Err(CurveAssetLoadError::NoKeyframes);
+        }
+        curve.sort_keyframes();
+        Ok(curve)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        self.extensions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AnimationPlugin;
+    use bevy_app::{App, TaskPoolPlugin};
+    use bevy_asset::{AssetPlugin, AssetServer};
+    use bevy_math::{Vec2, Vec3, Vec4};
+
+    #[test]
+    fn sample_keyframe_interpolations() {
+        let curve = CurveAsset::new([
+            CurveKeyframe::linear(1.0, 2.0),
+            CurveKeyframe::constant(0.0, 0.0),
+            CurveKeyframe::cubic(2.0, 4.0).with_tangents(0.0, 0.0),
+            CurveKeyframe::linear(3.0, 0.0).with_tangents(0.0, 0.0),
+        ]);
+        assert_eq!(curve.domain(), Interval::new(0.0, 3.0).unwrap());
+        assert_eq!(curve.sample_unchecked(0.5), 0.0);
+        assert_eq!(curve.sample_unchecked(1.5), 3.0);
+        assert_eq!(curve.sample_unchecked(2.5), 2.0);
+        assert_eq!(curve.sample_unchecked(-1.0), 0.0);
+        assert_eq!(curve.sample_unchecked(4.0), 0.0);
+
+        // The cubic curve matches the sampled curve, by segment.
+        let cubic = curve.to_cubic_curve().unwrap();
+        assert_eq!(cubic.position(1.0), 2.0);
+        assert_eq!(cubic.position(2.5), 2.0);
+    }
+
+    #[test]
+    fn sample_wrap_modes() {
+        let curve = CurveAsset::new([
+            CurveKeyframe::linear(0.0, Vec2::ZERO),
+            CurveKeyframe::linear(1.0, Vec2::ONE),
+        ])
+        .with_wrap(CurveWrapMode::PingPong, CurveWrapMode::Repeat);
+        assert_eq!(curve.domain(), Interval::EVERYWHERE);
+        assert_eq!(curve.sample_unchecked(1.25), Vec2::splat(0.25));
+        assert_eq!(curve.sample_unchecked(-0.25), Vec2::splat(0.25));
+        assert_eq!(curve.sample_unchecked(-1.25), Vec2::splat(0.75));
+    }
+
+    #[test]
+    fn round_trip_ron() {
+        let curve = CurveAsset::new([
+            CurveKeyframe::cubic(0.0, 1.0).with_tangents(0.0, -1.0),
+            CurveKeyframe::linear(0.5, 0.25),
+        ])
+        .with_wrap(CurveWrapMode::Clamp, CurveWrapMode::Repeat);
+        let mut bytes = Vec::new();
+        curve.save(&mut bytes).unwrap();
+
+        let loaded: CurveAsset<f32> = ron::de::from_bytes(&bytes).unwrap();
+        assert_eq!(loaded.keyframes.len(), 2);
+        assert_eq!(loaded.keyframes[0].out_tangent, Some(-1.0));
+        assert_eq!(loaded.post_wrap, CurveWrapMode::Repeat);
+
+        // Optional fields can be omitted by hand.
+        let loaded: CurveAsset<f32> =
+            ron::de::from_str("(keyframes: [(time: 0.0, value: 1.0)])").unwrap();
+        assert_eq!(
+            loaded.keyframes[0].interpolation,
+            KeyframeInterpolation::Linear
+        );
+        assert_eq!(loaded.pre_wrap, CurveWrapMode::Clamp);
+    }
+
+    #[test]
+    fn loader_per_value_type() {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin::default(),
+            AnimationPlugin,
+        ));
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        for (extension, type_name) in [
+            ("f32.curve.ron", core::any::type_name::<CurveAsset<f32>>()),
+            ("vec2.curve.ron", core::any::type_name::<CurveAsset<Vec2>>()),
+            ("vec3.curve.ron", core::any::type_name::<CurveAsset<Vec3>>()),
+            ("vec4.curve.ron", core::any::type_name::<CurveAsset<Vec4>>()),
+        ] {
+            let loader = futures_lite::future::block_on(
+                asset_server.get_asset_loader_with_extension(extension),
+            )
+            .unwrap();
+            assert_eq!(loader.asset_type_name(), type_name);
+        }
+    }
+}
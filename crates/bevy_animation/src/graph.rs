@@ -17,6 +17,7 @@ use bevy_ecs::{
     resource::Resource,
     system::{Res, ResMut},
 };
+use bevy_math::Vec2;
 use bevy_platform_support::collections::HashMap;
 use bevy_reflect::{prelude::ReflectDefault, Reflect, ReflectSerialize};
 use derive_more::derive::From;
@@ -29,7 +30,11 @@ use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 use thiserror::Error;
 
-use crate::{AnimationClip, AnimationTargetId};
+use crate::{
+    blend_space::{BlendSpace1D, BlendSpace1DPoint, BlendSpace2D, BlendSpace2DPoint},
+    sync::AnimationSyncMode,
+    AnimationClip, AnimationTargetId,
+};
 
 /// A graph structure that describes how animation clips are to be blended
 /// together.
@@ -127,6 +132,12 @@ pub struct AnimationGraph {
     /// Animation targets not in this collection are treated as though they
     /// don't belong to any mask groups.
     pub mask_groups: HashMap<AnimationTargetId, AnimationMask>,
+
+    /// The sync groups of the graph, indexed by the node whose children they
+    /// keep in phase.
+    ///
+    /// See [`AnimationSyncMode`] for more information.
+    pub sync_groups: HashMap<AnimationNodeIndex, AnimationSyncMode>,
 }
 
 /// A [`Handle`] to the [`AnimationGraph`] to be used by the [`AnimationPlayer`](crate::AnimationPlayer) on the same entity.
@@ -230,6 +241,18 @@ pub enum AnimationNodeType {
     /// top of a running animation to produce an animation of a character
     /// attacking while running.
     Add,
+
+    /// A *one-dimensional blend space node*, which blends its children like a
+    /// blend node, with weights computed from a float parameter.
+    ///
+    /// See [`BlendSpace1D`] for more information.
+    BlendSpace1D(BlendSpace1D),
+
+    /// A *two-dimensional blend space node*, which blends its children like a
+    /// blend node, with weights computed from two float parameters.
+    ///
+    /// See [`BlendSpace2D`] for more information.
+    BlendSpace2D(BlendSpace2D),
 }
 
 /// An [`AssetLoader`] that can load [`AnimationGraph`]s as assets.
@@ -365,6 +388,9 @@ pub struct SerializedAnimationGraph {
     pub root: NodeIndex,
     /// Corresponds to the `mask_groups` field on [`AnimationGraph`].
     pub mask_groups: HashMap<AnimationTargetId, AnimationMask>,
+    /// Corresponds to the `sync_groups` field on [`AnimationGraph`].
+    #[serde(default)]
+    pub sync_groups: HashMap<AnimationNodeIndex, AnimationSyncMode>,
 }
 
 /// A version of [`AnimationGraphNode`] suitable for serializing as an asset.
@@ -390,6 +416,10 @@ pub enum SerializedAnimationNodeType {
     Blend,
     /// Corresponds to [`AnimationNodeType::Add`].
     Add,
+    /// Corresponds to [`AnimationNodeType::BlendSpace1D`].
+    BlendSpace1D(BlendSpace1D),
+    /// Corresponds to [`AnimationNodeType::BlendSpace2D`].
+    BlendSpace2D(BlendSpace2D),
 }
 
 /// A version of `Handle<AnimationClip>` suitable for serializing as an asset.
@@ -424,6 +454,7 @@ impl AnimationGraph {
             graph,
             root,
             mask_groups: HashMap::default(),
+            sync_groups: HashMap::default(),
         }
     }
 
@@ -601,6 +632,103 @@ impl AnimationGraph {
         node_index
     }
 
+    /// Adds a one-dimensional blend space node to the animation graph with the
+    /// given weight and returns its index.
+    ///
+    /// The blend space will be placed under the supplied `parent` node, and
+    /// its input read from the float parameter `parameter`. Add clips to it
+    /// with [`add_blend_space_clip_1d`](Self::add_blend_space_clip_1d). The
+    /// blend space node will have no mask.
+    pub fn add_blend_space_1d(
+        &mut self,
+        parameter: impl Into<String>,
+        weight: f32,
+        parent: AnimationNodeIndex,
+    ) -> AnimationNodeIndex {
+        let node_index = self.graph.add_node(AnimationGraphNode {
+            node_type: AnimationNodeType::BlendSpace1D(BlendSpace1D::new(parameter)),
+            mask: 0,
+            weight,
+        });
+        self.graph.add_edge(parent, node_index, ());
+        node_index
+    }
+
+    /// Adds a two-dimensional blend space node to the animation graph with the
+    /// given weight and returns its index.
+    ///
+    /// The blend space will be placed under the supplied `parent` node, and
+    /// its input read from the float parameters `x` and `y`. Add clips to it
+    /// with [`add_blend_space_clip_2d`](Self::add_blend_space_clip_2d). The
+    /// blend space node will have no mask.
+    pub fn add_blend_space_2d(
+        &mut self,
+        x: impl Into<String>,
+        y: impl Into<String>,
+        weight: f32,
+        parent: AnimationNodeIndex,
+    ) -> AnimationNodeIndex {
+        let node_index = self.graph.add_node(AnimationGraphNode {
+            node_type: AnimationNodeType::BlendSpace2D(BlendSpace2D::new(x, y)),
+            mask: 0,
+            weight,
+        });
+        self.graph.add_edge(parent, node_index, ());
+        node_index
+    }
+
+    /// Adds an [`AnimationClip`] to the one-dimensional blend space
+    /// `blend_space` at the given `position`, and returns its index.
+    ///
+    /// Returns `None`, leaving the graph unchanged, if `blend_space` isn't a
+    /// [`AnimationNodeType::BlendSpace1D`] node.
+    pub fn add_blend_space_clip_1d(
+        &mut self,
+        clip: Handle<AnimationClip>,
+        position: f32,
+        blend_space: AnimationNodeIndex,
+    ) -> Option<AnimationNodeIndex> {
+        let AnimationNodeType::BlendSpace1D(_) = self.graph.node_weight(blend_space)?.node_type
+        else {
+            return None;
+        };
+        let node = self.add_clip(clip, 1.0, blend_space);
+        if let AnimationNodeType::BlendSpace1D(ref mut blend_space) =
+            self.graph[blend_space].node_type
+        {
+            blend_space
+                .points
+                .push(BlendSpace1DPoint { node, position });
+        }
+        Some(node)
+    }
+
+    /// Adds an [`AnimationClip`] to the two-dimensional blend space
+    /// `blend_space` at the given `position`, and returns its index.
+    ///
+    /// Returns `None`, leaving the graph unchanged, if `blend_space` isn't a
+    /// [`AnimationNodeType::BlendSpace2D`] node.
+    pub fn add_blend_space_clip_2d(
+        &mut self,
+        clip: Handle<AnimationClip>,
+        position: Vec2,
+        blend_space: AnimationNodeIndex,
+    ) -> Option<AnimationNodeIndex> {
+        let AnimationNodeType::BlendSpace2D(_) = self.graph.node_weight(blend_space)?.node_type
+        else {
+            return None;
+        };
+        let node = self.add_clip(clip, 1.0, blend_space);
+        if let AnimationNodeType::BlendSpace2D(ref mut blend_space) =
+            self.graph[blend_space].node_type
+        {
+            blend_space
+                .points
+                .push(BlendSpace2DPoint { node, position });
+        }
+        Some(node)
+    }
+
     /// Adds an edge from the edge `from` to `to`, making `to` a child of
     /// `from`.
     ///
@@ -661,6 +789,22 @@ impl AnimationGraph {
     pub fn add_target_to_mask_group(&mut self, target: AnimationTargetId, mask_group: u32) {
         *self.mask_groups.entry(target).or_default() |= 1 << mask_group;
     }
+
+    /// Makes the clip nodes that are children of `node` a sync group, kept in
+    /// phase according to the given `mode`.
+    ///
+    /// Calling this method again for the same node replaces its mode. A clip
+    /// that is a child of several sync groups only belongs to the group with
+    /// the lowest node index.
+    pub fn add_sync_group(&mut self, node: AnimationNodeIndex, mode: AnimationSyncMode) {
+        self.sync_groups.insert(node, mode);
+    }
+
+    /// Removes the sync group of the children of `node`, if any, and returns
+    /// its mode.
+    pub fn remove_sync_group(&mut self, node: AnimationNodeIndex) -> Option<AnimationSyncMode> {
+        self.sync_groups.remove(&node)
+    }
 }
 
 impl AnimationGraphNode {
@@ -771,6 +915,12 @@ impl AssetLoader for AnimationGraphAssetLoader {
                         },
                         SerializedAnimationNodeType::Blend => AnimationNodeType::Blend,
                         SerializedAnimationNodeType::Add => AnimationNodeType::Add,
+                        SerializedAnimationNodeType::BlendSpace1D(ref blend_space) => {
+                            AnimationNodeType::BlendSpace1D(blend_space.clone())
+                        }
+                        SerializedAnimationNodeType::BlendSpace2D(ref blend_space) => {
+                            AnimationNodeType::BlendSpace2D(blend_space.clone())
+                        }
                     },
                     mask: serialized_node.mask,
                     weight: serialized_node.weight,
@@ -779,6 +929,7 @@ impl AssetLoader for AnimationGraphAssetLoader {
             ),
             root: serialized_animation_graph.root,
             mask_groups: serialized_animation_graph.mask_groups,
+            sync_groups: serialized_animation_graph.sync_groups,
         })
     }
 
@@ -808,12 +959,19 @@ impl From<AnimationGraph> for SerializedAnimationGraph {
                         },
                         AnimationNodeType::Blend => SerializedAnimationNodeType::Blend,
                         AnimationNodeType::Add => SerializedAnimationNodeType::Add,
+                        AnimationNodeType::BlendSpace1D(ref blend_space) => {
+                            SerializedAnimationNodeType::BlendSpace1D(blend_space.clone())
+                        }
+                        AnimationNodeType::BlendSpace2D(ref blend_space) => {
+                            SerializedAnimationNodeType::BlendSpace2D(blend_space.clone())
+                        }
                     },
                 },
                 |_, _| (),
             ),
             root: animation_graph.root,
             mask_groups: animation_graph.mask_groups,
+            sync_groups: animation_graph.sync_groups,
         }
     }
 }
@@ -926,3 +1084,103 @@ impl ThreadedAnimationGraph {
         self.threaded_graph.push(node_index);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::weak_handle;
+    use bevy_math::Vec2;
+
+    use super::*;
+
+    #[test]
+    fn serialized_blend_spaces_round_trip() {
+        let mut graph = AnimationGraph::new();
+        let locomotion = graph.add_blend_space_1d("speed", 0.5, graph.root);
+        let idle = graph
+            .add_blend_space_clip_1d(
+                weak_handle!("00000000-0000-0000-0000-000000000001"),
+                0.0,
+                locomotion,
+            )
+            .unwrap();
+        let run = graph
+            .add_blend_space_clip_1d(
+                weak_handle!("00000000-0000-0000-0000-000000000002"),
+                4.0,
+                locomotion,
+            )
+            .unwrap();
+        let strafe = graph.add_blend_space_2d("x", "y", 1.0, graph.root);
+        let left = graph
+            .add_blend_space_clip_2d(
+                weak_handle!("00000000-0000-0000-0000-000000000003"),
+                Vec2::NEG_X,
+                strafe,
+            )
+            .unwrap();
+        let forward = graph
+            .add_blend_space_clip_2d(
+                weak_handle!("00000000-0000-0000-0000-000000000004"),
+                Vec2::Y,
+                strafe,
+            )
+            .unwrap();
+
+        // Clips can only be added to blend spaces of the matching dimension.
+        let node_count = graph.graph.node_count();
+        assert!(graph
+            .add_blend_space_clip_1d(
+                weak_handle!("00000000-0000-0000-0000-000000000005"),
+                1.0,
+                strafe,
+            )
+            .is_none());
+        assert!(graph
+            .add_blend_space_clip_2d(
+                weak_handle!("00000000-0000-0000-0000-000000000005"),
+                Vec2::X,
+                graph.root,
+            )
+            .is_none());
+        assert_eq!(graph.graph.node_count(), node_count);
+
+        let mut bytes = Vec::new();
+        graph.save(&mut bytes).unwrap();
+        let serialized: SerializedAnimationGraph = ron::de::from_bytes(&bytes).unwrap();
+
+        let SerializedAnimationNodeType::BlendSpace1D(ref blend_space) =
+            serialized.graph[locomotion].node_type
+        else {
+            panic!("the 1D blend space was serialized as another node type");
+        };
+        assert_eq!(serialized.graph[locomotion].weight, 0.5);
+        assert_eq!(blend_space.parameter, "speed");
+        assert_eq!(
+            blend_space
+                .points
+                .iter()
+                .map(|point| (point.node, point.position))
+                .collect::<Vec<_>>(),
+            [(idle, 0.0), (run, 4.0)]
+        );
+
+        let SerializedAnimationNodeType::BlendSpace2D(ref blend_space) =
+            serialized.graph[strafe].node_type
+        else {
+            panic!("the 2D blend space was serialized as another node type");
+        };
+        assert_eq!(blend_space.parameters, ["x", "y"]);
+        assert_eq!(
+            blend_space
+                .points
+                .iter()
+                .map(|point| (point.node, point.position))
+                .collect::<Vec<_>>(),
+            [(left, Vec2::NEG_X), (forward, Vec2::Y)]
+        );
+        assert!(serialized
+            .graph
+            .neighbors(strafe)
+            .all(|child| child == left || child == forward));
+    }
+}
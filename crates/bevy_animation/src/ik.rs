@@ -0,0 +1,575 @@
+//! Inverse kinematics, which adjusts the pose of animated joints so that they
+//! reach or face a target.
+//!
+//! The solvers run as post-animation passes: after [`animate_targets`] has
+//! posed the joints, and before transform propagation. They only change the
+//! rotations of the [`Transform`]s of joints, so the lengths of bones are
+//! preserved. Each solver has a `weight`, which blends between the animated
+//! pose (`0.0`) and the fully solved pose (`1.0`).
+//!
+//! The IK components are added to joints, which are typically the
+//! [`AnimationTarget`](crate::AnimationTarget)s of an armature:
+//!
+//! * [`TwoBoneIk`] solves a joint and its two ancestors analytically, as for
+//!   placing a foot or a hand.
+//!
+//! * [`ChainIk`] solves a chain of any length iteratively, as for tails and
+//!   tentacles.
+//!
+//! * [`LookAtIk`] rotates a single joint to face the target, as for aiming a
+//!   head or a weapon.
+//!
+//! [`animate_targets`]: crate::animate_targets
+
+use bevy_ecs::{
+    component::Component, entity::Entity, hierarchy::ChildOf, reflect::ReflectComponent,
+    system::Query,
+};
+use bevy_math::{ops, Dir3, Quat, Vec3};
+use bevy_reflect::{prelude::ReflectDefault, Reflect};
+use bevy_transform::components::Transform;
+
+/// Solves a joint and its parent and grandparent so that the joint reaches a
+/// target, as for placing a foot on the ground.
+///
+/// Add this component to the end of a limb, such as a foot. Its parent, such
+/// as the knee, bends so that the end reaches the [`target`](Self::target),
+/// and its grandparent, such as the hip, turns the limb toward the target.
+/// If the target is out of reach, the limb extends fully toward it.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component, Debug)]
+pub struct TwoBoneIk {
+    /// The entity whose position the end of the limb reaches.
+    #[entities]
+    pub target: Entity,
+    /// An entity whose position the middle joint bends toward, such as a
+    /// point in front of a knee.
+    ///
+    /// If `None`, the limb keeps bending in the direction it's animated in.
+    #[entities]
+    pub pole: Option<Entity>,
+    /// The blend between the animated pose (`0.0`) and the solved pose
+    /// (`1.0`).
+    pub weight: f32,
+}
+
+impl TwoBoneIk {
+    /// Creates a new [`TwoBoneIk`] reaching `target`, with full weight and no
+    /// pole.
+    pub fn new(target: Entity) -> Self {
+        Self {
+            target,
+            pole: None,
+            weight: 1.0,
+        }
+    }
+
+    /// Sets the entity the middle joint bends toward.
+    pub fn with_pole(mut self, pole: Entity) -> Self {
+        self.pole = Some(pole);
+        self
+    }
+
+    /// Sets the blend weight of the solved pose.
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+/// The algorithm used to solve a [`ChainIk`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default, Debug, PartialEq)]
+pub enum ChainIkSolver {
+    /// Forward And Backward Reaching Inverse Kinematics, which spreads the
+    /// bending smoothly over the whole chain.
+    #[default]
+    Fabrik,
+    /// Cyclic Coordinate Descent, which favors bending the joints closest to
+    /// the end of the chain.
+    Ccd,
+}
+
+/// Solves a chain of joints ending at this joint so that it reaches a target.
+///
+/// The chain is made of this joint and its `chain_length` closest ancestors,
+/// all of which are rotated.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component, Debug)]
+pub struct ChainIk {
+    /// The entity whose position the end of the chain reaches.
+    #[entities]
+    pub target: Entity,
+    /// The number of bones in the chain, that is the number of ancestors of
+    /// this joint that are rotated.
+    pub chain_length: usize,
+    /// The algorithm used to solve the chain.
+    pub solver: ChainIkSolver,
+    /// The maximum number of iterations of the solver.
+    pub iterations: u32,
+    /// The distance to the target under which the chain is considered
+    /// solved.
+    pub tolerance: f32,
+    /// The blend between the animated pose (`0.0`) and the solved pose
+    /// (`1.0`).
+    pub weight: f32,
+}
+
+impl ChainIk {
+    /// Creates a new [`ChainIk`] of `chain_length` bones reaching `target`,
+    /// solved with [`ChainIkSolver::Fabrik`] and full weight.
+    pub fn new(target: Entity, chain_length: usize) -> Self {
+        Self {
+            target,
+            chain_length,
+            solver: ChainIkSolver::Fabrik,
+            iterations: 10,
+            tolerance: 0.001,
+            weight: 1.0,
+        }
+    }
+
+    /// Sets the algorithm used to solve the chain.
+    pub fn with_solver(mut self, solver: ChainIkSolver) -> Self {
+        self.solver = solver;
+        self
+    }
+
+    /// Sets the blend weight of the solved pose.
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+/// Rotates this joint so that one of its local axes points at a target, as
+/// for aiming a head or a weapon.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component, Debug)]
+pub struct LookAtIk {
+    /// The entity this joint looks at.
+    #[entities]
+    pub target: Entity,
+    /// The local axis of this joint that points at the target.
+    pub axis: Dir3,
+    /// The blend between the animated pose (`0.0`) and the solved pose
+    /// (`1.0`).
+    pub weight: f32,
+}
+
+impl LookAtIk {
+    /// Creates a new [`LookAtIk`] pointing the local forward axis ([`Dir3::NEG_Z`]) at
+    /// `target`, with full weight.
+    pub fn new(target: Entity) -> Self {
+        Self {
+            target,
+            axis: Dir3::NEG_Z,
+            weight: 1.0,
+        }
+    }
+
+    /// Sets the local axis that points at the target.
+    pub fn with_axis(mut self, axis: Dir3) -> Self {
+        self.axis = axis;
+        self
+    }
+
+    /// Sets the blend weight of the solved pose.
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+/// The joints of an IK chain from its root to its end, in world space.
+struct IkChain {
+    entities: Vec<Entity>,
+    positions: Vec<Vec3>,
+    rotations: Vec<Quat>,
+    /// The world rotation of the parent of the root of the chain.
+    parent_rotation: Quat,
+}
+
+impl IkChain {
+    /// Collects the chain made of `end` and its `length` closest ancestors.
+    fn new(
+        end: Entity,
+        length: usize,
+        transforms: &Query<&mut Transform>,
+        parents: &Query<&ChildOf>,
+    ) -> Option<Self> {
+        let mut entities = vec![end];
+        for _ in 0..length {
+            let parent = parents.get(*entities.last().unwrap()).ok()?.get();
+            entities.push(parent);
+        }
+        entities.reverse();
+
+        let root = entities[0];
+        let parent_rotation = parents
+            .get(root)
+            .ok()
+            .and_then(|parent| world_transform(parent.get(), transforms, parents))
+            .map_or(Quat::IDENTITY, |parent| parent.rotation);
+        let (positions, rotations) = entities
+            .iter()
+            .map(|entity| {
+                world_transform(*entity, transforms, parents)
+                    .map(|transform| (transform.translation, transform.rotation))
+            })
+            .collect::<Option<_>>()?;
+
+        Some(Self {
+            entities,
+            positions,
+            rotations,
+            parent_rotation,
+        })
+    }
+
+    fn end(&self) -> Vec3 {
+        *self.positions.last().unwrap()
+    }
+
+    /// Rotates the joint at `index`, and the joints after it along with it,
+    /// by the world space `rotation`.
+    fn rotate(&mut self, index: usize, rotation: Quat) {
+        let pivot = self.positions[index];
+        for joint in index..self.positions.len() {
+            self.positions[joint] = pivot + rotation * (self.positions[joint] - pivot);
+            self.rotations[joint] = (rotation * self.rotations[joint]).normalize();
+        }
+    }
+
+    /// Writes the local rotations of all joints but the end, blended with the
+    /// animated pose by `weight`.
+    fn apply(&self, weight: f32, transforms: &mut Query<&mut Transform>) {
+        let mut parent_rotation = self.parent_rotation;
+        for (entity, rotation) in self.entities.iter().zip(&self.rotations) {
+            if *entity == *self.entities.last().unwrap() {
+                break;
+            }
+            let local_rotation = parent_rotation.inverse() * *rotation;
+            if let Ok(mut transform) = transforms.get_mut(*entity) {
+                transform.rotation = transform
+                    .rotation
+                    .slerp(local_rotation, weight.clamp(0.0, 1.0));
+            }
+            parent_rotation = *rotation;
+        }
+    }
+
+    /// Analytically solves a chain of two bones.
+    fn solve_two_bone(&mut self, target: Vec3, pole: Option<Vec3>) {
+        let [a, b, c] = [self.positions[0], self.positions[1], self.positions[2]];
+        let length_ab = a.distance(b);
+        let length_bc = b.distance(c);
+        let length_at = a
+            .distance(target)
+            .clamp(f32::EPSILON, length_ab + length_bc - f32::EPSILON);
+
+        // The plane the limb bends in.
+        let bend_axis = (c - a)
+            .cross(b - a)
+            .try_normalize()
+            .or_else(|| (c - a).cross(pole? - a).try_normalize())
+            .unwrap_or_else(|| (c - a).any_orthonormal_vector());
+
+        // Bend the middle joint so that the limb is as long as the distance
+        // to the target, then turn the limb toward the target.
+        let ac_ab = angle_between(c - a, b - a);
+        let ba_bc = angle_between(a - b, c - b);
+        let ac_ab_solved = cosine_rule(length_ab, length_at, length_bc);
+        let ba_bc_solved = cosine_rule(length_ab, length_bc, length_at);
+        self.rotate(1, Quat::from_axis_angle(bend_axis, ba_bc_solved - ba_bc));
+        self.rotate(0, Quat::from_axis_angle(bend_axis, ac_ab_solved - ac_ab));
+        if let (Some(from), Some(to)) = (
+            (self.positions[2] - a).try_normalize(),
+            (target - a).try_normalize(),
+        ) {
+            self.rotate(0, Quat::from_rotation_arc(from, to));
+        }
+
+        // Swing the limb around the line to the target toward the pole.
+        let Some(pole) = pole else {
+            return;
+        };
+        let Some(axis) = (target - a).try_normalize() else {
+            return;
+        };
+        let from = (self.positions[1] - a).reject_from_normalized(axis);
+        let to = (pole - a).reject_from_normalized(axis);
+        if let (Some(from), Some(to)) = (from.try_normalize(), to.try_normalize()) {
+            self.rotate(0, Quat::from_rotation_arc(from, to));
+        }
+    }
+
+    /// Iteratively solves the chain with FABRIK.
+    fn solve_fabrik(&mut self, target: Vec3, iterations: u32, tolerance: f32) {
+        let count = self.positions.len();
+        let lengths: Vec<f32> = self
+            .positions
+            .windows(2)
+            .map(|joints| joints[0].distance(joints[1]))
+            .collect();
+        let root = self.positions[0];
+        let mut positions = self.positions.clone();
+
+        for _ in 0..iterations {
+            if positions[count - 1].distance(target) <= tolerance {
+                break;
+            }
+
+            // Backward pass, from the end to the root.
+            positions[count - 1] = target;
+            for joint in (0..count - 1).rev() {
+                let direction = (positions[joint] - positions[joint + 1]).normalize_or_zero();
+                positions[joint] = positions[joint + 1] + direction * lengths[joint];
+            }
+
+            // Forward pass, from the root to the end.
+            positions[0] = root;
+            for joint in 0..count - 1 {
+                let direction = (positions[joint + 1] - positions[joint]).normalize_or_zero();
+                positions[joint + 1] = positions[joint] + direction * lengths[joint];
+            }
+        }
+
+        // Turn each bone toward its solved direction.
+        for joint in 0..count - 1 {
+            let from = (self.positions[joint + 1] - self.positions[joint]).try_normalize();
+            let to = (positions[joint + 1] - positions[joint]).try_normalize();
+            if let (Some(from), Some(to)) = (from, to) {
+                self.rotate(joint, Quat::from_rotation_arc(from, to));
+            }
+        }
+    }
+
+    /// Iteratively solves the chain with CCD.
+    fn solve_ccd(&mut self, target: Vec3, iterations: u32, tolerance: f32) {
+        let count = self.positions.len();
+        for _ in 0..iterations {
+            if self.end().distance(target) <= tolerance {
+                break;
+            }
+            for joint in (0..count - 1).rev() {
+                let from = (self.end() - self.positions[joint]).try_normalize();
+                let to = (target - self.positions[joint]).try_normalize();
+                if let (Some(from), Some(to)) = (from, to) {
+                    self.rotate(joint, Quat::from_rotation_arc(from, to));
+                }
+            }
+        }
+    }
+}
+
+/// Returns the angle between two vectors, or zero if either is zero.
+fn angle_between(a: Vec3, b: Vec3) -> f32 {
+    match (a.try_normalize(), b.try_normalize()) {
+        (Some(a), Some(b)) => ops::acos(a.dot(b).clamp(-1.0, 1.0)),
+        _ => 0.0,
+    }
+}
+
+/// Returns the angle between the sides `a` and `b` of a triangle whose third
+/// side is `c`.
+fn cosine_rule(a: f32, b: f32, c: f32) -> f32 {
+    ops::acos(((a * a + b * b - c * c) / (2.0 * a * b)).clamp(-1.0, 1.0))
+}
+
+/// Computes the world space transform of `entity` from the local transforms
+/// of it and its ancestors.
+///
+/// [`GlobalTransform`](bevy_transform::components::GlobalTransform)s can't be
+/// used here, because they aren't propagated yet when the solvers run.
+fn world_transform(
+    entity: Entity,
+    transforms: &Query<&mut Transform>,
+    parents: &Query<&ChildOf>,
+) -> Option<Transform> {
+    let mut world = *transforms.get(entity).ok()?;
+    let mut current = entity;
+    while let Ok(parent) = parents.get(current) {
+        let Ok(parent_transform) = transforms.get(parent.get()) else {
+            break;
+        };
+        world = parent_transform.mul_transform(world);
+        current = parent.get();
+    }
+    Some(world)
+}
+
+/// A system that solves every [`TwoBoneIk`].
+pub fn solve_two_bone_ik(
+    solvers: Query<(Entity, &TwoBoneIk)>,
+    mut transforms: Query<&mut Transform>,
+    parents: Query<&ChildOf>,
+) {
+    for (entity, ik) in &solvers {
+        let Some(target) = world_transform(ik.target, &transforms, &parents) else {
+            continue;
+        };
+        let pole = ik
+            .pole
+            .and_then(|pole| world_transform(pole, &transforms, &parents));
+        let Some(mut chain) = IkChain::new(entity, 2, &transforms, &parents) else {
+            continue;
+        };
+        chain.solve_two_bone(
+            target.translation,
+            pole.map(|transform| transform.translation),
+        );
+        chain.apply(ik.weight, &mut transforms);
+    }
+}
+
+/// A system that solves every [`ChainIk`].
+pub fn solve_chain_ik(
+    solvers: Query<(Entity, &ChainIk)>,
+    mut transforms: Query<&mut Transform>,
+    parents: Query<&ChildOf>,
+) {
+    for (entity, ik) in &solvers {
+        if ik.chain_length == 0 {
+            continue;
+        }
+        let Some(target) = world_transform(ik.target, &transforms, &parents) else {
+            continue;
+        };
+        let Some(mut chain) = IkChain::new(entity, ik.chain_length, &transforms, &parents) else {
+            continue;
+        };
+        match ik.solver {
+            ChainIkSolver::Fabrik => {
+                chain.solve_fabrik(target.translation, ik.iterations, ik.tolerance);
+            }
+            ChainIkSolver::Ccd => chain.solve_ccd(target.translation, ik.iterations, ik.tolerance),
+        }
+        chain.apply(ik.weight, &mut transforms);
+    }
+}
+
+/// A system that solves every [`LookAtIk`].
+pub fn solve_look_at_ik(
+    solvers: Query<(Entity, &LookAtIk)>,
+    mut transforms: Query<&mut Transform>,
+    parents: Query<&ChildOf>,
+) {
+    for (entity, ik) in &solvers {
+        let (Some(target), Some(joint)) = (
+            world_transform(ik.target, &transforms, &parents),
+            world_transform(entity, &transforms, &parents),
+        ) else {
+            continue;
+        };
+        let Some(direction) = (target.translation - joint.translation).try_normalize() else {
+            continue;
+        };
+        let parent_rotation = parents
+            .get(entity)
+            .ok()
+            .and_then(|parent| world_transform(parent.get(), &transforms, &parents))
+            .map_or(Quat::IDENTITY, |parent| parent.rotation);
+
+        let rotation = Quat::from_rotation_arc(joint.rotation * *ik.axis, direction);
+        let local_rotation = parent_rotation.inverse() * rotation * joint.rotation;
+        if let Ok(mut transform) = transforms.get_mut(entity) {
+            transform.rotation = transform
+                .rotation
+                .slerp(local_rotation, ik.weight.clamp(0.0, 1.0));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{schedule::Schedule, system::SystemState, world::World};
+
+    /// Spawns a chain of joints one unit apart along +Y, and returns them
+    /// from the root to the end.
+    fn spawn_chain(world: &mut World, joints: usize) -> Vec<Entity> {
+        let mut entities = vec![world.spawn(Transform::default()).id()];
+        for _ in 1..joints {
+            let parent = *entities.last().unwrap();
+            let joint = world
+                .spawn((Transform::from_xyz(0.0, 1.0, 0.0), ChildOf(parent)))
+                .id();
+            entities.push(joint);
+        }
+        entities
+    }
+
+    fn world_position(world: &mut World, entity: Entity) -> Vec3 {
+        let mut state = SystemState::<(Query<&mut Transform>, Query<&ChildOf>)>::new(world);
+        let (transforms, parents) = state.get_mut(world);
+        world_transform(entity, &transforms, &parents)
+            .unwrap()
+            .translation
+    }
+
+    #[test]
+    fn two_bone_ik_reaches_target() {
+        let mut world = World::new();
+        let joints = spawn_chain(&mut world, 3);
+        let target = world.spawn(Transform::from_xyz(1.0, 1.0, 0.0)).id();
+        let pole = world.spawn(Transform::from_xyz(0.0, 1.0, 5.0)).id();
+        world
+            .entity_mut(joints[2])
+            .insert(TwoBoneIk::new(target).with_pole(pole));
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(solve_two_bone_ik);
+        schedule.run(&mut world);
+
+        let end = world_position(&mut world, joints[2]);
+        assert!(end.distance(Vec3::new(1.0, 1.0, 0.0)) < 1e-4, "{end}");
+        // The knee bends toward the pole.
+        assert!(world_position(&mut world, joints[1]).z > 0.1);
+    }
+
+    #[test]
+    fn chain_ik_reaches_target() {
+        for solver in [ChainIkSolver::Fabrik, ChainIkSolver::Ccd] {
+            let mut world = World::new();
+            let joints = spawn_chain(&mut world, 4);
+            let target = world.spawn(Transform::from_xyz(1.5, 1.5, 0.5)).id();
+            let mut ik = ChainIk::new(target, 3).with_solver(solver);
+            ik.iterations = 50;
+            world.entity_mut(joints[3]).insert(ik);
+
+            let mut schedule = Schedule::default();
+            schedule.add_systems(solve_chain_ik);
+            schedule.run(&mut world);
+
+            let end = world_position(&mut world, joints[3]);
+            assert!(
+                end.distance(Vec3::new(1.5, 1.5, 0.5)) < 0.01,
+                "{solver:?}: {end}"
+            );
+        }
+    }
+
+    #[test]
+    fn look_at_ik_blends_with_weight() {
+        let mut world = World::new();
+        let joints = spawn_chain(&mut world, 2);
+        let target = world.spawn(Transform::from_xyz(0.0, 1.0, 1.0)).id();
+        world
+            .entity_mut(joints[1])
+            .insert(LookAtIk::new(target).with_axis(Dir3::Y).with_weight(0.5));
+        world
+            .entity_mut(joints[0])
+            .insert(Transform::from_rotation(Quat::from_rotation_y(1.0)));
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(solve_look_at_ik);
+        schedule.run(&mut world);
+
+        // Halfway between pointing up and pointing toward +Z.
+        let rotation = world.get::<Transform>(joints[1]).unwrap().rotation;
+        let world_axis = Quat::from_rotation_y(1.0) * rotation * Vec3::Y;
+        let expected = Vec3::new(0.0, 1.0, 1.0).normalize();
+        assert!(world_axis.distance(expected) < 1e-4, "{world_axis}");
+    }
+}
@@ -11,9 +11,18 @@ extern crate alloc;
 
 pub mod animatable;
 pub mod animation_curves;
+pub mod blend_space;
+pub mod compression;
+pub mod curve_asset;
 pub mod gltf_curves;
 pub mod graph;
+pub mod ik;
+pub mod retarget;
+pub mod root_motion;
+pub mod state_machine;
+pub mod sync;
 pub mod transition;
+pub mod tween;
 mod util;
 
 use core::{
@@ -34,7 +43,7 @@ use crate::{
 use bevy_app::{Animation, App, Plugin, PostUpdate};
 use bevy_asset::{Asset, AssetApp, AssetEvents, Assets};
 use bevy_ecs::{prelude::*, world::EntityMutExcept};
-use bevy_math::FloatOrd;
+use bevy_math::{FloatOrd, Vec2, Vec3, Vec4};
 use bevy_platform_support::{collections::HashMap, hash::NoOpHash};
 use bevy_reflect::{prelude::ReflectDefault, Reflect, TypePath};
 use bevy_time::Time;
@@ -52,14 +61,27 @@ use uuid::Uuid;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        animatable::*, animation_curves::*, graph::*, transition::*, AnimationClip,
-        AnimationPlayer, AnimationPlugin, VariableCurve,
+        animatable::*, animation_curves::*, blend_space::*, graph::*, ik::*, state_machine::*,
+        sync::*, transition::*, tween::*, AnimationClip, AnimationPlayer, AnimationPlugin,
+        VariableCurve,
     };
 }
 
 use crate::{
     animation_curves::AnimationCurve,
+    blend_space::{BlendSpace1D, BlendSpace2D},
+    compression::CompressedAnimationClipLoader,
+    curve_asset::{CurveAsset, CurveAssetLoader, CurveWrapMode, KeyframeInterpolation},
     graph::{AnimationGraph, AnimationGraphAssetLoader, AnimationNodeIndex},
+    ik::{ChainIk, LookAtIk, TwoBoneIk},
+    retarget::{BoneMapping, BoneMappingAssetLoader},
+    root_motion::{RootMotion, RootMotionDelta},
+    state_machine::{
+        advance_animation_state_machines, AnimationParameters, AnimationStateMachine,
+        AnimationStateMachineAssetLoader, AnimationStateMachineHandle,
+        AnimationStateMachineInstance,
+    },
+    sync::{AnimationSyncMode, ResolvedSyncGroups},
     transition::{advance_transitions, expire_completed_transitions, AnimationTransitions},
 };
 use alloc::sync::Arc;
@@ -102,6 +124,7 @@ pub struct AnimationClip {
     curves: AnimationCurves,
     events: AnimationEvents,
     duration: f32,
+    sync_markers: Vec<f32>,
 }
 
 #[derive(Reflect, Debug, Clone)]
@@ -262,6 +285,32 @@ impl AnimationClip {
         self.duration = duration_sec;
     }
 
+    /// The times of the sync markers of the clip, in seconds, in ascending
+    /// order.
+    ///
+    /// See [`add_sync_marker`](Self::add_sync_marker) for more information.
+    #[inline]
+    pub fn sync_markers(&self) -> &[f32] {
+        &self.sync_markers
+    }
+
+    /// Adds a sync marker at the given `time`, in seconds.
+    ///
+    /// Sync markers divide the cycle of a clip into phases, such as the times
+    /// at which each foot touches the ground in a walk cycle. When clips with
+    /// markers are played in the same [sync group], their markers are aligned,
+    /// so that their feet touch the ground at the same time even if the steps
+    /// aren't evenly spaced in both clips. Clips without markers are synced by
+    /// their normalized time instead.
+    ///
+    /// [sync group]: crate::sync::AnimationSyncMode
+    pub fn add_sync_marker(&mut self, time: f32) {
+        let index = self.sync_markers.partition_point(|&marker| marker < time);
+        if self.sync_markers.get(index) != Some(&time) {
+            self.sync_markers.insert(index, time);
+        }
+    }
+
     /// Adds an [`AnimationCurve`] to an [`AnimationTarget`] named by an
     /// [`AnimationTargetId`].
     ///
@@ -547,6 +596,28 @@ impl ActiveAnimation {
         }
     }
 
+    /// Moves the animation to `seek_time` to follow the leader of its sync
+    /// group, given the delta time and the playback direction of the leader.
+    fn sync(&mut self, seek_time: f32, delta: f32, reversed: bool) {
+        self.just_completed = false;
+        self.last_seek_time = Some(self.seek_time);
+
+        if self.is_finished() {
+            return;
+        }
+
+        self.elapsed += delta;
+        let wrapped = match reversed {
+            false => seek_time < self.seek_time,
+            true => seek_time > self.seek_time,
+        };
+        if wrapped {
+            self.just_completed = true;
+            self.completions += 1;
+        }
+        self.seek_time = seek_time;
+    }
+
     /// Reset back to the initial state as if no time has elapsed.
     pub fn replay(&mut self) {
         self.just_completed = false;
@@ -956,7 +1027,7 @@ fn trigger_untargeted_animation_events(
                 .get(*index)
                 .and_then(|node| match &node.node_type {
                     AnimationNodeType::Clip(handle) => Some(handle),
-                    AnimationNodeType::Blend | AnimationNodeType::Add => None,
+                    _ => None,
                 })
                 .and_then(|id| clips.get(id))
             else {
@@ -998,20 +1069,33 @@ pub fn advance_animations(
                 ..
             } = *player;
 
+            let sync_groups =
+                ResolvedSyncGroups::new(animation_graph, &animation_clips, active_animations);
+
             for node_index in animation_graph.graph.node_indices() {
                 let node = &animation_graph[node_index];
 
+                // Followers of sync groups are moved by their leaders below.
+                if sync_groups.is_follower(node_index) {
+                    continue;
+                }
+
                 if let Some(active_animation) = active_animations.get_mut(&node_index) {
                     // Tick the animation if necessary.
                     if !active_animation.paused {
                         if let AnimationNodeType::Clip(ref clip_handle) = node.node_type {
                             if let Some(clip) = animation_clips.get(clip_handle) {
-                                active_animation.update(delta_seconds, clip.duration);
+                                active_animation.update(
+                                    delta_seconds * sync_groups.time_scale(node_index),
+                                    clip.duration,
+                                );
                             }
                         }
                     }
                 }
             }
+
+            sync_groups.sync_followers(active_animations, delta_seconds);
         });
 }
 
@@ -1082,8 +1166,11 @@ pub fn animate_targets(
                 };
 
                 match animation_graph_node.node_type {
-                    AnimationNodeType::Blend => {
-                        // This is a blend node.
+                    AnimationNodeType::Blend
+                    | AnimationNodeType::BlendSpace1D(_)
+                    | AnimationNodeType::BlendSpace2D(_) => {
+                        // This is a blend node, or a blend space whose weights
+                        // were given to its children's active animations.
                         for edge_index in threaded_animation_graph.sorted_edge_ranges
                             [animation_graph_node_index.index()]
                         .clone()
@@ -1226,21 +1313,55 @@ impl Plugin for AnimationPlugin {
     fn build(&self, app: &mut App) {
         app.init_asset::<AnimationClip>()
             .init_asset::<AnimationGraph>()
+            .init_asset::<AnimationStateMachine>()
             .init_asset_loader::<AnimationGraphAssetLoader>()
+            .init_asset::<BoneMapping>()
+            .init_asset_loader::<AnimationStateMachineAssetLoader>()
+            .init_asset_loader::<BoneMappingAssetLoader>()
+            .init_asset_loader::<CompressedAnimationClipLoader>()
             .register_asset_reflect::<AnimationClip>()
             .register_asset_reflect::<AnimationGraph>()
+            .register_asset_reflect::<AnimationStateMachine>()
+            .register_asset_reflect::<BoneMapping>()
+            .init_asset::<CurveAsset<f32>>()
+            .init_asset::<CurveAsset<Vec2>>()
+            .init_asset::<CurveAsset<Vec3>>()
+            .init_asset::<CurveAsset<Vec4>>()
+            .register_asset_loader(CurveAssetLoader::<f32>::new(&["f32.curve.ron"]))
+            .register_asset_loader(CurveAssetLoader::<Vec2>::new(&["vec2.curve.ron"]))
+            .register_asset_loader(CurveAssetLoader::<Vec3>::new(&["vec3.curve.ron"]))
+            .register_asset_loader(CurveAssetLoader::<Vec4>::new(&["vec4.curve.ron"]))
+            .register_asset_reflect::<CurveAsset<f32>>()
+            .register_asset_reflect::<CurveAsset<Vec2>>()
+            .register_asset_reflect::<CurveAsset<Vec3>>()
+            .register_asset_reflect::<CurveAsset<Vec4>>()
             .register_type::<AnimationPlayer>()
             .register_type::<AnimationTarget>()
             .register_type::<AnimationTransitions>()
             .register_type::<AnimationGraphHandle>()
+            .register_type::<AnimationStateMachineHandle>()
+            .register_type::<AnimationStateMachineInstance>()
+            .register_type::<AnimationParameters>()
+            .register_type::<TwoBoneIk>()
+            .register_type::<ChainIk>()
+            .register_type::<LookAtIk>()
+            .register_type::<RootMotion>()
+            .register_type::<RootMotionDelta>()
+            .register_type::<AnimationSyncMode>()
+            .register_type::<BlendSpace1D>()
+            .register_type::<BlendSpace2D>()
             .register_type::<NodeIndex>()
             .register_type::<ThreadedAnimationGraphs>()
+            .register_type::<KeyframeInterpolation>()
+            .register_type::<CurveWrapMode>()
             .init_resource::<ThreadedAnimationGraphs>()
             .add_systems(
                 PostUpdate,
                 (
                     graph::thread_animation_graphs.before(AssetEvents),
+                    advance_animation_state_machines,
                     advance_transitions,
+                    blend_space::update_blend_spaces,
                     advance_animations,
                     // TODO: `animate_targets` can animate anything, so
                     // ambiguity testing currently considers it ambiguous with
@@ -1251,6 +1372,16 @@ impl Plugin for AnimationPlugin {
                     animate_targets
                         .before(bevy_render::mesh::inherit_weights)
                         .ambiguous_with_all(),
+                    tween::advance_tweens,
+                    root_motion::extract_root_motion,
+                    // Inverse kinematics adjusts the animated pose, before
+                    // it's propagated.
+                    (
+                        ik::solve_two_bone_ik,
+                        ik::solve_chain_ik,
+                        ik::solve_look_at_ik,
+                    )
+                        .chain(),
                     trigger_untargeted_animation_events,
                     expire_completed_transitions,
                 )
@@ -0,0 +1,589 @@
+//! Animation retargeting, which adapts clips authored for one skeleton so that
+//! they play on another skeleton with different bone names and proportions.
+//!
+//! Since [`AnimationTargetId`]s are derived from the name paths of joints, a
+//! clip only animates the skeleton it was authored for. Retargeting produces a
+//! new [`AnimationClip`] for the other skeleton, which can be added to an
+//! [`AnimationGraph`](crate::graph::AnimationGraph) and played by an
+//! [`AnimationPlayer`](crate::AnimationPlayer) like any other clip.
+//!
+//! Retargeting needs three things:
+//!
+//! * A [`BoneMapping`] between the name paths of the joints of both
+//!   skeletons. Bone mappings are assets, so that one can be written once per
+//!   rig and shared between all the clips.
+//!
+//! * The [`SkeletonRestPose`] of both skeletons, which the animated rotations
+//!   are corrected against.
+//!
+//! * An [`AnimationRetargeter`], which combines them and retargets clips.
+
+use core::fmt::Debug;
+use std::io::{self, Write};
+
+use bevy_asset::{io::Reader, Asset, AssetLoader, LoadContext};
+use bevy_math::{
+    curve::{ConstantCurve, Interval, SampleAutoCurve},
+    Quat, StableInterpolate, Vec3,
+};
+use bevy_platform_support::collections::HashMap;
+use bevy_reflect::{
+    prelude::ReflectDefault, FromReflect, Reflect, ReflectDeserialize, ReflectSerialize,
+    Reflectable,
+};
+use bevy_transform::components::Transform;
+use ron::de::SpannedError;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    animatable::Animatable,
+    animated_field,
+    animation_curves::{transform_field, AnimatableCurve, AnimatedField, AnimationCurve},
+    AnimationClip, AnimationEventTarget, AnimationTargetId, VariableCurve,
+};
+
+/// A mapping between the joints of two skeletons, used to retarget clips
+/// from the source skeleton to the target skeleton.
+///
+/// Joints are named by their name paths from the root of their armature, the
+/// same way [`AnimationTargetId`]s are derived.
+///
+/// Bone mappings are assets and can be serialized to and loaded from [RON]
+/// files. Canonically, such files have a `.bonemap.ron` extension.
+///
+/// [RON]: https://github.com/ron-rs/ron
+#[derive(Asset, Reflect, Clone, Debug, Default, Serialize, Deserialize)]
+#[reflect(Default, Debug, Serialize, Deserialize)]
+pub struct BoneMapping {
+    /// The mapped joints.
+    pub bones: Vec<BoneMappingEntry>,
+}
+
+/// A single pair of mapped joints in a [`BoneMapping`].
+#[derive(Reflect, Clone, Debug, Default, Serialize, Deserialize)]
+#[reflect(Default, Debug)]
+pub struct BoneMappingEntry {
+    /// The name path of the joint in the source skeleton.
+    pub source: Vec<String>,
+    /// The name path of the joint in the target skeleton.
+    pub target: Vec<String>,
+}
+
+impl BoneMapping {
+    /// Creates an empty bone mapping.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps the joint at the `source` name path to the joint at the `target`
+    /// name path.
+    pub fn add<S, T>(
+        &mut self,
+        source: impl IntoIterator<Item = S>,
+        target: impl IntoIterator<Item = T>,
+    ) -> &mut Self
+    where
+        S: Into<String>,
+        T: Into<String>,
+    {
+        self.bones.push(BoneMappingEntry {
+            source: source.into_iter().map(Into::into).collect(),
+            target: target.into_iter().map(Into::into).collect(),
+        });
+        self
+    }
+
+    /// Returns the IDs of the mapped source and target joints.
+    pub fn target_ids(&self) -> impl Iterator<Item = (AnimationTargetId, AnimationTargetId)> + '_ {
+        self.bones
+            .iter()
+            .map(|entry| (entry.source.iter().collect(), entry.target.iter().collect()))
+    }
+
+    /// Serializes the bone mapping to the given [`Write`]r in RON format.
+    ///
+    /// If writing to a file, it can later be loaded with the
+    /// [`BoneMappingAssetLoader`] to reconstruct the bone mapping.
+    pub fn save<W>(&self, writer: &mut W) -> Result<(), BoneMappingLoadError>
+    where
+        W: Write,
+    {
+        let mut ron_serializer = ron::ser::Serializer::new(writer, None)?;
+        Ok(self.serialize(&mut ron_serializer)?)
+    }
+}
+
+/// An [`AssetLoader`] that can load [`BoneMapping`]s as assets.
+///
+/// The canonical extension for [`BoneMapping`]s is `.bonemap.ron`. Plain
+/// `.bonemap` is supported as well.
+#[derive(Default)]
+pub struct BoneMappingAssetLoader;
+
+/// Various errors that can occur when serializing or deserializing bone
+/// mappings to and from RON, respectively.
+#[derive(Error, Debug)]
+pub enum BoneMappingLoadError {
+    /// An I/O error occurred.
+    #[error("I/O")]
+    Io(#[from] io::Error),
+    /// An error occurred in RON serialization or deserialization.
+    #[error("RON serialization")]
+    Ron(#[from] ron::Error),
+    /// An error occurred in RON deserialization, and the location of the error
+    /// is supplied.
+    #[error("RON serialization")]
+    SpannedRon(#[from] SpannedError),
+}
+
+impl AssetLoader for BoneMappingAssetLoader {
+    type Asset = BoneMapping;
+
+    type Settings = ();
+
+    type Error = BoneMappingLoadError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _: &Self::Settings,
+        _: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+        BoneMapping::deserialize(&mut deserializer)
+            .map_err(|err| deserializer.span_error(err).into())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["bonemap", "bonemap.ron"]
+    }
+}
+
+/// The local transforms of the joints of a skeleton in its rest pose, such as
+/// its bind pose.
+///
+/// A rest pose can be collected from a spawned skeleton before it's animated:
+///
+/// ```
+/// # use bevy_animation::{retarget::SkeletonRestPose, AnimationTarget};
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_transform::components::Transform;
+/// fn collect_rest_pose(joints: Query<(&AnimationTarget, &Transform)>) -> SkeletonRestPose {
+///     joints
+///         .iter()
+///         .map(|(target, transform)| (target.id, *transform))
+///         .collect()
+/// }
+/// ```
+#[derive(Reflect, Clone, Debug, Default)]
+#[reflect(Default, Debug)]
+pub struct SkeletonRestPose {
+    /// The rest transform of each joint, relative to its parent.
+    pub joints: HashMap<AnimationTargetId, Transform>,
+}
+
+impl SkeletonRestPose {
+    /// Sets the rest transform of the joint `target`.
+    pub fn insert(&mut self, target: AnimationTargetId, transform: Transform) -> &mut Self {
+        self.joints.insert(target, transform);
+        self
+    }
+
+    /// Returns the rest transform of the joint `target`, if known.
+    pub fn get(&self, target: AnimationTargetId) -> Option<&Transform> {
+        self.joints.get(&target)
+    }
+}
+
+impl FromIterator<(AnimationTargetId, Transform)> for SkeletonRestPose {
+    fn from_iter<I: IntoIterator<Item = (AnimationTargetId, Transform)>>(iter: I) -> Self {
+        Self {
+            joints: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Retargets [`AnimationClip`]s from a source skeleton to a target skeleton.
+///
+/// For each joint of the [`BoneMapping`], the curves of the source joint are
+/// moved to the target joint, and the curves of its [`Transform`] are
+/// corrected:
+///
+/// * Rotations are applied relative to the rest pose, so that a joint whose
+///   rest orientation differs between both skeletons still moves the same way.
+///   An animated rotation is split into the rest rotation of the source joint
+///   and a delta in the local space of that joint,
+///   `source_rest⁻¹ * rotation`, and the delta is applied on top of the rest
+///   rotation of the target joint, `target_rest * (source_rest⁻¹ * rotation)`.
+///   This assumes that the local axes of both joints correspond, for example
+///   that both bones point along the same local axis.
+///
+/// * Translations are applied relative to the rest pose and scaled by the
+///   ratio between the lengths of the bones, so that a character with longer
+///   legs takes longer strides.
+///
+/// * Scales are applied relative to the rest pose.
+///
+/// The corrected curves are resampled at [`sample_rate`](Self::sample_rate).
+/// Curves of joints missing from either rest pose, and curves of other
+/// properties, are moved to the target joint unchanged. Curves and events of
+/// joints missing from the bone mapping are dropped.
+#[derive(Clone, Copy, Debug)]
+pub struct AnimationRetargeter<'a> {
+    /// The mapping from the joints of the source skeleton to the joints of the
+    /// target skeleton.
+    pub mapping: &'a BoneMapping,
+    /// The rest pose of the skeleton the clips were authored for.
+    pub source_rest_pose: &'a SkeletonRestPose,
+    /// The rest pose of the skeleton the clips are retargeted to.
+    pub target_rest_pose: &'a SkeletonRestPose,
+    /// The number of samples per second of the corrected curves.
+    pub sample_rate: f32,
+}
+
+impl<'a> AnimationRetargeter<'a> {
+    /// Creates a new [`AnimationRetargeter`], resampling curves at 30 samples
+    /// per second.
+    pub fn new(
+        mapping: &'a BoneMapping,
+        source_rest_pose: &'a SkeletonRestPose,
+        target_rest_pose: &'a SkeletonRestPose,
+    ) -> Self {
+        Self {
+            mapping,
+            source_rest_pose,
+            target_rest_pose,
+            sample_rate: 30.0,
+        }
+    }
+
+    /// Sets the number of samples per second of the corrected curves.
+    pub fn with_sample_rate(mut self, sample_rate: f32) -> Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    /// Returns a copy of `clip` retargeted to the target skeleton.
+    pub fn retarget(&self, clip: &AnimationClip) -> AnimationClip {
+        let mut retargeted = AnimationClip {
+            duration: clip.duration,
+            sync_markers: clip.sync_markers.clone(),
+            ..AnimationClip::default()
+        };
+        let mut target_ids: HashMap<AnimationTargetId, AnimationTargetId> = HashMap::default();
+
+        for (source, target) in self.mapping.target_ids() {
+            target_ids.insert(source, target);
+            let Some(curves) = clip.curves_for_target(source) else {
+                continue;
+            };
+            let rest_poses = self
+                .source_rest_pose
+                .get(source)
+                .zip(self.target_rest_pose.get(target));
+            for curve in curves {
+                let curve = match rest_poses {
+                    Some((source_rest, target_rest)) => self
+                        .correct_curve(&*curve.0, clip.duration, source_rest, target_rest)
+                        .unwrap_or_else(|| curve.clone()),
+                    None => curve.clone(),
+                };
+                retargeted.curves.entry(target).or_default().push(curve);
+            }
+        }
+
+        for (event_target, events) in &clip.events {
+            let event_target = match event_target {
+                AnimationEventTarget::Root => AnimationEventTarget::Root,
+                AnimationEventTarget::Node(source) => match target_ids.get(source) {
+                    Some(target) => AnimationEventTarget::Node(*target),
+                    None => continue,
+                },
+            };
+            retargeted
+                .events
+                .entry(event_target)
+                .or_default()
+                .extend(events.iter().cloned());
+        }
+
+        retargeted
+    }
+
+    /// Returns `curve` corrected for the rest poses of both joints, or `None`
+    /// if it doesn't animate their [`Transform`] or can't be sampled.
+    fn correct_curve(
+        &self,
+        curve: &dyn AnimationCurve,
+        duration: f32,
+        source_rest: &Transform,
+        target_rest: &Transform,
+    ) -> Option<VariableCurve> {
+        match transform_field(curve)? {
+            "translation" => {
+                let source_length = source_rest.translation.length();
+                let ratio = match source_length > f32::EPSILON {
+                    true => target_rest.translation.length() / source_length,
+                    false => 1.0,
+                };
+                let samples = self.resample::<Vec3>(curve, duration, |translation| {
+                    target_rest.translation + (translation - source_rest.translation) * ratio
+                })?;
+                Some(samples.into_variable_curve(animated_field!(Transform::translation)))
+            }
+            "rotation" => {
+                let source_rest_inverse = source_rest.rotation.inverse();
+                let samples = self.resample::<Quat>(curve, duration, |rotation| {
+                    let delta = source_rest_inverse * rotation;
+                    (target_rest.rotation * delta).normalize()
+                })?;
+                Some(samples.into_variable_curve(animated_field!(Transform::rotation)))
+            }
+            "scale" => {
+                let correction = target_rest.scale / source_rest.scale;
+                let samples = self.resample::<Vec3>(curve, duration, |scale| scale * correction)?;
+                Some(samples.into_variable_curve(animated_field!(Transform::scale)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Samples `curve` over the clip and maps each sample through `correct`.
+    fn resample<T: Reflect + Copy>(
+        &self,
+        curve: &dyn AnimationCurve,
+        duration: f32,
+        correct: impl Fn(T) -> T,
+    ) -> Option<CorrectedSamples<T>> {
+        let sample = |t: f32| -> Option<T> {
+            curve
+                .sample_value(t)?
+                .downcast::<T>()
+                .ok()
+                .map(|value| correct(*value))
+        };
+
+        let domain = curve.domain();
+        let start = domain.start().max(0.0);
+        let end = domain.end().min(duration);
+        let domain = match Interval::new(start, end) {
+            Ok(domain) if domain.length() > 0.0 => domain,
+            _ => return Some(CorrectedSamples::Constant(sample(start.min(end))?)),
+        };
+
+        let count = ((domain.length() * self.sample_rate).ceil() as usize).max(1) + 1;
+        let samples = (0..count)
+            .map(|index| sample(start + domain.length() * index as f32 / (count - 1) as f32))
+            .collect::<Option<Vec<T>>>()?;
+        Some(CorrectedSamples::Sampled(domain, samples))
+    }
+}
+
+/// The corrected samples of a curve.
+enum CorrectedSamples<T> {
+    /// The curve holds a single value.
+    Constant(T),
+    /// The curve is sampled evenly over the interval.
+    Sampled(Interval, Vec<T>),
+}
+
+impl<T> CorrectedSamples<T>
+where
+    T: Animatable + StableInterpolate + Reflectable + FromReflect + Clone + Debug,
+{
+    fn into_variable_curve<F>(self, property: AnimatedField<Transform, T, F>) -> VariableCurve
+    where
+        F: Fn(&mut Transform) -> &mut T + Clone + Send + Sync + 'static,
+    {
+        match self {
+            CorrectedSamples::Constant(value) => VariableCurve::new(AnimatableCurve::new(
+                property,
+                ConstantCurve::new(Interval::EVERYWHERE, value),
+            )),
+            CorrectedSamples::Sampled(domain, samples) => VariableCurve::new(AnimatableCurve::new(
+                property,
+                // There are always at least two samples over a bounded domain.
+                SampleAutoCurve::new(domain, samples).unwrap(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::{App, TaskPoolPlugin};
+    use bevy_asset::{
+        io::{
+            memory::{Dir, MemoryAssetReader},
+            AssetSource, AssetSourceId,
+        },
+        AssetApp, AssetPlugin, AssetServer, Assets, Handle, LoadState,
+    };
+    use bevy_math::curve::FunctionCurve;
+    use std::path::Path;
+
+    #[test]
+    fn retarget_corrects_rest_pose_and_proportions() {
+        let source_hips: AnimationTargetId = ["Armature", "Hips"].into_iter().collect();
+        let target_hips: AnimationTargetId = ["Rig", "pelvis"].into_iter().collect();
+
+        let mut mapping = BoneMapping::new();
+        mapping.add(["Armature", "Hips"], ["Rig", "pelvis"]);
+
+        let source_rest: SkeletonRestPose = [(source_hips, Transform::from_xyz(0.0, 1.0, 0.0))]
+            .into_iter()
+            .collect();
+        let target_rest: SkeletonRestPose = [(
+            target_hips,
+            Transform::from_xyz(0.0, 2.0, 0.0).with_rotation(Quat::from_rotation_y(1.0)),
+        )]
+        .into_iter()
+        .collect();
+
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_target(
+            source_hips,
+            AnimatableCurve::new(
+                animated_field!(Transform::translation),
+                FunctionCurve::new(Interval::new(0.0, 1.0).unwrap(), |t| {
+                    Vec3::new(0.0, 1.0, 2.0 * t)
+                }),
+            ),
+        );
+        clip.add_curve_to_target(
+            source_hips,
+            AnimatableCurve::new(
+                animated_field!(Transform::rotation),
+                ConstantCurve::new(Interval::EVERYWHERE, Quat::from_rotation_x(0.5)),
+            ),
+        );
+
+        let retargeted =
+            AnimationRetargeter::new(&mapping, &source_rest, &target_rest).retarget(&clip);
+        assert_eq!(retargeted.duration(), 1.0);
+        assert!(retargeted.curves_for_target(source_hips).is_none());
+        let curves = retargeted.curves_for_target(target_hips).unwrap();
+        assert_eq!(curves.len(), 2);
+
+        // Strides are twice as long, as the legs are twice as long.
+        let translation = curves[0]
+            .0
+            .sample_value(1.0)
+            .unwrap()
+            .downcast::<Vec3>()
+            .unwrap();
+        assert!(translation.abs_diff_eq(Vec3::new(0.0, 2.0, 4.0), 1e-5));
+
+        let rotation = curves[1]
+            .0
+            .sample_value(0.5)
+            .unwrap()
+            .downcast::<Quat>()
+            .unwrap();
+        let expected = Quat::from_rotation_y(1.0) * Quat::from_rotation_x(0.5);
+        assert!(rotation.abs_diff_eq(expected, 1e-5));
+    }
+
+    #[test]
+    fn retarget_rotations_between_rest_orientations() {
+        let source_arm: AnimationTargetId = ["Armature", "UpperArm.L"].into_iter().collect();
+        let target_arm: AnimationTargetId = ["Rig", "arm_l"].into_iter().collect();
+        let mut mapping = BoneMapping::new();
+        mapping.add(["Armature", "UpperArm.L"], ["Rig", "arm_l"]);
+
+        // The arms rest in different orientations, with neither being the
+        // identity.
+        let source_rotation = Quat::from_rotation_z(core::f32::consts::FRAC_PI_2);
+        let target_rotation =
+            Quat::from_rotation_x(core::f32::consts::FRAC_PI_2) * Quat::from_rotation_y(0.3);
+        let source_rest: SkeletonRestPose =
+            [(source_arm, Transform::from_rotation(source_rotation))]
+                .into_iter()
+                .collect();
+        let target_rest: SkeletonRestPose =
+            [(target_arm, Transform::from_rotation(target_rotation))]
+                .into_iter()
+                .collect();
+
+        // The source arm swings around its own local Y axis, starting from its
+        // rest pose.
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_target(
+            source_arm,
+            AnimatableCurve::new(
+                animated_field!(Transform::rotation),
+                FunctionCurve::new(Interval::new(0.0, 1.0).unwrap(), move |t| {
+                    source_rotation * Quat::from_rotation_y(t)
+                }),
+            ),
+        );
+
+        let retargeted =
+            AnimationRetargeter::new(&mapping, &source_rest, &target_rest).retarget(&clip);
+        let curves = retargeted.curves_for_target(target_arm).unwrap();
+        for t in [0.0, 0.5, 1.0] {
+            let rotation = curves[0]
+                .0
+                .sample_value(t)
+                .unwrap()
+                .downcast::<Quat>()
+                .unwrap();
+            // The target arm swings the same way around its own local Y axis.
+            let expected = target_rotation * Quat::from_rotation_y(t);
+            assert!(
+                rotation.abs_diff_eq(expected, 1e-5) || rotation.abs_diff_eq(-expected, 1e-5),
+                "{rotation} != {expected} at {t}"
+            );
+        }
+    }
+
+    #[test]
+    fn bone_mapping_round_trip() {
+        let mut mapping = BoneMapping::new();
+        mapping
+            .add(["Armature", "Hips"], ["Rig", "pelvis"])
+            .add(["Armature", "Hips", "Spine"], ["Rig", "pelvis", "spine_01"]);
+        let mut bytes = Vec::new();
+        mapping.save(&mut bytes).unwrap();
+
+        let dir = Dir::default();
+        dir.insert_asset(Path::new("rig.bonemap.ron"), bytes);
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+        )
+        .add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+        .init_asset::<BoneMapping>()
+        .init_asset_loader::<BoneMappingAssetLoader>();
+
+        let handle: Handle<BoneMapping> = app
+            .world()
+            .resource::<AssetServer>()
+            .load("rig.bonemap.ron");
+        for _ in 0..10_000 {
+            app.update();
+            match app.world().resource::<AssetServer>().load_state(&handle) {
+                LoadState::Loaded => break,
+                LoadState::Failed(error) => panic!("failed to load the bone mapping: {error}"),
+                _ => {}
+            }
+        }
+        let loaded = app
+            .world()
+            .resource::<Assets<BoneMapping>>()
+            .get(&handle)
+            .unwrap();
+        assert_eq!(
+            loaded.target_ids().collect::<Vec<_>>(),
+            mapping.target_ids().collect::<Vec<_>>()
+        );
+        assert_eq!(loaded.bones[1].target, ["Rig", "pelvis", "spine_01"]);
+    }
+}
@@ -0,0 +1,468 @@
+//! Root motion, which extracts the movement of the root bone of an armature
+//! from its animations so that gameplay code can move the character instead.
+
+use bevy_asset::Assets;
+use bevy_ecs::{
+    component::{require, Component},
+    reflect::ReflectComponent,
+    system::{Query, Res},
+};
+use bevy_math::{Quat, Vec3};
+use bevy_reflect::{prelude::ReflectDefault, Reflect};
+use bevy_transform::components::Transform;
+use petgraph::Direction;
+
+use crate::{
+    animation_curves::{transform_field, AnimationCurve},
+    graph::{
+        AnimationGraph, AnimationGraphHandle, AnimationMask, AnimationNodeIndex, AnimationNodeType,
+    },
+    ActiveAnimation, AnimationClip, AnimationPlayer, AnimationTarget, AnimationTargetId,
+};
+
+/// Extracts the motion of a root bone from the animations of the
+/// [`AnimationPlayer`] on the same entity.
+///
+/// Every frame, the change in translation and rotation of the
+/// [`root`](Self::root) bone is sampled from the curves of the clips being
+/// played, blended by the weights of their nodes and of the nodes above them
+/// in the [`AnimationGraph`], and written to the [`RootMotionDelta`] of this
+/// entity. Movement code can then apply that
+/// delta to the character, for example to its character controller, instead
+/// of letting the animation slide the bone away from the character.
+///
+/// When [`remove_from_bone`](Self::remove_from_bone) is set, the extracted
+/// motion is also taken out of the pose of the bone, so that it stays in
+/// place relative to the character.
+///
+/// Motion is only extracted from the `Transform::translation` and
+/// `Transform::rotation` curves of the root bone that can be sampled with
+/// [`AnimationCurve::sample_value`], which are those built with
+/// [`AnimatableCurve`](crate::animation_curves::AnimatableCurve).
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component, Debug)]
+#[require(RootMotionDelta)]
+pub struct RootMotion {
+    /// The root bone whose motion is extracted.
+    pub root: AnimationTargetId,
+    /// The axes of the translation that are extracted, in the space of the
+    /// parent of the root bone.
+    ///
+    /// For example, `Vec3::new(1.0, 0.0, 1.0)` extracts the horizontal motion
+    /// only, and leaves the vertical motion, such as the bobbing of a walk
+    /// cycle, to the animation.
+    pub translation_mask: Vec3,
+    /// Whether the rotation of the root bone is extracted.
+    pub extract_rotation: bool,
+    /// Whether the extracted motion is taken out of the pose of the root
+    /// bone.
+    pub remove_from_bone: bool,
+}
+
+impl RootMotion {
+    /// Creates a new [`RootMotion`] extracting the full translation of the
+    /// `root` bone, and removing it from the bone.
+    pub fn new(root: AnimationTargetId) -> Self {
+        Self {
+            root,
+            translation_mask: Vec3::ONE,
+            extract_rotation: false,
+            remove_from_bone: true,
+        }
+    }
+
+    /// Sets the axes of the translation that are extracted.
+    pub fn with_translation_mask(mut self, translation_mask: Vec3) -> Self {
+        self.translation_mask = translation_mask;
+        self
+    }
+
+    /// Sets whether the rotation of the root bone is extracted.
+    pub fn with_rotation(mut self, extract_rotation: bool) -> Self {
+        self.extract_rotation = extract_rotation;
+        self
+    }
+
+    /// Sets whether the extracted motion is taken out of the pose of the
+    /// root bone.
+    pub fn with_remove_from_bone(mut self, remove_from_bone: bool) -> Self {
+        self.remove_from_bone = remove_from_bone;
+        self
+    }
+}
+
+/// The motion of the root bone extracted by [`RootMotion`] during the last
+/// update, in the space of the parent of the root bone.
+///
+/// This component is added along with [`RootMotion`]. To move a character by
+/// it, apply the rotation to the character, and the translation rotated by
+/// the orientation of the character.
+#[derive(Component, Clone, Copy, Debug, Reflect, PartialEq)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub struct RootMotionDelta {
+    /// The change in translation of the root bone.
+    pub translation: Vec3,
+    /// The change in rotation of the root bone.
+    pub rotation: Quat,
+    /// The blended pose of the root bone at the start of the clips, used to
+    /// remove the extracted motion from the bone.
+    #[reflect(ignore)]
+    reference: Transform,
+}
+
+impl Default for RootMotionDelta {
+    fn default() -> Self {
+        Self {
+            translation: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+            reference: Transform::IDENTITY,
+        }
+    }
+}
+
+/// The translation and rotation curves of a root bone in a clip.
+#[derive(Default)]
+struct RootCurves<'a> {
+    translation: Option<&'a dyn AnimationCurve>,
+    rotation: Option<&'a dyn AnimationCurve>,
+}
+
+impl<'a> RootCurves<'a> {
+    fn new(clip: &'a AnimationClip, root: AnimationTargetId) -> Self {
+        let mut root_curves = RootCurves::default();
+        for curve in clip.curves_for_target(root).into_iter().flatten() {
+            match transform_field(&*curve.0) {
+                Some("translation") => root_curves.translation = Some(&*curve.0),
+                Some("rotation") => root_curves.rotation = Some(&*curve.0),
+                _ => {}
+            }
+        }
+        root_curves
+    }
+
+    fn translation(&self, t: f32) -> Option<Vec3> {
+        self.translation?
+            .sample_value(t)?
+            .downcast::<Vec3>()
+            .ok()
+            .map(|value| *value)
+    }
+
+    fn rotation(&self, t: f32) -> Option<Quat> {
+        self.rotation?
+            .sample_value(t)?
+            .downcast::<Quat>()
+            .ok()
+            .map(|value| *value)
+    }
+}
+
+/// The motion of a root bone between two sample times of a clip, and its pose
+/// at the start of the clip.
+struct ClipRootMotion {
+    translation: Vec3,
+    rotation: Quat,
+    reference: Transform,
+}
+
+impl ClipRootMotion {
+    /// Computes the motion of the root bone since the last update of the
+    /// `active_animation`, accounting for the clip looping in between.
+    fn new(root_curves: &RootCurves, active_animation: &ActiveAnimation, duration: f32) -> Self {
+        let (start, end) = match active_animation.is_playback_reversed() {
+            false => (0.0, duration),
+            true => (duration, 0.0),
+        };
+        let now = active_animation.seek_time;
+        let last = active_animation.last_seek_time.unwrap_or(now);
+        let wrapped = match active_animation.is_playback_reversed() {
+            false => now < last,
+            true => now > last,
+        };
+
+        let translation = |t| root_curves.translation(t).unwrap_or(Vec3::ZERO);
+        let rotation = |t| root_curves.rotation(t).unwrap_or(Quat::IDENTITY);
+        let (translation_delta, rotation_delta) = match wrapped {
+            false => (
+                translation(now) - translation(last),
+                rotation(now) * rotation(last).inverse(),
+            ),
+            true => (
+                translation(end) - translation(last) + translation(now) - translation(start),
+                rotation(now)
+                    * rotation(start).inverse()
+                    * rotation(end)
+                    * rotation(last).inverse(),
+            ),
+        };
+
+        Self {
+            translation: translation_delta,
+            rotation: rotation_delta.normalize(),
+            reference: Transform {
+                translation: translation(0.0),
+                rotation: rotation(0.0),
+                ..Transform::IDENTITY
+            },
+        }
+    }
+}
+
+/// Accumulates a weighted average of rotations.
+///
+/// Each rotation is flipped into the hemisphere of the identity before being
+/// added, so that `q` and `-q` blend as the same rotation.
+fn accumulate_rotation(accumulated: Quat, rotation: Quat, weight: f32) -> Quat {
+    let rotation = match rotation.w < 0.0 {
+        true => -rotation,
+        false => rotation,
+    };
+    accumulated + rotation * weight
+}
+
+/// Returns the weight `node` contributes to the pose of targets in the mask
+/// groups `target_mask`, which is the product of the weights of the nodes on
+/// the paths from the root of the graph to `node`, summed over these paths.
+///
+/// Paths through a node masking out `target_mask` don't contribute.
+fn evaluated_weight(
+    graph: &AnimationGraph,
+    node: AnimationNodeIndex,
+    target_mask: AnimationMask,
+) -> f32 {
+    let Some(graph_node) = graph.get(node) else {
+        return 0.0;
+    };
+    if graph_node.mask & target_mask != 0 {
+        return 0.0;
+    }
+    let parent_weight = match node == graph.root {
+        true => 1.0,
+        false => graph
+            .graph
+            .neighbors_directed(node, Direction::Incoming)
+            .map(|parent| evaluated_weight(graph, parent, target_mask))
+            .sum(),
+    };
+    graph_node.weight * parent_weight
+}
+
+/// A system that extracts the motion of the root bone of every [`RootMotion`]
+/// from the clips being played, and optionally removes it from the bone.
+pub fn extract_root_motion(
+    animation_clips: Res<Assets<AnimationClip>>,
+    animation_graphs: Res<Assets<AnimationGraph>>,
+    mut players: Query<(
+        &RootMotion,
+        &mut RootMotionDelta,
+        &AnimationPlayer,
+        &AnimationGraphHandle,
+    )>,
+    mut targets: Query<(&AnimationTarget, &mut Transform)>,
+) {
+    for (root_motion, mut delta, player, graph_handle) in &mut players {
+        let Some(animation_graph) = animation_graphs.get(graph_handle) else {
+            continue;
+        };
+        let target_mask = animation_graph
+            .mask_groups
+            .get(&root_motion.root)
+            .copied()
+            .unwrap_or_default();
+
+        let mut total_weight = 0.0;
+        let mut translation = Vec3::ZERO;
+        let mut rotation = Quat::from_xyzw(0.0, 0.0, 0.0, 0.0);
+        let mut reference_translation = Vec3::ZERO;
+        let mut reference_rotation = Quat::from_xyzw(0.0, 0.0, 0.0, 0.0);
+        for (node_index, active_animation) in player.playing_animations() {
+            if active_animation.is_paused() {
+                continue;
+            }
+            let Some(node) = animation_graph.get(*node_index) else {
+                continue;
+            };
+            let AnimationNodeType::Clip(ref clip_handle) = node.node_type else {
+                continue;
+            };
+            let Some(clip) = animation_clips.get(clip_handle) else {
+                continue;
+            };
+            let weight = active_animation.weight()
+                * evaluated_weight(animation_graph, *node_index, target_mask);
+            if weight <= 0.0 {
+                continue;
+            }
+            let root_curves = RootCurves::new(clip, root_motion.root);
+            if root_curves.translation.is_none() && root_curves.rotation.is_none() {
+                continue;
+            }
+
+            let clip_motion = ClipRootMotion::new(&root_curves, active_animation, clip.duration());
+            total_weight += weight;
+            translation += clip_motion.translation * weight;
+            rotation = accumulate_rotation(rotation, clip_motion.rotation, weight);
+            reference_translation += clip_motion.reference.translation * weight;
+            reference_rotation =
+                accumulate_rotation(reference_rotation, clip_motion.reference.rotation, weight);
+        }
+
+        *delta = match total_weight > 0.0 {
+            false => RootMotionDelta::default(),
+            true => RootMotionDelta {
+                translation: translation / total_weight * root_motion.translation_mask,
+                rotation: match root_motion.extract_rotation {
+                    true => rotation.normalize(),
+                    false => Quat::IDENTITY,
+                },
+                reference: Transform {
+                    translation: reference_translation / total_weight,
+                    rotation: reference_rotation.normalize(),
+                    ..Transform::IDENTITY
+                },
+            },
+        };
+    }
+
+    // Take the extracted motion out of the animated pose of the root bones.
+    for (target, mut transform) in &mut targets {
+        let Ok((root_motion, delta, ..)) = players.get(target.player) else {
+            continue;
+        };
+        if !root_motion.remove_from_bone || target.id != root_motion.root {
+            continue;
+        }
+        let mask = root_motion.translation_mask;
+        transform.translation =
+            transform.translation * (Vec3::ONE - mask) + delta.reference.translation * mask;
+        if root_motion.extract_rotation {
+            transform.rotation = delta.reference.rotation;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{animated_field, animation_curves::AnimatableCurve, prelude::AnimatedField};
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+    use bevy_math::curve::{FunctionCurve, Interval};
+
+    fn walk_clip(root: AnimationTargetId) -> AnimationClip {
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_target(
+            root,
+            AnimatableCurve::new(
+                animated_field!(Transform::translation),
+                FunctionCurve::new(Interval::new(0.0, 2.0).unwrap(), |t| Vec3::new(0.0, 1.0, t)),
+            ),
+        );
+        clip
+    }
+
+    fn active_animation(last_seek_time: f32, seek_time: f32) -> ActiveAnimation {
+        ActiveAnimation {
+            last_seek_time: Some(last_seek_time),
+            seek_time,
+            ..ActiveAnimation::default()
+        }
+    }
+
+    #[test]
+    fn root_motion_across_loop() {
+        let root = AnimationTargetId::from_name(&"root".into());
+        let clip = walk_clip(root);
+        let root_curves = RootCurves::new(&clip, root);
+        assert!(root_curves.translation.is_some());
+        assert!(root_curves.rotation.is_none());
+
+        let motion = ClipRootMotion::new(&root_curves, &active_animation(0.5, 0.75), 2.0);
+        assert!(motion
+            .translation
+            .abs_diff_eq(Vec3::new(0.0, 0.0, 0.25), 1e-5));
+
+        // Looping from 1.75 to 0.25 moves forward by 0.5 instead of jumping
+        // back to the start.
+        let motion = ClipRootMotion::new(&root_curves, &active_animation(1.75, 0.25), 2.0);
+        assert!(motion
+            .translation
+            .abs_diff_eq(Vec3::new(0.0, 0.0, 0.5), 1e-5));
+        assert_eq!(motion.reference.translation, Vec3::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn extract_and_remove_from_bone() {
+        let root = AnimationTargetId::from_name(&"root".into());
+        let mut world = World::new();
+        let mut clips = Assets::<AnimationClip>::default();
+        let walk = clips.add(walk_clip(root));
+        let mut idle = AnimationClip::default();
+        idle.add_curve_to_target(
+            root,
+            AnimatableCurve::new(
+                animated_field!(Transform::translation),
+                FunctionCurve::new(Interval::new(0.0, 2.0).unwrap(), |_| Vec3::Y),
+            ),
+        );
+        let idle = clips.add(idle);
+        world.insert_resource(clips);
+
+        // The idle clip is under a node three times heavier than the walk
+        // clip, so it contributes three quarters of the blended motion.
+        let mut graph = AnimationGraph::new();
+        let walk_node = graph.add_clip(walk, 1.0, graph.root);
+        let heavy = graph.add_blend(3.0, graph.root);
+        let idle_node = graph.add_clip(idle, 1.0, heavy);
+        world.init_resource::<Assets<AnimationGraph>>();
+        let graph = world.resource_mut::<Assets<AnimationGraph>>().add(graph);
+
+        let mut player = AnimationPlayer::default();
+        for node in [walk_node, idle_node] {
+            *player.play(node) = active_animation(0.5, 1.5);
+        }
+        let player = world
+            .spawn((
+                player,
+                AnimationGraphHandle(graph),
+                RootMotion::new(root).with_translation_mask(Vec3::new(1.0, 0.0, 1.0)),
+            ))
+            .id();
+        let bone = world
+            .spawn((
+                AnimationTarget { id: root, player },
+                Transform::from_xyz(0.0, 1.0, 1.5),
+            ))
+            .id();
+
+        world.run_system_once(extract_root_motion).unwrap();
+        let delta = world.get::<RootMotionDelta>(player).unwrap();
+        assert!(delta
+            .translation
+            .abs_diff_eq(Vec3::new(0.0, 0.0, 0.25), 1e-5));
+        assert_eq!(delta.rotation, Quat::IDENTITY);
+        // The extracted axes are reset to the start of the clips, and the
+        // others are left as animated.
+        assert!(world
+            .get::<Transform>(bone)
+            .unwrap()
+            .translation
+            .abs_diff_eq(Vec3::Y, 1e-5));
+
+        world
+            .entity_mut(player)
+            .insert(RootMotion::new(root).with_remove_from_bone(false));
+        world
+            .entity_mut(bone)
+            .insert(Transform::from_xyz(0.0, 1.0, 1.5));
+        world.run_system_once(extract_root_motion).unwrap();
+        assert!(world
+            .get::<RootMotionDelta>(player)
+            .unwrap()
+            .translation
+            .abs_diff_eq(Vec3::new(0.0, 0.0, 0.25), 1e-5));
+        assert_eq!(
+            world.get::<Transform>(bone).unwrap().translation,
+            Vec3::new(0.0, 1.0, 1.5)
+        );
+    }
+}
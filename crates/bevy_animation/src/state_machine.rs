@@ -0,0 +1,938 @@
+//! Animation state machines, which drive an [`AnimationPlayer`] from a set of
+//! states and the transitions between them.
+
+use core::time::Duration;
+use std::io::{self, Write};
+
+use bevy_asset::{io::Reader, Asset, AssetId, AssetLoader, Assets, Handle, LoadContext};
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    component::{require, Component},
+    entity::Entity,
+    event::Event,
+    reflect::ReflectComponent,
+    system::{Commands, Query, Res},
+};
+use bevy_platform_support::collections::HashMap;
+use bevy_reflect::{prelude::ReflectDefault, Reflect, ReflectSerialize};
+use bevy_time::Time;
+use derive_more::derive::From;
+use ron::de::SpannedError;
+use serde::{ser::Error as _, Deserialize, Serialize, Serializer};
+use thiserror::Error;
+
+use crate::{
+    graph::{AnimationGraph, AnimationGraphHandle, AnimationNodeIndex, AnimationNodeType},
+    transition::AnimationTransitions,
+    AnimationClip, AnimationPlayer,
+};
+
+/// A declarative state machine that decides which animation an
+/// [`AnimationPlayer`] plays.
+///
+/// Each state references a node of the [`AnimationGraph`] used by the player,
+/// and the transitions between states are evaluated every frame against the
+/// [`AnimationParameters`] of the player. When a transition is taken, the
+/// node of the new state is played through [`AnimationTransitions`], which
+/// crossfades from the previous state over the duration of the transition.
+///
+/// A transition is taken once all of its conditions hold and, if it has an
+/// exit time, once the current state has played for long enough. Transitions
+/// are evaluated in the order they were added, and at most one transition is
+/// taken per frame.
+///
+/// To use a state machine, add an [`AnimationStateMachineHandle`] to the
+/// entity with the [`AnimationPlayer`] and [`AnimationGraphHandle`]. Don't
+/// play animations on that player manually, as the state machine takes
+/// responsibility for it.
+///
+/// Animation state machines are assets and can be serialized to and loaded
+/// from [RON] files. Canonically, such files have an `.animsm.ron` extension.
+///
+/// [RON]: https://github.com/ron-rs/ron
+#[derive(Asset, Reflect, Clone, Debug, Default)]
+#[reflect(Serialize, Debug, Default)]
+pub struct AnimationStateMachine {
+    /// The states of the state machine.
+    pub states: Vec<AnimationState>,
+    /// The transitions between states, in the order they're evaluated.
+    pub transitions: Vec<AnimationStateTransition>,
+    /// The state entered when the state machine starts.
+    pub initial_state: AnimationStateIndex,
+}
+
+/// The index of a state in an [`AnimationStateMachine`].
+pub type AnimationStateIndex = usize;
+
+/// A single state of an [`AnimationStateMachine`].
+#[derive(Clone, Reflect, Debug)]
+pub struct AnimationState {
+    /// The name of the state, which must be unique within its state machine.
+    pub name: String,
+    /// The node of the [`AnimationGraph`] played while in this state.
+    pub node: AnimationNodeIndex,
+    /// The playback speed of the node.
+    pub speed: f32,
+    /// Whether the node repeats forever, instead of playing once.
+    pub looping: bool,
+}
+
+/// A transition between two states of an [`AnimationStateMachine`].
+#[derive(Clone, Reflect, Debug)]
+pub struct AnimationStateTransition {
+    /// The state this transition leaves, or `None` if it can be taken from
+    /// any state other than [`to`](Self::to).
+    pub from: Option<AnimationStateIndex>,
+    /// The state this transition enters.
+    pub to: AnimationStateIndex,
+    /// How long the previous state is faded out for.
+    pub duration: Duration,
+    /// The conditions that must all hold for this transition to be taken.
+    pub conditions: Vec<AnimationCondition>,
+    /// If set, the transition can only be taken once the current state has
+    /// played this many times.
+    ///
+    /// This is a fraction of the duration of the state's clip, so `0.5`
+    /// means halfway through the first playthrough. For states whose node
+    /// isn't a clip, this is measured in seconds instead.
+    pub exit_time: Option<f32>,
+}
+
+/// A condition on the [`AnimationParameters`] of a player, required for an
+/// [`AnimationStateTransition`] to be taken.
+///
+/// Parameters that aren't set are treated as `0.0` or `false`.
+#[derive(Clone, Reflect, Debug, PartialEq, Serialize, Deserialize)]
+#[reflect(Serialize, Debug, PartialEq)]
+pub enum AnimationCondition {
+    /// The float parameter is greater than the value.
+    Greater(String, f32),
+    /// The float parameter is less than the value.
+    Less(String, f32),
+    /// The bool parameter is `true`.
+    True(String),
+    /// The bool parameter is `false`.
+    False(String),
+    /// The trigger parameter is set.
+    ///
+    /// The trigger is reset when the transition is taken.
+    Trigger(String),
+}
+
+/// The value of a parameter in [`AnimationParameters`].
+#[derive(Clone, Copy, Reflect, Debug, PartialEq)]
+pub enum AnimationParameter {
+    /// A float parameter, tested with [`AnimationCondition::Greater`] and
+    /// [`AnimationCondition::Less`].
+    Float(f32),
+    /// A bool parameter, tested with [`AnimationCondition::True`] and
+    /// [`AnimationCondition::False`].
+    Bool(bool),
+    /// A trigger parameter, tested with [`AnimationCondition::Trigger`].
+    ///
+    /// Triggers stay set until a transition testing them is taken.
+    Trigger(bool),
+}
+
+/// The parameters the transitions of an [`AnimationStateMachine`] are
+/// evaluated against.
+///
+/// This component is added along with [`AnimationStateMachineHandle`], and
+/// is where gameplay code feeds values such as the speed of a character.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct AnimationParameters {
+    values: HashMap<String, AnimationParameter>,
+}
+
+impl AnimationParameters {
+    /// Sets the float parameter `name`.
+    pub fn set_float(&mut self, name: impl Into<String>, value: f32) -> &mut Self {
+        self.values
+            .insert(name.into(), AnimationParameter::Float(value));
+        self
+    }
+
+    /// Sets the bool parameter `name`.
+    pub fn set_bool(&mut self, name: impl Into<String>, value: bool) -> &mut Self {
+        self.values
+            .insert(name.into(), AnimationParameter::Bool(value));
+        self
+    }
+
+    /// Sets the trigger parameter `name`.
+    pub fn set_trigger(&mut self, name: impl Into<String>) -> &mut Self {
+        self.values
+            .insert(name.into(), AnimationParameter::Trigger(true));
+        self
+    }
+
+    /// Resets the trigger parameter `name`.
+    pub fn reset_trigger(&mut self, name: &str) -> &mut Self {
+        if let Some(AnimationParameter::Trigger(set)) = self.values.get_mut(name) {
+            *set = false;
+        }
+        self
+    }
+
+    /// Returns the parameter `name`, if it's set.
+    pub fn get(&self, name: &str) -> Option<AnimationParameter> {
+        self.values.get(name).copied()
+    }
+
+    /// Returns the float parameter `name`, or `0.0` if it isn't a float.
+    pub fn float(&self, name: &str) -> f32 {
+        match self.get(name) {
+            Some(AnimationParameter::Float(value)) => value,
+            _ => 0.0,
+        }
+    }
+
+    /// Returns the bool parameter `name`, or `false` if it isn't a bool.
+    pub fn bool(&self, name: &str) -> bool {
+        matches!(self.get(name), Some(AnimationParameter::Bool(true)))
+    }
+
+    /// Returns `true` if the trigger parameter `name` is set.
+    pub fn is_triggered(&self, name: &str) -> bool {
+        matches!(self.get(name), Some(AnimationParameter::Trigger(true)))
+    }
+}
+
+impl AnimationCondition {
+    /// Returns `true` if this condition holds for the given parameters.
+    pub fn holds(&self, parameters: &AnimationParameters) -> bool {
+        match self {
+            AnimationCondition::Greater(name, value) => parameters.float(name) > *value,
+            AnimationCondition::Less(name, value) => parameters.float(name) < *value,
+            AnimationCondition::True(name) => parameters.bool(name),
+            AnimationCondition::False(name) => !parameters.bool(name),
+            AnimationCondition::Trigger(name) => parameters.is_triggered(name),
+        }
+    }
+}
+
+/// A [`Handle`] to the [`AnimationStateMachine`] driving the
+/// [`AnimationPlayer`] on the same entity.
+#[derive(Component, Clone, Debug, Default, Deref, DerefMut, Reflect, PartialEq, Eq, From)]
+#[reflect(Component, Default)]
+#[require(
+    AnimationParameters,
+    AnimationStateMachineInstance,
+    AnimationTransitions
+)]
+pub struct AnimationStateMachineHandle(pub Handle<AnimationStateMachine>);
+
+impl From<&AnimationStateMachineHandle> for AssetId<AnimationStateMachine> {
+    fn from(handle: &AnimationStateMachineHandle) -> Self {
+        handle.id()
+    }
+}
+
+/// The progress of an entity through its [`AnimationStateMachine`].
+///
+/// This component is added along with [`AnimationStateMachineHandle`].
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct AnimationStateMachineInstance {
+    current_state: Option<AnimationStateIndex>,
+    elapsed: f32,
+}
+
+impl AnimationStateMachineInstance {
+    /// Returns the current state, or `None` if the state machine hasn't
+    /// started yet.
+    pub fn current_state(&self) -> Option<AnimationStateIndex> {
+        self.current_state
+    }
+
+    /// Returns how long the current state has been played for, in seconds,
+    /// scaled by the speed of the state.
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// Restarts the state machine from its initial state on the next update.
+    pub fn restart(&mut self) {
+        self.current_state = None;
+        self.elapsed = 0.0;
+    }
+}
+
+/// Triggered on an entity when its [`AnimationStateMachine`] enters a state.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AnimationStateEntered {
+    /// The state that was exited, or `None` if the state machine just
+    /// started.
+    pub exited: Option<AnimationStateIndex>,
+    /// The state that was entered.
+    pub entered: AnimationStateIndex,
+}
+
+impl AnimationStateMachine {
+    /// Creates a new state machine with no states.
+    ///
+    /// The first state added becomes the initial state.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a looping state named `name` that plays `node`, and returns its
+    /// index.
+    pub fn add_state(
+        &mut self,
+        name: impl Into<String>,
+        node: AnimationNodeIndex,
+    ) -> AnimationStateIndex {
+        self.states.push(AnimationState {
+            name: name.into(),
+            node,
+            speed: 1.0,
+            looping: true,
+        });
+        self.states.len() - 1
+    }
+
+    /// Sets the state entered when the state machine starts.
+    pub fn set_initial_state(&mut self, state: AnimationStateIndex) -> &mut Self {
+        self.initial_state = state;
+        self
+    }
+
+    /// Adds a transition from `from` to `to` which fades over `duration`, and
+    /// returns it so that conditions and an exit time can be added.
+    pub fn add_transition(
+        &mut self,
+        from: AnimationStateIndex,
+        to: AnimationStateIndex,
+        duration: Duration,
+    ) -> &mut AnimationStateTransition {
+        self.push_transition(Some(from), to, duration)
+    }
+
+    /// Adds a transition from any state to `to` which fades over `duration`,
+    /// and returns it so that conditions and an exit time can be added.
+    pub fn add_transition_from_any_state(
+        &mut self,
+        to: AnimationStateIndex,
+        duration: Duration,
+    ) -> &mut AnimationStateTransition {
+        self.push_transition(None, to, duration)
+    }
+
+    fn push_transition(
+        &mut self,
+        from: Option<AnimationStateIndex>,
+        to: AnimationStateIndex,
+        duration: Duration,
+    ) -> &mut AnimationStateTransition {
+        self.transitions.push(AnimationStateTransition {
+            from,
+            to,
+            duration,
+            conditions: vec![],
+            exit_time: None,
+        });
+        self.transitions.last_mut().unwrap()
+    }
+
+    /// Returns the state at `index`, if any.
+    pub fn state(&self, index: AnimationStateIndex) -> Option<&AnimationState> {
+        self.states.get(index)
+    }
+
+    /// Returns the state at `index` mutably, if any.
+    pub fn state_mut(&mut self, index: AnimationStateIndex) -> Option<&mut AnimationState> {
+        self.states.get_mut(index)
+    }
+
+    /// Returns the index of the state named `name`, if any.
+    pub fn state_index(&self, name: &str) -> Option<AnimationStateIndex> {
+        self.states.iter().position(|state| state.name == name)
+    }
+
+    /// Returns the first transition that can be taken from `current`, given
+    /// the `parameters` and how far through the current state playback is.
+    ///
+    /// `progress` is measured in the same units as
+    /// [`AnimationStateTransition::exit_time`].
+    pub fn find_transition(
+        &self,
+        current: AnimationStateIndex,
+        progress: f32,
+        parameters: &AnimationParameters,
+    ) -> Option<&AnimationStateTransition> {
+        self.transitions.iter().find(|transition| {
+            let from_current = match transition.from {
+                Some(from) => from == current,
+                None => transition.to != current,
+            };
+            from_current
+                && transition
+                    .exit_time
+                    .is_none_or(|exit_time| progress >= exit_time)
+                && transition
+                    .conditions
+                    .iter()
+                    .all(|condition| condition.holds(parameters))
+        })
+    }
+
+    /// Serializes the state machine to the given [`Write`]r in RON format.
+    ///
+    /// If writing to a file, it can later be loaded with the
+    /// [`AnimationStateMachineAssetLoader`] to reconstruct the state machine.
+    pub fn save<W>(&self, writer: &mut W) -> Result<(), AnimationStateMachineLoadError>
+    where
+        W: Write,
+    {
+        let mut ron_serializer = ron::ser::Serializer::new(writer, None)?;
+        Ok(self.serialize(&mut ron_serializer)?)
+    }
+}
+
+impl AnimationStateTransition {
+    /// Adds a condition that must hold for this transition to be taken.
+    pub fn with_condition(&mut self, condition: AnimationCondition) -> &mut Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Sets the exit time of this transition.
+    pub fn with_exit_time(&mut self, exit_time: f32) -> &mut Self {
+        self.exit_time = Some(exit_time);
+        self
+    }
+}
+
+impl AnimationState {
+    /// Sets the playback speed of this state.
+    pub fn with_speed(&mut self, speed: f32) -> &mut Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Sets whether this state repeats forever.
+    pub fn with_looping(&mut self, looping: bool) -> &mut Self {
+        self.looping = looping;
+        self
+    }
+}
+
+/// A version of [`AnimationStateMachine`] suitable for serializing as an
+/// asset.
+///
+/// States are referred to by name rather than by index, so that the file
+/// stays readable and robust to reordering.
+#[derive(Serialize, Deserialize)]
+pub struct SerializedAnimationStateMachine {
+    /// Corresponds to the `states` field on [`AnimationStateMachine`].
+    pub states: Vec<SerializedAnimationState>,
+    /// Corresponds to the `transitions` field on [`AnimationStateMachine`].
+    #[serde(default)]
+    pub transitions: Vec<SerializedAnimationStateTransition>,
+    /// The name of the initial state.
+    pub initial_state: String,
+}
+
+/// A version of [`AnimationState`] suitable for serializing as part of a
+/// [`SerializedAnimationStateMachine`] asset.
+#[derive(Serialize, Deserialize)]
+pub struct SerializedAnimationState {
+    /// Corresponds to the `name` field on [`AnimationState`].
+    pub name: String,
+    /// Corresponds to the `node` field on [`AnimationState`].
+    pub node: AnimationNodeIndex,
+    /// Corresponds to the `speed` field on [`AnimationState`].
+    pub speed: f32,
+    /// Corresponds to the `looping` field on [`AnimationState`].
+    pub looping: bool,
+}
+
+/// A version of [`AnimationStateTransition`] suitable for serializing as part
+/// of a [`SerializedAnimationStateMachine`] asset.
+#[derive(Serialize, Deserialize)]
+pub struct SerializedAnimationStateTransition {
+    /// The name of the state this transition leaves, or `None` for any
+    /// state.
+    pub from: Option<String>,
+    /// The name of the state this transition enters.
+    pub to: String,
+    /// Corresponds to the `duration` field on [`AnimationStateTransition`],
+    /// in seconds.
+    pub duration: f32,
+    /// Corresponds to the `conditions` field on [`AnimationStateTransition`].
+    #[serde(default)]
+    pub conditions: Vec<AnimationCondition>,
+    /// Corresponds to the `exit_time` field on [`AnimationStateTransition`].
+    pub exit_time: Option<f32>,
+}
+
+/// An [`AssetLoader`] that can load [`AnimationStateMachine`]s as assets.
+///
+/// The canonical extension for [`AnimationStateMachine`]s is `.animsm.ron`.
+/// Plain `.animsm` is supported as well.
+#[derive(Default)]
+pub struct AnimationStateMachineAssetLoader;
+
+/// Various errors that can occur when serializing or deserializing animation
+/// state machines to and from RON, respectively.
+#[derive(Error, Debug)]
+pub enum AnimationStateMachineLoadError {
+    /// An I/O error occurred.
+    #[error("I/O")]
+    Io(#[from] io::Error),
+    /// An error occurred in RON serialization or deserialization.
+    #[error("RON serialization")]
+    Ron(#[from] ron::Error),
+    /// An error occurred in RON deserialization, and the location of the error
+    /// is supplied.
+    #[error("RON serialization")]
+    SpannedRon(#[from] SpannedError),
+    /// A transition or the initial state refers to a state that doesn't
+    /// exist.
+    #[error("unknown animation state `{0}`")]
+    UnknownState(String),
+    /// A transition or the initial state refers to a state index that's out
+    /// of range.
+    #[error("animation state index {0} is out of range")]
+    InvalidStateIndex(AnimationStateIndex),
+    /// The duration of a transition is negative, infinite or NaN.
+    #[error("invalid animation transition duration {0}")]
+    InvalidDuration(f32),
+}
+
+impl TryFrom<SerializedAnimationStateMachine> for AnimationStateMachine {
+    type Error = AnimationStateMachineLoadError;
+
+    fn try_from(serialized: SerializedAnimationStateMachine) -> Result<Self, Self::Error> {
+        let states: Vec<_> = serialized
+            .states
+            .into_iter()
+            .map(|state| AnimationState {
+                name: state.name,
+                node: state.node,
+                speed: state.speed,
+                looping: state.looping,
+            })
+            .collect();
+        let index_of = |name: String| {
+            states
+                .iter()
+                .position(|state| state.name == name)
+                .ok_or(AnimationStateMachineLoadError::UnknownState(name))
+        };
+
+        let transitions = serialized
+            .transitions
+            .into_iter()
+            .map(|transition| {
+                Ok(AnimationStateTransition {
+                    from: transition.from.map(index_of).transpose()?,
+                    to: index_of(transition.to)?,
+                    duration: Duration::try_from_secs_f32(transition.duration).map_err(|_| {
+                        AnimationStateMachineLoadError::InvalidDuration(transition.duration)
+                    })?,
+                    conditions: transition.conditions,
+                    exit_time: transition.exit_time,
+                })
+            })
+            .collect::<Result<_, Self::Error>>()?;
+        let initial_state = index_of(serialized.initial_state)?;
+
+        Ok(AnimationStateMachine {
+            states,
+            transitions,
+            initial_state,
+        })
+    }
+}
+
+impl TryFrom<&AnimationStateMachine> for SerializedAnimationStateMachine {
+    type Error = AnimationStateMachineLoadError;
+
+    fn try_from(state_machine: &AnimationStateMachine) -> Result<Self, Self::Error> {
+        let name_of = |index: AnimationStateIndex| {
+            state_machine
+                .state(index)
+                .map(|state| state.name.clone())
+                .ok_or(AnimationStateMachineLoadError::InvalidStateIndex(index))
+        };
+        Ok(Self {
+            transitions: state_machine
+                .transitions
+                .iter()
+                .map(|transition| {
+                    Ok(SerializedAnimationStateTransition {
+                        from: transition.from.map(name_of).transpose()?,
+                        to: name_of(transition.to)?,
+                        duration: transition.duration.as_secs_f32(),
+                        conditions: transition.conditions.clone(),
+                        exit_time: transition.exit_time,
+                    })
+                })
+                .collect::<Result<_, Self::Error>>()?,
+            initial_state: name_of(state_machine.initial_state)?,
+            states: state_machine
+                .states
+                .iter()
+                .map(|state| SerializedAnimationState {
+                    name: state.name.clone(),
+                    node: state.node,
+                    speed: state.speed,
+                    looping: state.looping,
+                })
+                .collect(),
+        })
+    }
+}
+
+impl Serialize for AnimationStateMachine {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        SerializedAnimationStateMachine::try_from(self)
+            .map_err(S::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+impl AssetLoader for AnimationStateMachineAssetLoader {
+    type Asset = AnimationStateMachine;
+
+    type Settings = ();
+
+    type Error = AnimationStateMachineLoadError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _: &Self::Settings,
+        _: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+        let serialized_state_machine =
+            SerializedAnimationStateMachine::deserialize(&mut deserializer)
+                .map_err(|err| deserializer.span_error(err))?;
+
+        serialized_state_machine.try_into()
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["animsm", "animsm.ron"]
+    }
+}
+
+/// A system that advances every [`AnimationStateMachine`], taking at most one
+/// transition per entity and playing the new state through
+/// [`AnimationTransitions`].
+pub fn advance_animation_state_machines(
+    mut commands: Commands,
+    time: Res<Time>,
+    state_machines: Res<Assets<AnimationStateMachine>>,
+    animation_graphs: Res<Assets<AnimationGraph>>,
+    animation_clips: Res<Assets<AnimationClip>>,
+    mut query: Query<(
+        Entity,
+        &AnimationStateMachineHandle,
+        &AnimationGraphHandle,
+        &mut AnimationParameters,
+        &mut AnimationStateMachineInstance,
+        &mut AnimationTransitions,
+        &mut AnimationPlayer,
+    )>,
+) {
+    let delta_seconds = time.delta_secs();
+    for (
+        entity,
+        state_machine_handle,
+        graph_handle,
+        mut parameters,
+        mut instance,
+        mut transitions,
+        mut player,
+    ) in &mut query
+    {
+        // The state machine might not have loaded yet. Safely bail.
+        let Some(state_machine) = state_machines.get(state_machine_handle) else {
+            continue;
+        };
+
+        // A reloaded state machine may no longer have the current state, in
+        // which case it starts over from its initial state.
+        let current = instance
+            .current_state
+            .and_then(|current| Some((current, state_machine.state(current)?)));
+        let (exited, entered, duration) = match current {
+            None => (None, state_machine.initial_state, Duration::ZERO),
+            Some((current, state)) => {
+                instance.elapsed += delta_seconds * state.speed.abs();
+
+                // Measure the progress in playthroughs of the clip, if the
+                // state plays one.
+                let clip_duration = animation_graphs
+                    .get(graph_handle)
+                    .and_then(|graph| graph.get(state.node))
+                    .and_then(|node| match &node.node_type {
+                        AnimationNodeType::Clip(handle) => animation_clips.get(handle),
+                        _ => None,
+                    })
+                    .map(AnimationClip::duration)
+                    .filter(|duration| *duration > 0.0);
+                let progress = match clip_duration {
+                    Some(clip_duration) => instance.elapsed / clip_duration,
+                    None => instance.elapsed,
+                };
+
+                let Some(transition) =
+                    state_machine.find_transition(current, progress, &parameters)
+                else {
+                    continue;
+                };
+                for condition in &transition.conditions {
+                    if let AnimationCondition::Trigger(name) = condition {
+                        parameters.reset_trigger(name);
+                    }
+                }
+                (Some(current), transition.to, transition.duration)
+            }
+        };
+
+        let Some(state) = state_machine.state(entered) else {
+            continue;
+        };
+        let active_animation = transitions.play(&mut player, state.node, duration);
+        active_animation.set_speed(state.speed);
+        if state.looping {
+            active_animation.repeat();
+        }
+
+        instance.current_state = Some(entered);
+        instance.elapsed = 0.0;
+        commands.trigger_targets(AnimationStateEntered { exited, entered }, entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{
+        observer::Trigger,
+        resource::Resource,
+        system::{ResMut, RunSystemOnce},
+        world::World,
+    };
+
+    use super::*;
+
+    fn locomotion() -> AnimationStateMachine {
+        let mut state_machine = AnimationStateMachine::new();
+        let idle = state_machine.add_state("idle", AnimationNodeIndex::new(1));
+        let run = state_machine.add_state("run", AnimationNodeIndex::new(2));
+        let jump = state_machine.add_state("jump", AnimationNodeIndex::new(3));
+        state_machine.state_mut(jump).unwrap().with_looping(false);
+
+        state_machine
+            .add_transition(idle, run, Duration::from_millis(200))
+            .with_condition(AnimationCondition::Greater("speed".into(), 0.1));
+        state_machine
+            .add_transition(run, idle, Duration::from_millis(200))
+            .with_condition(AnimationCondition::Less("speed".into(), 0.1));
+        state_machine
+            .add_transition_from_any_state(jump, Duration::from_millis(100))
+            .with_condition(AnimationCondition::Trigger("jump".into()));
+        state_machine
+            .add_transition(jump, idle, Duration::from_millis(100))
+            .with_exit_time(1.0);
+        state_machine
+    }
+
+    #[test]
+    fn find_transition() {
+        let state_machine = locomotion();
+        let [idle, run, jump] =
+            ["idle", "run", "jump"].map(|name| state_machine.state_index(name).unwrap());
+        let mut parameters = AnimationParameters::default();
+
+        assert!(state_machine
+            .find_transition(idle, 0.0, &parameters)
+            .is_none());
+
+        parameters.set_float("speed", 1.0);
+        assert_eq!(
+            state_machine
+                .find_transition(idle, 0.0, &parameters)
+                .map(|transition| transition.to),
+            Some(run)
+        );
+
+        parameters.set_trigger("jump");
+        assert_eq!(
+            state_machine
+                .find_transition(run, 0.0, &parameters)
+                .map(|transition| transition.to),
+            Some(jump)
+        );
+
+        // Any-state transitions don't re-enter the state they lead to, and
+        // exit times hold the state until it's played long enough.
+        assert!(state_machine
+            .find_transition(jump, 0.5, &parameters)
+            .is_none());
+        assert_eq!(
+            state_machine
+                .find_transition(jump, 1.0, &parameters)
+                .map(|transition| transition.to),
+            Some(idle)
+        );
+    }
+
+    #[test]
+    fn serialized_round_trip() {
+        let state_machine = locomotion();
+        let mut bytes = Vec::new();
+        state_machine.save(&mut bytes).unwrap();
+
+        let serialized: SerializedAnimationStateMachine = ron::de::from_bytes(&bytes).unwrap();
+        assert_eq!(serialized.transitions[2].from, None);
+        assert_eq!(serialized.transitions[3].from.as_deref(), Some("jump"));
+
+        let loaded = AnimationStateMachine::try_from(serialized).unwrap();
+        assert_eq!(loaded.states.len(), 3);
+        assert!(!loaded.states[2].looping);
+        assert_eq!(loaded.transitions.len(), 4);
+        assert_eq!(loaded.transitions[2].from, None);
+        assert_eq!(loaded.transitions[2].to, 2);
+        assert_eq!(loaded.transitions[3].exit_time, Some(1.0));
+        assert_eq!(
+            loaded.transitions[0].conditions,
+            [AnimationCondition::Greater("speed".into(), 0.1)]
+        );
+
+        let unknown = SerializedAnimationStateMachine {
+            states: vec![],
+            transitions: vec![],
+            initial_state: "idle".into(),
+        };
+        assert!(matches!(
+            AnimationStateMachine::try_from(unknown),
+            Err(AnimationStateMachineLoadError::UnknownState(name)) if name == "idle"
+        ));
+
+        let mut negative_duration =
+            SerializedAnimationStateMachine::try_from(&locomotion()).unwrap();
+        negative_duration.transitions[0].duration = -1.0;
+        assert!(matches!(
+            AnimationStateMachine::try_from(negative_duration),
+            Err(AnimationStateMachineLoadError::InvalidDuration(-1.0))
+        ));
+
+        let mut dangling = locomotion();
+        dangling.transitions[0].to = 3;
+        assert!(matches!(
+            SerializedAnimationStateMachine::try_from(&dangling),
+            Err(AnimationStateMachineLoadError::InvalidStateIndex(3))
+        ));
+        assert!(dangling.save(&mut Vec::new()).is_err());
+    }
+
+    #[derive(Resource, Default)]
+    struct EnteredStates(Vec<AnimationStateEntered>);
+
+    #[test]
+    fn advance_state_machine() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        world.init_resource::<Assets<AnimationClip>>();
+        world.init_resource::<Assets<AnimationGraph>>();
+        world.init_resource::<EnteredStates>();
+        let graph = world
+            .resource_mut::<Assets<AnimationGraph>>()
+            .add(AnimationGraph::new());
+        let mut state_machines = Assets::<AnimationStateMachine>::default();
+        let state_machine = state_machines.add(locomotion());
+        world.insert_resource(state_machines);
+        world.add_observer(
+            |trigger: Trigger<AnimationStateEntered>, mut entered: ResMut<EnteredStates>| {
+                entered.0.push(*trigger.event());
+            },
+        );
+        let entity = world
+            .spawn((
+                AnimationPlayer::default(),
+                AnimationGraphHandle(graph),
+                AnimationStateMachineHandle(state_machine.clone()),
+            ))
+            .id();
+        let advance = |world: &mut World| {
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(100));
+            world
+                .run_system_once(advance_animation_state_machines)
+                .unwrap();
+        };
+        let current_state = |world: &World| {
+            world
+                .get::<AnimationStateMachineInstance>(entity)
+                .unwrap()
+                .current_state()
+        };
+
+        advance(&mut world);
+        assert_eq!(current_state(&world), Some(0));
+        assert!(world
+            .get::<AnimationPlayer>(entity)
+            .unwrap()
+            .is_playing_animation(AnimationNodeIndex::new(1)));
+
+        world
+            .get_mut::<AnimationParameters>(entity)
+            .unwrap()
+            .set_trigger("jump");
+        advance(&mut world);
+        assert_eq!(current_state(&world), Some(2));
+        assert!(!world
+            .get::<AnimationParameters>(entity)
+            .unwrap()
+            .is_triggered("jump"));
+        assert_eq!(
+            world
+                .get::<AnimationTransitions>(entity)
+                .unwrap()
+                .get_main_animation(),
+            Some(AnimationNodeIndex::new(3))
+        );
+
+        // After a hot reload that removes the current state, the state machine
+        // starts over.
+        let mut reloaded = AnimationStateMachine::new();
+        reloaded.add_state("idle", AnimationNodeIndex::new(1));
+        world
+            .resource_mut::<Assets<AnimationStateMachine>>()
+            .insert(&state_machine, reloaded);
+        advance(&mut world);
+        assert_eq!(current_state(&world), Some(0));
+
+        assert_eq!(
+            world.resource::<EnteredStates>().0,
+            [
+                AnimationStateEntered {
+                    exited: None,
+                    entered: 0,
+                },
+                AnimationStateEntered {
+                    exited: Some(0),
+                    entered: 2,
+                },
+                AnimationStateEntered {
+                    exited: None,
+                    entered: 0,
+                },
+            ]
+        );
+    }
+}
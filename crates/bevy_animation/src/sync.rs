@@ -0,0 +1,383 @@
+//! Sync groups, which keep the clips blended under a node of an
+//! [`AnimationGraph`] at the same phase.
+//!
+//! Blending a walk and a run clip plays each at its own duration, so their
+//! steps drift apart and the feet of the character slide. When the blend node
+//! of both clips is made a sync group with
+//! [`AnimationGraph::add_sync_group`], the clips share a normalized *phase*
+//! instead: a value in `[0, 1)` that describes how far along its cycle each
+//! clip is.
+//!
+//! The phase of a clip is its seek time divided by its duration, unless the
+//! clip has [sync markers], such as the times at which each foot touches the
+//! ground. In that case, each marker starts an equal part of the phase, so
+//! that the markers of the clips of a group line up.
+//!
+//! [sync markers]: AnimationClip::add_sync_marker
+
+use bevy_asset::Assets;
+use bevy_math::ops;
+use bevy_platform_support::collections::HashMap;
+use bevy_reflect::{prelude::ReflectDefault, Reflect, ReflectDeserialize, ReflectSerialize};
+use petgraph::Direction;
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+
+use crate::{
+    graph::{AnimationGraph, AnimationNodeIndex, AnimationNodeType},
+    ActiveAnimation, AnimationClip,
+};
+
+/// How the clips of a sync group are kept in phase.
+///
+/// A sync group contains the clip nodes that are direct children of a node of
+/// an [`AnimationGraph`], usually a blend node, and that are playing and not
+/// paused. One of them is the *leader*, which advances the phase of the group,
+/// and the others are *followers*, which are moved to the phase of the leader
+/// every frame. Followers ignore their own speed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum AnimationSyncMode {
+    /// The clip with the highest weight leads, playing at its own duration.
+    ///
+    /// The weight of a clip is the weight of its node multiplied by the weight
+    /// of its [`ActiveAnimation`].
+    #[default]
+    DominantWeight,
+
+    /// The clip of the given node leads, playing at its own duration.
+    ///
+    /// If that clip isn't playing, the clip with the highest weight leads
+    /// instead.
+    Leader(AnimationNodeIndex),
+
+    /// The clip with the highest weight leads, but its duration is stretched
+    /// to the average of the durations of all the clips, weighted by their
+    /// weights.
+    ///
+    /// This makes a blend of a walk and a run take a time between both to
+    /// complete a cycle.
+    WeightedDuration,
+}
+
+/// Returns the phase of `clip` at `seek_time`, in `[0, 1)`.
+pub(crate) fn phase_at(clip: &AnimationClip, seek_time: f32) -> f32 {
+    let duration = clip.duration();
+    if duration <= 0.0 {
+        return 0.0;
+    }
+    let markers = clip.sync_markers();
+    if markers.is_empty() {
+        return ops::rem_euclid(seek_time / duration, 1.0);
+    }
+
+    // Find the markers surrounding the seek time, wrapping around the clip.
+    let count = markers.len();
+    let next = markers.partition_point(|&marker| marker <= seek_time);
+    let (index, start, end) = match next {
+        0 => (count - 1, markers[count - 1] - duration, markers[0]),
+        next if next == count => (count - 1, markers[count - 1], markers[0] + duration),
+        next => (next - 1, markers[next - 1], markers[next]),
+    };
+    let fraction = match end > start {
+        true => (seek_time - start) / (end - start),
+        false => 0.0,
+    };
+    ops::rem_euclid((index as f32 + fraction) / count as f32, 1.0)
+}
+
+/// Returns the seek time at which `clip` has the given `phase`.
+pub(crate) fn seek_time_at_phase(clip: &AnimationClip, phase: f32) -> f32 {
+    let duration = clip.duration();
+    if duration <= 0.0 {
+        return 0.0;
+    }
+    let phase = ops::rem_euclid(phase, 1.0);
+    let markers = clip.sync_markers();
+    if markers.is_empty() {
+        return phase * duration;
+    }
+
+    let count = markers.len();
+    let scaled = phase * count as f32;
+    let index = (ops::floor(scaled) as usize).min(count - 1);
+    let start = markers[index];
+    let end = match markers.get(index + 1) {
+        Some(&end) => end,
+        None => markers[0] + duration,
+    };
+    ops::rem_euclid(start + (scaled - index as f32) * (end - start), duration)
+}
+
+/// A sync group of a player, resolved for the current frame.
+struct ResolvedSyncGroup<'a> {
+    leader: AnimationNodeIndex,
+    leader_clip: &'a AnimationClip,
+    /// The factor by which the delta time of the leader is scaled.
+    time_scale: f32,
+    followers: SmallVec<[(AnimationNodeIndex, &'a AnimationClip); 4]>,
+}
+
+/// The sync groups of a player, resolved for the current frame.
+pub(crate) struct ResolvedSyncGroups<'a> {
+    groups: SmallVec<[ResolvedSyncGroup<'a>; 1]>,
+}
+
+impl<'a> ResolvedSyncGroups<'a> {
+    /// Resolves the leaders and followers of the sync groups of
+    /// `animation_graph` among the `active_animations` of a player.
+    ///
+    /// The groups are resolved in the order of their node indices, and a clip
+    /// that belongs to several sync groups only belongs to the first one.
+    pub(crate) fn new(
+        animation_graph: &AnimationGraph,
+        animation_clips: &'a Assets<AnimationClip>,
+        active_animations: &HashMap<AnimationNodeIndex, ActiveAnimation>,
+    ) -> Self {
+        let mut sync_groups: SmallVec<[(AnimationNodeIndex, AnimationSyncMode); 1]> =
+            animation_graph
+                .sync_groups
+                .iter()
+                .map(|(&node, &mode)| (node, mode))
+                .collect();
+        sync_groups.sort_unstable_by_key(|&(node, _)| node);
+
+        let mut groups: SmallVec<[ResolvedSyncGroup<'a>; 1]> = SmallVec::new();
+        for (group_node, mode) in sync_groups {
+            let mut members: SmallVec<[(AnimationNodeIndex, &'a AnimationClip, f32); 4]> =
+                SmallVec::new();
+            for child in animation_graph
+                .graph
+                .neighbors_directed(group_node, Direction::Outgoing)
+            {
+                let already_synced = groups.iter().any(|group| {
+                    group.leader == child
+                        || group
+                            .followers
+                            .iter()
+                            .any(|(follower, _)| *follower == child)
+                });
+                if already_synced {
+                    continue;
+                }
+                let node = &animation_graph[child];
+                let AnimationNodeType::Clip(ref clip_handle) = node.node_type else {
+                    continue;
+                };
+                let (Some(clip), Some(active_animation)) = (
+                    animation_clips.get(clip_handle),
+                    active_animations.get(&child),
+                ) else {
+                    continue;
+                };
+                if active_animation.is_paused() || clip.duration() <= 0.0 {
+                    continue;
+                }
+                members.push((child, clip, node.weight * active_animation.weight()));
+            }
+            if members.len() < 2 {
+                continue;
+            }
+
+            let dominant = members
+                .iter()
+                .enumerate()
+                .max_by(|(_, a), (_, b)| a.2.total_cmp(&b.2))
+                .map_or(0, |(index, _)| index);
+            let leader_index = match mode {
+                AnimationSyncMode::Leader(leader) => members
+                    .iter()
+                    .position(|(node, ..)| *node == leader)
+                    .unwrap_or(dominant),
+                AnimationSyncMode::DominantWeight | AnimationSyncMode::WeightedDuration => dominant,
+            };
+            let (leader, leader_clip, leader_weight) = members.remove(leader_index);
+
+            let time_scale = match mode {
+                AnimationSyncMode::WeightedDuration => {
+                    let (total_weight, weighted_duration) = members.iter().fold(
+                        (leader_weight, leader_clip.duration() * leader_weight),
+                        |(total, duration), (_, clip, weight)| {
+                            (total + weight, duration + clip.duration() * weight)
+                        },
+                    );
+                    match total_weight > 0.0 && weighted_duration > 0.0 {
+                        true => leader_clip.duration() * total_weight / weighted_duration,
+                        false => 1.0,
+                    }
+                }
+                AnimationSyncMode::DominantWeight | AnimationSyncMode::Leader(_) => 1.0,
+            };
+
+            groups.push(ResolvedSyncGroup {
+                leader,
+                leader_clip,
+                time_scale,
+                followers: members
+                    .into_iter()
+                    .map(|(node, clip, _)| (node, clip))
+                    .collect(),
+            });
+        }
+        Self { groups }
+    }
+
+    /// Returns true if the animation of `node` is moved by the leader of its
+    /// sync group instead of being advanced by itself.
+    pub(crate) fn is_follower(&self, node: AnimationNodeIndex) -> bool {
+        self.groups.iter().any(|group| {
+            group
+                .followers
+                .iter()
+                .any(|(follower, _)| *follower == node)
+        })
+    }
+
+    /// Returns the factor by which the delta time of the animation of `node`
+    /// is scaled.
+    pub(crate) fn time_scale(&self, node: AnimationNodeIndex) -> f32 {
+        self.groups
+            .iter()
+            .find(|group| group.leader == node)
+            .map_or(1.0, |group| group.time_scale)
+    }
+
+    /// Moves the followers of every sync group to the phase of their leader,
+    /// once the leaders have been advanced by `delta` seconds.
+    pub(crate) fn sync_followers(
+        &self,
+        active_animations: &mut HashMap<AnimationNodeIndex, ActiveAnimation>,
+        delta: f32,
+    ) {
+        for group in &self.groups {
+            let Some(leader) = active_animations.get(&group.leader) else {
+                continue;
+            };
+            let phase = phase_at(group.leader_clip, leader.seek_time());
+            let reversed = leader.is_playback_reversed();
+            for (follower, clip) in &group.followers {
+                if let Some(active_animation) = active_animations.get_mut(follower) {
+                    active_animation.sync(seek_time_at_phase(clip, phase), delta, reversed);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clip_with_markers(duration: f32, markers: &[f32]) -> AnimationClip {
+        let mut clip = AnimationClip::default();
+        clip.set_duration(duration);
+        for &marker in markers {
+            clip.add_sync_marker(marker);
+        }
+        clip
+    }
+
+    #[test]
+    fn phase_between_sync_markers() {
+        let clip = clip_with_markers(1.0, &[0.75, 0.25]);
+        assert_eq!(clip.sync_markers(), &[0.25, 0.75]);
+
+        for (seek_time, phase) in [(0.25, 0.0), (0.5, 0.25), (0.75, 0.5), (0.0, 0.75)] {
+            assert!((phase_at(&clip, seek_time) - phase).abs() < 1e-5);
+            assert!((seek_time_at_phase(&clip, phase) - seek_time).abs() < 1e-5);
+        }
+
+        // Unevenly spaced markers split the phase evenly.
+        let clip = clip_with_markers(2.0, &[0.0, 0.5]);
+        assert!((phase_at(&clip, 0.25) - 0.25).abs() < 1e-5);
+        assert!((phase_at(&clip, 1.25) - 0.75).abs() < 1e-5);
+    }
+
+    #[test]
+    fn followers_match_leader_phase() {
+        let mut animation_clips = Assets::<AnimationClip>::default();
+        let walk_clip = animation_clips.add(clip_with_markers(1.0, &[]));
+        let run_clip = animation_clips.add(clip_with_markers(2.0, &[]));
+
+        let mut animation_graph = AnimationGraph::new();
+        let blend = animation_graph.add_blend(1.0, animation_graph.root);
+        let walk = animation_graph.add_clip(walk_clip, 0.25, blend);
+        let run = animation_graph.add_clip(run_clip, 0.75, blend);
+
+        for (mode, run_seek_time) in [
+            (AnimationSyncMode::DominantWeight, 0.5),
+            (AnimationSyncMode::Leader(walk), 1.0),
+            // The leader is stretched to 0.25 * 1.0 + 0.75 * 2.0 = 1.75
+            // seconds.
+            (AnimationSyncMode::WeightedDuration, 0.5 * 2.0 / 1.75),
+        ] {
+            animation_graph.add_sync_group(blend, mode);
+            let mut active_animations = HashMap::default();
+            active_animations.insert(walk, ActiveAnimation::default());
+            active_animations.insert(run, ActiveAnimation::default());
+
+            let sync_groups =
+                ResolvedSyncGroups::new(&animation_graph, &animation_clips, &active_animations);
+            let leader = match mode {
+                AnimationSyncMode::Leader(_) => walk,
+                _ => run,
+            };
+            assert!(!sync_groups.is_follower(leader));
+            let duration = match leader == walk {
+                true => 1.0,
+                false => 2.0,
+            };
+            active_animations
+                .get_mut(&leader)
+                .unwrap()
+                .update(0.5 * sync_groups.time_scale(leader), duration);
+            sync_groups.sync_followers(&mut active_animations, 0.5);
+
+            let run_seek_time_actual = active_animations[&run].seek_time();
+            let walk_seek_time = active_animations[&walk].seek_time();
+            assert!((run_seek_time_actual - run_seek_time).abs() < 1e-5);
+            assert!((walk_seek_time - run_seek_time * 0.5).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn overlapping_sync_groups_resolve_in_node_order() {
+        let mut animation_clips = Assets::<AnimationClip>::default();
+        let mut animation_graph = AnimationGraph::new();
+        let first = animation_graph.add_blend(1.0, animation_graph.root);
+        let second = animation_graph.add_blend(1.0, animation_graph.root);
+        let walk = animation_graph.add_clip(
+            animation_clips.add(clip_with_markers(1.0, &[])),
+            0.25,
+            first,
+        );
+        let run = animation_graph.add_clip(
+            animation_clips.add(clip_with_markers(2.0, &[])),
+            0.75,
+            first,
+        );
+        let jog = animation_graph.add_clip(
+            animation_clips.add(clip_with_markers(1.5, &[])),
+            0.9,
+            second,
+        );
+        // The run clip belongs to both groups, and would follow the jog clip in
+        // the second group.
+        animation_graph.add_edge(second, run);
+
+        animation_graph.add_sync_group(second, AnimationSyncMode::DominantWeight);
+        animation_graph.add_sync_group(first, AnimationSyncMode::DominantWeight);
+        let active_animations = [walk, run, jog]
+            .into_iter()
+            .map(|node| (node, ActiveAnimation::default()))
+            .collect::<HashMap<_, _>>();
+
+        let sync_groups =
+            ResolvedSyncGroups::new(&animation_graph, &animation_clips, &active_animations);
+        assert!(sync_groups.is_follower(walk));
+        assert!(!sync_groups.is_follower(run));
+        // The second group is left with a single clip, so it isn't synced.
+        assert!(!sync_groups.is_follower(jog));
+        assert_eq!(sync_groups.groups.len(), 1);
+    }
+}
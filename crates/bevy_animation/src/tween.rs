@@ -0,0 +1,745 @@
+//! Tweens, which animate a single property of an entity without an
+//! [`AnimationClip`](crate::AnimationClip) or an
+//! [`AnimationGraph`](crate::graph::AnimationGraph).
+//!
+//! A [`Tween`] animates one or more [animatable properties] of an entity,
+//! one after the other. The [`Tweens`] component plays any number of tweens
+//! on its entity at the same time, and drops each of them when it completes.
+//! For example, moving an entity to a new position over 0.3 seconds with an
+//! ease-out while it grows looks like this:
+//!
+//! ```
+//! # use bevy_animation::{animated_field, prelude::*};
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_math::{curve::EaseFunction, Vec3};
+//! # use bevy_transform::components::Transform;
+//! # use core::time::Duration;
+//! fn move_up(mut commands: Commands, entity: Entity) {
+//!     let tweens = Tweens::default()
+//!         .with(Tween::to(
+//!             animated_field!(Transform::translation),
+//!             Vec3::Y,
+//!             Duration::from_secs_f32(0.3),
+//!             EaseFunction::QuadraticOut,
+//!         ))
+//!         .with(Tween::to(
+//!             animated_field!(Transform::scale),
+//!             Vec3::splat(2.0),
+//!             Duration::from_secs_f32(0.5),
+//!             EaseFunction::Linear,
+//!         ));
+//!     commands
+//!         .entity(entity)
+//!         .insert(tweens)
+//!         .observe(|trigger: Trigger<TweenCompleted>| {
+//!             println!("{:?} of {} completed", trigger.tween, trigger.target());
+//!         });
+//! }
+//! ```
+//!
+//! Tweens are advanced by [`Time<Virtual>`], after the animations of
+//! [`AnimationPlayer`](crate::AnimationPlayer)s are applied, so they override
+//! animations of the same properties.
+//!
+//! [animatable properties]: AnimatableProperty
+
+use alloc::boxed::Box;
+use core::{mem, time::Duration};
+
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    event::Event,
+    query::With,
+    system::{ParallelCommands, Query, Res},
+};
+use bevy_math::{
+    curve::{Curve, EaseFunction},
+    ops,
+};
+use bevy_time::{Time, Virtual};
+use tracing::warn;
+
+use crate::{
+    animatable::Animatable, animation_curves::AnimatableProperty, AnimationEntityMut,
+    AnimationEvaluationError, RepeatAnimation,
+};
+
+/// An animation of properties of an entity over time, played by the
+/// [`Tweens`] component of the entity.
+///
+/// A tween is a sequence of steps, each of which either animates a property
+/// or waits, created with [`Tween::to`], [`Tween::curve`] and [`Tween::wait`]
+/// and chained with [`Tween::then`]. The whole sequence can be delayed,
+/// repeated, played back and forth, and sped up or slowed down.
+///
+/// When the tween completes, it is removed from the [`Tweens`] of the entity
+/// and [`TweenCompleted`] is triggered on the entity. A tween that repeats
+/// forever never completes.
+pub struct Tween {
+    steps: Vec<TweenStep>,
+    delay: f32,
+    repeat: RepeatAnimation,
+    yoyo: bool,
+    time_scale: f32,
+    paused: bool,
+    /// The time the tween has been played for, including the delay.
+    elapsed: f32,
+}
+
+impl Default for Tween {
+    fn default() -> Self {
+        Self {
+            steps: vec![],
+            delay: 0.0,
+            repeat: RepeatAnimation::default(),
+            yoyo: false,
+            time_scale: 1.0,
+            paused: false,
+            elapsed: 0.0,
+        }
+    }
+}
+
+/// A component that plays [`Tween`]s on its entity.
+///
+/// All of the tweens play at the same time, in the order they were added, so
+/// the latest tween wins when several animate the same property. Each tween
+/// is removed when it completes, and the component removes itself once it
+/// has no tweens left.
+#[derive(Component, Default)]
+pub struct Tweens {
+    tweens: Vec<(TweenId, Tween)>,
+    next_id: u32,
+}
+
+/// Identifies a [`Tween`] within the [`Tweens`] of an entity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TweenId(u32);
+
+/// An event triggered on an entity when one of its [`Tweens`] completes.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TweenCompleted {
+    /// The tween that completed, which is no longer part of the [`Tweens`].
+    pub tween: TweenId,
+}
+
+impl Tweens {
+    /// Adds `tween` to these tweens, and returns them.
+    pub fn with(mut self, tween: Tween) -> Self {
+        self.add(tween);
+        self
+    }
+
+    /// Starts playing `tween` along with the others, and returns its
+    /// identifier.
+    pub fn add(&mut self, tween: Tween) -> TweenId {
+        let id = TweenId(self.next_id);
+        self.next_id += 1;
+        self.tweens.push((id, tween));
+        id
+    }
+
+    /// Returns the tween with the given `id`, if it is still playing.
+    pub fn get(&self, id: TweenId) -> Option<&Tween> {
+        self.tweens
+            .iter()
+            .find(|(tween_id, _)| *tween_id == id)
+            .map(|(_, tween)| tween)
+    }
+
+    /// Returns a mutable reference to the tween with the given `id`, if it is
+    /// still playing.
+    pub fn get_mut(&mut self, id: TweenId) -> Option<&mut Tween> {
+        self.tweens
+            .iter_mut()
+            .find(|(tween_id, _)| *tween_id == id)
+            .map(|(_, tween)| tween)
+    }
+
+    /// Stops the tween with the given `id` where it is, without triggering
+    /// [`TweenCompleted`], and returns it.
+    pub fn remove(&mut self, id: TweenId) -> Option<Tween> {
+        let index = self
+            .tweens
+            .iter()
+            .position(|(tween_id, _)| *tween_id == id)?;
+        Some(self.tweens.remove(index).1)
+    }
+
+    /// Returns an iterator over the playing tweens and their identifiers.
+    pub fn iter(&self) -> impl Iterator<Item = (TweenId, &Tween)> {
+        self.tweens.iter().map(|(id, tween)| (*id, tween))
+    }
+
+    /// Returns a mutable iterator over the playing tweens and their
+    /// identifiers.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (TweenId, &mut Tween)> {
+        self.tweens.iter_mut().map(|(id, tween)| (*id, tween))
+    }
+
+    /// Returns the number of playing tweens.
+    pub fn len(&self) -> usize {
+        self.tweens.len()
+    }
+
+    /// Returns true if no tween is playing.
+    pub fn is_empty(&self) -> bool {
+        self.tweens.is_empty()
+    }
+}
+
+impl From<Tween> for Tweens {
+    fn from(tween: Tween) -> Self {
+        Self::default().with(tween)
+    }
+}
+
+/// A step of a [`Tween`].
+struct TweenStep {
+    duration: f32,
+    /// The animated property, or `None` if the step waits.
+    track: Option<Box<dyn TweenTrack>>,
+    /// Whether the step has been reached at least once.
+    started: bool,
+}
+
+/// Sets an animated property of an entity.
+trait TweenTrack: Send + Sync + 'static {
+    /// Sets the property to its value at the normalized time `s`, from 0 at
+    /// the start of the step to 1 at its end.
+    fn apply(
+        &mut self,
+        entity: &mut AnimationEntityMut,
+        s: f32,
+    ) -> Result<(), AnimationEvaluationError>;
+}
+
+/// A track that moves a property from its value when the step starts to a
+/// target value.
+struct ToTrack<P: AnimatableProperty> {
+    property: P,
+    start: Option<P::Property>,
+    end: P::Property,
+    ease: EaseFunction,
+}
+
+impl<P> TweenTrack for ToTrack<P>
+where
+    P: AnimatableProperty,
+    P::Property: Clone,
+{
+    fn apply(
+        &mut self,
+        entity: &mut AnimationEntityMut,
+        s: f32,
+    ) -> Result<(), AnimationEvaluationError> {
+        let value = self.property.get_mut(entity)?;
+        let start = self.start.get_or_insert_with(|| value.clone());
+        *value = P::Property::interpolate(start, &self.end, self.ease.sample_clamped(s));
+        Ok(())
+    }
+}
+
+/// A track that samples a property from a curve.
+struct CurveTrack<P, C> {
+    property: P,
+    curve: C,
+}
+
+impl<P, C> TweenTrack for CurveTrack<P, C>
+where
+    P: AnimatableProperty,
+    C: Curve<P::Property> + Send + Sync + 'static,
+{
+    fn apply(
+        &mut self,
+        entity: &mut AnimationEntityMut,
+        s: f32,
+    ) -> Result<(), AnimationEvaluationError> {
+        let domain = self.curve.domain();
+        let t = match domain.is_bounded() {
+            true => domain.start() + s * domain.length(),
+            false => s,
+        };
+        *self.property.get_mut(entity)? = self.curve.sample_clamped(t);
+        Ok(())
+    }
+}
+
+impl Tween {
+    /// Creates a tween from a single step.
+    fn from_step(duration: Duration, track: Option<Box<dyn TweenTrack>>) -> Self {
+        Self {
+            steps: vec![TweenStep {
+                duration: duration.as_secs_f32(),
+                track,
+                started: false,
+            }],
+            ..Self::default()
+        }
+    }
+
+    /// Creates a tween that moves `property` from its current value to `end`
+    /// over `duration`, following the `ease` function.
+    ///
+    /// The start value is read when the step starts, so that tweens of the
+    /// same property can be chained.
+    pub fn to<P>(property: P, end: P::Property, duration: Duration, ease: EaseFunction) -> Self
+    where
+        P: AnimatableProperty,
+        P::Property: Clone,
+    {
+        Self::from_step(
+            duration,
+            Some(Box::new(ToTrack {
+                property,
+                start: None,
+                end,
+                ease,
+            })),
+        )
+    }
+
+    /// Creates a tween that sets `property` from `curve`.
+    ///
+    /// The tween lasts as long as the domain of the curve. Curves with an
+    /// unbounded domain are sampled on `[0, 1]` over one second. Any
+    /// [`Curve`] can be used, such as an
+    /// [`EasingCurve`](bevy_math::curve::EasingCurve) or the result of its
+    /// adaptors.
+    pub fn curve<P, C>(property: P, curve: C) -> Self
+    where
+        P: AnimatableProperty,
+        C: Curve<P::Property> + Send + Sync + 'static,
+    {
+        let domain = curve.domain();
+        let duration = match domain.is_bounded() {
+            true => domain.length(),
+            false => 1.0,
+        };
+        Self::from_step(
+            Duration::from_secs_f32(duration),
+            Some(Box::new(CurveTrack { property, curve })),
+        )
+    }
+
+    /// Creates a tween that waits for `duration` without animating anything.
+    pub fn wait(duration: Duration) -> Self {
+        Self::from_step(duration, None)
+    }
+
+    /// Appends the steps of `next` to this tween, so that they play when this
+    /// tween's steps are done.
+    ///
+    /// Only the steps of `next` are appended; its delay, repetition, and time
+    /// scale are ignored.
+    pub fn then(mut self, next: Tween) -> Self {
+        self.steps.extend(next.steps);
+        self
+    }
+
+    /// Appends a step that waits for `duration` to this tween.
+    pub fn then_wait(self, duration: Duration) -> Self {
+        self.then(Self::wait(duration))
+    }
+
+    /// Delays the start of the tween by `delay`.
+    ///
+    /// The delay only applies once, not on every repetition.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay.as_secs_f32();
+        self
+    }
+
+    /// Sets how many times the tween plays.
+    pub fn with_repeat(mut self, repeat: RepeatAnimation) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Makes the tween repeat forever.
+    pub fn repeat(self) -> Self {
+        self.with_repeat(RepeatAnimation::Forever)
+    }
+
+    /// Sets whether every other repetition of the tween plays backwards.
+    pub fn with_yoyo(mut self, yoyo: bool) -> Self {
+        self.yoyo = yoyo;
+        self
+    }
+
+    /// Sets the factor by which the time of the tween is scaled.
+    ///
+    /// Tweens can't play backwards, so negative factors are treated as `0.0`.
+    pub fn with_time_scale(mut self, time_scale: f32) -> Self {
+        self.set_time_scale(time_scale);
+        self
+    }
+
+    /// Returns the factor by which the time of the tween is scaled.
+    pub fn time_scale(&self) -> f32 {
+        self.time_scale
+    }
+
+    /// Sets the factor by which the time of the tween is scaled.
+    ///
+    /// Tweens can't play backwards, so negative factors are treated as `0.0`.
+    pub fn set_time_scale(&mut self, time_scale: f32) -> &mut Self {
+        self.time_scale = time_scale.max(0.0);
+        self
+    }
+
+    /// Pauses the tween.
+    pub fn pause(&mut self) -> &mut Self {
+        self.paused = true;
+        self
+    }
+
+    /// Resumes the tween.
+    pub fn resume(&mut self) -> &mut Self {
+        self.paused = false;
+        self
+    }
+
+    /// Returns true if the tween is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Returns the duration of one play of the steps of the tween, excluding
+    /// the delay.
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f32(self.cycle_duration())
+    }
+
+    /// Returns the time the tween has been played for, including the delay.
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_secs_f32(self.elapsed)
+    }
+
+    fn cycle_duration(&self) -> f32 {
+        self.steps.iter().map(|step| step.duration).sum()
+    }
+
+    /// Advances the tween by `delta` seconds and applies it to `entity`.
+    ///
+    /// Returns true if the tween completed.
+    fn advance(&mut self, delta: f32, entity: &mut AnimationEntityMut) -> bool {
+        if self.paused {
+            return false;
+        }
+        self.elapsed += delta * self.time_scale;
+        let time = self.elapsed - self.delay;
+        if time < 0.0 {
+            return false;
+        }
+
+        let plays = match self.repeat {
+            RepeatAnimation::Never => 1,
+            RepeatAnimation::Count(count) => count.max(1),
+            RepeatAnimation::Forever => u32::MAX,
+        };
+        let cycle_duration = self.cycle_duration();
+        let (play, local_time, completed) = match cycle_duration > 0.0 {
+            true => {
+                let play = ops::floor(time / cycle_duration);
+                match play >= plays as f32 {
+                    true => (plays - 1, cycle_duration, true),
+                    false => (play as u32, time - play * cycle_duration, false),
+                }
+            }
+            false => (0, 0.0, true),
+        };
+        let position = match self.yoyo && play % 2 == 1 {
+            true => cycle_duration - local_time,
+            false => local_time,
+        };
+
+        self.apply(position, entity);
+        completed
+    }
+
+    /// Applies the steps of the tween at `position` seconds into a play.
+    ///
+    /// Steps that have already been reached and lie after `position` are
+    /// rewound to their start, latest first, and then the steps before and at
+    /// `position` are applied in order, so that the earliest and latest steps
+    /// animating the same property win respectively.
+    fn apply(&mut self, position: f32, entity: &mut AnimationEntityMut) {
+        let mut start = 0.0;
+        let mut progress = Vec::with_capacity(self.steps.len());
+        for step in &self.steps {
+            let s = match step.duration > 0.0 {
+                true => ((position - start) / step.duration).clamp(0.0, 1.0),
+                false => 1.0,
+            };
+            progress.push((position >= start, s));
+            start += step.duration;
+        }
+
+        let mut apply_step = |step: &mut TweenStep, s: f32| {
+            if let Some(ref mut track) = step.track {
+                if let Err(err) = track.apply(entity, s) {
+                    warn!("Failed to apply tween: {:?}", err);
+                }
+            }
+        };
+        for (step, _) in self
+            .steps
+            .iter_mut()
+            .zip(&progress)
+            .rev()
+            .filter(|(step, (reached, _))| step.started && !reached)
+        {
+            apply_step(step, 0.0);
+        }
+        for (step, &(_, s)) in self
+            .steps
+            .iter_mut()
+            .zip(&progress)
+            .filter(|(_, (reached, _))| *reached)
+        {
+            step.started = true;
+            apply_step(step, s);
+        }
+    }
+}
+
+/// A system that advances the [`Tweens`] of every entity by the
+/// [`Time<Virtual>`] delta, and removes the tweens that completed.
+pub fn advance_tweens(
+    time: Res<Time<Virtual>>,
+    par_commands: ParallelCommands,
+    mut targets: Query<(Entity, AnimationEntityMut), With<Tweens>>,
+) {
+    let delta = time.delta_secs();
+    targets.par_iter_mut().for_each(|(entity, mut target)| {
+        // Take the tweens out of the entity, so that the entity can be
+        // borrowed mutably while they are applied.
+        let Some(mut tweens) = target
+            .get_mut::<Tweens>()
+            .map(|mut tweens| mem::take(&mut *tweens))
+        else {
+            return;
+        };
+        let mut completed = Vec::new();
+        tweens.tweens.retain_mut(|(id, tween)| {
+            let done = tween.advance(delta, &mut target);
+            if done {
+                completed.push(*id);
+            }
+            !done
+        });
+        let is_empty = tweens.is_empty();
+        if let Some(mut slot) = target.get_mut::<Tweens>() {
+            *slot = tweens;
+        }
+
+        if !completed.is_empty() {
+            par_commands.command_scope(|mut commands| {
+                if is_empty {
+                    commands.entity(entity).remove::<Tweens>();
+                }
+                for tween in completed {
+                    commands.trigger_targets(TweenCompleted { tween }, entity);
+                }
+            });
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{animated_field, animation_curves::AnimatedField};
+    use bevy_ecs::{observer::Trigger, resource::Resource, system::ResMut, world::World};
+    use bevy_math::Vec3;
+    use bevy_transform::components::Transform;
+
+    #[derive(Resource, Default)]
+    struct Completions(Vec<TweenId>);
+
+    fn advance(world: &mut World, seconds: f32) {
+        world
+            .resource_mut::<Time<Virtual>>()
+            .advance_by(Duration::from_secs_f32(seconds));
+        world.run_system_cached(advance_tweens).unwrap();
+    }
+
+    fn translation(world: &World, entity: Entity) -> Vec3 {
+        world.get::<Transform>(entity).unwrap().translation
+    }
+
+    #[test]
+    fn tween_sequence_with_delay() {
+        let mut world = World::new();
+        world.init_resource::<Time<Virtual>>();
+        world.init_resource::<Completions>();
+        let entity = world
+            .spawn((
+                Transform::IDENTITY,
+                Tweens::from(
+                    Tween::to(
+                        animated_field!(Transform::translation),
+                        Vec3::X,
+                        Duration::from_secs(1),
+                        EaseFunction::Linear,
+                    )
+                    .then_wait(Duration::from_secs(1))
+                    .then(Tween::to(
+                        animated_field!(Transform::translation),
+                        Vec3::ZERO,
+                        Duration::from_secs(1),
+                        EaseFunction::Linear,
+                    ))
+                    .with_delay(Duration::from_secs(1)),
+                ),
+            ))
+            .observe(
+                |trigger: Trigger<TweenCompleted>, mut completions: ResMut<Completions>| {
+                    completions.0.push(trigger.tween);
+                },
+            )
+            .id();
+
+        advance(&mut world, 0.5);
+        assert_eq!(translation(&world, entity), Vec3::ZERO);
+        advance(&mut world, 1.0);
+        assert!(translation(&world, entity).abs_diff_eq(Vec3::X * 0.5, 1e-5));
+        advance(&mut world, 1.0);
+        assert_eq!(translation(&world, entity), Vec3::X);
+        advance(&mut world, 1.0);
+        assert!(translation(&world, entity).abs_diff_eq(Vec3::X * 0.5, 1e-5));
+        assert!(world.get::<Tweens>(entity).is_some());
+
+        advance(&mut world, 1.0);
+        assert_eq!(translation(&world, entity), Vec3::ZERO);
+        assert!(world.get::<Tweens>(entity).is_none());
+        assert_eq!(world.resource::<Completions>().0, [TweenId(0)]);
+    }
+
+    #[test]
+    fn tweens_play_together() {
+        let mut world = World::new();
+        world.init_resource::<Time<Virtual>>();
+        world.init_resource::<Completions>();
+        let tweens = Tweens::default()
+            .with(Tween::to(
+                animated_field!(Transform::translation),
+                Vec3::X,
+                Duration::from_secs(1),
+                EaseFunction::Linear,
+            ))
+            .with(Tween::to(
+                animated_field!(Transform::scale),
+                Vec3::splat(3.0),
+                Duration::from_secs(2),
+                EaseFunction::Linear,
+            ));
+        let entity = world
+            .spawn((Transform::IDENTITY, tweens))
+            .observe(
+                |trigger: Trigger<TweenCompleted>, mut completions: ResMut<Completions>| {
+                    completions.0.push(trigger.tween);
+                },
+            )
+            .id();
+
+        advance(&mut world, 0.5);
+        let transform = *world.get::<Transform>(entity).unwrap();
+        assert!(transform.translation.abs_diff_eq(Vec3::X * 0.5, 1e-5));
+        assert!(transform.scale.abs_diff_eq(Vec3::splat(1.5), 1e-5));
+
+        // The first tween completes while the second keeps playing.
+        advance(&mut world, 1.0);
+        assert_eq!(translation(&world, entity), Vec3::X);
+        assert_eq!(world.resource::<Completions>().0, [TweenId(0)]);
+        let tweens = world.get::<Tweens>(entity).unwrap();
+        assert_eq!(tweens.len(), 1);
+        assert!(tweens.get(TweenId(0)).is_none());
+        assert!(tweens.get(TweenId(1)).is_some());
+
+        // Tweens can be added while others play.
+        let added = world
+            .get_mut::<Tweens>(entity)
+            .unwrap()
+            .add(Tween::wait(Duration::from_secs(1)));
+        assert_eq!(added, TweenId(2));
+        advance(&mut world, 0.5);
+        assert_eq!(
+            world.get::<Transform>(entity).unwrap().scale,
+            Vec3::splat(3.0)
+        );
+        assert_eq!(world.resource::<Completions>().0, [TweenId(0), TweenId(1)]);
+        advance(&mut world, 0.5);
+        assert!(world.get::<Tweens>(entity).is_none());
+        assert_eq!(
+            world.resource::<Completions>().0,
+            [TweenId(0), TweenId(1), TweenId(2)]
+        );
+    }
+
+    #[test]
+    fn tween_yoyo_with_time_scale() {
+        let mut world = World::new();
+        world.init_resource::<Time<Virtual>>();
+        let entity = world
+            .spawn((
+                Transform::IDENTITY,
+                Tweens::from(
+                    Tween::to(
+                        animated_field!(Transform::scale),
+                        Vec3::splat(3.0),
+                        Duration::from_secs(1),
+                        EaseFunction::Linear,
+                    )
+                    .with_repeat(RepeatAnimation::Count(2))
+                    .with_yoyo(true)
+                    .with_time_scale(2.0),
+                ),
+            ))
+            .id();
+
+        let scale = |world: &World| world.get::<Transform>(entity).unwrap().scale;
+        advance(&mut world, 0.25);
+        assert!(scale(&world).abs_diff_eq(Vec3::splat(2.0), 1e-5));
+        advance(&mut world, 0.5);
+        assert!(scale(&world).abs_diff_eq(Vec3::splat(2.0), 1e-5));
+        advance(&mut world, 0.5);
+        assert_eq!(scale(&world), Vec3::ONE);
+        assert!(world.get::<Tweens>(entity).is_none());
+    }
+
+    #[test]
+    fn tween_negative_time_scale() {
+        let mut world = World::new();
+        world.init_resource::<Time<Virtual>>();
+        let entity = world
+            .spawn((
+                Transform::IDENTITY,
+                Tweens::from(Tween::wait(Duration::from_secs(1)).with_time_scale(-1.0)),
+            ))
+            .id();
+
+        fn tween(world: &World, entity: Entity) -> &Tween {
+            world
+                .get::<Tweens>(entity)
+                .unwrap()
+                .get(TweenId(0))
+                .unwrap()
+        }
+        assert_eq!(tween(&world, entity).time_scale(), 0.0);
+        advance(&mut world, 0.5);
+        assert_eq!(tween(&world, entity).elapsed(), Duration::ZERO);
+
+        world
+            .get_mut::<Tweens>(entity)
+            .unwrap()
+            .get_mut(TweenId(0))
+            .unwrap()
+            .set_time_scale(2.0);
+        advance(&mut world, 0.5);
+        assert!(world.get::<Tweens>(entity).is_none());
+    }
+}
@@ -4,13 +4,14 @@
 use bevy_macro_utils::BevyManifest;
 use proc_macro::{Span, TokenStream};
 use quote::{format_ident, quote};
-use syn::{parse_macro_input, Data, DeriveInput, Path};
+use syn::{parse_macro_input, Data, DeriveInput, LitStr, Path, Type};
 
 pub(crate) fn bevy_asset_path() -> Path {
     BevyManifest::shared().get_path("bevy_asset")
 }
 
 const DEPENDENCY_ATTRIBUTE: &str = "dependency";
+const ASSET_ATTRIBUTE: &str = "asset";
 
 #[proc_macro_derive(Asset, attributes(dependency))]
 pub fn derive_asset(input: TokenStream) -> TokenStream {
@@ -122,3 +123,135 @@ fn derive_dependency_visitor_internal(
         }
     })
 }
+
+#[proc_macro_derive(AssetCollection, attributes(asset))]
+pub fn derive_asset_collection(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let bevy_asset_path: Path = bevy_asset_path();
+    match derive_asset_collection_internal(&ast, &bevy_asset_path) {
+        Ok(asset_collection) => TokenStream::from(asset_collection),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+fn derive_asset_collection_internal(
+    ast: &DeriveInput,
+    bevy_asset_path: &Path,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let struct_name = &ast.ident;
+    let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
+
+    let Data::Struct(data_struct) = &ast.data else {
+        return Err(syn::Error::new(
+            Span::call_site().into(),
+            "AssetCollection derive only works on structs with named fields",
+        ));
+    };
+    let syn::Fields::Named(fields) = &data_struct.fields else {
+        return Err(syn::Error::new(
+            Span::call_site().into(),
+            "AssetCollection derive only works on structs with named fields",
+        ));
+    };
+
+    let mut loads = Vec::new();
+    let mut field_values = Vec::new();
+    for field in &fields.named {
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let mut source = None;
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|a| a.path().is_ident(ASSET_ATTRIBUTE))
+        {
+            attr.parse_nested_meta(|meta| {
+                let method = if meta.path.is_ident("path") {
+                    "load_path"
+                } else if meta.path.is_ident("glob") {
+                    "load_glob"
+                } else if meta.path.is_ident("manifest") {
+                    "load_manifest"
+                } else {
+                    return Err(meta.error("expected `path`, `glob` or `manifest`"));
+                };
+                if source.is_some() {
+                    return Err(meta.error("only one asset source can be set per field"));
+                }
+                if method != "load_path" && !is_vec(ty) {
+                    return Err(meta.error(
+                        "`glob` and `manifest` can only be used on `Vec<Handle<T>>` fields",
+                    ));
+                }
+                let value: LitStr = meta.value()?.parse()?;
+                if method == "load_glob"
+                    && value
+                        .value()
+                        .trim_start_matches('/')
+                        .split('/')
+                        .next()
+                        .is_some_and(|folder| folder.contains(['*', '?']))
+                {
+                    return Err(syn::Error::new(
+                        value.span(),
+                        "`glob` patterns must start with a folder, such as `sounds/*.ogg`",
+                    ));
+                }
+                source = Some((format_ident!("{method}"), value));
+                Ok(())
+            })?;
+        }
+
+        match source {
+            Some((method, value)) => {
+                let index = loads.len();
+                loads.push(quote! {
+                    handles.#method::<<#ty as #bevy_asset_path::AssetCollectionField>::Asset>(asset_server, #value);
+                });
+                field_values.push(quote! {
+                    #ident: #bevy_asset_path::AssetCollectionField::from_handles(handles.typed_handles(#index, folders))
+                });
+            }
+            None => field_values.push(quote! {
+                #ident: ::core::default::Default::default()
+            }),
+        }
+    }
+
+    // prevent unused variable warnings in case no field has an asset source
+    let (handles, folders, handles_mut) = if loads.is_empty() {
+        (quote! { _handles }, quote! { _folders }, quote! {})
+    } else {
+        (quote! { handles }, quote! { folders }, quote! { mut })
+    };
+
+    Ok(quote! {
+        impl #impl_generics #bevy_asset_path::AssetCollection for #struct_name #type_generics #where_clause {
+            fn load(asset_server: &#bevy_asset_path::AssetServer) -> #bevy_asset_path::AssetCollectionHandles {
+                let #handles_mut handles = #bevy_asset_path::AssetCollectionHandles::default();
+                #(#loads)*
+                handles
+            }
+
+            fn from_handles(
+                #handles: &#bevy_asset_path::AssetCollectionHandles,
+                #folders: &#bevy_asset_path::Assets<#bevy_asset_path::LoadedFolder>,
+            ) -> Self {
+                Self {
+                    #(#field_values,)*
+                }
+            }
+        }
+    })
+}
+
+fn is_vec(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Vec"),
+        _ => false,
+    }
+}
@@ -0,0 +1,437 @@
+use crate::{
+    io::Reader, Asset, AssetLoader, AssetPath, AssetServer, Assets, Handle, LoadContext,
+    LoadedFolder, ParseAssetPathError, RecursiveDependencyLoadState, UntypedHandle,
+};
+use alloc::{string::String, vec, vec::Vec};
+use bevy_ecs::{
+    resource::Resource,
+    system::{Commands, Res, ResMut},
+};
+use bevy_reflect::TypePath;
+use core::marker::PhantomData;
+use thiserror::Error;
+
+pub use bevy_asset_macros::AssetCollection;
+
+/// A group of assets that is loaded as a whole, such as everything a level or a menu needs.
+///
+/// This is usually derived. Each field that should be loaded is annotated with one of:
+/// * `#[asset(path = "...")]`: loads a single asset, for `Handle<T>` fields.
+/// * `#[asset(glob = "...")]`: loads every asset of type `T` matching the pattern, for `Vec<Handle<T>>` fields.
+///   `*` and `?` match within a folder, and `**` matches any number of folders. This builds on
+///   [`AssetServer::load_folder`], so the folder before the first wildcard is loaded in full, and
+///   patterns with wildcards must start with a folder. A pattern without wildcards loads that file.
+/// * `#[asset(manifest = "...")]`: loads every asset listed in an [`AssetCollectionManifest`] file, for `Vec<Handle<T>>` fields.
+///
+/// Fields without an `#[asset]` attribute are initialized with [`Default::default`].
+///
+/// ```
+/// # use bevy_asset::prelude::*;
+/// # use bevy_asset::AssetCollection;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_reflect::TypePath;
+/// # #[derive(Asset, TypePath)]
+/// # struct Image;
+/// # #[derive(Asset, TypePath)]
+/// # struct AudioSource;
+/// #[derive(AssetCollection, Resource)]
+/// struct MenuAssets {
+///     #[asset(path = "menu/background.png")]
+///     background: Handle<Image>,
+///     #[asset(glob = "menu/sounds/*.ogg")]
+///     sounds: Vec<Handle<AudioSource>>,
+///     #[asset(manifest = "menu/icons.collection.ron")]
+///     icons: Vec<Handle<Image>>,
+/// }
+/// ```
+///
+/// Use [`AssetApp::init_asset_collection`](crate::AssetApp::init_asset_collection) to load a collection
+/// as a [`Resource`] and track its progress with [`LoadingAssetCollection`].
+pub trait AssetCollection: Sized + Send + Sync + 'static {
+    /// Starts loading every asset of this collection.
+    fn load(asset_server: &AssetServer) -> AssetCollectionHandles;
+
+    /// Builds this collection from the given `handles`, once [`AssetCollectionHandles::load_state`] is
+    /// [`RecursiveDependencyLoadState::Loaded`].
+    fn from_handles(handles: &AssetCollectionHandles, folders: &Assets<LoadedFolder>) -> Self;
+}
+
+/// A field of an [`AssetCollection`], which can be built from a list of typed handles.
+pub trait AssetCollectionField {
+    /// The type of asset this field refers to.
+    type Asset: Asset;
+
+    /// Builds this field from the handles loaded for it.
+    fn from_handles(handles: Vec<Handle<Self::Asset>>) -> Self;
+}
+
+impl<A: Asset> AssetCollectionField for Handle<A> {
+    type Asset = A;
+
+    fn from_handles(handles: Vec<Handle<A>>) -> Self {
+        handles.into_iter().next().unwrap_or_default()
+    }
+}
+
+impl<A: Asset> AssetCollectionField for Vec<Handle<A>> {
+    type Asset = A;
+
+    fn from_handles(handles: Vec<Handle<A>>) -> Self {
+        handles
+    }
+}
+
+/// The handles an [`AssetCollection`] is loading, with one entry per loaded field.
+#[derive(Default)]
+pub struct AssetCollectionHandles {
+    entries: Vec<CollectionEntry>,
+}
+
+enum CollectionEntry {
+    Path(UntypedHandle),
+    Glob {
+        folder: Handle<LoadedFolder>,
+        pattern: String,
+    },
+    Manifest {
+        manifest: Handle<AssetCollectionManifest>,
+        handles: Option<Vec<UntypedHandle>>,
+        load: fn(&AssetServer, AssetPath<'static>) -> UntypedHandle,
+    },
+}
+
+impl AssetCollectionHandles {
+    /// Adds an entry that loads the asset at `path`.
+    pub fn load_path<'a, A: Asset>(
+        &mut self,
+        asset_server: &AssetServer,
+        path: impl Into<AssetPath<'a>>,
+    ) {
+        let handle = asset_server.load::<A>(path).untyped();
+        self.entries.push(CollectionEntry::Path(handle));
+    }
+
+    /// Adds an entry that loads every asset of type `A` whose path matches the glob `pattern`.
+    ///
+    /// The folder before the first wildcard of `pattern` is loaded with [`AssetServer::load_folder`],
+    /// and a `pattern` without wildcards loads the asset at that path.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pattern` has a wildcard in its first component, such as `*.png` or `**/*.ogg`,
+    /// since that would load every asset of the source.
+    pub fn load_glob<A: Asset>(&mut self, asset_server: &AssetServer, pattern: &str) {
+        let pattern = pattern.trim_start_matches('/');
+        let Some(folder) = glob_folder(pattern) else {
+            self.load_path::<A>(asset_server, pattern);
+            return;
+        };
+        assert!(
+            !folder.is_empty(),
+            "the glob pattern `{pattern}` must start with a folder, such as `sounds/*.ogg`"
+        );
+        self.entries.push(CollectionEntry::Glob {
+            folder: asset_server.load_folder(folder),
+            pattern: pattern.into(),
+        });
+    }
+
+    /// Adds an entry that loads every asset listed in the [`AssetCollectionManifest`] at `path`.
+    pub fn load_manifest<'a, A: Asset>(
+        &mut self,
+        asset_server: &AssetServer,
+        path: impl Into<AssetPath<'a>>,
+    ) {
+        self.entries.push(CollectionEntry::Manifest {
+            manifest: asset_server.load(path),
+            handles: None,
+            load: |asset_server, path| asset_server.load::<A>(path).untyped(),
+        });
+    }
+
+    /// Starts loading the assets listed in manifests that have finished loading.
+    pub fn update(
+        &mut self,
+        asset_server: &AssetServer,
+        manifests: &Assets<AssetCollectionManifest>,
+    ) {
+        for entry in &mut self.entries {
+            if let CollectionEntry::Manifest {
+                manifest,
+                handles: handles @ None,
+                load,
+            } = entry
+            {
+                if let Some(manifest) = manifests.get(manifest) {
+                    *handles = Some(
+                        manifest
+                            .paths
+                            .iter()
+                            .map(|path| load(asset_server, path.clone()))
+                            .collect(),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Returns the combined load state of every asset in the collection, including their dependencies.
+    ///
+    /// This is [`RecursiveDependencyLoadState::Failed`] as soon as any asset fails, and only
+    /// [`RecursiveDependencyLoadState::Loaded`] once every asset (and every manifest entry) has loaded.
+    pub fn load_state(&self, asset_server: &AssetServer) -> RecursiveDependencyLoadState {
+        let mut all_loaded = true;
+        let mut any_started = false;
+        let mut states = Vec::new();
+        for entry in &self.entries {
+            match entry {
+                CollectionEntry::Path(handle) => states.push(handle.id()),
+                CollectionEntry::Glob { folder, .. } => states.push(folder.id().untyped()),
+                CollectionEntry::Manifest {
+                    manifest, handles, ..
+                } => {
+                    states.push(manifest.id().untyped());
+                    match handles {
+                        Some(handles) => states.extend(handles.iter().map(UntypedHandle::id)),
+                        None => all_loaded = false,
+                    }
+                }
+            }
+        }
+        for id in states {
+            match asset_server.recursive_dependency_load_state(id) {
+                RecursiveDependencyLoadState::Failed(error) => {
+                    return RecursiveDependencyLoadState::Failed(error);
+                }
+                RecursiveDependencyLoadState::Loaded => any_started = true,
+                RecursiveDependencyLoadState::Loading => {
+                    any_started = true;
+                    all_loaded = false;
+                }
+                RecursiveDependencyLoadState::NotLoaded => all_loaded = false,
+            }
+        }
+        if all_loaded {
+            RecursiveDependencyLoadState::Loaded
+        } else if any_started {
+            RecursiveDependencyLoadState::Loading
+        } else {
+            RecursiveDependencyLoadState::NotLoaded
+        }
+    }
+
+    /// Returns the handles of type `A` loaded for the entry at `index`.
+    pub fn typed_handles<A: Asset>(
+        &self,
+        index: usize,
+        folders: &Assets<LoadedFolder>,
+    ) -> Vec<Handle<A>> {
+        match &self.entries[index] {
+            CollectionEntry::Path(handle) => vec![handle.clone().typed::<A>()],
+            CollectionEntry::Glob { folder, pattern } => folders
+                .get(folder)
+                .map(|folder| {
+                    folder
+                        .handles
+                        .iter()
+                        .filter(|handle| {
+                            handle.path().is_some_and(|path| {
+                                glob_matches(pattern, &path.path().to_string_lossy())
+                            })
+                        })
+                        .filter_map(|handle| handle.clone().try_typed::<A>().ok())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            CollectionEntry::Manifest { handles, .. } => handles
+                .iter()
+                .flatten()
+                .map(|handle| handle.clone().typed::<A>())
+                .collect(),
+        }
+    }
+}
+
+/// Returns the folder before the first wildcard of the glob `pattern`, or `None` if it has no
+/// wildcards.
+fn glob_folder(pattern: &str) -> Option<&str> {
+    let wildcard = pattern.find(['*', '?'])?;
+    Some(
+        pattern[..wildcard]
+            .rsplit_once('/')
+            .map_or("", |(folder, _)| folder),
+    )
+}
+
+/// Returns `true` if `path` matches the glob `pattern`.
+fn glob_matches(pattern: &str, path: &str) -> bool {
+    fn matches(pattern: &[u8], path: &[u8]) -> bool {
+        match pattern {
+            [] => path.is_empty(),
+            [b'*', b'*', b'/', rest @ ..] => {
+                // `**/` matches zero or more folders
+                matches(rest, path)
+                    || path
+                        .iter()
+                        .position(|&c| c == b'/')
+                        .is_some_and(|i| matches(pattern, &path[i + 1..]))
+            }
+            [b'*', b'*', rest @ ..] => (0..=path.len()).any(|i| matches(rest, &path[i..])),
+            [b'*', rest @ ..] => (0..=path.len())
+                .take_while(|&i| i == 0 || path[i - 1] != b'/')
+                .any(|i| matches(rest, &path[i..])),
+            [b'?', rest @ ..] => !path.is_empty() && path[0] != b'/' && matches(rest, &path[1..]),
+            [c, rest @ ..] => path.first() == Some(c) && matches(rest, &path[1..]),
+        }
+    }
+    matches(pattern.as_bytes(), path.replace('\\', "/").as_bytes())
+}
+
+/// A list of asset paths loaded by an `#[asset(manifest = "...")]` field of an [`AssetCollection`].
+///
+/// Manifests are RON files with the `.collection.ron` extension containing a list of paths, which are
+/// resolved relative to the manifest:
+///
+/// ```ron
+/// [
+///     "icons/sword.png",
+///     "../shared/shield.png",
+/// ]
+/// ```
+#[derive(Asset, TypePath, Debug)]
+pub struct AssetCollectionManifest {
+    /// The paths listed in this manifest.
+    pub paths: Vec<AssetPath<'static>>,
+}
+
+/// Loads [`AssetCollectionManifest`] files.
+#[derive(Default)]
+pub struct AssetCollectionManifestLoader;
+
+/// An error that occurs while loading an [`AssetCollectionManifest`].
+#[derive(Error, Debug)]
+pub enum AssetCollectionManifestLoaderError {
+    /// An [IO](std::io) Error
+    #[error("Could not read the manifest: {0}")]
+    Io(#[from] std::io::Error),
+    /// A [RON](ron) Error
+    #[error("Could not parse RON: {0}")]
+    RonSpannedError(#[from] ron::error::SpannedError),
+    /// A path in the manifest could not be parsed.
+    #[error(transparent)]
+    ParseAssetPathError(#[from] ParseAssetPathError),
+}
+
+impl AssetLoader for AssetCollectionManifestLoader {
+    type Asset = AssetCollectionManifest;
+    type Settings = ();
+    type Error = AssetCollectionManifestLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<AssetCollectionManifest, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let paths: Vec<String> = ron::de::from_bytes(&bytes)?;
+        let paths = paths
+            .iter()
+            .map(|path| load_context.asset_path().resolve_embed(path))
+            .collect::<Result<_, _>>()?;
+        Ok(AssetCollectionManifest { paths })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["collection.ron"]
+    }
+}
+
+/// Tracks the loading of the [`AssetCollection`] `C`, which is inserted as a resource once it has fully loaded.
+///
+/// This is added by [`AssetApp::init_asset_collection`](crate::AssetApp::init_asset_collection), and can be
+/// used to drive a loading screen.
+#[derive(Resource)]
+pub struct LoadingAssetCollection<C: AssetCollection> {
+    handles: AssetCollectionHandles,
+    load_state: RecursiveDependencyLoadState,
+    marker: PhantomData<fn() -> C>,
+}
+
+impl<C: AssetCollection> LoadingAssetCollection<C> {
+    /// Starts loading the collection `C`.
+    pub fn new(asset_server: &AssetServer) -> Self {
+        Self {
+            handles: C::load(asset_server),
+            load_state: RecursiveDependencyLoadState::NotLoaded,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the combined load state of the collection, as of the last update.
+    pub fn load_state(&self) -> &RecursiveDependencyLoadState {
+        &self.load_state
+    }
+
+    /// Returns the handles of the collection.
+    pub fn handles(&self) -> &AssetCollectionHandles {
+        &self.handles
+    }
+
+    /// Updates the load state of the collection, and inserts `C` as a resource once it has loaded.
+    pub fn update_system(
+        mut commands: Commands,
+        mut loading: ResMut<Self>,
+        asset_server: Res<AssetServer>,
+        manifests: Res<Assets<AssetCollectionManifest>>,
+        folders: Res<Assets<LoadedFolder>>,
+    ) where
+        C: Resource,
+    {
+        if loading.load_state.is_loaded() {
+            return;
+        }
+        let loading = &mut *loading;
+        loading.handles.update(&asset_server, &manifests);
+        loading.load_state = loading.handles.load_state(&asset_server);
+        if loading.load_state.is_loaded() {
+            commands.insert_resource(C::from_handles(&loading.handles, &folders));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{glob_folder, glob_matches};
+
+    #[test]
+    fn glob() {
+        assert!(glob_matches("sounds/*.ogg", "sounds/a.ogg"));
+        assert!(!glob_matches("sounds/*.ogg", "sounds/a.wav"));
+        assert!(!glob_matches("sounds/*.ogg", "sounds/sub/a.ogg"));
+        assert!(glob_matches("sounds/**/*.ogg", "sounds/a.ogg"));
+        assert!(glob_matches("sounds/**/*.ogg", "sounds/sub/deeper/a.ogg"));
+        assert!(glob_matches("sounds/?.ogg", "sounds/a.ogg"));
+        assert!(!glob_matches("sounds/?.ogg", "sounds/ab.ogg"));
+        assert!(glob_matches("sounds/**", "sounds/sub/a.ogg"));
+        assert!(glob_matches("sounds/**/a.ogg", "sounds/a.ogg"));
+        assert!(glob_matches("sounds/**/a.ogg", "sounds/sub/a.ogg"));
+        assert!(!glob_matches("sounds/**/a.ogg", "sounds/sub/b.ogg"));
+        assert!(glob_matches("sounds/a.ogg", "sounds/a.ogg"));
+        assert!(!glob_matches("sounds/a.ogg", "sounds/sub/a.ogg"));
+    }
+
+    #[test]
+    fn glob_folders() {
+        assert_eq!(glob_folder("sounds/*.ogg"), Some("sounds"));
+        assert_eq!(glob_folder("menu/sounds/**/a.ogg"), Some("menu/sounds"));
+        assert_eq!(glob_folder("menu/s*/a.ogg"), Some("menu"));
+        // patterns without a folder would load the whole source
+        assert_eq!(glob_folder("*.png"), Some(""));
+        assert_eq!(glob_folder("**/a.ogg"), Some(""));
+        // patterns without wildcards name a single file
+        assert_eq!(glob_folder("a.png"), None);
+        assert_eq!(glob_folder("menu/a.png"), None);
+    }
+}
@@ -64,27 +64,38 @@ impl FilesystemEventHandler for EmbeddedEventHandler {
     fn get_path(&self, absolute_path: &Path) -> Option<(PathBuf, bool)> {
         let (local_path, is_meta) = get_asset_path(&self.root, absolute_path);
         let final_path = self.root_paths.read().get(local_path.as_path())?.clone();
-        if is_meta {
-            warn!("Meta file asset hot-reloading is not supported yet: {final_path:?}");
-        }
-        Some((final_path, false))
+        Some((final_path, is_meta))
     }
 
     fn handle(&mut self, absolute_paths: &[PathBuf], event: AssetSourceEvent) {
         if self.last_event.as_ref() != Some(&event) {
-            if let AssetSourceEvent::ModifiedAsset(path) = &event {
-                if let Ok(file) = File::open(&absolute_paths[0]) {
-                    let mut reader = BufReader::new(file);
-                    let mut buffer = Vec::new();
-
-                    // Read file into vector.
-                    if reader.read_to_end(&mut buffer).is_ok() {
+            match &event {
+                AssetSourceEvent::ModifiedAsset(path) => {
+                    if let Some(buffer) = read_file(&absolute_paths[0]) {
                         self.dir.insert_asset(path, buffer);
                     }
                 }
+                AssetSourceEvent::AddedMeta(path) | AssetSourceEvent::ModifiedMeta(path) => {
+                    if let Some(buffer) = read_file(&absolute_paths[0]) {
+                        self.dir.insert_meta(path, buffer);
+                    }
+                }
+                _ => {}
             }
             self.last_event = Some(event.clone());
             self.sender.send(event).unwrap();
         }
     }
 }
+
+/// Reads the full contents of the file at `path`, warning if it cannot be read.
+fn read_file(path: &Path) -> Option<Vec<u8>> {
+    let mut buffer = Vec::new();
+    match File::open(path).and_then(|file| BufReader::new(file).read_to_end(&mut buffer)) {
+        Ok(_) => Some(buffer),
+        Err(err) => {
+            warn!("Failed to read embedded asset source file {path:?}: {err}");
+            None
+        }
+    }
+}
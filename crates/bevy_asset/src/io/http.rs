@@ -0,0 +1,288 @@
+use crate::io::{get_meta_path, AssetReader, AssetReaderError, PathStream, Reader, VecReader};
+use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+use tracing::warn;
+
+/// Reader implementation for loading assets over HTTP(S) on native platforms.
+///
+/// Asset paths are appended to the base URL given to [`HttpAssetReader::new`]. If a cache folder is set with
+/// [`HttpAssetReader::with_cache`], every downloaded file is stored on disk along with its `ETag` and `Last-Modified`
+/// headers. Later requests revalidate the cached copy instead of downloading it again, and the cached copy is used
+/// when the server cannot be reached at all.
+#[derive(Clone)]
+pub struct HttpAssetReader {
+    base_url: String,
+    cache_path: Option<PathBuf>,
+    agent: ureq::Agent,
+}
+
+/// The validators of a cached response, stored next to the cached bytes.
+#[derive(Serialize, Deserialize, Default)]
+struct CachedResponse {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl HttpAssetReader {
+    /// Creates a new [`HttpAssetReader`] that loads assets relative to `base_url`, without a local cache.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        let mut base_url = base_url.into();
+        while base_url.ends_with('/') {
+            base_url.pop();
+        }
+        Self {
+            base_url,
+            cache_path: None,
+            agent: ureq::Agent::new(),
+        }
+    }
+
+    /// Stores downloaded assets in the folder at `cache_path`, which is created if it doesn't exist.
+    pub fn with_cache(mut self, cache_path: impl Into<PathBuf>) -> Self {
+        self.cache_path = Some(cache_path.into());
+        self
+    }
+
+    /// Uses the given [`ureq::Agent`] to make requests, e.g. to configure timeouts or proxies.
+    pub fn with_agent(mut self, agent: ureq::Agent) -> Self {
+        self.agent = agent;
+        self
+    }
+
+    /// Returns the URL assets are loaded relative to.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Returns the folder downloaded assets are cached in, if any.
+    pub fn cache_path(&self) -> Option<&Path> {
+        self.cache_path.as_deref()
+    }
+
+    fn url(&self, path: &Path) -> String {
+        let path = path.to_string_lossy().replace('\\', "/");
+        format!("{}/{}", self.base_url, path.trim_start_matches('/'))
+    }
+
+    async fn fetch_bytes(&self, path: &Path) -> Result<impl Reader, AssetReaderError> {
+        let url = self.url(path);
+        let path = path.to_owned();
+        let agent = self.agent.clone();
+        let cache_path = self.cache_path.clone();
+        let bytes =
+            blocking::unblock(move || fetch(&agent, url, path, cache_path.as_deref())).await?;
+        Ok(VecReader::new(bytes))
+    }
+}
+
+/// Downloads `url`, revalidating and updating the cached copy in `cache_path` if it is set.
+fn fetch(
+    agent: &ureq::Agent,
+    url: String,
+    path: PathBuf,
+    cache_path: Option<&Path>,
+) -> Result<Vec<u8>, AssetReaderError> {
+    let cache_file = cache_path.map(|cache_path| {
+        let name = blake3::hash(url.as_bytes()).to_hex();
+        cache_path.join(name.as_str())
+    });
+    let cached = cache_file.as_deref().and_then(read_cache);
+
+    let mut request = agent.get(&url);
+    if let Some((response, _)) = &cached {
+        if let Some(etag) = &response.etag {
+            request = request.set("If-None-Match", etag);
+        }
+        if let Some(last_modified) = &response.last_modified {
+            request = request.set("If-Modified-Since", last_modified);
+        }
+    }
+
+    match request.call() {
+        Ok(response) if response.status() == 304 => match cached {
+            Some((_, bytes)) => Ok(bytes),
+            None => Err(AssetReaderError::HttpError(304)),
+        },
+        Ok(response) => {
+            let cached_response = CachedResponse {
+                url: url.clone(),
+                etag: response.header("ETag").map(ToOwned::to_owned),
+                last_modified: response.header("Last-Modified").map(ToOwned::to_owned),
+            };
+            let mut bytes = Vec::new();
+            response
+                .into_reader()
+                .read_to_end(&mut bytes)
+                .map_err(|err| AssetReaderError::Io(Arc::new(err)))?;
+            if let Some(cache_file) = &cache_file {
+                if let Err(err) = write_cache(cache_file, &cached_response, &bytes) {
+                    warn!("Failed to cache '{url}' at {}: {err}", cache_file.display());
+                }
+            }
+            Ok(bytes)
+        }
+        Err(ureq::Error::Status(404, _)) => Err(AssetReaderError::NotFound(path)),
+        Err(ureq::Error::Status(status, _)) => Err(AssetReaderError::HttpError(status)),
+        Err(ureq::Error::Transport(transport)) => match cached {
+            Some((_, bytes)) => {
+                warn!("Failed to fetch '{url}', using the cached copy instead: {transport}");
+                Ok(bytes)
+            }
+            None => Err(AssetReaderError::Io(Arc::new(std::io::Error::other(
+                transport.to_string(),
+            )))),
+        },
+    }
+}
+
+fn read_cache(cache_file: &Path) -> Option<(CachedResponse, Vec<u8>)> {
+    let response = std::fs::read_to_string(cache_file.with_extension("ron")).ok()?;
+    let response = ron::de::from_str(&response).ok()?;
+    let bytes = std::fs::read(cache_file).ok()?;
+    Some((response, bytes))
+}
+
+fn write_cache(cache_file: &Path, response: &CachedResponse, bytes: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = cache_file.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let response = ron::ser::to_string(response).map_err(std::io::Error::other)?;
+    // Write the bytes before the validators so a partially written entry is never revalidated.
+    std::fs::write(cache_file, bytes)?;
+    std::fs::write(cache_file.with_extension("ron"), response)
+}
+
+impl AssetReader for HttpAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        self.fetch_bytes(path).await
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        self.fetch_bytes(&get_meta_path(path)).await
+    }
+
+    /// Directories can't be listed over HTTP, so this always returns
+    /// [`AssetReaderError::NotFound`].
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        Err(AssetReaderError::NotFound(path.to_owned()))
+    }
+
+    /// Directories can't be listed over HTTP, so this always returns
+    /// [`AssetReaderError::NotFound`].
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        Err(AssetReaderError::NotFound(path.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HttpAssetReader;
+    use crate::io::{AssetReader, AssetReaderError, Reader};
+    use alloc::{format, string::String, sync::Arc, vec, vec::Vec};
+    use futures_lite::future::block_on;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        path::Path,
+        sync::Mutex,
+        thread,
+    };
+
+    /// Serves one canned response per entry of `responses` and records the request headers it received.
+    fn serve(
+        responses: Vec<&'static str>,
+    ) -> (String, Arc<Mutex<Vec<String>>>, thread::JoinHandle<()>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/assets", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let received = requests.clone();
+        let handle = thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = String::new();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    request.push_str(&line);
+                }
+                received.lock().unwrap().push(request);
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        (url, requests, handle)
+    }
+
+    fn read_text(reader: &HttpAssetReader, path: &str) -> Result<String, AssetReaderError> {
+        block_on(async {
+            let mut bytes = Vec::new();
+            reader
+                .read(Path::new(path))
+                .await?
+                .read_to_end(&mut bytes)
+                .await?;
+            Ok(String::from_utf8(bytes).unwrap())
+        })
+    }
+
+    #[test]
+    fn revalidates_and_falls_back_to_cache() {
+        let cache = std::env::temp_dir().join(format!("bevy_http_cache_{}", uuid::Uuid::new_v4()));
+        let (url, requests, server) = serve(vec![
+            "HTTP/1.1 200 OK\r\nETag: \"v1\"\r\nContent-Length: 5\r\nConnection: close\r\n\r\nhello",
+            "HTTP/1.1 304 Not Modified\r\nETag: \"v1\"\r\nConnection: close\r\n\r\n",
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        ]);
+        let reader = HttpAssetReader::new(url).with_cache(&cache);
+
+        assert_eq!(read_text(&reader, "text/a.txt").unwrap(), "hello");
+        assert_eq!(read_text(&reader, "text/a.txt").unwrap(), "hello");
+        assert!(matches!(
+            read_text(&reader, "missing.txt"),
+            Err(AssetReaderError::NotFound(_))
+        ));
+        server.join().unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert!(requests[0].starts_with("GET /assets/text/a.txt "));
+        assert!(!requests[0].contains("If-None-Match"));
+        assert!(requests[1].contains("If-None-Match: \"v1\""));
+        drop(requests);
+
+        // The server is gone, so the cached copy is used.
+        assert_eq!(read_text(&reader, "text/a.txt").unwrap(), "hello");
+        assert!(matches!(
+            read_text(&reader, "missing.txt"),
+            Err(AssetReaderError::Io(_))
+        ));
+
+        assert!(matches!(
+            block_on(reader.is_directory(Path::new("text"))),
+            Err(AssetReaderError::NotFound(_))
+        ));
+        assert!(matches!(
+            block_on(reader.read_directory(Path::new("text"))),
+            Err(AssetReaderError::NotFound(_))
+        ));
+
+        std::fs::remove_dir_all(cache).unwrap();
+    }
+}
@@ -1,8 +1,10 @@
-use crate::io::{AssetReader, AssetReaderError, PathStream, Reader};
+use crate::io::{
+    AssetReader, AssetReaderError, AssetWriter, AssetWriterError, PathStream, Reader, Writer,
+};
 use alloc::{borrow::ToOwned, boxed::Box, sync::Arc, vec::Vec};
 use bevy_platform_support::collections::HashMap;
 use core::{pin::Pin, task::Poll};
-use futures_io::AsyncRead;
+use futures_io::{AsyncRead, AsyncWrite};
 use futures_lite::{ready, Stream};
 use parking_lot::RwLock;
 use std::path::{Path, PathBuf};
@@ -64,6 +66,17 @@ impl Dir {
         data
     }
 
+    /// Removes the stored metadata at `path` and returns the `Data` stored if found and otherwise `None`.
+    pub fn remove_meta(&self, path: &Path) -> Option<Data> {
+        let mut dir = self.clone();
+        if let Some(parent) = path.parent() {
+            dir = self.get_or_insert_dir(parent);
+        }
+        let key: Box<str> = path.file_name().unwrap().to_string_lossy().into();
+        let data = dir.0.write().metadata.remove(&key);
+        data
+    }
+
     pub fn insert_meta(&self, path: &Path, value: impl Into<Value>) {
         let mut dir = self.clone();
         if let Some(parent) = path.parent() {
@@ -95,6 +108,17 @@ impl Dir {
         dir
     }
 
+    /// Removes the folder at `path` and returns it if found and otherwise `None`.
+    pub fn remove_dir(&self, path: &Path) -> Option<Dir> {
+        let mut dir = self.clone();
+        if let Some(parent) = path.parent() {
+            dir = self.get_dir(parent)?;
+        }
+        let key: Box<str> = path.file_name()?.to_string_lossy().into();
+        let removed = dir.0.write().dirs.remove(&key);
+        removed
+    }
+
     pub fn get_dir(&self, path: &Path) -> Option<Dir> {
         let mut dir = self.clone();
         for p in path.components() {
@@ -180,6 +204,14 @@ pub struct MemoryAssetReader {
     pub root: Dir,
 }
 
+/// In-memory [`AssetWriter`] implementation, which writes into the same kind of [`Dir`] that a
+/// [`MemoryAssetReader`] reads from. Written bytes are stored when their [`Writer`] is dropped.
+/// This is primarily intended for unit tests.
+#[derive(Default, Clone)]
+pub struct MemoryAssetWriter {
+    pub root: Dir,
+}
+
 /// Asset data stored in a [`Dir`].
 #[derive(Clone, Debug)]
 pub struct Data {
@@ -326,6 +358,143 @@ impl AssetReader for MemoryAssetReader {
     }
 }
 
+/// Collects written bytes, and stores them in its [`Dir`] when dropped.
+struct DataWriter {
+    dir: Dir,
+    path: PathBuf,
+    is_meta: bool,
+    bytes: Vec<u8>,
+}
+
+impl AsyncWrite for DataWriter {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut core::task::Context<'_>,
+        buf: &[u8],
+    ) -> Poll<futures_io::Result<usize>> {
+        self.get_mut().bytes.extend_from_slice(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(
+        self: Pin<&mut Self>,
+        _cx: &mut core::task::Context<'_>,
+    ) -> Poll<futures_io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(
+        self: Pin<&mut Self>,
+        _cx: &mut core::task::Context<'_>,
+    ) -> Poll<futures_io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Drop for DataWriter {
+    fn drop(&mut self) {
+        let bytes = core::mem::take(&mut self.bytes);
+        if self.is_meta {
+            self.dir.insert_meta(&self.path, bytes);
+        } else {
+            self.dir.insert_asset(&self.path, bytes);
+        }
+    }
+}
+
+/// The error returned when a file or folder of a [`MemoryAssetWriter`] doesn't exist.
+fn not_found() -> AssetWriterError {
+    std::io::Error::from(std::io::ErrorKind::NotFound).into()
+}
+
+impl AssetWriter for MemoryAssetWriter {
+    async fn write<'a>(&'a self, path: &'a Path) -> Result<Box<Writer>, AssetWriterError> {
+        Ok(Box::new(DataWriter {
+            dir: self.root.clone(),
+            path: path.to_owned(),
+            is_meta: false,
+            bytes: Vec::new(),
+        }))
+    }
+
+    async fn write_meta<'a>(&'a self, path: &'a Path) -> Result<Box<Writer>, AssetWriterError> {
+        Ok(Box::new(DataWriter {
+            dir: self.root.clone(),
+            path: path.to_owned(),
+            is_meta: true,
+            bytes: Vec::new(),
+        }))
+    }
+
+    async fn remove<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
+        self.root
+            .remove_asset(path)
+            .map(|_| ())
+            .ok_or_else(not_found)
+    }
+
+    async fn remove_meta<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
+        self.root
+            .remove_meta(path)
+            .map(|_| ())
+            .ok_or_else(not_found)
+    }
+
+    async fn rename<'a>(
+        &'a self,
+        old_path: &'a Path,
+        new_path: &'a Path,
+    ) -> Result<(), AssetWriterError> {
+        let data = self.root.remove_asset(old_path).ok_or_else(not_found)?;
+        self.root.insert_asset(new_path, data.value);
+        Ok(())
+    }
+
+    async fn rename_meta<'a>(
+        &'a self,
+        old_path: &'a Path,
+        new_path: &'a Path,
+    ) -> Result<(), AssetWriterError> {
+        let data = self.root.remove_meta(old_path).ok_or_else(not_found)?;
+        self.root.insert_meta(new_path, data.value);
+        Ok(())
+    }
+
+    async fn create_directory<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
+        self.root.get_or_insert_dir(path);
+        Ok(())
+    }
+
+    async fn remove_directory<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
+        self.root.remove_dir(path).map(|_| ()).ok_or_else(not_found)
+    }
+
+    async fn remove_empty_directory<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
+        let dir = self.root.get_dir(path).ok_or_else(not_found)?;
+        let is_empty = {
+            let dir = dir.0.read();
+            dir.assets.is_empty() && dir.metadata.is_empty() && dir.dirs.is_empty()
+        };
+        if !is_empty {
+            return Err(std::io::Error::from(std::io::ErrorKind::DirectoryNotEmpty).into());
+        }
+        self.root.remove_dir(path);
+        Ok(())
+    }
+
+    async fn remove_assets_in_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<(), AssetWriterError> {
+        let dir = self.root.get_dir(path).ok_or_else(not_found)?;
+        let mut dir = dir.0.write();
+        dir.assets.clear();
+        dir.metadata.clear();
+        dir.dirs.clear();
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 pub mod test {
     use super::Dir;
@@ -11,6 +11,8 @@ pub mod embedded;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod file;
 pub mod gated;
+#[cfg(all(feature = "http_source", not(target_arch = "wasm32")))]
+pub mod http;
 pub mod memory;
 pub mod processor_gated;
 #[cfg(target_arch = "wasm32")]
@@ -16,6 +16,10 @@ use tracing::{error, warn};
 
 use super::{ErasedAssetReader, ErasedAssetWriter};
 
+mod overlay;
+
+pub use overlay::*;
+
 /// A reference to an "asset source", which maps to an [`AssetReader`](crate::io::AssetReader) and/or [`AssetWriter`](crate::io::AssetWriter).
 ///
 /// * [`AssetSourceId::Default`] corresponds to "default asset paths" that don't specify a source: `/path/to/asset.png`
@@ -316,6 +320,34 @@ impl AssetSourceBuilder {
             default
         }
     }
+
+    /// Returns a builder containing a source that loads assets over HTTP(S) relative to `base_url`.
+    /// On native platforms, this will use [`HttpAssetReader`](crate::io::http::HttpAssetReader), which caches downloaded
+    /// assets in `cache_path` (relative to the asset root, like [`platform_default`](Self::platform_default) paths)
+    /// and falls back to the cache when offline. On Wasm, the browser cache is used instead.
+    #[cfg(feature = "http_source")]
+    #[cfg_attr(
+        target_arch = "wasm32",
+        expect(unused_variables, reason = "The browser cache is used on Wasm.")
+    )]
+    pub fn http(base_url: &str, cache_path: Option<&str>) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let reader = super::http::HttpAssetReader::new(base_url);
+            let reader = match cache_path {
+                Some(cache_path) => reader
+                    .with_cache(super::file::FileAssetReader::get_base_path().join(cache_path)),
+                None => reader,
+            };
+            Self::default().with_reader(move || Box::new(reader.clone()))
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            let base_url = base_url.to_string();
+            Self::default()
+                .with_reader(move || Box::new(super::wasm::HttpWasmAssetReader::new(&base_url)))
+        }
+    }
 }
 
 /// A [`Resource`] that hold (repeatable) functions capable of producing new [`AssetReader`](crate::io::AssetReader) and [`AssetWriter`](crate::io::AssetWriter) instances
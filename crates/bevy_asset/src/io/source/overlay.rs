@@ -0,0 +1,799 @@
+use crate::io::{
+    AssetReader, AssetReaderError, AssetSourceBuilder, AssetSourceEvent, AssetWatcher,
+    ErasedAssetReader, PathStream, Reader,
+};
+use alloc::{borrow::ToOwned, boxed::Box, format, sync::Arc, vec::Vec};
+use atomicow::CowArc;
+use bevy_platform_support::collections::{HashMap, HashSet};
+use futures_lite::StreamExt;
+use parking_lot::{Mutex, RwLock};
+use std::path::{Path, PathBuf};
+
+/// The file name prefix of a "whiteout" marker. A file named `.wh.name` in a layer of an [`OverlayAssetReader`]
+/// hides the file or folder `name` next to it in all lower priority layers.
+pub const WHITEOUT_PREFIX: &str = ".wh.";
+
+/// The file name of an "opaque" marker. A folder in a layer of an [`OverlayAssetReader`] that contains this file
+/// hides the contents of the same folder in all lower priority layers.
+pub const OPAQUE_WHITEOUT: &str = ".wh..wh..opq";
+
+/// Returns `true` if `path` is a [whiteout](WHITEOUT_PREFIX) or [opaque](OPAQUE_WHITEOUT) marker.
+fn is_marker(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().starts_with(WHITEOUT_PREFIX))
+}
+
+/// Returns the path hidden by the [whiteout](WHITEOUT_PREFIX) marker at `path`, if it is one.
+fn whiteout_target(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?.to_str()?;
+    if name == OPAQUE_WHITEOUT {
+        return None;
+    }
+    let target = name.strip_prefix(WHITEOUT_PREFIX)?;
+    Some(path.parent().unwrap_or(Path::new("")).join(target))
+}
+
+/// Returns `true` if `event` is about a [whiteout](WHITEOUT_PREFIX) or [opaque](OPAQUE_WHITEOUT) marker.
+fn is_marker_event(event: &AssetSourceEvent) -> bool {
+    match event {
+        AssetSourceEvent::AddedAsset(path)
+        | AssetSourceEvent::ModifiedAsset(path)
+        | AssetSourceEvent::RemovedAsset(path)
+        | AssetSourceEvent::AddedMeta(path)
+        | AssetSourceEvent::ModifiedMeta(path)
+        | AssetSourceEvent::RemovedMeta(path)
+        | AssetSourceEvent::AddedFolder(path)
+        | AssetSourceEvent::RemovedFolder(path)
+        | AssetSourceEvent::RemovedUnknown { path, .. } => is_marker(path),
+        AssetSourceEvent::RenamedAsset { old, new }
+        | AssetSourceEvent::RenamedMeta { old, new }
+        | AssetSourceEvent::RenamedFolder { old, new } => is_marker(old) || is_marker(new),
+    }
+}
+
+/// A single layer of an [`OverlayAssetReader`].
+pub struct OverlayLayer {
+    /// The name of this layer, as reported by [`OverlayResolutions`].
+    pub name: CowArc<'static, str>,
+    /// The reader used to read assets from this layer.
+    pub reader: Box<dyn ErasedAssetReader>,
+}
+
+impl OverlayLayer {
+    /// Creates a new layer with the given `name` that reads from `reader`.
+    pub fn new(name: impl Into<CowArc<'static, str>>, reader: Box<dyn ErasedAssetReader>) -> Self {
+        Self {
+            name: name.into(),
+            reader,
+        }
+    }
+}
+
+/// Tracks which layer of an [`OverlayAssetReader`] each asset path was last read from.
+///
+/// This can be cloned and shared freely. All clones refer to the same underlying data.
+#[derive(Default, Clone)]
+pub struct OverlayResolutions {
+    resolved: Arc<RwLock<HashMap<PathBuf, CowArc<'static, str>>>>,
+}
+
+impl OverlayResolutions {
+    /// Returns the name of the layer the asset at `path` was last read from, if it has been read.
+    pub fn get(&self, path: impl AsRef<Path>) -> Option<CowArc<'static, str>> {
+        self.resolved.read().get(path.as_ref()).cloned()
+    }
+
+    fn insert(&self, path: &Path, layer: &CowArc<'static, str>) {
+        self.resolved.write().insert(path.to_owned(), layer.clone());
+    }
+}
+
+/// An [`AssetReader`] that stacks several readers on top of each other, which is useful for mods or patches
+/// that override assets by path without copying the whole asset folder.
+///
+/// Layers are ordered by priority: an asset is read from the first layer that contains it. A higher priority
+/// layer can hide assets of lower layers using [whiteout](WHITEOUT_PREFIX) and [opaque](OPAQUE_WHITEOUT) markers.
+/// Meta files are always read from the same layer as their asset, so an overridden asset never picks up the
+/// settings of the asset it replaced. Directory listings merge the entries of all visible layers.
+///
+/// Use [`AssetSourceBuilder::overlay`] to build an asset source that also forwards the watcher events of every layer.
+pub struct OverlayAssetReader {
+    layers: Vec<OverlayLayer>,
+    resolutions: OverlayResolutions,
+}
+
+impl OverlayAssetReader {
+    /// Creates a new [`OverlayAssetReader`] from the given `layers`, ordered from highest to lowest priority.
+    pub fn new(layers: Vec<OverlayLayer>) -> Self {
+        Self::with_resolutions(layers, OverlayResolutions::default())
+    }
+
+    /// Creates a new [`OverlayAssetReader`] that records the layer each asset is read from in `resolutions`.
+    pub fn with_resolutions(layers: Vec<OverlayLayer>, resolutions: OverlayResolutions) -> Self {
+        Self {
+            layers,
+            resolutions,
+        }
+    }
+
+    /// Returns the layers of this reader, ordered from highest to lowest priority.
+    pub fn layers(&self) -> &[OverlayLayer] {
+        &self.layers
+    }
+
+    /// Returns the [`OverlayResolutions`] this reader records into.
+    pub fn resolutions(&self) -> &OverlayResolutions {
+        &self.resolutions
+    }
+
+    /// Returns the index of the layer that provides the asset at `path`.
+    pub async fn resolve_layer(&self, path: &Path) -> Result<usize, AssetReaderError> {
+        if is_marker(path) {
+            return Err(AssetReaderError::NotFound(path.to_owned()));
+        }
+        for (index, layer) in self.layers.iter().enumerate() {
+            match layer.reader.read(path).await {
+                Ok(_) => return Ok(index),
+                Err(AssetReaderError::NotFound(_)) => {}
+                Err(err) => return Err(err),
+            }
+            if self.hides_lower_layers(index, path).await? {
+                break;
+            }
+        }
+        Err(AssetReaderError::NotFound(path.to_owned()))
+    }
+
+    /// Returns `true` if `path` in the layer at `index` is not shadowed by a higher priority layer, either because
+    /// that layer has an asset or folder at `path` or because it hides `path` with a marker.
+    async fn is_visible(&self, index: usize, path: &Path) -> Result<bool, AssetReaderError> {
+        for (higher, layer) in self.layers[..index.min(self.layers.len())]
+            .iter()
+            .enumerate()
+        {
+            match layer.reader.read(path).await {
+                Ok(_) => return Ok(false),
+                Err(AssetReaderError::NotFound(_)) => {}
+                Err(err) => return Err(err),
+            }
+            if layer.reader.is_directory(path).await.unwrap_or(false)
+                || self.hides_lower_layers(higher, path).await?
+            {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Translates an `event` of the layer at `index` into the event the overlay as a whole sees, if any.
+    ///
+    /// Events for paths shadowed by a higher priority layer are dropped. Creating a [whiteout](WHITEOUT_PREFIX)
+    /// marker removes the path it hides, and deleting it makes that path reappear from the lower layers. An asset
+    /// added over or removed from a lower layer's asset is modified, since the overlay still provides it.
+    async fn translate_event(
+        &self,
+        index: usize,
+        event: AssetSourceEvent,
+    ) -> Result<Option<AssetSourceEvent>, AssetReaderError> {
+        if is_marker_event(&event) {
+            return self.translate_marker_event(index, event).await;
+        }
+        let event = match event {
+            AssetSourceEvent::AddedAsset(path) => {
+                if !self.is_visible(index, &path).await? {
+                    return Ok(None);
+                }
+                if self.provided_below(index, &path).await? {
+                    AssetSourceEvent::ModifiedAsset(path)
+                } else {
+                    AssetSourceEvent::AddedAsset(path)
+                }
+            }
+            AssetSourceEvent::RemovedAsset(path)
+            | AssetSourceEvent::RemovedUnknown {
+                path,
+                is_meta: false,
+            } => {
+                if !self.is_visible(index, &path).await? {
+                    return Ok(None);
+                }
+                if self.resolve_layer(&path).await.is_ok() {
+                    AssetSourceEvent::ModifiedAsset(path)
+                } else {
+                    AssetSourceEvent::RemovedUnknown {
+                        path,
+                        is_meta: false,
+                    }
+                }
+            }
+            AssetSourceEvent::RenamedAsset { old, new } => {
+                match (
+                    self.is_visible(index, &old).await?,
+                    self.is_visible(index, &new).await?,
+                ) {
+                    (true, true) => AssetSourceEvent::RenamedAsset { old, new },
+                    (true, false) => AssetSourceEvent::RemovedAsset(old),
+                    (false, true) => AssetSourceEvent::AddedAsset(new),
+                    (false, false) => return Ok(None),
+                }
+            }
+            AssetSourceEvent::RenamedMeta { old, new } => {
+                match (
+                    self.is_visible(index, &old).await?,
+                    self.is_visible(index, &new).await?,
+                ) {
+                    (true, true) => AssetSourceEvent::RenamedMeta { old, new },
+                    (true, false) => AssetSourceEvent::RemovedMeta(old),
+                    (false, true) => AssetSourceEvent::AddedMeta(new),
+                    (false, false) => return Ok(None),
+                }
+            }
+            AssetSourceEvent::RenamedFolder { old, new } => {
+                match (
+                    self.is_visible(index, &old).await?,
+                    self.is_visible(index, &new).await?,
+                ) {
+                    (true, true) => AssetSourceEvent::RenamedFolder { old, new },
+                    (true, false) => AssetSourceEvent::RemovedFolder(old),
+                    (false, true) => AssetSourceEvent::AddedFolder(new),
+                    (false, false) => return Ok(None),
+                }
+            }
+            AssetSourceEvent::ModifiedAsset(ref path)
+            | AssetSourceEvent::AddedMeta(ref path)
+            | AssetSourceEvent::ModifiedMeta(ref path)
+            | AssetSourceEvent::RemovedMeta(ref path)
+            | AssetSourceEvent::RemovedUnknown {
+                ref path,
+                is_meta: true,
+            }
+            | AssetSourceEvent::AddedFolder(ref path)
+            | AssetSourceEvent::RemovedFolder(ref path) => {
+                if !self.is_visible(index, path).await? {
+                    return Ok(None);
+                }
+                event
+            }
+        };
+        Ok(Some(event))
+    }
+
+    /// Translates an `event` about a marker of the layer at `index`. See [`Self::translate_event`].
+    async fn translate_marker_event(
+        &self,
+        index: usize,
+        event: AssetSourceEvent,
+    ) -> Result<Option<AssetSourceEvent>, AssetReaderError> {
+        let (marker, added) = match &event {
+            AssetSourceEvent::AddedAsset(path) => (path, true),
+            AssetSourceEvent::RemovedAsset(path)
+            | AssetSourceEvent::RemovedUnknown {
+                path,
+                is_meta: false,
+            } => (path, false),
+            _ => return Ok(None),
+        };
+        let Some(target) = whiteout_target(marker) else {
+            return Ok(None);
+        };
+        if !self.is_visible(index, &target).await? {
+            return Ok(None);
+        }
+        Ok(if added {
+            Some(AssetSourceEvent::RemovedUnknown {
+                path: target,
+                is_meta: false,
+            })
+        } else if AssetReader::is_directory(self, &target).await? {
+            Some(AssetSourceEvent::AddedFolder(target))
+        } else if self.resolve_layer(&target).await.is_ok() {
+            Some(AssetSourceEvent::AddedAsset(target))
+        } else {
+            None
+        })
+    }
+
+    /// Returns `true` if a layer below the one at `index` provides the asset at `path`, and the layer at `index`
+    /// doesn't hide it.
+    async fn provided_below(&self, index: usize, path: &Path) -> Result<bool, AssetReaderError> {
+        if self.hides_lower_layers(index, path).await? {
+            return Ok(false);
+        }
+        for (lower, layer) in self.layers.iter().enumerate().skip(index + 1) {
+            match layer.reader.read(path).await {
+                Ok(_) => return Ok(true),
+                Err(AssetReaderError::NotFound(_)) => {}
+                Err(err) => return Err(err),
+            }
+            if self.hides_lower_layers(lower, path).await? {
+                break;
+            }
+        }
+        Ok(false)
+    }
+
+    /// Returns `true` if the layer at `index` contains a marker that hides `path` in all lower priority layers.
+    async fn hides_lower_layers(
+        &self,
+        index: usize,
+        path: &Path,
+    ) -> Result<bool, AssetReaderError> {
+        let reader = &self.layers[index].reader;
+        let mut current = Some(path);
+        while let Some(path) = current {
+            let parent = path.parent();
+            let mut markers = Vec::with_capacity(2);
+            if let Some(name) = path.file_name() {
+                let whiteout = format!("{WHITEOUT_PREFIX}{}", name.to_string_lossy());
+                markers.push(parent.unwrap_or(Path::new("")).join(whiteout));
+            }
+            if let Some(parent) = parent {
+                markers.push(parent.join(OPAQUE_WHITEOUT));
+            }
+            for marker in markers {
+                match reader.read(&marker).await {
+                    Ok(_) => return Ok(true),
+                    Err(AssetReaderError::NotFound(_)) => {}
+                    Err(err) => return Err(err),
+                }
+            }
+            current = parent;
+        }
+        Ok(false)
+    }
+}
+
+impl AssetReader for OverlayAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        if is_marker(path) {
+            return Err(AssetReaderError::NotFound(path.to_owned()));
+        }
+        for (index, layer) in self.layers.iter().enumerate() {
+            match layer.reader.read(path).await {
+                Ok(reader) => {
+                    self.resolutions.insert(path, &layer.name);
+                    return Ok(reader);
+                }
+                Err(AssetReaderError::NotFound(_)) => {}
+                Err(err) => return Err(err),
+            }
+            if self.hides_lower_layers(index, path).await? {
+                break;
+            }
+        }
+        Err(AssetReaderError::NotFound(path.to_owned()))
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        let index = self.resolve_layer(path).await?;
+        self.layers[index].reader.read_meta(path).await
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        if is_marker(path) {
+            return Err(AssetReaderError::NotFound(path.to_owned()));
+        }
+        let mut found = false;
+        let mut entries = Vec::new();
+        let mut seen = <HashSet<PathBuf>>::default();
+        let mut hidden = <HashSet<PathBuf>>::default();
+        for (index, layer) in self.layers.iter().enumerate() {
+            match layer.reader.read_directory(path).await {
+                Ok(mut stream) => {
+                    found = true;
+                    let mut opaque = false;
+                    let mut whiteouts = Vec::new();
+                    while let Some(entry) = stream.next().await {
+                        let name = entry
+                            .file_name()
+                            .map(|name| name.to_string_lossy().into_owned());
+                        match name.as_deref() {
+                            Some(OPAQUE_WHITEOUT) => opaque = true,
+                            Some(name) if name.starts_with(WHITEOUT_PREFIX) => {
+                                whiteouts.push(path.join(&name[WHITEOUT_PREFIX.len()..]));
+                            }
+                            _ => {
+                                if !hidden.contains(&entry) && seen.insert(entry.clone()) {
+                                    entries.push(entry);
+                                }
+                            }
+                        }
+                    }
+                    hidden.extend(whiteouts);
+                    if opaque {
+                        break;
+                    }
+                }
+                Err(AssetReaderError::NotFound(_)) => {}
+                Err(err) => return Err(err),
+            }
+            if self.hides_lower_layers(index, path).await? {
+                break;
+            }
+        }
+
+        if found {
+            let stream: Box<PathStream> = Box::new(futures_lite::stream::iter(entries));
+            Ok(stream)
+        } else {
+            Err(AssetReaderError::NotFound(path.to_owned()))
+        }
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        if is_marker(path) {
+            return Ok(false);
+        }
+        for (index, layer) in self.layers.iter().enumerate() {
+            match layer.reader.is_directory(path).await {
+                Ok(true) => return Ok(true),
+                Ok(false) | Err(AssetReaderError::NotFound(_)) => {}
+                Err(err) => return Err(err),
+            }
+            if self.hides_lower_layers(index, path).await? {
+                break;
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// Constructs the [`ErasedAssetReader`] of a layer.
+type ReaderConstructor = Box<dyn FnMut() -> Box<dyn ErasedAssetReader> + Send + Sync>;
+
+/// Constructs the [`AssetWatcher`] of a layer, if it has one.
+type WatcherConstructor = Box<
+    dyn FnMut(crossbeam_channel::Sender<AssetSourceEvent>) -> Option<Box<dyn AssetWatcher>>
+        + Send
+        + Sync,
+>;
+
+/// The layers of an overlay asset source that are being built, shared between its reader and watcher.
+#[derive(Default)]
+struct OverlayLayers {
+    readers: Vec<(CowArc<'static, str>, ReaderConstructor)>,
+    /// The watchers of the layers, along with the index of the layer's reader.
+    watchers: Vec<(usize, WatcherConstructor)>,
+}
+
+impl OverlayLayers {
+    fn reader(&mut self, resolutions: OverlayResolutions) -> OverlayAssetReader {
+        OverlayAssetReader::with_resolutions(
+            self.readers
+                .iter_mut()
+                .map(|(name, reader)| OverlayLayer::new(name.clone(), reader()))
+                .collect(),
+            resolutions,
+        )
+    }
+}
+
+/// An [`AssetWatcher`] that watches every layer of an overlay asset source, and forwards the
+/// [translated](OverlayAssetReader::translate_event) events of the layers from a thread that stops when this watcher
+/// is dropped.
+struct OverlayWatcher {
+    watchers: Vec<Box<dyn AssetWatcher>>,
+    /// Dropped to stop the forwarding thread.
+    stop: Option<crossbeam_channel::Sender<()>>,
+    forwarder: Option<std::thread::JoinHandle<()>>,
+}
+
+impl AssetWatcher for OverlayWatcher {}
+
+impl Drop for OverlayWatcher {
+    fn drop(&mut self) {
+        self.watchers.clear();
+        self.stop = None;
+        if let Some(forwarder) = self.forwarder.take() {
+            let _ = forwarder.join();
+        }
+    }
+}
+
+impl OverlayWatcher {
+    /// Starts the watchers of the given `layers`, forwarding their translated events to `sender`.
+    fn start(
+        layers: &mut OverlayLayers,
+        sender: crossbeam_channel::Sender<AssetSourceEvent>,
+    ) -> Option<Box<dyn AssetWatcher>> {
+        let mut watchers = Vec::new();
+        let mut receivers = Vec::new();
+        for (index, watcher) in &mut layers.watchers {
+            let (layer_sender, receiver) = crossbeam_channel::unbounded();
+            if let Some(watcher) = watcher(layer_sender) {
+                watchers.push(watcher);
+                receivers.push((*index, receiver));
+            }
+        }
+        if watchers.is_empty() {
+            return None;
+        }
+        // The watcher reads the layers with its own readers, so it doesn't record resolutions.
+        let reader = layers.reader(OverlayResolutions::default());
+        let (stop, stopped) = crossbeam_channel::bounded::<()>(0);
+        let forwarder = std::thread::Builder::new()
+            .name("overlay asset watcher".to_owned())
+            .spawn(move || loop {
+                let (position, event) = {
+                    let mut select = crossbeam_channel::Select::new();
+                    select.recv(&stopped);
+                    for (_, receiver) in &receivers {
+                        select.recv(receiver);
+                    }
+                    let operation = select.select();
+                    if operation.index() == 0 {
+                        let _ = operation.recv(&stopped);
+                        return;
+                    }
+                    let position = operation.index() - 1;
+                    (position, operation.recv(&receivers[position].1))
+                };
+                let Ok(event) = event else {
+                    // This layer's watcher stopped.
+                    receivers.remove(position);
+                    if receivers.is_empty() {
+                        return;
+                    }
+                    continue;
+                };
+                let index = receivers[position].0;
+                // Events whose paths can't be checked are forwarded as they are.
+                let event = bevy_tasks::block_on(reader.translate_event(index, event.clone()))
+                    .unwrap_or(Some(event));
+                if let Some(event) = event {
+                    if sender.send(event).is_err() {
+                        return;
+                    }
+                }
+            })
+            .ok()?;
+        Some(Box::new(OverlayWatcher {
+            watchers,
+            stop: Some(stop),
+            forwarder: Some(forwarder),
+        }))
+    }
+}
+
+impl AssetSourceBuilder {
+    /// Returns a builder for an asset source that stacks the given `layers` using an [`OverlayAssetReader`],
+    /// ordered from highest to lowest priority. Also returns the [`OverlayResolutions`] its readers record into.
+    ///
+    /// The source watches every layer and forwards their events as the overlay sees them: events for paths shadowed
+    /// by a higher priority layer are dropped, and creating or deleting a [whiteout](WHITEOUT_PREFIX) marker removes
+    /// or restores the path it hides. Processed assets are read from the layers that have a processed reader. Writes
+    /// go to the highest priority layer.
+    pub fn overlay(
+        layers: impl IntoIterator<Item = (impl Into<CowArc<'static, str>>, AssetSourceBuilder)>,
+    ) -> (Self, OverlayResolutions) {
+        let resolutions = OverlayResolutions::default();
+        let mut builder = Self::default();
+        let mut unprocessed = OverlayLayers::default();
+        let mut processed = OverlayLayers::default();
+        for (index, (name, mut layer)) in layers.into_iter().enumerate() {
+            let name: CowArc<'static, str> = name.into();
+            if index == 0 {
+                builder.writer = layer.writer.take();
+                builder.processed_writer = layer.processed_writer.take();
+                builder.watch_warning = layer.watch_warning;
+                builder.processed_watch_warning = layer.processed_watch_warning;
+            }
+            if let Some(watcher) = layer.watcher.take() {
+                unprocessed
+                    .watchers
+                    .push((unprocessed.readers.len(), watcher));
+            }
+            if let Some(watcher) = layer.processed_watcher.take() {
+                processed.watchers.push((processed.readers.len(), watcher));
+            }
+            if let Some(reader) = layer.reader.take() {
+                unprocessed.readers.push((name.clone(), reader));
+            }
+            if let Some(reader) = layer.processed_reader.take() {
+                processed.readers.push((name, reader));
+            }
+        }
+
+        let has_processed_readers = !processed.readers.is_empty();
+        let unprocessed = Arc::new(Mutex::new(unprocessed));
+        let unprocessed_watched = unprocessed.clone();
+        let unprocessed_resolutions = resolutions.clone();
+        builder = builder
+            .with_reader(move || {
+                Box::new(unprocessed.lock().reader(unprocessed_resolutions.clone()))
+            })
+            .with_watcher(move |sender| {
+                OverlayWatcher::start(&mut unprocessed_watched.lock(), sender)
+            });
+
+        if has_processed_readers {
+            let processed = Arc::new(Mutex::new(processed));
+            let processed_watched = processed.clone();
+            let processed_resolutions = resolutions.clone();
+            builder = builder
+                .with_processed_reader(move || {
+                    Box::new(processed.lock().reader(processed_resolutions.clone()))
+                })
+                .with_processed_watcher(move |sender| {
+                    OverlayWatcher::start(&mut processed_watched.lock(), sender)
+                });
+        }
+
+        (builder, resolutions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OverlayAssetReader, OverlayLayer, OPAQUE_WHITEOUT};
+    use crate::io::{
+        memory::{Dir, MemoryAssetReader},
+        AssetReader, AssetReaderError, AssetSourceBuilder, AssetSourceEvent, AssetSourceId, Reader,
+    };
+    use alloc::{boxed::Box, string::String, vec, vec::Vec};
+    use core::time::Duration;
+    use futures_lite::{future::block_on, StreamExt};
+    use std::path::{Path, PathBuf};
+
+    fn layer(name: &'static str, dir: &Dir) -> OverlayLayer {
+        OverlayLayer::new(name, Box::new(MemoryAssetReader { root: dir.clone() }))
+    }
+
+    fn read_text(reader: &OverlayAssetReader, path: &str) -> Result<String, AssetReaderError> {
+        block_on(async {
+            let mut bytes = Vec::new();
+            reader
+                .read(Path::new(path))
+                .await?
+                .read_to_end(&mut bytes)
+                .await?;
+            Ok(String::from_utf8(bytes).unwrap())
+        })
+    }
+
+    fn list(reader: &OverlayAssetReader, path: &str) -> Vec<PathBuf> {
+        let mut entries = block_on(async {
+            reader
+                .read_directory(Path::new(path))
+                .await
+                .unwrap()
+                .collect::<Vec<_>>()
+                .await
+        });
+        entries.sort();
+        entries
+    }
+
+    #[test]
+    fn overlay_priority_and_whiteouts() {
+        let base = Dir::default();
+        base.insert_asset_text(Path::new("a.txt"), "base a");
+        base.insert_meta_text(Path::new("a.txt"), "base a meta");
+        base.insert_asset_text(Path::new("b.txt"), "base b");
+        base.insert_asset_text(Path::new("removed.txt"), "base removed");
+        base.insert_asset_text(Path::new("folder/c.txt"), "base c");
+        base.insert_asset_text(Path::new("replaced/d.txt"), "base d");
+
+        let mod_layer = Dir::default();
+        mod_layer.insert_asset_text(Path::new("a.txt"), "mod a");
+        mod_layer.insert_asset_text(Path::new(".wh.removed.txt"), "");
+        mod_layer.insert_asset_text(Path::new(".wh.folder"), "");
+        mod_layer.insert_asset_text(Path::new("replaced").join(OPAQUE_WHITEOUT).as_path(), "");
+        mod_layer.insert_asset_text(Path::new("replaced/e.txt"), "mod e");
+
+        let reader = OverlayAssetReader::new(vec![layer("mod", &mod_layer), layer("base", &base)]);
+
+        assert_eq!(read_text(&reader, "a.txt").unwrap(), "mod a");
+        assert_eq!(reader.resolutions().get("a.txt").as_deref(), Some("mod"));
+        assert_eq!(read_text(&reader, "b.txt").unwrap(), "base b");
+        assert_eq!(reader.resolutions().get("b.txt").as_deref(), Some("base"));
+
+        // The meta file comes from the layer that provides the asset, which has none here.
+        assert!(matches!(
+            block_on(reader.read_meta_bytes(Path::new("a.txt"))),
+            Err(AssetReaderError::NotFound(_))
+        ));
+
+        assert!(read_text(&reader, "removed.txt").is_err());
+        assert!(read_text(&reader, "folder/c.txt").is_err());
+        assert!(read_text(&reader, "replaced/d.txt").is_err());
+        assert_eq!(read_text(&reader, "replaced/e.txt").unwrap(), "mod e");
+
+        // Markers are hidden like the files they remove.
+        assert!(read_text(&reader, ".wh.removed.txt").is_err());
+        assert!(read_text(&reader, "replaced/.wh..wh..opq").is_err());
+        assert!(!block_on(reader.is_directory(Path::new(".wh.folder"))).unwrap());
+
+        assert_eq!(
+            list(&reader, ""),
+            vec![
+                PathBuf::from("a.txt"),
+                PathBuf::from("b.txt"),
+                PathBuf::from("replaced"),
+            ]
+        );
+        assert_eq!(
+            list(&reader, "replaced"),
+            vec![PathBuf::from("replaced/e.txt")]
+        );
+        assert!(!block_on(reader.is_directory(Path::new("folder"))).unwrap());
+    }
+
+    #[test]
+    fn overlay_translates_events_of_every_layer() {
+        struct TestWatcher;
+        impl crate::io::AssetWatcher for TestWatcher {}
+
+        fn watched_layer(dir: Dir, events: Vec<AssetSourceEvent>) -> AssetSourceBuilder {
+            let reader_dir = dir.clone();
+            AssetSourceBuilder::default()
+                .with_reader(move || {
+                    Box::new(MemoryAssetReader {
+                        root: reader_dir.clone(),
+                    })
+                })
+                .with_watcher(move |sender| {
+                    for event in &events {
+                        sender.send(event.clone()).unwrap();
+                    }
+                    Some(Box::new(TestWatcher))
+                })
+        }
+
+        let base = Dir::default();
+        for path in ["a.txt", "b.txt", "removed.txt", "restored.txt"] {
+            base.insert_asset_text(Path::new(path), path);
+        }
+        let mod_layer = Dir::default();
+        mod_layer.insert_asset_text(Path::new("a.txt"), "mod a");
+        mod_layer.insert_asset_text(Path::new(".wh.removed.txt"), "");
+
+        let (mut builder, _resolutions) = AssetSourceBuilder::overlay([
+            (
+                "mod",
+                watched_layer(
+                    mod_layer,
+                    vec![
+                        AssetSourceEvent::AddedAsset("a.txt".into()),
+                        AssetSourceEvent::AddedAsset(".wh.removed.txt".into()),
+                        AssetSourceEvent::RemovedAsset(".wh.restored.txt".into()),
+                    ],
+                ),
+            ),
+            (
+                "base",
+                watched_layer(
+                    base,
+                    vec![
+                        AssetSourceEvent::ModifiedAsset("a.txt".into()),
+                        AssetSourceEvent::ModifiedAsset("b.txt".into()),
+                        AssetSourceEvent::ModifiedAsset("removed.txt".into()),
+                    ],
+                ),
+            ),
+        ]);
+        let source = builder.build(AssetSourceId::Default, true, false).unwrap();
+        let receiver = source.event_receiver().unwrap();
+        let events = (0..4)
+            .map(|_| receiver.recv_timeout(Duration::from_secs(5)).unwrap())
+            .collect::<Vec<_>>();
+        for expected in [
+            // Adding an asset over a lower layer's asset modifies it.
+            AssetSourceEvent::ModifiedAsset("a.txt".into()),
+            // Adding a whiteout marker removes the path it hides, and removing one restores it.
+            AssetSourceEvent::RemovedUnknown {
+                path: "removed.txt".into(),
+                is_meta: false,
+            },
+            AssetSourceEvent::AddedAsset("restored.txt".into()),
+            AssetSourceEvent::ModifiedAsset("b.txt".into()),
+        ] {
+            assert!(events.contains(&expected), "{expected:?} in {events:?}");
+        }
+        // The base layer's events for the assets shadowed by the mod layer were dropped.
+        assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+    }
+}
@@ -62,6 +62,9 @@
 //! To enable asset hot reloading on desktop platforms, enable `bevy`'s `file_watcher` cargo feature.
 //! To toggle it at runtime, you can use the `watch_for_changes_override` field in the [`AssetPlugin`] to enable or disable hot reloading.
 //!
+//! When an asset is reloaded, assets that depend on it are notified with [`AssetEvent::Modified`],
+//! and assets whose loaders used it are reloaded after it. See [`DependentReloadMode`] to keep those dependents in place instead.
+//!
 //! # Procedural asset creation
 //!
 //! Not all assets are loaded from disk: some are generated at runtime, such as procedural materials, sounds or even levels.
@@ -167,13 +170,14 @@ pub mod prelude {
 
     #[doc(hidden)]
     pub use crate::{
-        Asset, AssetApp, AssetEvent, AssetId, AssetMode, AssetPlugin, AssetServer, Assets,
-        DirectAssetAccessExt, Handle, UntypedHandle,
+        Asset, AssetApp, AssetCollection, AssetEvent, AssetId, AssetMode, AssetPlugin, AssetServer,
+        Assets, DirectAssetAccessExt, Handle, UntypedHandle,
     };
 }
 
 mod asset_changed;
 mod assets;
+mod collection;
 mod direct_access_ext;
 mod event;
 mod folder;
@@ -188,6 +192,7 @@ mod server;
 
 pub use assets::*;
 pub use bevy_asset_macros::Asset;
+pub use collection::*;
 pub use direct_access_ext::DirectAssetAccessExt;
 pub use event::*;
 pub use folder::*;
@@ -210,6 +215,7 @@ pub use uuid;
 use crate::{
     io::{embedded::EmbeddedAssetRegistry, AssetSourceBuilder, AssetSourceBuilders, AssetSourceId},
     processor::{AssetProcessor, Process},
+    saver::AssetSaver,
 };
 use alloc::{
     string::{String, ToString},
@@ -220,6 +226,7 @@ use bevy_app::{App, Plugin, PostUpdate, PreUpdate};
 use bevy_ecs::prelude::Component;
 use bevy_ecs::{
     reflect::AppTypeRegistry,
+    resource::Resource,
     schedule::{IntoSystemConfigs, IntoSystemSetConfigs, SystemSet},
     world::FromWorld,
 };
@@ -258,6 +265,8 @@ pub struct AssetPlugin {
     pub mode: AssetMode,
     /// How/If asset meta files should be checked.
     pub meta_check: AssetMetaCheck,
+    /// How assets that depend on a hot-reloaded asset are updated. This only has an effect when watching for changes.
+    pub dependent_reload_mode: DependentReloadMode,
 }
 
 /// Controls whether or not assets are pre-processed before being loaded.
@@ -303,6 +312,24 @@ pub enum AssetMetaCheck {
     Never,
 }
 
+/// Configures how assets that depend on a hot-reloaded asset are updated.
+///
+/// Assets that hold a [`Handle`] to the reloaded asset (see [`VisitAssetDependencies`]) always observe the new value
+/// and receive an [`AssetEvent::Modified`]. This setting controls what happens to assets that _used_ the reloaded
+/// asset inside their [`AssetLoader`] (for example through [`LoadContext::read_asset_bytes`] or an immediate nested load).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DependentReloadMode {
+    /// Re-run the loaders of all assets that used the reloaded asset while loading. Reloads happen in dependency order,
+    /// so an asset is only reloaded once the assets its loader depends on have been reloaded.
+    #[default]
+    Reload,
+    /// Keep dependents in place and only notify them with [`AssetEvent::Modified`], without re-running their loaders.
+    ///
+    /// This avoids redundant work when loaders only keep [`Handle`]s to their dependencies, but any data a loader
+    /// copied out of the reloaded asset will stay stale until the dependent itself is reloaded.
+    InPlace,
+}
+
 impl Default for AssetPlugin {
     fn default() -> Self {
         Self {
@@ -311,6 +338,7 @@ impl Default for AssetPlugin {
             processed_file_path: Self::DEFAULT_PROCESSED_FILE_PATH.to_string(),
             watch_for_changes_override: None,
             meta_check: AssetMetaCheck::default(),
+            dependent_reload_mode: DependentReloadMode::default(),
         }
     }
 }
@@ -346,11 +374,13 @@ impl Plugin for AssetPlugin {
                     let mut builders = app.world_mut().resource_mut::<AssetSourceBuilders>();
                     let sources = builders.build_sources(watch, false);
 
-                    app.insert_resource(AssetServer::new_with_meta_check(
+                    app.insert_resource(AssetServer::new_with_loaders(
                         sources,
+                        Default::default(),
                         AssetServerMode::Unprocessed,
                         self.meta_check.clone(),
                         watch,
+                        self.dependent_reload_mode,
                     ));
                 }
                 AssetMode::Processed => {
@@ -367,6 +397,7 @@ impl Plugin for AssetPlugin {
                             AssetServerMode::Processed,
                             AssetMetaCheck::Always,
                             watch,
+                            self.dependent_reload_mode,
                         ))
                         .insert_resource(processor)
                         .add_systems(bevy_app::Startup, AssetProcessor::start);
@@ -375,11 +406,13 @@ impl Plugin for AssetPlugin {
                     {
                         let mut builders = app.world_mut().resource_mut::<AssetSourceBuilders>();
                         let sources = builders.build_sources(false, watch);
-                        app.insert_resource(AssetServer::new_with_meta_check(
+                        app.insert_resource(AssetServer::new_with_loaders(
                             sources,
+                            Default::default(),
                             AssetServerMode::Processed,
                             AssetMetaCheck::Always,
                             watch,
+                            self.dependent_reload_mode,
                         ));
                     }
                 }
@@ -389,6 +422,8 @@ impl Plugin for AssetPlugin {
             .init_asset::<LoadedFolder>()
             .init_asset::<LoadedUntypedAsset>()
             .init_asset::<()>()
+            .init_asset::<AssetCollectionManifest>()
+            .init_asset_loader::<AssetCollectionManifestLoader>()
             .add_event::<UntypedAssetLoadFailedEvent>()
             .configure_sets(PreUpdate, TrackAssets.after(handle_internal_asset_events))
             // `handle_internal_asset_events` requires the use of `&mut World`,
@@ -479,6 +514,8 @@ impl VisitAssetDependencies for Vec<UntypedHandle> {
 pub trait AssetApp {
     /// Registers the given `loader` in the [`App`]'s [`AssetServer`].
     fn register_asset_loader<L: AssetLoader>(&mut self, loader: L) -> &mut Self;
+    /// Registers the given `saver` in the [`App`]'s [`AssetServer`], which uses it to [save](AssetServer::save) assets at runtime.
+    fn register_asset_saver<S: AssetSaver>(&mut self, saver: S) -> &mut Self;
     /// Registers the given `processor` in the [`App`]'s [`AssetProcessor`].
     fn register_asset_processor<P: Process>(&mut self, processor: P) -> &mut Self;
     /// Registers the given [`AssetSourceBuilder`] with the given `id`.
@@ -512,6 +549,9 @@ pub trait AssetApp {
     /// Preregisters a loader for the given extensions, that will block asset loads until a real loader
     /// is registered.
     fn preregister_asset_loader<L: AssetLoader>(&mut self, extensions: &[&str]) -> &mut Self;
+    /// Starts loading the [`AssetCollection`] `C`, which is inserted as a resource once all of its assets have loaded.
+    /// Loading progress can be tracked through the [`LoadingAssetCollection<C>`] resource.
+    fn init_asset_collection<C: AssetCollection + Resource>(&mut self) -> &mut Self;
 }
 
 impl AssetApp for App {
@@ -522,6 +562,11 @@ impl AssetApp for App {
         self
     }
 
+    fn register_asset_saver<S: AssetSaver>(&mut self, saver: S) -> &mut Self {
+        self.world().resource::<AssetServer>().register_saver(saver);
+        self
+    }
+
     fn register_asset_processor<P: Process>(&mut self, processor: P) -> &mut Self {
         if let Some(asset_processor) = self.world().get_resource::<AssetProcessor>() {
             asset_processor.register_processor(processor);
@@ -615,6 +660,14 @@ impl AssetApp for App {
             .preregister_loader::<L>(extensions);
         self
     }
+
+    fn init_asset_collection<C: AssetCollection + Resource>(&mut self) -> &mut Self {
+        let loading = LoadingAssetCollection::<C>::new(self.world().resource::<AssetServer>());
+        self.insert_resource(loading).add_systems(
+            PreUpdate,
+            LoadingAssetCollection::<C>::update_system.after(TrackAssets),
+        )
+    }
 }
 
 /// A system set that holds all "track asset" operations.
@@ -634,12 +687,15 @@ mod tests {
         handle::Handle,
         io::{
             gated::{GateOpener, GatedReader},
-            memory::{Dir, MemoryAssetReader},
-            AssetReader, AssetReaderError, AssetSource, AssetSourceId, Reader,
+            memory::{Dir, MemoryAssetReader, MemoryAssetWriter},
+            AssetReader, AssetReaderError, AssetSource, AssetSourceEvent, AssetSourceId,
+            AssetWatcher, Reader, Writer,
         },
         loader::{AssetLoader, LoadContext},
-        Asset, AssetApp, AssetEvent, AssetId, AssetLoadError, AssetLoadFailedEvent, AssetPath,
-        AssetPlugin, AssetServer, Assets,
+        saver::{AssetSaver, SavedAsset},
+        Asset, AssetApp, AssetCollection, AssetEvent, AssetId, AssetLoadError,
+        AssetLoadFailedEvent, AssetPath, AssetPlugin, AssetServer, Assets, DependentReloadMode,
+        LoadingAssetCollection,
     };
     use alloc::{
         boxed::Box,
@@ -1634,6 +1690,89 @@ mod tests {
         });
     }
 
+    #[derive(AssetCollection, Resource)]
+    struct CoolTextCollection {
+        #[asset(path = "a.cool.ron")]
+        a: Handle<CoolText>,
+        #[asset(glob = "text/*.cool.ron")]
+        texts: Vec<Handle<CoolText>>,
+        #[asset(glob = "text/**/d.cool.ron")]
+        nested: Vec<Handle<CoolText>>,
+        #[asset(glob = "a.cool.ron")]
+        file: Vec<Handle<CoolText>>,
+        #[asset(manifest = "lists/text.collection.ron")]
+        listed: Vec<Handle<CoolText>>,
+        not_loaded: Option<Handle<CoolText>>,
+    }
+
+    #[test]
+    fn load_asset_collection() {
+        // The particular usage of GatedReader in this test will cause deadlocking if running single-threaded
+        #[cfg(not(feature = "multi_threaded"))]
+        panic!("This test requires the \"multi_threaded\" feature, otherwise it will deadlock.\ncargo test --package bevy_asset --features multi_threaded");
+
+        let text_ron = |text: &str| {
+            format!(
+                "(text: \"{text}\", dependencies: [], embedded_dependencies: [], sub_texts: [])"
+            )
+        };
+        let dir = Dir::default();
+        let paths = [
+            "a.cool.ron",
+            "text/b.cool.ron",
+            "text/c.cool.ron",
+            "text/nested/d.cool.ron",
+        ];
+        for path in paths {
+            dir.insert_asset_text(Path::new(path), &text_ron(path));
+        }
+        dir.insert_asset_text(
+            Path::new("lists/text.collection.ron"),
+            r#"["../a.cool.ron", "../text/nested/d.cool.ron"]"#,
+        );
+
+        let (mut app, gate_opener) = test_app(dir);
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader)
+            .init_asset_collection::<CoolTextCollection>();
+
+        app.update();
+        assert!(!app.world().contains_resource::<CoolTextCollection>());
+        assert!(app
+            .world()
+            .resource::<LoadingAssetCollection<CoolTextCollection>>()
+            .load_state()
+            .is_loading());
+
+        for path in paths.iter().chain(&["lists/text.collection.ron"]) {
+            gate_opener.open(path);
+        }
+        run_app_until(&mut app, |world| {
+            world.get_resource::<CoolTextCollection>().map(|_| ())
+        });
+
+        let world = app.world();
+        let collection = world.resource::<CoolTextCollection>();
+        let texts = world.resource::<Assets<CoolText>>();
+        let text = |handle: &Handle<CoolText>| texts.get(handle).unwrap().text.as_str();
+        assert_eq!(text(&collection.a), "a.cool.ron");
+        let mut globbed = collection.texts.iter().map(text).collect::<Vec<_>>();
+        globbed.sort();
+        assert_eq!(globbed, ["text/b.cool.ron", "text/c.cool.ron"]);
+        let nested = collection.nested.iter().map(text).collect::<Vec<_>>();
+        assert_eq!(nested, ["text/nested/d.cool.ron"]);
+        let file = collection.file.iter().map(text).collect::<Vec<_>>();
+        assert_eq!(file, ["a.cool.ron"]);
+        let listed = collection.listed.iter().map(text).collect::<Vec<_>>();
+        assert_eq!(listed, ["a.cool.ron", "text/nested/d.cool.ron"]);
+        assert!(collection.not_loaded.is_none());
+        assert!(world
+            .resource::<LoadingAssetCollection<CoolTextCollection>>()
+            .load_state()
+            .is_loaded());
+    }
+
     /// Tests that `AssetLoadFailedEvent<A>` events are emitted and can be used to retry failed assets.
     #[test]
     fn load_error_events() {
@@ -1759,6 +1898,223 @@ mod tests {
         });
     }
 
+    /// An [`AssetWatcher`] whose events are sent manually through the stored sender.
+    struct ManualWatcher;
+
+    impl AssetWatcher for ManualWatcher {}
+
+    type WatcherSender = Arc<std::sync::Mutex<Option<crossbeam_channel::Sender<AssetSourceEvent>>>>;
+
+    fn hot_reload_test_app(dir: Dir, mode: DependentReloadMode) -> (App, WatcherSender) {
+        let mut app = App::new();
+        let sender = WatcherSender::default();
+        let watcher_sender = sender.clone();
+        let writer_dir = dir.clone();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || Box::new(MemoryAssetReader { root: dir.clone() }))
+                .with_writer(move |_| {
+                    Some(Box::new(MemoryAssetWriter {
+                        root: writer_dir.clone(),
+                    }))
+                })
+                .with_watcher(move |event_sender| {
+                    *watcher_sender.lock().unwrap() = Some(event_sender);
+                    Some(Box::new(ManualWatcher))
+                }),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            LogPlugin::default(),
+            AssetPlugin {
+                watch_for_changes_override: Some(true),
+                dependent_reload_mode: mode,
+                ..Default::default()
+            },
+        ))
+        .init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .register_asset_loader(CoolTextLoader)
+        .init_resource::<StoredEvents>()
+        .add_systems(Update, store_asset_events);
+        (app, sender)
+    }
+
+    fn hot_reload_dir() -> Dir {
+        let dir = Dir::default();
+        dir.insert_asset_text(
+            Path::new("a.cool.ron"),
+            r#"(text: "a", dependencies: ["b.cool.ron"], embedded_dependencies: [], sub_texts: [])"#,
+        );
+        dir.insert_asset_text(
+            Path::new("b.cool.ron"),
+            r#"(text: "b", dependencies: [], embedded_dependencies: [], sub_texts: [])"#,
+        );
+        dir.insert_asset_text(
+            Path::new("c.cool.ron"),
+            r#"(text: "c", dependencies: [], embedded_dependencies: ["b.cool.ron"], sub_texts: [])"#,
+        );
+        dir
+    }
+
+    #[test]
+    fn hot_reload_propagates_to_dependents() {
+        let dir = hot_reload_dir();
+        let (mut app, sender) = hot_reload_test_app(dir.clone(), DependentReloadMode::Reload);
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let a: Handle<CoolText> = asset_server.load("a.cool.ron");
+        let c: Handle<CoolText> = asset_server.load("c.cool.ron");
+        run_app_until(&mut app, |_| {
+            (asset_server.is_loaded_with_dependencies(&a)
+                && asset_server.is_loaded_with_dependencies(&c))
+            .then_some(())
+        });
+        app.world_mut().resource_mut::<StoredEvents>().0.clear();
+
+        dir.insert_asset_text(
+            Path::new("b.cool.ron"),
+            r#"(text: "b2", dependencies: [], embedded_dependencies: [], sub_texts: [])"#,
+        );
+        sender
+            .lock()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .send(AssetSourceEvent::ModifiedAsset("b.cool.ron".into()))
+            .unwrap();
+
+        // `c` used `b` in its loader, so it is reloaded after `b` and picks up the new text.
+        run_app_until(&mut app, |world| {
+            let c_text = get::<CoolText>(world, c.id())?;
+            (c_text.embedded == "b2").then_some(())
+        });
+
+        let b_id = asset_server
+            .get_handle::<CoolText>("b.cool.ron")
+            .unwrap()
+            .id();
+        assert_eq!(get::<CoolText>(app.world(), b_id).unwrap().text, "b2");
+        app.update();
+        let events = &app.world().resource::<StoredEvents>().0;
+        assert!(events.contains(&AssetEvent::Modified { id: b_id }));
+        // `a` holds a handle to `b`, so it is notified without being reloaded.
+        assert!(events.contains(&AssetEvent::Modified { id: a.id() }));
+        assert!(events.contains(&AssetEvent::Modified { id: c.id() }));
+    }
+
+    #[test]
+    fn hot_reload_dependents_in_place() {
+        let dir = hot_reload_dir();
+        let (mut app, sender) = hot_reload_test_app(dir.clone(), DependentReloadMode::InPlace);
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let a: Handle<CoolText> = asset_server.load("a.cool.ron");
+        let c: Handle<CoolText> = asset_server.load("c.cool.ron");
+        run_app_until(&mut app, |_| {
+            (asset_server.is_loaded_with_dependencies(&a)
+                && asset_server.is_loaded_with_dependencies(&c))
+            .then_some(())
+        });
+        app.world_mut().resource_mut::<StoredEvents>().0.clear();
+
+        dir.insert_asset_text(
+            Path::new("b.cool.ron"),
+            r#"(text: "b2", dependencies: [], embedded_dependencies: [], sub_texts: [])"#,
+        );
+        sender
+            .lock()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .send(AssetSourceEvent::ModifiedAsset("b.cool.ron".into()))
+            .unwrap();
+
+        run_app_until(&mut app, |world| {
+            let events = &world.resource::<StoredEvents>().0;
+            (events.contains(&AssetEvent::Modified { id: a.id() })
+                && events.contains(&AssetEvent::Modified { id: c.id() }))
+            .then_some(())
+        });
+
+        // `c` was notified, but its loader was not re-run.
+        assert_eq!(get::<CoolText>(app.world(), c.id()).unwrap().embedded, "b");
+    }
+
+    #[test]
+    fn hot_reload_through_nested_immediate_loads() {
+        let dir = hot_reload_dir();
+        dir.insert_asset_text(
+            Path::new("d.cool.ron"),
+            r#"(text: "d", dependencies: [], embedded_dependencies: ["c.cool.ron"], sub_texts: [])"#,
+        );
+        let (mut app, sender) = hot_reload_test_app(dir.clone(), DependentReloadMode::Reload);
+
+        // Only `d` is loaded: `c` and `b` are loaded immediately while loading it, so neither is tracked on its own.
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let d: Handle<CoolText> = asset_server.load("d.cool.ron");
+        run_app_until(&mut app, |_| asset_server.is_loaded(&d).then_some(()));
+        app.world_mut().resource_mut::<StoredEvents>().0.clear();
+
+        dir.insert_asset_text(
+            Path::new("b.cool.ron"),
+            r#"(text: "b2", dependencies: [], embedded_dependencies: [], sub_texts: [])"#,
+        );
+        sender
+            .lock()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .send(AssetSourceEvent::ModifiedAsset("b.cool.ron".into()))
+            .unwrap();
+
+        // `c` recorded `b` as its loader dependency, so the change reaches `d` through it.
+        run_app_until(&mut app, |world| {
+            let events = &world.resource::<StoredEvents>().0;
+            events
+                .contains(&AssetEvent::Modified { id: d.id() })
+                .then_some(())
+        });
+    }
+
+    #[test]
+    fn order_reloads_by_loader_dependencies() {
+        let dir = hot_reload_dir();
+        dir.insert_asset_text(
+            Path::new("d.cool.ron"),
+            r#"(text: "d", dependencies: [], embedded_dependencies: ["c.cool.ron"], sub_texts: [])"#,
+        );
+        let (mut app, _sender) = hot_reload_test_app(dir, DependentReloadMode::Reload);
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let d: Handle<CoolText> = asset_server.load("d.cool.ron");
+        let c: Handle<CoolText> = asset_server.load("c.cool.ron");
+        run_app_until(&mut app, |_| {
+            (asset_server.is_loaded_with_dependencies(&d)
+                && asset_server.is_loaded_with_dependencies(&c))
+            .then_some(())
+        });
+
+        let paths = ["d.cool.ron", "c.cool.ron", "b.cool.ron"]
+            .into_iter()
+            .map(AssetPath::from)
+            .collect();
+        let batches = asset_server
+            .data
+            .infos
+            .read()
+            .order_by_loader_dependencies(paths);
+        assert_eq!(
+            batches,
+            vec![
+                vec![AssetPath::from("b.cool.ron")],
+                vec![AssetPath::from("c.cool.ron")],
+                vec![AssetPath::from("d.cool.ron")],
+            ]
+        );
+    }
+
     #[test]
     fn ignore_system_ambiguities_on_assets() {
         let mut app = App::new();
@@ -1811,4 +2167,110 @@ mod tests {
 
     #[derive(Asset, TypePath)]
     pub struct TupleTestAsset(#[dependency] Handle<TestAsset>);
+
+    #[derive(Default)]
+    struct CoolTextSaver;
+
+    impl AssetSaver for CoolTextSaver {
+        type Asset = CoolText;
+        type Settings = ();
+        type OutputLoader = CoolTextLoader;
+        type Error = std::io::Error;
+
+        async fn save(
+            &self,
+            writer: &mut Writer,
+            asset: SavedAsset<'_, CoolText>,
+            _settings: &(),
+        ) -> Result<(), std::io::Error> {
+            let ron = CoolTextRon {
+                text: asset.text.clone(),
+                dependencies: Vec::new(),
+                embedded_dependencies: Vec::new(),
+                sub_texts: Vec::new(),
+            };
+            crate::AsyncWriteExt::write_all(writer, ron::ser::to_string(&ron).unwrap().as_bytes())
+                .await
+        }
+    }
+
+    #[test]
+    fn save_asset_round_trip() {
+        let dir = hot_reload_dir();
+        let (mut app, sender) = hot_reload_test_app(dir.clone(), DependentReloadMode::Reload);
+        app.register_asset_saver(CoolTextSaver);
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let b: Handle<CoolText> = asset_server.load("b.cool.ron");
+        run_app_until(&mut app, |_| asset_server.is_loaded(&b).then_some(()));
+        app.world_mut().resource_mut::<StoredEvents>().0.clear();
+
+        let edited = CoolText {
+            text: "saved".to_string(),
+            ..Default::default()
+        };
+        futures_lite::future::block_on(asset_server.save("b.cool.ron", &edited)).unwrap();
+        let meta = futures_lite::future::block_on(
+            MemoryAssetReader { root: dir.clone() }.read_meta_bytes(Path::new("b.cool.ron")),
+        )
+        .unwrap();
+        let meta = String::from_utf8(meta).unwrap();
+        assert!(meta.contains(core::any::type_name::<CoolTextLoader>()));
+
+        // Saving doesn't update the loaded asset, and the watcher reports the save, which must
+        // not reload the asset from disk either.
+        assert_eq!(get::<CoolText>(app.world(), b.id()).unwrap().text, "b");
+        let sender = sender.lock().unwrap().clone().unwrap();
+        sender
+            .send(AssetSourceEvent::ModifiedAsset("b.cool.ron".into()))
+            .unwrap();
+        sender
+            .send(AssetSourceEvent::ModifiedMeta("b.cool.ron".into()))
+            .unwrap();
+        for _ in 0..10 {
+            app.update();
+        }
+        assert_eq!(get::<CoolText>(app.world(), b.id()).unwrap().text, "b");
+        assert!(!app
+            .world()
+            .resource::<StoredEvents>()
+            .0
+            .iter()
+            .any(|event| matches!(event, AssetEvent::Modified { .. })));
+
+        // Changes made by something else after a save still reload the asset.
+        dir.insert_asset_text(
+            Path::new("b.cool.ron"),
+            r#"(text: "edited", dependencies: [], embedded_dependencies: [], sub_texts: [])"#,
+        );
+        sender
+            .send(AssetSourceEvent::ModifiedAsset("b.cool.ron".into()))
+            .unwrap();
+        run_app_until(&mut app, |world| {
+            (get::<CoolText>(world, b.id())?.text == "edited").then_some(())
+        });
+
+        // Saving replaces an existing meta that processes the asset, which may not read the
+        // saved bytes.
+        dir.insert_meta_text(
+            Path::new("processed.cool.ron"),
+            r#"(meta_format_version: "1.0", asset: Process(processor: "Processor", settings: ()))"#,
+        );
+        futures_lite::future::block_on(asset_server.save("processed.cool.ron", &edited)).unwrap();
+        let meta = futures_lite::future::block_on(
+            MemoryAssetReader { root: dir.clone() }
+                .read_meta_bytes(Path::new("processed.cool.ron")),
+        )
+        .unwrap();
+        let meta = String::from_utf8(meta).unwrap();
+        assert!(!meta.contains("Process"));
+        assert!(meta.contains(core::any::type_name::<CoolTextLoader>()));
+
+        // Saved assets load back through the loader and settings written to their meta.
+        futures_lite::future::block_on(asset_server.save("new.cool.ron", &edited)).unwrap();
+        let new: Handle<CoolText> = asset_server.load("new.cool.ron");
+        run_app_until(&mut app, |world| {
+            (get::<CoolText>(world, new.id())?.text == "saved").then_some(())
+        });
+    }
 }
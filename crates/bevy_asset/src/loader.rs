@@ -618,6 +618,13 @@ impl<'a> LoadContext<'a> {
             })?;
         let info = meta.processed_info().as_ref();
         let hash = info.map(|i| i.full_hash).unwrap_or_default();
+        // The nested asset is not tracked on its own, so record its loader dependencies here to let changes to them
+        // reach the assets loading it.
+        self.asset_server
+            .data
+            .infos
+            .write()
+            .add_loader_dependents(&path, complete_asset.asset.loader_dependencies.keys());
         self.loader_dependencies.insert(path, hash);
         Ok(complete_asset)
     }
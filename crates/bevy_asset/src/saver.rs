@@ -1,12 +1,15 @@
 use crate::{
-    io::Writer, meta::Settings, transformer::TransformedAsset, Asset, AssetLoader,
-    CompleteErasedLoadedAsset, ErasedLoadedAsset, Handle, LabeledAsset, UntypedHandle,
+    io::Writer,
+    meta::{AssetAction, AssetMeta, AssetMetaDyn, Settings},
+    transformer::TransformedAsset,
+    Asset, AssetLoader, CompleteErasedLoadedAsset, ErasedLoadedAsset, Handle, LabeledAsset,
+    UntypedHandle,
 };
-use alloc::boxed::Box;
+use alloc::{boxed::Box, string::ToString, vec::Vec};
 use atomicow::CowArc;
-use bevy_platform_support::collections::HashMap;
+use bevy_platform_support::{collections::HashMap, hash::FixedHasher};
 use bevy_tasks::{BoxedFuture, ConditionalSendFuture};
-use core::{borrow::Borrow, hash::Hash, ops::Deref};
+use core::{any::Any, borrow::Borrow, hash::Hash, ops::Deref};
 use serde::{Deserialize, Serialize};
 
 /// Saves an [`Asset`] of a given [`AssetSaver::Asset`] type. [`AssetSaver::OutputLoader`] will then be used to load the saved asset
@@ -48,6 +51,19 @@ pub trait ErasedAssetSaver: Send + Sync + 'static {
         settings: &'a dyn Settings,
     ) -> BoxedFuture<'a, Result<(), Box<dyn core::error::Error + Send + Sync + 'static>>>;
 
+    /// Saves the given `asset` (which must be of the [`AssetSaver::Asset`] type) by writing it to a byte format using `writer`.
+    /// Returns the `.meta` bytes that load the written bytes back with [`AssetSaver::OutputLoader`] and the settings returned
+    /// by the saver.
+    fn save_with_meta<'a>(
+        &'a self,
+        writer: &'a mut Writer,
+        asset: &'a (dyn Any + Send + Sync),
+        settings: &'a dyn Settings,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, Box<dyn core::error::Error + Send + Sync + 'static>>>;
+
+    /// Returns the default settings of the [`AssetSaver`].
+    fn default_settings(&self) -> Box<dyn Settings>;
+
     /// The type name of the [`AssetSaver`].
     fn type_name(&self) -> &'static str;
 }
@@ -70,6 +86,37 @@ impl<S: AssetSaver> ErasedAssetSaver for S {
             Ok(())
         })
     }
+
+    fn save_with_meta<'a>(
+        &'a self,
+        writer: &'a mut Writer,
+        asset: &'a (dyn Any + Send + Sync),
+        settings: &'a dyn Settings,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, Box<dyn core::error::Error + Send + Sync + 'static>>> {
+        Box::pin(async move {
+            let settings = settings
+                .downcast_ref::<S::Settings>()
+                .expect("AssetSaver settings should match the saver type");
+            let asset = asset
+                .downcast_ref::<S::Asset>()
+                .expect("Asset should match the saver's asset type");
+            let loader_settings = self
+                .save(writer, SavedAsset::from_asset(asset), settings)
+                .await
+                .map_err(Into::into)?;
+            Ok(AssetMetaDyn::serialize(
+                &AssetMeta::<S::OutputLoader, ()>::new(AssetAction::Load {
+                    loader: core::any::type_name::<S::OutputLoader>().to_string(),
+                    settings: loader_settings,
+                }),
+            ))
+        })
+    }
+
+    fn default_settings(&self) -> Box<dyn Settings> {
+        Box::<S::Settings>::default()
+    }
+
     fn type_name(&self) -> &'static str {
         core::any::type_name::<S>()
     }
@@ -89,7 +136,19 @@ impl<'a, A: Asset> Deref for SavedAsset<'a, A> {
     }
 }
 
+/// The labeled assets of a [`SavedAsset`] created without any.
+static NO_LABELED_ASSETS: HashMap<CowArc<'static, str>, LabeledAsset> =
+    HashMap::with_hasher(FixedHasher);
+
 impl<'a, A: Asset> SavedAsset<'a, A> {
+    /// Creates a new [`SavedAsset`] from a runtime `value`, such as an entry of [`Assets`](crate::Assets), without any labeled assets.
+    pub fn from_asset(value: &'a A) -> Self {
+        Self {
+            value,
+            labeled_assets: &NO_LABELED_ASSETS,
+        }
+    }
+
     /// Creates a new [`SavedAsset`] from `asset` if its internal value matches `A`.
     pub fn from_loaded(complete_asset: &'a CompleteErasedLoadedAsset) -> Option<Self> {
         let value = complete_asset.asset.value.downcast_ref::<A>()?;
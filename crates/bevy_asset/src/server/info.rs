@@ -1,13 +1,14 @@
 use crate::{
     meta::{AssetHash, MetaTransform},
-    Asset, AssetHandleProvider, AssetLoadError, AssetPath, DependencyLoadState, ErasedLoadedAsset,
-    Handle, InternalAssetEvent, LoadState, RecursiveDependencyLoadState, StrongHandle,
-    UntypedAssetId, UntypedHandle,
+    Asset, AssetHandleProvider, AssetLoadError, AssetPath, DependencyLoadState,
+    DependentReloadMode, ErasedLoadedAsset, Handle, InternalAssetEvent, LoadState,
+    RecursiveDependencyLoadState, StrongHandle, UntypedAssetId, UntypedHandle,
 };
 use alloc::{
     borrow::ToOwned,
     boxed::Box,
     sync::{Arc, Weak},
+    vec,
     vec::Vec,
 };
 use bevy_ecs::world::World;
@@ -40,6 +41,23 @@ pub(crate) struct AssetInfo {
     ///
     /// [`LoadedAsset`]: crate::loader::LoadedAsset
     loader_dependencies: HashMap<AssetPath<'static>, AssetHash>,
+    /// The paths of the source assets this asset was processed from, as recorded in its processed meta.
+    /// This will only be populated in [`AssetServerMode::Processed`] if [`AssetInfos::watching_for_changes`]
+    /// is set to `true`.
+    ///
+    /// [`AssetServerMode::Processed`]: crate::AssetServerMode::Processed
+    pub(crate) process_dependencies: HashSet<AssetPath<'static>>,
+    /// The direct dependencies of this asset from its last load.
+    /// This will only be populated if [`AssetInfos::watching_for_changes`] is set to `true` to
+    /// save memory.
+    dependencies: HashSet<UntypedAssetId>,
+    /// The assets that have this asset as a direct dependency. These are notified with
+    /// [`AssetEvent::Modified`](crate::AssetEvent::Modified) when this asset is reloaded.
+    /// This will only be populated if [`AssetInfos::watching_for_changes`] is set to `true` to
+    /// save memory.
+    dependents: HashSet<UntypedAssetId>,
+    /// Whether this asset has finished loading at least once. This is used to tell reloads apart from initial loads.
+    loaded_once: bool,
     /// The number of handle drops to skip for this asset.
     /// See usage (and comments) in `get_or_create_path_handle` for context.
     handle_drops_to_skip: usize,
@@ -60,6 +78,10 @@ impl AssetInfo {
             loading_rec_dependencies: HashSet::default(),
             failed_rec_dependencies: HashSet::default(),
             loader_dependencies: HashMap::default(),
+            process_dependencies: HashSet::default(),
+            dependencies: HashSet::default(),
+            dependents: HashSet::default(),
+            loaded_once: false,
             dependents_waiting_on_load: HashSet::default(),
             dependents_waiting_on_recursive_dep_load: HashSet::default(),
             handle_drops_to_skip: 0,
@@ -75,16 +97,22 @@ pub(crate) struct AssetInfos {
     /// If set to `true`, this informs [`AssetInfos`] to track data relevant to watching for changes (such as `load_dependents`)
     /// This should only be set at startup.
     pub(crate) watching_for_changes: bool,
+    /// How dependents of a hot-reloaded asset are updated. This should only be set at startup.
+    pub(crate) dependent_reload_mode: DependentReloadMode,
     /// Tracks assets that depend on the "key" asset path inside their asset loaders ("loader dependencies")
     /// This should only be set when watching for changes to avoid unnecessary work.
     pub(crate) loader_dependents: HashMap<AssetPath<'static>, HashSet<AssetPath<'static>>>,
     /// Tracks living labeled assets for a given source asset.
     /// This should only be set when watching for changes to avoid unnecessary work.
     pub(crate) living_labeled_assets: HashMap<AssetPath<'static>, HashSet<Box<str>>>,
+    /// Tracks the content last written to asset paths by [`AssetServer::save`](crate::AssetServer::save), so the change
+    /// events caused by the save don't reload them. This should only be set when watching for changes.
+    pub(crate) saved_paths: HashMap<AssetPath<'static>, SavedContent>,
     pub(crate) handle_providers: TypeIdMap<AssetHandleProvider>,
     pub(crate) dependency_loaded_event_sender: TypeIdMap<fn(&mut World, UntypedAssetId)>,
     pub(crate) dependency_failed_event_sender:
         TypeIdMap<fn(&mut World, UntypedAssetId, AssetPath<'static>, AssetLoadError)>,
+    pub(crate) dependency_modified_event_sender: TypeIdMap<fn(&mut World, UntypedAssetId)>,
     pub(crate) pending_tasks: HashMap<UntypedAssetId, Task<()>>,
 }
 
@@ -383,6 +411,24 @@ impl AssetInfos {
         )
     }
 
+    /// Records that `path` was loaded using each of its `loader_dependencies`, so that reloading one of them also
+    /// reloads `path`. This is only tracked when watching for changes.
+    pub(crate) fn add_loader_dependents<'a>(
+        &mut self,
+        path: &AssetPath<'static>,
+        loader_dependencies: impl IntoIterator<Item = &'a AssetPath<'static>>,
+    ) {
+        if !self.watching_for_changes {
+            return;
+        }
+        for loader_dependency in loader_dependencies {
+            self.loader_dependents
+                .entry(loader_dependency.clone())
+                .or_default()
+                .insert(path.clone());
+        }
+    }
+
     /// Updates [`AssetInfo`] / load state for an asset that has finished loading (and relevant dependencies / dependents).
     pub(crate) fn process_asset_load(
         &mut self,
@@ -396,8 +442,10 @@ impl AssetInfos {
             return;
         }
 
+        let reloaded = self.infos[&loaded_asset_id].loaded_once;
         loaded_asset.value.insert(loaded_asset_id, world);
         let mut loading_deps = loaded_asset.dependencies;
+        let dependencies = self.watching_for_changes.then(|| loading_deps.clone());
         let mut failed_deps = <HashSet<_>>::default();
         let mut dep_error = None;
         let mut loading_rec_deps = loading_deps.clone();
@@ -480,13 +528,31 @@ impl AssetInfos {
                     .infos
                     .get(&loaded_asset_id)
                     .expect("Asset info should always exist at this point");
-                if let Some(asset_path) = &info.path {
-                    for loader_dependency in loaded_asset.loader_dependencies.keys() {
-                        let dependents = self
-                            .loader_dependents
-                            .entry(loader_dependency.clone())
-                            .or_default();
-                        dependents.insert(asset_path.clone());
+                if let Some(asset_path) = info.path.clone() {
+                    self.add_loader_dependents(
+                        &asset_path,
+                        loaded_asset.loader_dependencies.keys(),
+                    );
+                }
+            }
+            // if watching for changes, track reverse dependencies so dependents can be notified of reloads
+            if let Some(dependencies) = dependencies {
+                let old_dependencies = core::mem::replace(
+                    &mut self
+                        .infos
+                        .get_mut(&loaded_asset_id)
+                        .expect("Asset info should always exist at this point")
+                        .dependencies,
+                    dependencies.clone(),
+                );
+                for dependency in old_dependencies.difference(&dependencies) {
+                    if let Some(dependency_info) = self.infos.get_mut(dependency) {
+                        dependency_info.dependents.remove(&loaded_asset_id);
+                    }
+                }
+                for dependency in &dependencies {
+                    if let Some(dependency_info) = self.infos.get_mut(dependency) {
+                        dependency_info.dependents.insert(loaded_asset_id);
                     }
                 }
             }
@@ -498,6 +564,7 @@ impl AssetInfos {
             info.loading_rec_dependencies = loading_rec_deps;
             info.failed_rec_dependencies = failed_rec_deps;
             info.load_state = LoadState::Loaded;
+            info.loaded_once = true;
             info.dep_load_state = dep_load_state;
             info.rec_dep_load_state = rec_dep_load_state.clone();
             if watching_for_changes {
@@ -547,6 +614,116 @@ impl AssetInfos {
                 }
             }
         }
+
+        if reloaded {
+            self.propagate_modified(loaded_asset_id, world);
+        }
+    }
+
+    /// Sends an [`AssetEvent::Modified`](crate::AssetEvent::Modified) event for every loaded asset that (transitively)
+    /// depends on the reloaded asset. Dependents that are currently loading are skipped, as they will produce their own
+    /// events when they finish.
+    ///
+    /// In [`DependentReloadMode::InPlace`], assets that use the reloaded asset inside their loaders are notified as well,
+    /// as their loaders are not re-run.
+    fn propagate_modified(&self, reloaded_id: UntypedAssetId, world: &mut World) {
+        let mut visited = <HashSet<_>>::default();
+        visited.insert(reloaded_id);
+        let mut queue = vec![reloaded_id];
+        while let Some(id) = queue.pop() {
+            let Some(info) = self.infos.get(&id) else {
+                continue;
+            };
+            let mut dependents = info.dependents.iter().copied().collect::<Vec<_>>();
+            if self.dependent_reload_mode == DependentReloadMode::InPlace {
+                if let Some(loader_dependents) = info
+                    .path
+                    .as_ref()
+                    .and_then(|p| self.loader_dependents.get(p))
+                {
+                    for path in loader_dependents {
+                        dependents.extend(self.get_path_ids(path));
+                    }
+                }
+            }
+            for dependent in dependents {
+                if !visited.insert(dependent) {
+                    continue;
+                }
+                if !self
+                    .infos
+                    .get(&dependent)
+                    .is_some_and(|info| matches!(info.load_state, LoadState::Loaded))
+                {
+                    continue;
+                }
+                if let Some(sender) = self
+                    .dependency_modified_event_sender
+                    .get(&dependent.type_id())
+                {
+                    sender(world, dependent);
+                }
+                queue.push(dependent);
+            }
+        }
+    }
+
+    /// Returns `true` if the asset at `dependent` was processed using the asset at `dependency`. When watching for changes,
+    /// the [`AssetProcessor`](crate::processor::AssetProcessor) reprocesses these dependents itself, so they should be reloaded
+    /// when their own processed files change rather than when `dependency` does.
+    pub(crate) fn is_process_dependent(
+        &self,
+        dependent: &AssetPath<'static>,
+        dependency: &AssetPath<'static>,
+    ) -> bool {
+        self.get_path_ids(dependent)
+            .filter_map(|id| self.infos.get(&id))
+            .any(|info| info.process_dependencies.contains(dependency))
+    }
+
+    /// Splits `paths` into batches so that each path comes after every path in `paths` it depends on inside
+    /// its loader. Reloading the batches in order guarantees that an asset is only reloaded after its loader dependencies.
+    pub(crate) fn order_by_loader_dependencies(
+        &self,
+        paths: HashSet<AssetPath<'static>>,
+    ) -> Vec<Vec<AssetPath<'static>>> {
+        fn depth(
+            infos: &AssetInfos,
+            path: &AssetPath<'static>,
+            paths: &HashSet<AssetPath<'static>>,
+            depths: &mut HashMap<AssetPath<'static>, usize>,
+        ) -> usize {
+            if let Some(depth) = depths.get(path) {
+                return *depth;
+            }
+            // Guard against dependency cycles, which would otherwise recurse forever.
+            depths.insert(path.clone(), 0);
+            let loader_dependencies = infos
+                .get_path_ids(path)
+                .filter_map(|id| infos.infos.get(&id))
+                .flat_map(|info| info.loader_dependencies.keys())
+                .filter(|dependency| paths.contains(*dependency))
+                .cloned()
+                .collect::<Vec<_>>();
+            let depth = loader_dependencies
+                .iter()
+                .map(|dependency| depth(infos, dependency, paths, depths) + 1)
+                .max()
+                .unwrap_or(0);
+            depths.insert(path.clone(), depth);
+            depth
+        }
+
+        let mut depths = <HashMap<_, _>>::default();
+        let mut batches = Vec::<Vec<AssetPath<'static>>>::new();
+        for path in &paths {
+            let depth = depth(self, path, &paths, &mut depths);
+            if batches.len() <= depth {
+                batches.resize_with(depth + 1, Vec::new);
+            }
+            batches[depth].push(path.clone());
+        }
+        batches
     }
 
     /// Recursively propagates loaded state up the dependency tree.
@@ -701,6 +878,14 @@ impl AssetInfos {
         let type_id = entry.key().type_id();
 
         let info = entry.remove();
+        if watching_for_changes {
+            for dependency in &info.dependencies {
+                if let Some(dependency_info) = infos.get_mut(dependency) {
+                    dependency_info.dependents.remove(&id);
+                }
+            }
+        }
+
         let Some(path) = &info.path else {
             return true;
         };
@@ -749,6 +934,31 @@ impl AssetInfos {
         }
     }
 }
+/// The hashes of the asset and meta bytes written to an asset path by [`AssetServer::save`](crate::AssetServer::save).
+pub(crate) struct SavedContent {
+    asset_hash: AssetHash,
+    meta_hash: AssetHash,
+}
+
+impl SavedContent {
+    pub(crate) fn new(asset_bytes: &[u8], meta_bytes: &[u8]) -> Self {
+        Self {
+            asset_hash: *blake3::hash(asset_bytes).as_bytes(),
+            meta_hash: *blake3::hash(meta_bytes).as_bytes(),
+        }
+    }
+
+    /// Returns `true` if `bytes` are the saved meta bytes if `meta` is `true`, or the saved asset bytes otherwise.
+    pub(crate) fn matches(&self, bytes: &[u8], meta: bool) -> bool {
+        let hash = *blake3::hash(bytes).as_bytes();
+        if meta {
+            hash == self.meta_hash
+        } else {
+            hash == self.asset_hash
+        }
+    }
+}
+
 /// Determines how a handle should be initialized
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub(crate) enum HandleLoadingMode {
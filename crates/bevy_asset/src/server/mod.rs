@@ -5,7 +5,8 @@ use crate::{
     folder::LoadedFolder,
     io::{
         AssetReaderError, AssetSource, AssetSourceEvent, AssetSourceId, AssetSources,
-        ErasedAssetReader, MissingAssetSourceError, MissingProcessedAssetReaderError, Reader,
+        AssetWriterError, ErasedAssetReader, MissingAssetSourceError, MissingAssetWriterError,
+        MissingProcessedAssetReaderError, Reader,
     },
     loader::{AssetLoader, ErasedAssetLoader, LoadContext, LoadedAsset},
     meta::{
@@ -13,9 +14,10 @@ use crate::{
         MetaTransform, Settings,
     },
     path::AssetPath,
+    saver::{AssetSaver, ErasedAssetSaver},
     Asset, AssetEvent, AssetHandleProvider, AssetId, AssetLoadFailedEvent, AssetMetaCheck, Assets,
-    CompleteErasedLoadedAsset, DeserializeMetaError, ErasedLoadedAsset, Handle, LoadedUntypedAsset,
-    UntypedAssetId, UntypedAssetLoadFailedEvent, UntypedHandle,
+    CompleteErasedLoadedAsset, DependentReloadMode, DeserializeMetaError, ErasedLoadedAsset,
+    Handle, LoadedUntypedAsset, UntypedAssetId, UntypedAssetLoadFailedEvent, UntypedHandle,
 };
 use alloc::{borrow::ToOwned, boxed::Box, vec, vec::Vec};
 use alloc::{
@@ -27,6 +29,7 @@ use atomicow::CowArc;
 use bevy_ecs::prelude::*;
 use bevy_platform_support::collections::HashSet;
 use bevy_tasks::IoTaskPool;
+use bevy_utils::TypeIdMap;
 use core::{any::TypeId, future::Future, panic::AssertUnwindSafe, task::Poll};
 use crossbeam_channel::{Receiver, Sender};
 use either::Either;
@@ -61,6 +64,7 @@ pub struct AssetServer {
 pub(crate) struct AssetServerData {
     pub(crate) infos: RwLock<AssetInfos>,
     pub(crate) loaders: Arc<RwLock<AssetLoaders>>,
+    savers: RwLock<TypeIdMap<Arc<dyn ErasedAssetSaver>>>,
     asset_event_sender: Sender<InternalAssetEvent>,
     asset_event_receiver: Receiver<InternalAssetEvent>,
     sources: AssetSources,
@@ -87,6 +91,7 @@ impl AssetServer {
             mode,
             AssetMetaCheck::Always,
             watching_for_changes,
+            DependentReloadMode::default(),
         )
     }
 
@@ -104,6 +109,7 @@ impl AssetServer {
             mode,
             meta_check,
             watching_for_changes,
+            DependentReloadMode::default(),
         )
     }
 
@@ -113,10 +119,12 @@ impl AssetServer {
         mode: AssetServerMode,
         meta_check: AssetMetaCheck,
         watching_for_changes: bool,
+        dependent_reload_mode: DependentReloadMode,
     ) -> Self {
         let (asset_event_sender, asset_event_receiver) = crossbeam_channel::unbounded();
         let mut infos = AssetInfos::default();
         infos.watching_for_changes = watching_for_changes;
+        infos.dependent_reload_mode = dependent_reload_mode;
         Self {
             data: Arc::new(AssetServerData {
                 sources,
@@ -125,6 +133,7 @@ impl AssetServer {
                 asset_event_sender,
                 asset_event_receiver,
                 loaders,
+                savers: Default::default(),
                 infos: RwLock::new(infos),
             }),
         }
@@ -143,11 +152,25 @@ impl AssetServer {
         self.data.infos.read().watching_for_changes
     }
 
+    /// Returns how this [`AssetServer`] updates the dependents of hot-reloaded assets.
+    pub fn dependent_reload_mode(&self) -> DependentReloadMode {
+        self.data.infos.read().dependent_reload_mode
+    }
+
     /// Registers a new [`AssetLoader`]. [`AssetLoader`]s must be registered before they can be used.
     pub fn register_loader<L: AssetLoader>(&self, loader: L) {
         self.data.loaders.write().push(loader);
     }
 
+    /// Registers a new [`AssetSaver`], which [`AssetServer::save`] uses to save assets of type [`AssetSaver::Asset`].
+    /// If a saver was already registered for that asset type, it is replaced.
+    pub fn register_saver<S: AssetSaver>(&self, saver: S) {
+        self.data
+            .savers
+            .write()
+            .insert(TypeId::of::<S::Asset>(), Arc::new(saver));
+    }
+
     /// Registers a new [`Asset`] type. [`Asset`] types must be registered before assets of that type can be loaded.
     pub fn register_asset<A: Asset>(&self, assets: &Assets<A>) {
         self.register_handle_provider(assets.get_handle_provider());
@@ -170,6 +193,14 @@ impl AssetServer {
                     error,
                 });
         }
+        fn modified_sender<A: Asset>(world: &mut World, id: UntypedAssetId) {
+            let id = id.typed::<A>();
+            if world.resource::<Assets<A>>().contains(id) {
+                world
+                    .resource_mut::<Events<AssetEvent<A>>>()
+                    .send(AssetEvent::Modified { id });
+            }
+        }
 
         let mut infos = self.data.infos.write();
 
@@ -180,6 +211,10 @@ impl AssetServer {
         infos
             .dependency_failed_event_sender
             .insert(TypeId::of::<A>(), failed_sender::<A>);
+
+        infos
+            .dependency_modified_event_sender
+            .insert(TypeId::of::<A>(), modified_sender::<A>);
     }
 
     pub(crate) fn register_handle_provider(&self, handle_provider: AssetHandleProvider) {
@@ -681,6 +716,20 @@ impl AssetServer {
                     handle.unwrap()
                 };
 
+                if self.data.mode == AssetServerMode::Processed {
+                    let mut infos = self.data.infos.write();
+                    if infos.watching_for_changes {
+                        if let Some(info) = infos.get_mut(base_handle.id()) {
+                            info.process_dependencies = meta
+                                .processed_info()
+                                .iter()
+                                .flat_map(|processed_info| &processed_info.process_dependencies)
+                                .map(|dependency| dependency.path.clone())
+                                .collect();
+                        }
+                    }
+                }
+
                 self.send_loaded_asset(base_handle.id(), loaded_asset);
                 Ok(final_handle)
             }
@@ -716,33 +765,58 @@ impl AssetServer {
         let server = self.clone();
         let path = path.into().into_owned();
         IoTaskPool::get()
-            .spawn(async move {
-                let mut reloaded = false;
-
-                let requests = server
-                    .data
-                    .infos
-                    .read()
-                    .get_path_handles(&path)
-                    .map(|handle| server.load_internal(Some(handle), path.clone(), true, None))
-                    .collect::<Vec<_>>();
-
-                for result in requests {
-                    match result.await {
-                        Ok(_) => reloaded = true,
-                        Err(err) => error!("{}", err),
-                    }
-                }
+            .spawn(async move { server.reload_internal(path).await })
+            .detach();
+    }
 
-                if !reloaded && server.data.infos.read().should_reload(&path) {
-                    if let Err(err) = server.load_internal(None, path, true, None).await {
-                        error!("{}", err);
+    /// Kicks off a reload of each batch of paths, starting each batch only after the previous one finished reloading.
+    /// Paths within a batch are reloaded in parallel.
+    fn reload_batches(&self, batches: Vec<Vec<AssetPath<'static>>>) {
+        let server = self.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                for batch in batches {
+                    let tasks = batch
+                        .into_iter()
+                        .map(|path| {
+                            let server = server.clone();
+                            IoTaskPool::get()
+                                .spawn(async move { server.reload_internal(path).await })
+                        })
+                        .collect::<Vec<_>>();
+                    for task in tasks {
+                        task.await;
                     }
                 }
             })
             .detach();
     }
 
+    async fn reload_internal(&self, path: AssetPath<'static>) {
+        let mut reloaded = false;
+
+        let requests = self
+            .data
+            .infos
+            .read()
+            .get_path_handles(&path)
+            .map(|handle| self.load_internal(Some(handle), path.clone(), true, None))
+            .collect::<Vec<_>>();
+
+        for result in requests {
+            match result.await {
+                Ok(_) => reloaded = true,
+                Err(err) => error!("{}", err),
+            }
+        }
+
+        if !reloaded && self.data.infos.read().should_reload(&path) {
+            if let Err(err) = self.load_internal(None, path, true, None).await {
+                error!("{}", err);
+            }
+        }
+    }
+
     /// Queues a new asset to be tracked by the [`AssetServer`] and returns a [`Handle`] to it. This can be used to track
     /// dependencies of assets created at runtime.
     ///
@@ -842,6 +916,144 @@ impl AssetServer {
         handle.typed_debug_checked()
     }
 
+    /// Saves `asset` to `path` using the [`AssetSaver`] registered for `A` with its default settings.
+    /// See [`AssetServer::save_with`] for details.
+    pub async fn save<'a, A: Asset>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        asset: &A,
+    ) -> Result<(), SaveAssetError> {
+        let saver = self
+            .data
+            .savers
+            .read()
+            .get(&TypeId::of::<A>())
+            .cloned()
+            .ok_or(SaveAssetError::MissingAssetSaver(
+                core::any::type_name::<A>(),
+            ))?;
+        let settings = saver.default_settings();
+        self.save_erased(path.into(), &*saver, asset, &*settings)
+            .await
+    }
+
+    /// Saves `asset` to `path` using `saver` and its `settings`, such as an edited entry of [`Assets`] or a newly created asset.
+    ///
+    /// The asset bytes are written with the [`AssetWriter`](crate::io::AssetWriter) of the path's [`AssetSource`], along with a
+    /// `.meta` file for [`AssetSaver::OutputLoader`], so the saved asset loads back exactly as the saver wrote it. This replaces
+    /// any existing `.meta` file, including one that processes the asset.
+    ///
+    /// Saving doesn't change the asset already loaded from `path`: its entry in [`Assets`] keeps its current value, and when
+    /// watching for changes, the change events caused by the save itself do not trigger a reload. Update the entry in [`Assets`]
+    /// to show the saved asset, or reload `path` to load it back.
+    pub async fn save_with<'a, S: AssetSaver>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        asset: &S::Asset,
+        saver: &S,
+        settings: &S::Settings,
+    ) -> Result<(), SaveAssetError> {
+        self.save_erased(path.into(), saver, asset, settings).await
+    }
+
+    async fn save_erased(
+        &self,
+        path: AssetPath<'_>,
+        saver: &dyn ErasedAssetSaver,
+        asset: &(dyn core::any::Any + Send + Sync),
+        settings: &dyn Settings,
+    ) -> Result<(), SaveAssetError> {
+        let path = path.into_owned();
+        let source = self.get_source(path.source())?;
+        let writer = source.writer()?;
+        let writer_error = |error| SaveAssetError::AssetWriterError {
+            path: path.clone(),
+            error: Arc::new(error),
+        };
+
+        let mut asset_bytes = Vec::new();
+        let meta_bytes = saver
+            .save_with_meta(&mut asset_bytes, asset, settings)
+            .await
+            .map_err(|error| SaveAssetError::AssetSaverError {
+                path: path.clone(),
+                error: Arc::from(error),
+            })?;
+
+        self.mark_saved(&path, &asset_bytes, &meta_bytes);
+        writer
+            .write_bytes(path.path(), &asset_bytes)
+            .await
+            .map_err(writer_error)?;
+        writer
+            .write_meta_bytes(path.path(), &meta_bytes)
+            .await
+            .map_err(writer_error)?;
+        Ok(())
+    }
+
+    /// Records the content saved to `path`, so the change events caused by writing it don't reload the asset.
+    fn mark_saved(&self, path: &AssetPath<'static>, asset_bytes: &[u8], meta_bytes: &[u8]) {
+        let mut infos = self.data.infos.write();
+        if infos.watching_for_changes {
+            infos
+                .saved_paths
+                .insert(path.clone(), SavedContent::new(asset_bytes, meta_bytes));
+        }
+    }
+
+    /// Reloads the asset at `path`, along with the assets depending on it, unless its asset bytes (or meta bytes if `meta`
+    /// is `true`) still match the content last saved there by [`AssetServer::save`].
+    fn reload_if_changed_since_save(&self, path: AssetPath<'static>, meta: bool) {
+        let server = self.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                let Ok(source) = server.get_source(path.source()) else {
+                    return;
+                };
+                let reader = match server.data.mode {
+                    AssetServerMode::Unprocessed { .. } => source.reader(),
+                    AssetServerMode::Processed { .. } => match source.processed_reader() {
+                        Ok(reader) => reader,
+                        Err(_) => return,
+                    },
+                };
+                let bytes = if meta {
+                    reader.read_meta_bytes(path.path()).await.ok()
+                } else {
+                    match reader.read(path.path()).await {
+                        Ok(mut asset_reader) => {
+                            let mut bytes = Vec::new();
+                            asset_reader
+                                .read_to_end(&mut bytes)
+                                .await
+                                .ok()
+                                .map(|_| bytes)
+                        }
+                        Err(_) => None,
+                    }
+                };
+
+                let batches = {
+                    let mut infos = server.data.infos.write();
+                    let unchanged = infos.saved_paths.get(&path).is_some_and(|saved| {
+                        bytes.is_some_and(|bytes| saved.matches(&bytes, meta))
+                    });
+                    if unchanged {
+                        return;
+                    }
+                    infos.saved_paths.remove(&path);
+                    let mut paths_to_reload = <HashSet<_>>::default();
+                    queue_ancestors(&path, &infos, server.data.mode, &mut paths_to_reload);
+                    info!("Reloading {path} because it has changed");
+                    paths_to_reload.insert(path);
+                    infos.order_by_loader_dependencies(paths_to_reload)
+                };
+                server.reload_batches(batches);
+            })
+            .detach();
+    }
+
     /// Loads all assets from the specified folder recursively. The [`LoadedFolder`] asset (when it loads) will
     /// contain handles to all assets in the folder. You can wait for all assets to load by checking the [`LoadedFolder`]'s
     /// [`RecursiveDependencyLoadState`].
@@ -1532,19 +1744,6 @@ pub fn handle_internal_asset_events(world: &mut World) {
             world.send_event_batch(untyped_failures);
         }
 
-        fn queue_ancestors(
-            asset_path: &AssetPath,
-            infos: &AssetInfos,
-            paths_to_reload: &mut HashSet<AssetPath<'static>>,
-        ) {
-            if let Some(dependents) = infos.loader_dependents.get(asset_path) {
-                for dependent in dependents {
-                    paths_to_reload.insert(dependent.to_owned());
-                    queue_ancestors(dependent, infos, paths_to_reload);
-                }
-            }
-        }
-
         let reload_parent_folders = |path: PathBuf, source: &AssetSourceId<'static>| {
             let mut current_folder = path;
             while let Some(parent) = current_folder.parent() {
@@ -1559,13 +1758,21 @@ pub fn handle_internal_asset_events(world: &mut World) {
         };
 
         let mut paths_to_reload = <HashSet<_>>::default();
+        let mut saved_paths_to_check = <HashSet<_>>::default();
         let mut handle_event = |source: AssetSourceId<'static>, event: AssetSourceEvent| {
+            let meta_modified = matches!(event, AssetSourceEvent::ModifiedMeta(_));
             match event {
                 // TODO: if the asset was processed and the processed file was changed, the first modified event
                 // should be skipped?
                 AssetSourceEvent::ModifiedAsset(path) | AssetSourceEvent::ModifiedMeta(path) => {
                     let path = AssetPath::from(path).with_source(source);
-                    queue_ancestors(&path, &infos, &mut paths_to_reload);
+                    if infos.saved_paths.contains_key(&path) {
+                        // the change may have been written by `AssetServer::save`, in which case the loaded asset
+                        // is already up to date, so only reload it if the content differs from what was saved
+                        saved_paths_to_check.insert((path, meta_modified));
+                        return;
+                    }
+                    queue_ancestors(&path, &infos, server.data.mode, &mut paths_to_reload);
                     paths_to_reload.insert(path);
                 }
                 AssetSourceEvent::RenamedFolder { old, new } => {
@@ -1601,9 +1808,15 @@ pub fn handle_internal_asset_events(world: &mut World) {
             }
         }
 
-        for path in paths_to_reload {
-            info!("Reloading {path} because it has changed");
-            server.reload(path);
+        if !paths_to_reload.is_empty() {
+            for path in &paths_to_reload {
+                info!("Reloading {path} because it has changed");
+            }
+            server.reload_batches(infos.order_by_loader_dependencies(paths_to_reload));
+        }
+
+        for (path, meta) in saved_paths_to_check {
+            server.reload_if_changed_since_save(path, meta);
         }
 
         #[cfg(not(any(target_arch = "wasm32", not(feature = "multi_threaded"))))]
@@ -1613,6 +1826,33 @@ pub fn handle_internal_asset_events(world: &mut World) {
     });
 }
 
+/// Queues the assets that load `asset_path` as a loader dependency, recursively, to be reloaded along with it.
+fn queue_ancestors(
+    asset_path: &AssetPath<'static>,
+    infos: &AssetInfos,
+    mode: AssetServerMode,
+    paths_to_reload: &mut HashSet<AssetPath<'static>>,
+) {
+    if infos.dependent_reload_mode == DependentReloadMode::InPlace {
+        // dependents are notified in place once the reloaded asset finishes loading
+        return;
+    }
+    if let Some(dependents) = infos.loader_dependents.get(asset_path) {
+        for dependent in dependents {
+            // the processor reprocesses these itself, which triggers their reload once the new
+            // processed asset is written
+            if mode == AssetServerMode::Processed
+                && infos.is_process_dependent(dependent, asset_path)
+            {
+                continue;
+            }
+            if paths_to_reload.insert(dependent.to_owned()) {
+                queue_ancestors(dependent, infos, mode, paths_to_reload);
+            }
+        }
+    }
+}
+
 /// Internal events for asset load results
 pub(crate) enum InternalAssetEvent {
     Loaded {
@@ -1872,6 +2112,32 @@ impl core::fmt::Debug for AssetServer {
 /// source for a given [`AssetPath`].
 const UNTYPED_SOURCE_SUFFIX: &str = "--untyped";
 
+/// An error that occurs while saving an [`Asset`] with [`AssetServer::save`].
+#[derive(Error, Debug, Clone)]
+pub enum SaveAssetError {
+    /// No [`AssetSaver`] is registered for the asset type.
+    #[error("No AssetSaver is registered for asset type {0}")]
+    MissingAssetSaver(&'static str),
+    /// The asset source of the path does not exist.
+    #[error(transparent)]
+    MissingAssetSourceError(#[from] MissingAssetSourceError),
+    /// The asset source of the path cannot be written to.
+    #[error(transparent)]
+    MissingAssetWriterError(#[from] MissingAssetWriterError),
+    /// The asset or its meta could not be written.
+    #[error("Failed to write '{path}': {error}")]
+    AssetWriterError {
+        path: AssetPath<'static>,
+        error: Arc<AssetWriterError>,
+    },
+    /// The [`AssetSaver`] failed to save the asset.
+    #[error("Failed to save '{path}': {error}")]
+    AssetSaverError {
+        path: AssetPath<'static>,
+        error: Arc<dyn core::error::Error + Send + Sync + 'static>,
+    },
+}
+
 /// An error when attempting to wait asynchronously for an [`Asset`] to load.
 #[derive(Error, Debug, Clone)]
 pub enum WaitForAssetError {
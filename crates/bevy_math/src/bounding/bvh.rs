@@ -0,0 +1,878 @@
+use super::{Aabb2d, Aabb3d, BoundingVolume, IntersectsVolume, RayCast2d, RayCast3d};
+use crate::{Vec2, Vec3A};
+use alloc::vec::Vec;
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::Reflect;
+
+/// The number of bins used to evaluate the surface area heuristic when building a [`Bvh`].
+const SAH_BINS: usize = 16;
+
+/// A bounding volume that can be stored in a [`Bvh`].
+pub trait BvhVolume: BoundingVolume + Copy {
+    /// The number of axes along which the volumes can be split, which is the dimension of the space.
+    const AXES: usize;
+
+    /// Returns the coordinate of the center of the volume along the given `axis`.
+    fn center_along(&self, axis: usize) -> f32;
+
+    /// Returns the squared distance from `point` to the volume, which is zero if the point is
+    /// inside of it.
+    fn distance_squared_to(&self, point: Self::Translation) -> f32;
+}
+
+impl BvhVolume for Aabb2d {
+    const AXES: usize = 2;
+
+    #[inline]
+    fn center_along(&self, axis: usize) -> f32 {
+        self.center()[axis]
+    }
+
+    #[inline]
+    fn distance_squared_to(&self, point: Vec2) -> f32 {
+        point.distance_squared(self.closest_point(point))
+    }
+}
+
+impl BvhVolume for Aabb3d {
+    const AXES: usize = 3;
+
+    #[inline]
+    fn center_along(&self, axis: usize) -> f32 {
+        self.center()[axis]
+    }
+
+    #[inline]
+    fn distance_squared_to(&self, point: Vec3A) -> f32 {
+        point.distance_squared(self.closest_point(point))
+    }
+}
+
+/// A handle to an item stored in a [`Bvh`].
+///
+/// Handles stay valid until their item is removed, after which they may be reused by new items.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Hash)
+)]
+pub struct BvhHandle(u32);
+
+impl BvhHandle {
+    /// Returns the index of the handle.
+    ///
+    /// For a [`Bvh`] made with [`Bvh::build`], this is the index of the item in the iterator it
+    /// was built from.
+    #[inline]
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum BvhNodeKind {
+    /// A leaf with the handle of its item.
+    Leaf(u32),
+    /// A branch with its two children.
+    Branch([u32; 2]),
+}
+
+#[derive(Clone, Debug)]
+struct BvhNode<B> {
+    volume: B,
+    parent: Option<u32>,
+    kind: BvhNodeKind,
+}
+
+#[derive(Clone, Debug)]
+struct BvhLeaf<T> {
+    item: T,
+    node: u32,
+}
+
+/// A bounding volume hierarchy, which is a binary tree of [`Aabb2d`]s or [`Aabb3d`]s used to
+/// speed up ray casts, overlap tests and nearest neighbor queries on many items.
+///
+/// Each item is stored with its bounding volume in a leaf of the tree, and every branch of the
+/// tree is bounded by the merged volumes of its children. Queries can then skip every item of a
+/// branch whose volume doesn't match.
+///
+/// The tree can be built all at once with the surface area heuristic, with [`Bvh::build`], or
+/// updated incrementally with [`Bvh::insert`], [`Bvh::remove`] and [`Bvh::update`]. When many
+/// items move, [`Bvh::refit`] updates all of their volumes at once, and [`Bvh::rebuild`] restores
+/// the quality of a tree that has been updated a lot.
+///
+/// ```
+/// # use bevy_math::{bounding::*, Dir3, Vec3};
+/// let boxes = (0..10).map(|i| Aabb3d::new(Vec3::new(i as f32 * 3.0, 0.0, 0.0), Vec3::ONE));
+/// let bvh = Bvh::build(boxes.map(|aabb| (aabb, aabb)));
+///
+/// // Cast a ray along the Y axis at x = 12, which only hits the fifth box.
+/// let ray = RayCast3d::new(Vec3::new(12.0, -5.0, 0.0), Dir3::Y, 100.0);
+/// let (handle, distance) = bvh
+///     .ray_cast(&ray, |_, aabb| ray.aabb_intersection_at(aabb))
+///     .unwrap();
+/// assert_eq!(handle.index(), 4);
+/// assert_eq!(distance, 4.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Bvh<B, T> {
+    nodes: Vec<BvhNode<B>>,
+    free_nodes: Vec<u32>,
+    leaves: Vec<Option<BvhLeaf<T>>>,
+    free_leaves: Vec<u32>,
+    root: Option<u32>,
+    len: usize,
+}
+
+impl<B, T> Default for Bvh<B, T> {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free_nodes: Vec::new(),
+            leaves: Vec::new(),
+            free_leaves: Vec::new(),
+            root: None,
+            len: 0,
+        }
+    }
+}
+
+impl<B: BvhVolume, T> Bvh<B, T> {
+    /// Creates an empty [`Bvh`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a [`Bvh`] from items and their bounding volumes, splitting them with the surface
+    /// area heuristic.
+    ///
+    /// The [index](BvhHandle::index) of the handle of each item is its index in `items`.
+    pub fn build(items: impl IntoIterator<Item = (B, T)>) -> Self {
+        let mut bvh = Self::new();
+        let mut volumes = Vec::new();
+        for (volume, item) in items {
+            volumes.push((volume, bvh.leaves.len() as u32));
+            bvh.leaves.push(Some(BvhLeaf { item, node: 0 }));
+        }
+        bvh.len = volumes.len();
+        bvh.nodes.reserve(2 * volumes.len());
+        if !volumes.is_empty() {
+            bvh.root = Some(bvh.build_node(&mut volumes, None));
+        }
+        bvh
+    }
+
+    /// Returns the number of items in the [`Bvh`].
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the [`Bvh`] contains no items.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Removes all of the items of the [`Bvh`].
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Returns the item with the given `handle`, if it exists.
+    pub fn get(&self, handle: BvhHandle) -> Option<&T> {
+        self.leaf(handle).map(|leaf| &leaf.item)
+    }
+
+    /// Returns a mutable reference to the item with the given `handle`, if it exists.
+    ///
+    /// Call [`Bvh::update`] if the bounding volume of the item changes.
+    pub fn get_mut(&mut self, handle: BvhHandle) -> Option<&mut T> {
+        self.leaves
+            .get_mut(handle.index())
+            .and_then(Option::as_mut)
+            .map(|leaf| &mut leaf.item)
+    }
+
+    /// Returns the bounding volume of the item with the given `handle`, if it exists.
+    pub fn volume(&self, handle: BvhHandle) -> Option<B> {
+        self.leaf(handle)
+            .map(|leaf| self.nodes[leaf.node as usize].volume)
+    }
+
+    /// Returns the bounding volume of all of the items, or `None` if the [`Bvh`] is empty.
+    pub fn root_volume(&self) -> Option<B> {
+        self.root.map(|root| self.nodes[root as usize].volume)
+    }
+
+    /// Returns an iterator over the items and their handles.
+    pub fn iter(&self) -> impl Iterator<Item = (BvhHandle, &T)> {
+        self.leaves.iter().enumerate().filter_map(|(index, leaf)| {
+            leaf.as_ref()
+                .map(|leaf| (BvhHandle(index as u32), &leaf.item))
+        })
+    }
+
+    /// Inserts an item with the given bounding volume, and returns its handle.
+    ///
+    /// The item is placed next to the node that increases the surface area of the tree the
+    /// least.
+    pub fn insert(&mut self, volume: B, item: T) -> BvhHandle {
+        let handle = match self.free_leaves.pop() {
+            Some(handle) => handle,
+            None => {
+                self.leaves.push(None);
+                self.leaves.len() as u32 - 1
+            }
+        };
+        let node = self.allocate_node(BvhNode {
+            volume,
+            parent: None,
+            kind: BvhNodeKind::Leaf(handle),
+        });
+        self.leaves[handle as usize] = Some(BvhLeaf { item, node });
+        self.len += 1;
+
+        let Some(root) = self.root else {
+            self.root = Some(node);
+            return BvhHandle(handle);
+        };
+
+        // Descend toward the cheapest sibling, where the cost of a node is the area it would
+        // have with the new volume, plus the area its ancestors would gain.
+        let mut sibling = root;
+        while let BvhNodeKind::Branch(children) = self.nodes[sibling as usize].kind {
+            let current = &self.nodes[sibling as usize].volume;
+            let merged_area = current.merge(&volume).visible_area();
+            let cost = 2.0 * merged_area;
+            let inherited = 2.0 * (merged_area - current.visible_area());
+            let child_cost = |child: u32| {
+                let child = &self.nodes[child as usize];
+                let area = child.volume.merge(&volume).visible_area();
+                match child.kind {
+                    BvhNodeKind::Leaf(_) => area + inherited,
+                    BvhNodeKind::Branch(_) => area - child.volume.visible_area() + inherited,
+                }
+            };
+            let (left, right) = (child_cost(children[0]), child_cost(children[1]));
+            if cost < left && cost < right {
+                break;
+            }
+            sibling = if left <= right {
+                children[0]
+            } else {
+                children[1]
+            };
+        }
+
+        // Replace the sibling with a new branch holding both it and the new leaf.
+        let parent = self.nodes[sibling as usize].parent;
+        let branch = self.allocate_node(BvhNode {
+            volume: self.nodes[sibling as usize].volume.merge(&volume),
+            parent,
+            kind: BvhNodeKind::Branch([sibling, node]),
+        });
+        self.replace_child(parent, sibling, branch);
+        self.nodes[sibling as usize].parent = Some(branch);
+        self.nodes[node as usize].parent = Some(branch);
+        self.refit_ancestors(parent);
+        BvhHandle(handle)
+    }
+
+    /// Removes the item with the given `handle`, and returns it if it existed.
+    pub fn remove(&mut self, handle: BvhHandle) -> Option<T> {
+        let leaf = self.leaves.get_mut(handle.index())?.take()?;
+        self.free_leaves.push(handle.0);
+        self.len -= 1;
+        self.free_nodes.push(leaf.node);
+
+        // Replace the parent of the leaf with its sibling.
+        if let Some(parent) = self.nodes[leaf.node as usize].parent {
+            let BvhNodeKind::Branch(children) = self.nodes[parent as usize].kind else {
+                unreachable!("the parent of a node is always a branch");
+            };
+            let sibling = if children[0] == leaf.node {
+                children[1]
+            } else {
+                children[0]
+            };
+            let grandparent = self.nodes[parent as usize].parent;
+            self.replace_child(grandparent, parent, sibling);
+            self.nodes[sibling as usize].parent = grandparent;
+            self.free_nodes.push(parent);
+            self.refit_ancestors(grandparent);
+        } else {
+            self.root = None;
+        }
+        Some(leaf.item)
+    }
+
+    /// Sets the bounding volume of the item with the given `handle`, and refits the volumes of
+    /// its ancestors.
+    ///
+    /// Returns `false` if the item doesn't exist.
+    pub fn update(&mut self, handle: BvhHandle, volume: B) -> bool {
+        let Some(leaf) = self.leaf(handle) else {
+            return false;
+        };
+        let node = leaf.node as usize;
+        self.nodes[node].volume = volume;
+        self.refit_ancestors(self.nodes[node].parent);
+        true
+    }
+
+    /// Sets the bounding volume of every item to the one returned by `volume`, and refits the
+    /// volumes of all of the branches, without changing the structure of the tree.
+    ///
+    /// This is faster than [`Bvh::rebuild`], but the queries get slower as the items move away
+    /// from where they were when the tree was built.
+    pub fn refit(&mut self, mut volume: impl FnMut(BvhHandle, &T) -> B) {
+        for (index, leaf) in self.leaves.iter().enumerate() {
+            if let Some(leaf) = leaf {
+                self.nodes[leaf.node as usize].volume = volume(BvhHandle(index as u32), &leaf.item);
+            }
+        }
+
+        // Walk the tree in post-order with the parent links, so that every branch is refitted
+        // right after its children.
+        let Some(root) = self.root else {
+            return;
+        };
+        let mut node = self.first_leaf(root);
+        while let Some(parent) = self.nodes[node as usize].parent {
+            let BvhNodeKind::Branch([left, right]) = self.nodes[parent as usize].kind else {
+                unreachable!("the parent of a node is always a branch");
+            };
+            if node == left {
+                node = self.first_leaf(right);
+            } else {
+                self.nodes[parent as usize].volume = self.nodes[left as usize]
+                    .volume
+                    .merge(&self.nodes[right as usize].volume);
+                node = parent;
+            }
+        }
+    }
+
+    /// Rebuilds the tree from the current bounding volumes of the items with the surface area
+    /// heuristic, keeping their handles.
+    pub fn rebuild(&mut self) {
+        let mut volumes: Vec<(B, u32)> = self
+            .leaves
+            .iter()
+            .enumerate()
+            .filter_map(|(index, leaf)| {
+                leaf.as_ref()
+                    .map(|leaf| (self.nodes[leaf.node as usize].volume, index as u32))
+            })
+            .collect();
+        self.nodes.clear();
+        self.free_nodes.clear();
+        self.root = None;
+        if !volumes.is_empty() {
+            self.root = Some(self.build_node(&mut volumes, None));
+        }
+    }
+
+    /// Returns an iterator over the items whose bounding volumes intersect the given `volume`.
+    pub fn intersecting<'a, V: IntersectsVolume<B>>(
+        &'a self,
+        volume: &'a V,
+    ) -> impl Iterator<Item = (BvhHandle, &'a T)> + 'a {
+        let mut stack: Vec<u32> = self.root.into_iter().collect();
+        core::iter::from_fn(move || {
+            while let Some(node) = stack.pop() {
+                let node = &self.nodes[node as usize];
+                if !volume.intersects(&node.volume) {
+                    continue;
+                }
+                match node.kind {
+                    BvhNodeKind::Leaf(handle) => {
+                        let handle = BvhHandle(handle);
+                        return self.get(handle).map(|item| (handle, item));
+                    }
+                    BvhNodeKind::Branch(children) => stack.extend(children),
+                }
+            }
+            None
+        })
+    }
+
+    /// Returns the pairs of items whose bounding volumes intersect each other, with the smaller
+    /// handle first.
+    ///
+    /// This can be used as the broad phase of collision detection.
+    pub fn overlapping_pairs(&self) -> Vec<(BvhHandle, BvhHandle)>
+    where
+        B: IntersectsVolume<B>,
+    {
+        let mut pairs = Vec::new();
+        for (handle, _) in self.iter() {
+            let Some(volume) = self.volume(handle) else {
+                continue;
+            };
+            pairs.extend(
+                self.intersecting(&volume)
+                    .filter(|&(other, _)| other > handle)
+                    .map(|(other, _)| (handle, other)),
+            );
+        }
+        pairs
+    }
+
+    /// Finds the item that is the closest to the start of a cast, such as a ray cast.
+    ///
+    /// `volume_distance` returns the distance along the cast at which it hits a bounding volume,
+    /// or `None` if it misses it. `item_distance` does the same for an item, and must never
+    /// return a smaller distance than the one of its bounding volume.
+    ///
+    /// Returns the handle of the closest item that is hit and its distance.
+    pub fn cast(
+        &self,
+        mut volume_distance: impl FnMut(&B) -> Option<f32>,
+        mut item_distance: impl FnMut(BvhHandle, &T) -> Option<f32>,
+    ) -> Option<(BvhHandle, f32)> {
+        let mut closest: Option<(BvhHandle, f32)> = None;
+        let mut stack: Vec<(u32, f32)> = Vec::new();
+        if let Some(root) = self.root {
+            if let Some(distance) = volume_distance(&self.nodes[root as usize].volume) {
+                stack.push((root, distance));
+            }
+        }
+        while let Some((node, distance)) = stack.pop() {
+            if closest.is_some_and(|(_, closest)| distance >= closest) {
+                continue;
+            }
+            match self.nodes[node as usize].kind {
+                BvhNodeKind::Leaf(handle) => {
+                    let handle = BvhHandle(handle);
+                    let Some(item) = self.get(handle) else {
+                        continue;
+                    };
+                    if let Some(distance) = item_distance(handle, item) {
+                        if closest.is_none_or(|(_, closest)| distance < closest) {
+                            closest = Some((handle, distance));
+                        }
+                    }
+                }
+                BvhNodeKind::Branch(children) => {
+                    // Visit the closest child first by pushing it last.
+                    let mut hits = children.map(|child| {
+                        volume_distance(&self.nodes[child as usize].volume)
+                            .map(|distance| (child, distance))
+                    });
+                    if let [Some((_, a)), Some((_, b))] = hits {
+                        if a < b {
+                            hits.swap(0, 1);
+                        }
+                    }
+                    stack.extend(hits.into_iter().flatten());
+                }
+            }
+        }
+        closest
+    }
+
+    /// Finds the item that is the closest to the given `point`.
+    ///
+    /// `item_distance_squared` returns the squared distance from the point to an item, and must
+    /// never return a smaller distance than the one to its bounding volume.
+    ///
+    /// Returns the handle of the closest item and its squared distance.
+    pub fn nearest(
+        &self,
+        point: B::Translation,
+        mut item_distance_squared: impl FnMut(BvhHandle, &T) -> f32,
+    ) -> Option<(BvhHandle, f32)> {
+        let mut closest: Option<(BvhHandle, f32)> = None;
+        let mut stack: Vec<(u32, f32)> = Vec::new();
+        if let Some(root) = self.root {
+            let distance = self.nodes[root as usize].volume.distance_squared_to(point);
+            stack.push((root, distance));
+        }
+        while let Some((node, distance)) = stack.pop() {
+            if closest.is_some_and(|(_, closest)| distance >= closest) {
+                continue;
+            }
+            match self.nodes[node as usize].kind {
+                BvhNodeKind::Leaf(handle) => {
+                    let handle = BvhHandle(handle);
+                    let Some(item) = self.get(handle) else {
+                        continue;
+                    };
+                    let distance = item_distance_squared(handle, item);
+                    if closest.is_none_or(|(_, closest)| distance < closest) {
+                        closest = Some((handle, distance));
+                    }
+                }
+                BvhNodeKind::Branch(children) => {
+                    // Visit the closest child first by pushing it last.
+                    let mut children = children.map(|child| {
+                        let volume = &self.nodes[child as usize].volume;
+                        (child, volume.distance_squared_to(point))
+                    });
+                    if children[0].1 < children[1].1 {
+                        children.swap(0, 1);
+                    }
+                    stack.extend(children);
+                }
+            }
+        }
+        closest
+    }
+
+    fn leaf(&self, handle: BvhHandle) -> Option<&BvhLeaf<T>> {
+        self.leaves.get(handle.index()).and_then(Option::as_ref)
+    }
+
+    fn allocate_node(&mut self, node: BvhNode<B>) -> u32 {
+        match self.free_nodes.pop() {
+            Some(index) => {
+                self.nodes[index as usize] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() as u32 - 1
+            }
+        }
+    }
+
+    /// Replaces the child `old` of `parent` with `new`, or the root if `parent` is `None`.
+    fn replace_child(&mut self, parent: Option<u32>, old: u32, new: u32) {
+        let Some(parent) = parent else {
+            self.root = Some(new);
+            return;
+        };
+        if let BvhNodeKind::Branch(children) = &mut self.nodes[parent as usize].kind {
+            for child in children.iter_mut().filter(|child| **child == old) {
+                *child = new;
+            }
+        }
+    }
+
+    /// Returns the leftmost leaf of the subtree of `node`.
+    fn first_leaf(&self, mut node: u32) -> u32 {
+        while let BvhNodeKind::Branch([left, _]) = self.nodes[node as usize].kind {
+            node = left;
+        }
+        node
+    }
+
+    /// Recomputes the volumes of `node` and all of its ancestors from their children.
+    fn refit_ancestors(&mut self, mut node: Option<u32>) {
+        while let Some(index) = node {
+            let index = index as usize;
+            if let BvhNodeKind::Branch([left, right]) = self.nodes[index].kind {
+                self.nodes[index].volume = self.nodes[left as usize]
+                    .volume
+                    .merge(&self.nodes[right as usize].volume);
+            }
+            node = self.nodes[index].parent;
+        }
+    }
+
+    /// Builds the subtree of the given volumes and the handles of their items, and returns its
+    /// root.
+    fn build_node(&mut self, volumes: &mut [(B, u32)], parent: Option<u32>) -> u32 {
+        if let [(volume, handle)] = *volumes {
+            let node = self.allocate_node(BvhNode {
+                volume,
+                parent,
+                kind: BvhNodeKind::Leaf(handle),
+            });
+            if let Some(leaf) = &mut self.leaves[handle as usize] {
+                leaf.node = node;
+            }
+            return node;
+        }
+
+        let split = sah_split(volumes);
+        let node = self.allocate_node(BvhNode {
+            volume: volumes[0].0,
+            parent,
+            kind: BvhNodeKind::Branch([0, 0]),
+        });
+        let (left, right) = volumes.split_at_mut(split);
+        let left = self.build_node(left, Some(node));
+        let right = self.build_node(right, Some(node));
+        self.nodes[node as usize].volume = self.nodes[left as usize]
+            .volume
+            .merge(&self.nodes[right as usize].volume);
+        self.nodes[node as usize].kind = BvhNodeKind::Branch([left, right]);
+        node
+    }
+}
+
+/// Partitions the volumes in two with the binned surface area heuristic, and returns the number
+/// of volumes in the first part.
+fn sah_split<B: BvhVolume>(volumes: &mut [(B, u32)]) -> usize {
+    let merge = |volume: Option<B>, other: &B| match volume {
+        Some(volume) => volume.merge(other),
+        None => *other,
+    };
+    let area = |volume: Option<B>| volume.map_or(0.0, |volume| volume.visible_area());
+
+    // Bin the volumes by their center along each axis, and look for the split between two bins
+    // that minimizes the total area of both sides weighted by their number of volumes.
+    let mut best: Option<(f32, usize, usize, f32, f32)> = None;
+    for axis in 0..B::AXES {
+        let (min, max) = volumes
+            .iter()
+            .fold((f32::MAX, f32::MIN), |(min, max), (volume, _)| {
+                let center = volume.center_along(axis);
+                (min.min(center), max.max(center))
+            });
+        if min >= max {
+            continue;
+        }
+        let bin_of = |volume: &B| {
+            let t = (volume.center_along(axis) - min) / (max - min);
+            ((t * SAH_BINS as f32) as usize).min(SAH_BINS - 1)
+        };
+        let mut bins: [(Option<B>, usize); SAH_BINS] = [(None, 0); SAH_BINS];
+        for (volume, _) in volumes.iter() {
+            let bin = &mut bins[bin_of(volume)];
+            *bin = (Some(merge(bin.0, volume)), bin.1 + 1);
+        }
+
+        let mut right_costs = [0.0; SAH_BINS];
+        let (mut right, mut right_count) = (None, 0);
+        for bin in (1..SAH_BINS).rev() {
+            if let Some(volume) = &bins[bin].0 {
+                right = Some(merge(right, volume));
+            }
+            right_count += bins[bin].1;
+            right_costs[bin] = area(right) * right_count as f32;
+        }
+        let (mut left, mut left_count) = (None, 0);
+        for bin in 0..SAH_BINS - 1 {
+            if let Some(volume) = &bins[bin].0 {
+                left = Some(merge(left, volume));
+            }
+            left_count += bins[bin].1;
+            if left_count == 0 || left_count == volumes.len() {
+                continue;
+            }
+            let cost = area(left) * left_count as f32 + right_costs[bin + 1];
+            if best.is_none_or(|(best, ..)| cost < best) {
+                best = Some((cost, axis, bin, min, max));
+            }
+        }
+    }
+
+    let Some((_, axis, split_bin, min, max)) = best else {
+        // Every volume has the same center, so split them evenly.
+        return volumes.len() / 2;
+    };
+    let mut split = 0;
+    for index in 0..volumes.len() {
+        let t = (volumes[index].0.center_along(axis) - min) / (max - min);
+        if ((t * SAH_BINS as f32) as usize).min(SAH_BINS - 1) <= split_bin {
+            volumes.swap(split, index);
+            split += 1;
+        }
+    }
+    split
+}
+
+impl<T> Bvh<Aabb2d, T> {
+    /// Finds the item that is the closest to the origin of the given `ray`.
+    ///
+    /// `item_distance` returns the distance along the ray at which it hits an item, or `None` if
+    /// it misses it.
+    ///
+    /// Returns the handle of the closest item that is hit and its distance.
+    pub fn ray_cast(
+        &self,
+        ray: &RayCast2d,
+        item_distance: impl FnMut(BvhHandle, &T) -> Option<f32>,
+    ) -> Option<(BvhHandle, f32)> {
+        self.cast(|aabb| ray.aabb_intersection_at(aabb), item_distance)
+    }
+}
+
+impl<T> Bvh<Aabb3d, T> {
+    /// Finds the item that is the closest to the origin of the given `ray`.
+    ///
+    /// `item_distance` returns the distance along the ray at which it hits an item, such as a
+    /// triangle of a mesh, or `None` if it misses it.
+    ///
+    /// Returns the handle of the closest item that is hit and its distance.
+    pub fn ray_cast(
+        &self,
+        ray: &RayCast3d,
+        item_distance: impl FnMut(BvhHandle, &T) -> Option<f32>,
+    ) -> Option<(BvhHandle, f32)> {
+        self.cast(|aabb| ray.aabb_intersection_at(aabb), item_distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bounding::BoundingSphere, Dir2, Dir3, Vec3};
+
+    /// Returns pseudo-random boxes scattered in a cube.
+    fn random_boxes(count: usize) -> Vec<Aabb3d> {
+        let mut seed = 0x2545_f491_u32;
+        let mut random = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            seed as f32 / u32::MAX as f32
+        };
+        (0..count)
+            .map(|_| {
+                let center = Vec3::new(random(), random(), random()) * 20.0 - 10.0;
+                let half_size = Vec3::new(random(), random(), random()) * 0.5 + 0.1;
+                Aabb3d::new(center, half_size)
+            })
+            .collect()
+    }
+
+    /// Checks that every branch contains its children and is their parent.
+    fn check_tree<T>(bvh: &Bvh<Aabb3d, T>) {
+        let mut leaves = 0;
+        let mut stack: Vec<u32> = bvh.root.into_iter().collect();
+        while let Some(node) = stack.pop() {
+            let node_ref = &bvh.nodes[node as usize];
+            match node_ref.kind {
+                BvhNodeKind::Leaf(handle) => {
+                    assert_eq!(bvh.leaves[handle as usize].as_ref().unwrap().node, node);
+                    leaves += 1;
+                }
+                BvhNodeKind::Branch(children) => {
+                    for child in children {
+                        assert_eq!(bvh.nodes[child as usize].parent, Some(node));
+                        assert!(node_ref.volume.contains(&bvh.nodes[child as usize].volume));
+                        stack.push(child);
+                    }
+                }
+            }
+        }
+        assert_eq!(leaves, bvh.len());
+    }
+
+    fn check_queries(bvh: &Bvh<Aabb3d, Aabb3d>) {
+        let ray = RayCast3d::new(Vec3::new(-12.0, 0.5, -0.3), Dir3::X, 30.0);
+        let expected = bvh
+            .iter()
+            .filter_map(|(handle, aabb)| ray.aabb_intersection_at(aabb).map(|t| (handle, t)))
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        let hit = bvh.ray_cast(&ray, |_, aabb| ray.aabb_intersection_at(aabb));
+        assert_eq!(hit.map(|(_, t)| t), expected.map(|(_, t)| t));
+
+        let point = Vec3A::new(1.0, -2.0, 3.0);
+        let expected = bvh
+            .iter()
+            .map(|(handle, aabb)| (handle, aabb.distance_squared_to(point)))
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        let nearest = bvh.nearest(point, |_, aabb| aabb.distance_squared_to(point));
+        assert_eq!(nearest.map(|(_, d)| d), expected.map(|(_, d)| d));
+
+        let sphere = BoundingSphere::new(Vec3::new(2.0, 1.0, 0.0), 4.0);
+        let mut expected: Vec<BvhHandle> = bvh
+            .iter()
+            .filter(|(_, aabb)| sphere.intersects(*aabb))
+            .map(|(handle, _)| handle)
+            .collect();
+        let mut found: Vec<BvhHandle> = bvh
+            .intersecting(&sphere)
+            .map(|(handle, _)| handle)
+            .collect();
+        expected.sort();
+        found.sort();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn build_and_query() {
+        let boxes = random_boxes(200);
+        let bvh = Bvh::build(boxes.iter().map(|&aabb| (aabb, aabb)));
+        assert_eq!(bvh.len(), 200);
+        check_tree(&bvh);
+        check_queries(&bvh);
+
+        let mut pairs = bvh.overlapping_pairs();
+        let mut expected = Vec::new();
+        for (a, first) in boxes.iter().enumerate() {
+            for (b, second) in boxes.iter().enumerate().skip(a + 1) {
+                if first.intersects(second) {
+                    expected.push((BvhHandle(a as u32), BvhHandle(b as u32)));
+                }
+            }
+        }
+        pairs.sort();
+        assert_eq!(pairs, expected);
+    }
+
+    #[test]
+    fn insert_remove_and_refit() {
+        let boxes = random_boxes(150);
+        let mut bvh = Bvh::new();
+        let handles: Vec<BvhHandle> = boxes.iter().map(|&aabb| bvh.insert(aabb, aabb)).collect();
+        check_tree(&bvh);
+        check_queries(&bvh);
+
+        for &handle in handles.iter().step_by(3) {
+            assert!(bvh.remove(handle).is_some());
+        }
+        assert!(bvh.remove(handles[0]).is_none());
+        assert_eq!(bvh.len(), 100);
+        check_tree(&bvh);
+        check_queries(&bvh);
+
+        // Move every box, updating some of them one by one and the rest all at once.
+        let offset = Vec3A::new(1.5, -0.5, 0.25);
+        for &handle in handles.iter().skip(1).step_by(3) {
+            let moved = bvh.volume(handle).unwrap().translated_by(offset);
+            *bvh.get_mut(handle).unwrap() = moved;
+            assert!(bvh.update(handle, moved));
+        }
+        for &handle in handles.iter().skip(2).step_by(3) {
+            let aabb = bvh.get_mut(handle).unwrap();
+            *aabb = aabb.translated_by(offset);
+        }
+        bvh.refit(|_, aabb| *aabb);
+        for (i, (&handle, aabb)) in handles.iter().zip(&boxes).enumerate() {
+            if i % 3 != 0 {
+                let moved = aabb.translated_by(offset);
+                let volume = bvh.volume(handle).unwrap();
+                assert_eq!((volume.min, volume.max), (moved.min, moved.max));
+            }
+        }
+        check_tree(&bvh);
+        check_queries(&bvh);
+
+        bvh.rebuild();
+        check_tree(&bvh);
+        check_queries(&bvh);
+
+        let reused = bvh.insert(boxes[0], boxes[0]);
+        assert!(handles.iter().step_by(3).any(|&handle| handle == reused));
+        assert_eq!(bvh.len(), 101);
+        check_tree(&bvh);
+    }
+
+    #[test]
+    fn ray_cast_2d() {
+        let bvh = Bvh::build((0..8).map(|i| {
+            (
+                Aabb2d::new(Vec2::new(i as f32 * 2.0, 0.0), Vec2::splat(0.5)),
+                i,
+            )
+        }));
+        let ray = RayCast2d::new(Vec2::new(20.0, 0.0), Dir2::NEG_X, 100.0);
+        let hit = bvh.ray_cast(&ray, |_, &i| {
+            (i % 2 == 0).then_some(20.0 - i as f32 * 2.0 - 0.5)
+        });
+        assert_eq!(hit, Some((BvhHandle(6), 7.5)));
+        assert!(Bvh::<Aabb2d, ()>::new()
+            .ray_cast(&ray, |_, _| Some(0.0))
+            .is_none());
+    }
+}
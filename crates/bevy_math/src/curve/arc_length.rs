@@ -0,0 +1,581 @@
+//! Arc-length parametrization of curves, and the rotation-minimizing frames of curves in space.
+//!
+//! Splines such as [`CubicCurve`] are parametrized by segment rather than by distance: a point
+//! moving through the parameter domain at a constant rate speeds up and slows down depending on
+//! how far apart the control points are. An [`ArcLengthCurve`] wraps such a curve so that it is
+//! instead parametrized by the distance traveled along it, which makes constant-speed motion along
+//! a path, spacing objects evenly on it, and finding the point of a path closest to another point
+//! straightforward.
+//!
+//! A [`RotationMinimizingCurve`] additionally computes an orientation at every point of a curve in
+//! space, which follows the direction of the curve while twisting around it as little as possible.
+//! It is a `Curve<Isometry3d>`, so that it can directly drive a camera on a rail or lay out the
+//! cross-sections of a road.
+//!
+//! ```
+//! # use bevy_math::{prelude::*, curve::arc_length::*};
+//! let spline = CubicCardinalSpline::new_catmull_rom([
+//!     vec3(0.0, 0.0, 0.0),
+//!     vec3(1.0, 0.0, 0.0),
+//!     vec3(4.0, 1.0, 0.0),
+//!     vec3(5.0, 1.0, 3.0),
+//! ])
+//! .to_curve()
+//! .unwrap();
+//!
+//! // Move along the spline at 2 units per second.
+//! let path = ArcLengthCurve::new(spline, 1e-4).unwrap();
+//! let elapsed_seconds = 1.5;
+//! let position = path.sample_clamped(2.0 * elapsed_seconds);
+//!
+//! // Orient a camera along the spline, keeping it upright.
+//! let rail = RotationMinimizingCurve::new(path, Vec3::Y);
+//! let camera_isometry = rail.sample_clamped(2.0 * elapsed_seconds);
+//! ```
+//!
+//! [`CubicCurve`]: crate::cubic_splines::CubicCurve
+
+use super::{derivatives::SampleDerivative, Curve, Interval};
+use crate::{ops, Isometry3d, Mat3, NormedVectorSpace, Quat, Vec3, WithDerivative};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use thiserror::Error;
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::{FromReflect, Reflect};
+
+/// The number of intervals that the domain of a curve is first split into when building the
+/// lookup table of an [`ArcLengthCurve`], before being subdivided adaptively.
+const INITIAL_SUBDIVISIONS: usize = 16;
+
+/// The number of times that each initial interval can be halved when building the lookup table of
+/// an [`ArcLengthCurve`].
+const MAX_SUBDIVISION_DEPTH: u32 = 10;
+
+/// The nodes and weights of the five-point Gauss-Legendre quadrature on `[-1, 1]`.
+const GAUSS_LEGENDRE: [(f32, f32); 5] = [
+    (0.0, 0.568_888_9),
+    (-0.538_469_3, 0.478_628_67),
+    (0.538_469_3, 0.478_628_67),
+    (-0.906_179_85, 0.236_926_88),
+    (0.906_179_85, 0.236_926_88),
+];
+
+/// An error indicating that an [`ArcLengthCurve`] couldn't be built.
+#[derive(Debug, Error)]
+#[error("Could not parametrize this curve by arc length")]
+pub enum ArcLengthError {
+    /// The source curve had an unbounded domain.
+    #[error("This curve has an unbounded domain")]
+    UnboundedDomain,
+
+    /// The source curve had a length of zero, or a length that couldn't be computed.
+    #[error("This curve has no length")]
+    ZeroLength,
+}
+
+/// A curve reparametrized by arc length, so that sampling it at `s` gives the point at a distance
+/// of `s` along the source curve from its start.
+///
+/// The domain of an arc-length curve is `[0, length]`. Its derivative, if the source curve has one,
+/// is the unit tangent of the source curve.
+///
+/// The distances along the curve are stored in a lookup table, which is subdivided adaptively where
+/// the speed of the source curve varies the most, and refined with Newton's method when sampling.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect, FromReflect),
+    reflect(from_reflect = false)
+)]
+pub struct ArcLengthCurve<P, C> {
+    curve: C,
+    /// The parameters of the source curve at which the distances were computed, sorted.
+    parameters: Vec<f32>,
+    /// The distance along the curve at each parameter.
+    distances: Vec<f32>,
+    #[cfg_attr(feature = "bevy_reflect", reflect(ignore))]
+    _phantom: PhantomData<fn() -> P>,
+}
+
+impl<P, C> ArcLengthCurve<P, C>
+where
+    P: NormedVectorSpace,
+    C: SampleDerivative<P>,
+{
+    /// Parametrizes `curve` by arc length.
+    ///
+    /// The lookup table is subdivided until the length of each of its intervals is known within
+    /// `tolerance`, so smaller tolerances give more accurate distances at the cost of memory.
+    ///
+    /// An [`ArcLengthError`] is returned if the domain of `curve` isn't bounded, or if it has no
+    /// length.
+    pub fn new(curve: C, tolerance: f32) -> Result<Self, ArcLengthError> {
+        let domain = curve.domain();
+        if !domain.is_bounded() {
+            return Err(ArcLengthError::UnboundedDomain);
+        }
+
+        let mut parameters = Vec::with_capacity(INITIAL_SUBDIVISIONS + 1);
+        let mut distances = Vec::with_capacity(INITIAL_SUBDIVISIONS + 1);
+        parameters.push(domain.start());
+        distances.push(0.0);
+        let step = domain.length() / INITIAL_SUBDIVISIONS as f32;
+        for index in 0..INITIAL_SUBDIVISIONS {
+            let start = domain.start() + step * index as f32;
+            let end = match index + 1 == INITIAL_SUBDIVISIONS {
+                true => domain.end(),
+                false => start + step,
+            };
+            subdivide(
+                &curve,
+                (start, end),
+                gauss_length(&curve, start, end),
+                tolerance.max(f32::EPSILON),
+                MAX_SUBDIVISION_DEPTH,
+                &mut parameters,
+                &mut distances,
+            );
+        }
+
+        let length = distances[distances.len() - 1];
+        if !(length > 0.0 && length.is_finite()) {
+            return Err(ArcLengthError::ZeroLength);
+        }
+        Ok(Self {
+            curve,
+            parameters,
+            distances,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// The length of the curve.
+    #[inline]
+    pub fn length(&self) -> f32 {
+        self.distances[self.distances.len() - 1]
+    }
+
+    /// The source curve.
+    #[inline]
+    pub fn inner(&self) -> &C {
+        &self.curve
+    }
+
+    /// Returns the parameter of the source curve at the given `distance` along it, clamped to its
+    /// domain.
+    pub fn parameter_at_distance(&self, distance: f32) -> f32 {
+        let distance = distance.clamp(0.0, self.length());
+        let index = self
+            .distances
+            .partition_point(|&other| other <= distance)
+            .clamp(1, self.distances.len() - 1);
+        let (start, end) = (self.parameters[index - 1], self.parameters[index]);
+        let (start_distance, end_distance) = (self.distances[index - 1], self.distances[index]);
+        if end_distance <= start_distance {
+            return start;
+        }
+
+        // Interpolate within the interval, then correct for the variation of speed inside it.
+        let mut t =
+            start + (end - start) * (distance - start_distance) / (end_distance - start_distance);
+        for _ in 0..2 {
+            let error = start_distance + gauss_length(&self.curve, start, t) - distance;
+            let speed = self.speed(t);
+            if speed <= f32::EPSILON {
+                break;
+            }
+            t = (t - error / speed).clamp(start, end);
+        }
+        t
+    }
+
+    /// Returns the distance along the curve at the given parameter of the source curve, clamped
+    /// to its domain.
+    pub fn distance_at_parameter(&self, t: f32) -> f32 {
+        let t = self.curve.domain().clamp(t);
+        let index = self
+            .parameters
+            .partition_point(|&other| other <= t)
+            .clamp(1, self.parameters.len() - 1);
+        let start = self.parameters[index - 1];
+        self.distances[index - 1] + gauss_length(&self.curve, start, t)
+    }
+
+    /// Returns the distance along the curve of the point of the curve closest to `point`, which
+    /// can be passed to [`Curve::sample`] on this curve to get the point itself.
+    ///
+    /// The lookup table is used to find the closest of its samples, and the result is refined in
+    /// the surrounding intervals. On curves that come back close to themselves, this may return a
+    /// point that is only locally the closest.
+    pub fn closest_distance(&self, point: P) -> f32 {
+        let distance_squared = |t: f32| self.curve.sample_unchecked(t).distance_squared(point);
+        let closest = self
+            .parameters
+            .iter()
+            .enumerate()
+            .map(|(index, &t)| (index, distance_squared(t)))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map_or(0, |(index, _)| index);
+
+        // Golden-section search around the closest sample.
+        const INVERSE_PHI: f32 = 0.618_034;
+        let mut start = self.parameters[closest.saturating_sub(1)];
+        let mut end = self.parameters[(closest + 1).min(self.parameters.len() - 1)];
+        let mut a = end - (end - start) * INVERSE_PHI;
+        let mut b = start + (end - start) * INVERSE_PHI;
+        let (mut distance_a, mut distance_b) = (distance_squared(a), distance_squared(b));
+        for _ in 0..32 {
+            if distance_a < distance_b {
+                end = b;
+                (b, distance_b) = (a, distance_a);
+                a = end - (end - start) * INVERSE_PHI;
+                distance_a = distance_squared(a);
+            } else {
+                start = a;
+                (a, distance_a) = (b, distance_b);
+                b = start + (end - start) * INVERSE_PHI;
+                distance_b = distance_squared(b);
+            }
+        }
+        self.distance_at_parameter((start + end) * 0.5)
+    }
+
+    /// The speed of the source curve at the parameter `t`.
+    #[inline]
+    fn speed(&self, t: f32) -> f32 {
+        self.curve
+            .sample_with_derivative_unchecked(t)
+            .derivative
+            .norm()
+    }
+}
+
+/// Splits the interval `(start, end)` of `curve`, whose length was estimated as `length`, until
+/// halving it doesn't change its length by more than `tolerance`, appending the end of every
+/// resulting interval to the lookup table.
+fn subdivide<P: NormedVectorSpace>(
+    curve: &impl SampleDerivative<P>,
+    (start, end): (f32, f32),
+    length: f32,
+    tolerance: f32,
+    depth: u32,
+    parameters: &mut Vec<f32>,
+    distances: &mut Vec<f32>,
+) {
+    let middle = (start + end) * 0.5;
+    let first_half = gauss_length(curve, start, middle);
+    let second_half = gauss_length(curve, middle, end);
+    if depth > 0 && ops::abs(first_half + second_half - length) > tolerance {
+        let tolerance = tolerance * 0.5;
+        subdivide(
+            curve,
+            (start, middle),
+            first_half,
+            tolerance,
+            depth - 1,
+            parameters,
+            distances,
+        );
+        subdivide(
+            curve,
+            (middle, end),
+            second_half,
+            tolerance,
+            depth - 1,
+            parameters,
+            distances,
+        );
+    } else {
+        let distance = distances[distances.len() - 1] + first_half + second_half;
+        parameters.push(end);
+        distances.push(distance);
+    }
+}
+
+/// Integrates the speed of `curve` between the parameters `start` and `end`.
+fn gauss_length<P: NormedVectorSpace>(
+    curve: &impl SampleDerivative<P>,
+    start: f32,
+    end: f32,
+) -> f32 {
+    let half_length = (end - start) * 0.5;
+    let middle = (start + end) * 0.5;
+    GAUSS_LEGENDRE
+        .iter()
+        .map(|&(node, weight)| {
+            let t = middle + half_length * node;
+            weight * curve.sample_with_derivative_unchecked(t).derivative.norm()
+        })
+        .sum::<f32>()
+        * half_length
+}
+
+impl<P, C> Curve<P> for ArcLengthCurve<P, C>
+where
+    P: NormedVectorSpace,
+    C: SampleDerivative<P>,
+{
+    #[inline]
+    fn domain(&self) -> Interval {
+        // The constructor guarantees a positive length.
+        Interval::new(0.0, self.length())
+            .expect("ArcLengthCurve is invalid because it has no length")
+    }
+
+    #[inline]
+    fn sample_unchecked(&self, t: f32) -> P {
+        self.curve.sample_unchecked(self.parameter_at_distance(t))
+    }
+}
+
+impl<P, C> SampleDerivative<P> for ArcLengthCurve<P, C>
+where
+    P: NormedVectorSpace,
+    C: SampleDerivative<P>,
+{
+    fn sample_with_derivative_unchecked(&self, t: f32) -> WithDerivative<P> {
+        let mut sample = self
+            .curve
+            .sample_with_derivative_unchecked(self.parameter_at_distance(t));
+        let speed = sample.derivative.norm();
+        sample.derivative = match speed > f32::EPSILON {
+            true => sample.derivative / speed,
+            false => P::ZERO,
+        };
+        sample
+    }
+}
+
+/// A curve in space parametrized by arc length, with an orientation at every point that follows
+/// the direction of the curve while twisting around it as little as possible.
+///
+/// Sampling it gives an [`Isometry3d`] whose forward direction, the negative z axis, is the
+/// direction of the curve, and whose up direction, the y axis, starts as close as possible to the
+/// `up` vector given on creation. The up direction is then carried along the curve by parallel
+/// transport, so that it doesn't flip or spin where the curve turns, unlike a frame that is kept
+/// as close as possible to a fixed up vector.
+///
+/// The frames are computed at every sample of the lookup table of the [`ArcLengthCurve`] with the
+/// double reflection method, and interpolated between them.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect, FromReflect),
+    reflect(from_reflect = false)
+)]
+pub struct RotationMinimizingCurve<C> {
+    curve: ArcLengthCurve<Vec3, C>,
+    /// The orientation of the frame at each sample of the lookup table of `curve`.
+    rotations: Vec<Quat>,
+}
+
+impl<C: SampleDerivative<Vec3>> RotationMinimizingCurve<C> {
+    /// Computes the rotation-minimizing frames of `curve`, starting with the up direction closest
+    /// to `up`.
+    pub fn new(curve: ArcLengthCurve<Vec3, C>, up: Vec3) -> Self {
+        let mut rotations = Vec::with_capacity(curve.parameters.len());
+        let mut previous: Option<(Vec3, Vec3, Vec3)> = None;
+        for &t in &curve.parameters {
+            let sample = curve.curve.sample_with_derivative_unchecked(t);
+            let position = sample.value;
+            let tangent = sample
+                .derivative
+                .try_normalize()
+                .or(previous.map(|(_, tangent, _)| tangent))
+                .unwrap_or(Vec3::NEG_Z);
+
+            let normal = match previous {
+                None => (up - tangent * up.dot(tangent))
+                    .try_normalize()
+                    .unwrap_or_else(|| tangent.any_orthonormal_vector()),
+                Some((previous_position, previous_tangent, previous_normal)) => {
+                    // Reflect the previous frame across the bisecting plane of both positions,
+                    // then across the plane that maps the reflected tangent to the new one.
+                    let reflect = |v: Vec3, axis: Vec3| {
+                        let length_squared = axis.length_squared();
+                        match length_squared > f32::EPSILON * f32::EPSILON {
+                            true => v - axis * (2.0 * axis.dot(v) / length_squared),
+                            false => v,
+                        }
+                    };
+                    let offset = position - previous_position;
+                    let normal = reflect(previous_normal, offset);
+                    let tangent_reflected = reflect(previous_tangent, offset);
+                    let normal = reflect(normal, tangent - tangent_reflected);
+                    (normal - tangent * normal.dot(tangent))
+                        .try_normalize()
+                        .unwrap_or(previous_normal)
+                }
+            };
+            rotations.push(frame_rotation(tangent, normal));
+            previous = Some((position, tangent, normal));
+        }
+        Self { curve, rotations }
+    }
+
+    /// The arc-length curve whose frames are computed.
+    #[inline]
+    pub fn arc_length_curve(&self) -> &ArcLengthCurve<Vec3, C> {
+        &self.curve
+    }
+}
+
+/// The rotation that maps the negative z axis to `forward` and the y axis to `up`, which must be
+/// orthonormal.
+fn frame_rotation(forward: Vec3, up: Vec3) -> Quat {
+    Quat::from_mat3(&Mat3::from_cols(forward.cross(up), up, -forward))
+}
+
+impl<C: SampleDerivative<Vec3>> Curve<Isometry3d> for RotationMinimizingCurve<C> {
+    #[inline]
+    fn domain(&self) -> Interval {
+        self.curve.domain()
+    }
+
+    fn sample_unchecked(&self, t: f32) -> Isometry3d {
+        let distances = &self.curve.distances;
+        let distance = t.clamp(0.0, self.curve.length());
+        let index = distances
+            .partition_point(|&other| other <= distance)
+            .clamp(1, distances.len() - 1);
+        let (start, end) = (distances[index - 1], distances[index]);
+        let s = match end > start {
+            true => (distance - start) / (end - start),
+            false => 0.0,
+        };
+        let rotation = self.rotations[index - 1].slerp(self.rotations[index], s);
+
+        // Align the interpolated frame with the exact direction of the curve.
+        let sample = self
+            .curve
+            .curve
+            .sample_with_derivative_unchecked(self.curve.parameter_at_distance(distance));
+        let rotation = match sample.derivative.try_normalize() {
+            Some(tangent) => {
+                (Quat::from_rotation_arc(rotation * Vec3::NEG_Z, tangent) * rotation).normalize()
+            }
+            None => rotation,
+        };
+        Isometry3d::new(sample.value, rotation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cubic_splines::{CubicBezier, CubicCardinalSpline, CubicGenerator},
+        vec2, vec3, Vec2,
+    };
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn constant_speed_sampling() {
+        // The control points are unevenly spaced, so the spline speeds up between them.
+        let spline = CubicCardinalSpline::new_catmull_rom([
+            vec2(0.0, 0.0),
+            vec2(0.1, 0.0),
+            vec2(3.0, 2.0),
+            vec2(3.5, 2.0),
+            vec2(6.0, -1.0),
+        ])
+        .to_curve()
+        .unwrap();
+        let curve = ArcLengthCurve::new(spline, 1e-4).unwrap();
+
+        // Measure the length of the spline between evenly spaced distances with a fine polyline.
+        let steps = 20;
+        let step = curve.length() / steps as f32;
+        for index in 0..steps {
+            let start = curve.parameter_at_distance(step * index as f32);
+            let end = curve.parameter_at_distance(step * (index + 1) as f32);
+            let polyline: Vec<Vec2> = (0..=100)
+                .map(|i| {
+                    curve
+                        .inner()
+                        .position(start + (end - start) * i as f32 / 100.0)
+                })
+                .collect();
+            let length: f32 = polyline
+                .windows(2)
+                .map(|pair| pair[0].distance(pair[1]))
+                .sum();
+            assert_abs_diff_eq!(length, step, epsilon = step * 0.01);
+        }
+        assert_abs_diff_eq!(
+            curve.sample_unchecked(curve.length()),
+            vec2(6.0, -1.0),
+            epsilon = 1e-3
+        );
+
+        // Distances and parameters are inverse of each other.
+        let t = curve.parameter_at_distance(curve.length() * 0.3);
+        assert_abs_diff_eq!(
+            curve.distance_at_parameter(t),
+            curve.length() * 0.3,
+            epsilon = 1e-3
+        );
+    }
+
+    #[test]
+    fn closest_distance_on_line() {
+        let line = CubicBezier::new([[
+            vec3(0.0, 0.0, 0.0),
+            vec3(1.0, 0.0, 0.0),
+            vec3(2.0, 0.0, 0.0),
+            vec3(10.0, 0.0, 0.0),
+        ]])
+        .to_curve()
+        .unwrap();
+        let curve = ArcLengthCurve::new(line, 1e-4).unwrap();
+        assert_abs_diff_eq!(curve.length(), 10.0, epsilon = 1e-3);
+        assert_abs_diff_eq!(
+            curve.closest_distance(vec3(6.5, 3.0, -1.0)),
+            6.5,
+            epsilon = 1e-3
+        );
+        assert_abs_diff_eq!(
+            curve.closest_distance(vec3(-4.0, 1.0, 0.0)),
+            0.0,
+            epsilon = 1e-3
+        );
+    }
+
+    #[test]
+    fn rotation_minimizing_frames_of_helix() {
+        // A quarter of a helix around the y axis.
+        let helix = CubicBezier::new([[
+            vec3(1.0, 0.0, 0.0),
+            vec3(1.0, 0.5, 0.55),
+            vec3(0.55, 1.0, 1.0),
+            vec3(0.0, 1.5, 1.0),
+        ]])
+        .to_curve()
+        .unwrap();
+        let curve =
+            RotationMinimizingCurve::new(ArcLengthCurve::new(helix, 1e-4).unwrap(), Vec3::Y);
+        let length = curve.domain().end();
+
+        let mut previous_up: Option<Vec3> = None;
+        for index in 0..=50 {
+            let distance = length * index as f32 / 50.0;
+            let isometry = curve.sample_unchecked(distance);
+            let forward = isometry.rotation * Vec3::NEG_Z;
+            let up = isometry.rotation * Vec3::Y;
+            let tangent = curve
+                .arc_length_curve()
+                .sample_with_derivative_unchecked(distance)
+                .derivative;
+            assert_abs_diff_eq!(forward, tangent, epsilon = 1e-3);
+            assert_abs_diff_eq!(up.dot(forward), 0.0, epsilon = 1e-3);
+
+            // The up direction never rotates around the curve, so it only changes as much as the
+            // direction of the curve does.
+            if let Some(previous_up) = previous_up {
+                assert!(previous_up.dot(up) > 0.99);
+            }
+            previous_up = Some(up);
+        }
+    }
+}
@@ -286,6 +286,8 @@
 //! (curve.domain(), |t| curve.sample_unchecked(t))` is an equivalent function curve.
 
 pub mod adaptors;
+#[cfg(feature = "alloc")]
+pub mod arc_length;
 pub mod cores;
 pub mod derivatives;
 pub mod easing;
@@ -44,6 +44,9 @@ mod rotation2d;
 #[cfg(feature = "curve")]
 pub mod curve;
 
+#[cfg(feature = "alloc")]
+pub mod query;
+
 #[cfg(feature = "rand")]
 pub mod sampling;
 
@@ -0,0 +1,489 @@
+//! Boolean operations on polygons.
+//!
+//! The edges of both operands are split where they intersect, and each resulting piece is kept or
+//! dropped depending on whether it lies inside, outside or on the boundary of the other operand.
+//! The kept pieces are then linked back into rings, which become the exteriors and holes of the
+//! resulting polygons.
+
+use super::{dim2::signed_area, BoxedPolygon, BoxedPolygonWithHoles};
+use crate::{ops, Vec2};
+use alloc::{boxed::Box, vec, vec::Vec};
+
+impl BoxedPolygon {
+    /// Computes the union of this polygon and `other`, which must both be simple.
+    ///
+    /// See [`BoxedPolygonWithHoles::union`].
+    pub fn union(&self, other: &BoxedPolygon) -> Vec<BoxedPolygonWithHoles> {
+        boolean(&[&self.vertices], &[&other.vertices], BooleanOp::Union)
+    }
+
+    /// Computes the intersection of this polygon and `other`, which must both be simple.
+    ///
+    /// See [`BoxedPolygonWithHoles::intersection`].
+    pub fn intersection(&self, other: &BoxedPolygon) -> Vec<BoxedPolygonWithHoles> {
+        boolean(
+            &[&self.vertices],
+            &[&other.vertices],
+            BooleanOp::Intersection,
+        )
+    }
+
+    /// Computes the difference of this polygon and `other`, which must both be simple.
+    ///
+    /// See [`BoxedPolygonWithHoles::difference`].
+    pub fn difference(&self, other: &BoxedPolygon) -> Vec<BoxedPolygonWithHoles> {
+        boolean(&[&self.vertices], &[&other.vertices], BooleanOp::Difference)
+    }
+}
+
+impl BoxedPolygonWithHoles {
+    /// Computes the union of this polygon and `other`: the area covered by either of them.
+    ///
+    /// The result is a list of disjoint polygons, whose exteriors are wound counterclockwise and
+    /// whose holes are wound clockwise.
+    pub fn union(&self, other: &BoxedPolygonWithHoles) -> Vec<BoxedPolygonWithHoles> {
+        boolean(&self.rings(), &other.rings(), BooleanOp::Union)
+    }
+
+    /// Computes the intersection of this polygon and `other`: the area covered by both of them.
+    ///
+    /// The result is a list of disjoint polygons, whose exteriors are wound counterclockwise and
+    /// whose holes are wound clockwise.
+    pub fn intersection(&self, other: &BoxedPolygonWithHoles) -> Vec<BoxedPolygonWithHoles> {
+        boolean(&self.rings(), &other.rings(), BooleanOp::Intersection)
+    }
+
+    /// Computes the difference of this polygon and `other`: the area covered by this polygon but
+    /// not by `other`.
+    ///
+    /// The result is a list of disjoint polygons, whose exteriors are wound counterclockwise and
+    /// whose holes are wound clockwise.
+    pub fn difference(&self, other: &BoxedPolygonWithHoles) -> Vec<BoxedPolygonWithHoles> {
+        boolean(&self.rings(), &other.rings(), BooleanOp::Difference)
+    }
+
+    /// The vertices of the exterior, followed by the vertices of each hole.
+    fn rings(&self) -> Vec<&[Vec2]> {
+        let mut rings = vec![&*self.exterior.vertices];
+        rings.extend(self.holes.iter().map(|hole| &*hole.vertices));
+        rings
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BooleanOp {
+    Union,
+    Intersection,
+    Difference,
+}
+
+/// Where a piece of the boundary of a polygon lies relative to another polygon.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Location {
+    Inside,
+    Outside,
+    /// On the boundary of the other polygon, in the same direction as its edge.
+    SameBoundary,
+    /// On the boundary of the other polygon, in the opposite direction to its edge.
+    OppositeBoundary,
+}
+
+type Edge = (Vec2, Vec2);
+
+fn boolean(a: &[&[Vec2]], b: &[&[Vec2]], op: BooleanOp) -> Vec<BoxedPolygonWithHoles> {
+    let edges_a = oriented_edges(a);
+    let edges_b = oriented_edges(b);
+    let extent = edges_a
+        .iter()
+        .chain(&edges_b)
+        .fold(0.0_f32, |extent, (start, _)| {
+            extent.max(start.abs().max_element())
+        });
+    let tolerance = extent.max(f32::MIN_POSITIVE) * 1e-5;
+
+    // Find the points at which each edge must be split.
+    let mut splits_a: Vec<Vec<Vec2>> = vec![Vec::new(); edges_a.len()];
+    let mut splits_b: Vec<Vec<Vec2>> = vec![Vec::new(); edges_b.len()];
+    for (edge_a, splits_a) in edges_a.iter().zip(&mut splits_a) {
+        for (edge_b, splits_b) in edges_b.iter().zip(&mut splits_b) {
+            intersect(*edge_a, *edge_b, tolerance, splits_a, splits_b);
+        }
+    }
+
+    let mut pieces: Vec<Edge> = Vec::new();
+    for (edge, splits) in edges_a.iter().zip(&splits_a) {
+        for piece in split(*edge, splits, tolerance) {
+            match (op, locate(piece, &edges_b, tolerance)) {
+                (BooleanOp::Union, Location::Outside | Location::SameBoundary)
+                | (BooleanOp::Intersection, Location::Inside | Location::SameBoundary)
+                | (BooleanOp::Difference, Location::Outside | Location::OppositeBoundary) => {
+                    pieces.push(piece);
+                }
+                _ => {}
+            }
+        }
+    }
+    // Pieces of `b` on the boundary of `a` are kept as pieces of `a`, if at all.
+    for (edge, splits) in edges_b.iter().zip(&splits_b) {
+        for piece in split(*edge, splits, tolerance) {
+            match (op, locate(piece, &edges_a, tolerance)) {
+                (BooleanOp::Union, Location::Outside)
+                | (BooleanOp::Intersection, Location::Inside) => pieces.push(piece),
+                (BooleanOp::Difference, Location::Inside) => pieces.push((piece.1, piece.0)),
+                _ => {}
+            }
+        }
+    }
+
+    assemble(link(pieces, tolerance), tolerance)
+}
+
+/// Returns the edges of the given rings, with the first ring wound counterclockwise and the
+/// others wound clockwise.
+fn oriented_edges(rings: &[&[Vec2]]) -> Vec<Edge> {
+    let mut edges = Vec::new();
+    for (index, ring) in rings.iter().enumerate() {
+        if ring.len() < 3 {
+            continue;
+        }
+        let reverse = (signed_area(ring) > 0.0) != (index == 0);
+        let ring_edges = (0..ring.len())
+            .map(|index| (ring[index], ring[(index + 1) % ring.len()]))
+            .filter(|(start, end)| start != end)
+            .map(|(start, end)| match reverse {
+                true => (end, start),
+                false => (start, end),
+            });
+        edges.extend(ring_edges);
+    }
+    edges
+}
+
+/// Records the points where the edges `a` and `b` intersect in their lists of split points.
+///
+/// Intersections close to an endpoint snap to it, so that both edges are split at exactly the
+/// same points.
+fn intersect(a: Edge, b: Edge, tolerance: f32, splits_a: &mut Vec<Vec2>, splits_b: &mut Vec<Vec2>) {
+    let (r, s) = (a.1 - a.0, b.1 - b.0);
+    let denominator = r.perp_dot(s);
+    let offset = b.0 - a.0;
+    let near_a = |point: Vec2| distance_to_segment(point, a) <= tolerance;
+    let near_b = |point: Vec2| distance_to_segment(point, b) <= tolerance;
+
+    if ops::abs(denominator) <= f32::EPSILON * r.length() * s.length() {
+        // Parallel edges only intersect where they overlap.
+        for point in [b.0, b.1] {
+            if near_a(point) {
+                splits_a.push(point);
+            }
+        }
+        for point in [a.0, a.1] {
+            if near_b(point) {
+                splits_b.push(point);
+            }
+        }
+        return;
+    }
+
+    let t = offset.perp_dot(s) / denominator;
+    let point = a.0 + r * t;
+    if !(near_a(point) && near_b(point)) {
+        return;
+    }
+    let point = [a.0, a.1, b.0, b.1]
+        .into_iter()
+        .find(|endpoint| endpoint.distance(point) <= tolerance)
+        .unwrap_or(point);
+    splits_a.push(point);
+    splits_b.push(point);
+}
+
+/// Splits `edge` at the given points.
+fn split(edge: Edge, splits: &[Vec2], tolerance: f32) -> Vec<Edge> {
+    let direction = edge.1 - edge.0;
+    let mut points: Vec<(f32, Vec2)> = splits
+        .iter()
+        .filter(|point| point.distance(edge.0) > tolerance && point.distance(edge.1) > tolerance)
+        .map(|&point| ((point - edge.0).dot(direction), point))
+        .collect();
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+    points.dedup_by(|a, b| a.1.distance(b.1) <= tolerance);
+
+    let mut pieces = Vec::with_capacity(points.len() + 1);
+    let mut start = edge.0;
+    for (_, point) in points {
+        pieces.push((start, point));
+        start = point;
+    }
+    pieces.push((start, edge.1));
+    pieces
+}
+
+/// Locates the piece of an edge relative to the polygon with the given oriented edges.
+fn locate(piece: Edge, edges: &[Edge], tolerance: f32) -> Location {
+    let middle = (piece.0 + piece.1) * 0.5;
+    if let Some(edge) = edges
+        .iter()
+        .find(|edge| distance_to_segment(middle, **edge) <= tolerance)
+    {
+        return match (piece.1 - piece.0).dot(edge.1 - edge.0) > 0.0 {
+            true => Location::SameBoundary,
+            false => Location::OppositeBoundary,
+        };
+    }
+
+    // Compute the winding number of the middle of the piece.
+    let winding: i32 = edges
+        .iter()
+        .map(|&(start, end)| {
+            let side = (end - start).perp_dot(middle - start);
+            match (start.y <= middle.y, end.y <= middle.y) {
+                (true, false) if side > 0.0 => 1,
+                (false, true) if side < 0.0 => -1,
+                _ => 0,
+            }
+        })
+        .sum();
+    match winding != 0 {
+        true => Location::Inside,
+        false => Location::Outside,
+    }
+}
+
+/// Links pieces of edges into closed rings.
+///
+/// Where several pieces start at the end of a piece, the one turning the most to the left is
+/// taken, so that polygons touching at a vertex give separate rings.
+fn link(pieces: Vec<Edge>, tolerance: f32) -> Vec<Vec<Vec2>> {
+    let tolerance_squared = tolerance * tolerance;
+    let mut used = vec![false; pieces.len()];
+    let mut rings = Vec::new();
+    for first in 0..pieces.len() {
+        if used[first] {
+            continue;
+        }
+        let mut ring = Vec::new();
+        let mut current = first;
+        let closed = loop {
+            used[current] = true;
+            let (start, end) = pieces[current];
+            ring.push(start);
+            if end.distance_squared(pieces[first].0) <= tolerance_squared {
+                break true;
+            }
+            let direction = end - start;
+            let next = (0..pieces.len())
+                .filter(|&index| {
+                    !used[index] && pieces[index].0.distance_squared(end) <= tolerance_squared
+                })
+                .max_by(|&a, &b| {
+                    let turn = |index: usize| {
+                        let next_direction = pieces[index].1 - pieces[index].0;
+                        ops::atan2(
+                            direction.perp_dot(next_direction),
+                            direction.dot(next_direction),
+                        )
+                    };
+                    turn(a).total_cmp(&turn(b))
+                });
+            match next {
+                Some(next) => current = next,
+                None => break false,
+            }
+        };
+        if closed {
+            rings.push(ring);
+        }
+    }
+    rings
+}
+
+/// Sorts simplified rings into exteriors, wound counterclockwise, and the holes they contain,
+/// wound clockwise. Holes that no exterior contains become exteriors.
+fn assemble(rings: Vec<Vec<Vec2>>, tolerance: f32) -> Vec<BoxedPolygonWithHoles> {
+    let (exteriors, holes): (Vec<_>, Vec<_>) = rings
+        .into_iter()
+        .map(|ring| simplify(ring, tolerance))
+        .filter(|ring| ring.len() >= 3 && ops::abs(signed_area(ring)) > tolerance * tolerance)
+        .partition(|ring| signed_area(ring) > 0.0);
+
+    let mut polygons: Vec<(Vec<Vec2>, Vec<Vec<Vec2>>)> = exteriors
+        .into_iter()
+        .map(|exterior| (exterior, Vec::new()))
+        .collect();
+    let point_inside = |hole: &[Vec2], polygons: &[(Vec<Vec2>, Vec<Vec<Vec2>>)]| {
+        // A point of the hole that isn't on the boundary of any exterior, if there is one.
+        hole.iter()
+            .find(|point| {
+                polygons.iter().all(|(exterior, _)| {
+                    !ring_edges(exterior)
+                        .any(|edge| distance_to_segment(**point, edge) <= tolerance)
+                })
+            })
+            .copied()
+            .unwrap_or(hole[0])
+    };
+
+    // Holes that no exterior encloses, which can come from rounding in nearly degenerate
+    // inputs, are kept as exteriors instead of being dropped.
+    let (holes, orphans): (Vec<_>, Vec<_>) = holes.into_iter().partition(|hole| {
+        let point = point_inside(hole, &polygons);
+        polygons
+            .iter()
+            .any(|(exterior, _)| contains(exterior, point))
+    });
+    polygons.extend(orphans.into_iter().map(|mut orphan| {
+        orphan.reverse();
+        (orphan, Vec::new())
+    }));
+
+    for hole in holes {
+        // The hole belongs to the smallest exterior containing it.
+        let point = point_inside(&hole, &polygons);
+        let owner = polygons
+            .iter_mut()
+            .filter(|(exterior, _)| contains(exterior, point))
+            .min_by(|(a, _), (b, _)| signed_area(a).total_cmp(&signed_area(b)));
+        if let Some((_, holes)) = owner {
+            holes.push(hole);
+        }
+    }
+
+    polygons
+        .into_iter()
+        .map(|(exterior, holes)| BoxedPolygonWithHoles {
+            exterior: BoxedPolygon::new(exterior),
+            holes: holes
+                .into_iter()
+                .map(BoxedPolygon::new)
+                .collect::<Box<[_]>>(),
+        })
+        .collect()
+}
+
+/// Removes duplicate and collinear vertices from a ring.
+fn simplify(mut ring: Vec<Vec2>, tolerance: f32) -> Vec<Vec2> {
+    let mut index = 0;
+    while index < ring.len() && ring.len() >= 3 {
+        let len = ring.len();
+        let previous = ring[(index + len - 1) % len];
+        let next = ring[(index + 1) % len];
+        let current = ring[index];
+        let degenerate = current.distance(previous) <= tolerance
+            || distance_to_segment(current, (previous, next)) <= tolerance;
+        if degenerate {
+            ring.remove(index);
+            index = index.saturating_sub(1);
+        } else {
+            index += 1;
+        }
+    }
+    ring
+}
+
+fn ring_edges(ring: &[Vec2]) -> impl Iterator<Item = Edge> + '_ {
+    (0..ring.len()).map(|index| (ring[index], ring[(index + 1) % ring.len()]))
+}
+
+/// Returns true if `point` is inside the ring, using the even-odd rule.
+fn contains(ring: &[Vec2], point: Vec2) -> bool {
+    ring_edges(ring)
+        .filter(|(start, end)| {
+            (start.y > point.y) != (end.y > point.y)
+                && point.x < start.x + (point.y - start.y) * (end.x - start.x) / (end.y - start.y)
+        })
+        .count()
+        % 2
+        == 1
+}
+
+fn distance_to_segment(point: Vec2, (start, end): Edge) -> f32 {
+    let direction = end - start;
+    let length_squared = direction.length_squared();
+    let t = match length_squared > 0.0 {
+        true => ((point - start).dot(direction) / length_squared).clamp(0.0, 1.0),
+        false => 0.0,
+    };
+    point.distance(start + direction * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::Measured2d;
+    use approx::assert_abs_diff_eq;
+
+    fn square(center: Vec2, half_size: f32) -> BoxedPolygon {
+        BoxedPolygon::new(
+            [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)]
+                .map(|(x, y)| center + Vec2::new(x, y) * half_size),
+        )
+    }
+
+    fn total_area(polygons: &[BoxedPolygonWithHoles]) -> f32 {
+        polygons.iter().map(Measured2d::area).sum()
+    }
+
+    #[test]
+    fn overlapping_squares() {
+        let a = square(Vec2::ZERO, 1.0);
+        let b = square(Vec2::ONE, 1.0);
+
+        let union = a.union(&b);
+        assert_eq!(union.len(), 1);
+        assert_eq!(union[0].exterior.vertices.len(), 8);
+        assert_abs_diff_eq!(total_area(&union), 7.0, epsilon = 1e-5);
+
+        let intersection = a.intersection(&b);
+        assert_eq!(intersection.len(), 1);
+        assert_abs_diff_eq!(total_area(&intersection), 1.0, epsilon = 1e-5);
+
+        let difference = a.difference(&b);
+        assert_eq!(difference.len(), 1);
+        assert_abs_diff_eq!(total_area(&difference), 3.0, epsilon = 1e-5);
+        assert!(difference[0].triangulate().is_ok());
+    }
+
+    #[test]
+    fn nested_and_disjoint_squares() {
+        let outer = square(Vec2::ZERO, 2.0);
+        let inner = square(Vec2::ZERO, 1.0);
+
+        // Cutting a hole.
+        let difference = outer.difference(&inner);
+        assert_eq!(difference.len(), 1);
+        assert_eq!(difference[0].holes.len(), 1);
+        assert!(signed_area(&difference[0].holes[0].vertices) < 0.0);
+        assert_abs_diff_eq!(total_area(&difference), 12.0, epsilon = 1e-5);
+
+        // Filling it back.
+        let union = difference[0].union(&inner.into());
+        assert_eq!(union.len(), 1);
+        assert!(union[0].holes.is_empty());
+        assert_abs_diff_eq!(total_area(&union), 16.0, epsilon = 1e-5);
+
+        // Disjoint polygons stay separate, and squares sharing an edge are merged.
+        let far = square(Vec2::new(10.0, 0.0), 1.0);
+        assert_eq!(outer.union(&far).len(), 2);
+        assert!(outer.intersection(&far).is_empty());
+        let neighbor = square(Vec2::new(4.0, 0.0), 2.0);
+        let union = outer.union(&neighbor);
+        assert_eq!(union.len(), 1);
+        assert_eq!(union[0].exterior.vertices.len(), 4);
+        assert_abs_diff_eq!(total_area(&union), 32.0, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn unenclosed_holes_become_exteriors() {
+        let exterior = square(Vec2::ZERO, 1.0).vertices.to_vec();
+        let mut hole = square(Vec2::new(5.0, 0.0), 1.0).vertices.to_vec();
+        hole.reverse();
+
+        let polygons = assemble(vec![exterior, hole], 1e-4);
+        assert_eq!(polygons.len(), 2);
+        assert!(polygons.iter().all(
+            |polygon| polygon.holes.is_empty() && signed_area(&polygon.exterior.vertices) > 0.0
+        ));
+        assert_abs_diff_eq!(total_area(&polygons), 8.0, epsilon = 1e-5);
+    }
+}
@@ -1819,6 +1819,138 @@ impl BoxedPolygon {
     pub fn is_simple(&self) -> bool {
         is_polygon_simple(&self.vertices)
     }
+
+    /// Get the signed area of the polygon, assuming that it is simple.
+    ///
+    /// The area is positive if the vertices are in counterclockwise order, and negative if they
+    /// are in clockwise order.
+    #[inline(always)]
+    pub fn signed_area(&self) -> f32 {
+        signed_area(&self.vertices)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Measured2d for BoxedPolygon {
+    /// Get the area of the polygon, assuming that it is simple
+    #[inline(always)]
+    fn area(&self) -> f32 {
+        ops::abs(signed_area(&self.vertices))
+    }
+
+    /// Get the perimeter of the polygon
+    #[inline(always)]
+    fn perimeter(&self) -> f32 {
+        ring_perimeter(&self.vertices)
+    }
+}
+
+/// A polygon with holes, allocated on the heap.
+///
+/// The exterior and each hole are simple [`BoxedPolygon`]s, with holes lying inside the exterior
+/// and not overlapping each other. Polygons with holes are produced by boolean operations such as
+/// [`BoxedPolygonWithHoles::union`], and can be triangulated with
+/// [`BoxedPolygonWithHoles::triangulate`].
+///
+/// The winding order of the exterior and the holes doesn't matter.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoxedPolygonWithHoles {
+    /// The outer boundary of the polygon
+    pub exterior: BoxedPolygon,
+    /// The holes of the polygon
+    pub holes: Box<[BoxedPolygon]>,
+}
+
+#[cfg(feature = "alloc")]
+impl Primitive2d for BoxedPolygonWithHoles {}
+
+#[cfg(feature = "alloc")]
+impl From<BoxedPolygon> for BoxedPolygonWithHoles {
+    fn from(exterior: BoxedPolygon) -> Self {
+        Self {
+            exterior,
+            holes: Box::new([]),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl BoxedPolygonWithHoles {
+    /// Create a new `BoxedPolygonWithHoles` from its exterior and its holes
+    pub fn new(exterior: BoxedPolygon, holes: impl IntoIterator<Item = BoxedPolygon>) -> Self {
+        Self {
+            exterior,
+            holes: holes.into_iter().collect(),
+        }
+    }
+
+    /// Iterate over the vertices of the exterior, followed by the vertices of each hole.
+    ///
+    /// This is the order of the vertices indexed by [`BoxedPolygonWithHoles::triangulate`].
+    pub fn vertices(&self) -> impl Iterator<Item = Vec2> + '_ {
+        self.exterior
+            .vertices
+            .iter()
+            .chain(self.holes.iter().flat_map(|hole| hole.vertices.iter()))
+            .copied()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Measured2d for BoxedPolygonWithHoles {
+    /// Get the area of the polygon, excluding its holes
+    #[inline(always)]
+    fn area(&self) -> f32 {
+        self.holes
+            .iter()
+            .fold(self.exterior.area(), |area, hole| area - hole.area())
+    }
+
+    /// Get the perimeter of the polygon, including the boundaries of its holes
+    #[inline(always)]
+    fn perimeter(&self) -> f32 {
+        self.holes
+            .iter()
+            .fold(self.exterior.perimeter(), |perimeter, hole| {
+                perimeter + hole.perimeter()
+            })
+    }
+}
+
+/// Computes the signed area of the polygon with the given vertices, which is positive if they
+/// are in counterclockwise order.
+#[cfg(feature = "alloc")]
+pub(crate) fn signed_area(vertices: &[Vec2]) -> f32 {
+    let Some(&last) = vertices.last() else {
+        return 0.0;
+    };
+    vertices
+        .iter()
+        .scan(last, |previous, &vertex| {
+            let cross = previous.perp_dot(vertex);
+            *previous = vertex;
+            Some(cross)
+        })
+        .sum::<f32>()
+        * 0.5
+}
+
+/// Computes the perimeter of the closed polygon with the given vertices.
+#[cfg(feature = "alloc")]
+fn ring_perimeter(vertices: &[Vec2]) -> f32 {
+    let Some(&last) = vertices.last() else {
+        return 0.0;
+    };
+    vertices
+        .iter()
+        .scan(last, |previous, &vertex| {
+            let length = previous.distance(vertex);
+            *previous = vertex;
+            Some(length)
+        })
+        .sum()
 }
 
 /// A polygon centered on the origin where all vertices lie on a circle, equally far apart.
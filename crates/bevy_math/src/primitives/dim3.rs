@@ -532,6 +532,71 @@ impl BoxedPolyline3d {
     }
 }
 
+/// The convex hull of a set of points in 3D space, allocated on the heap.
+///
+/// The hull is a closed triangle mesh whose faces are wound counterclockwise when seen from
+/// outside. It is computed with [`ConvexHull3d::convex_hull`].
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConvexHull3d {
+    /// The vertices of the hull, which are the extreme points of the source points.
+    pub(super) vertices: Box<[Vec3]>,
+    /// The triangular faces of the hull, as indices into `vertices`.
+    pub(super) faces: Box<[[u32; 3]]>,
+}
+
+#[cfg(feature = "alloc")]
+impl Primitive3d for ConvexHull3d {}
+
+#[cfg(feature = "alloc")]
+impl ConvexHull3d {
+    /// Get the vertices of the hull
+    #[inline(always)]
+    pub fn vertices(&self) -> &[Vec3] {
+        &self.vertices
+    }
+
+    /// Get the triangular faces of the hull, as indices into [`ConvexHull3d::vertices`]
+    #[inline(always)]
+    pub fn faces(&self) -> &[[u32; 3]] {
+        &self.faces
+    }
+
+    /// Iterate over the triangular faces of the hull
+    pub fn triangles(&self) -> impl Iterator<Item = Triangle3d> + '_ {
+        self.faces.iter().map(|&[a, b, c]| {
+            Triangle3d::new(
+                self.vertices[a as usize],
+                self.vertices[b as usize],
+                self.vertices[c as usize],
+            )
+        })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Measured3d for ConvexHull3d {
+    /// Get the surface area of the hull
+    #[inline(always)]
+    fn area(&self) -> f32 {
+        self.triangles().map(|triangle| triangle.area()).sum()
+    }
+
+    /// Get the volume of the hull
+    #[inline(always)]
+    fn volume(&self) -> f32 {
+        self.triangles()
+            .map(
+                |Triangle3d {
+                     vertices: [a, b, c],
+                 }| a.dot(b.cross(c)),
+            )
+            .sum::<f32>()
+            / 6.0
+    }
+}
+
 /// A cuboid primitive, which is like a cube, except that the x, y, and z dimensions are not
 /// required to be the same.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -0,0 +1,208 @@
+//! Convex hulls of point sets in 2D and 3D.
+
+use super::{BoxedPolygon, ConvexHull3d};
+use crate::{ops, Vec2, Vec3};
+use alloc::{vec, vec::Vec};
+
+impl BoxedPolygon {
+    /// Computes the convex hull of a set of points with Andrew's monotone chain algorithm.
+    ///
+    /// The vertices of the hull are in counterclockwise order, without collinear vertices.
+    /// Returns `None` if there are fewer than three distinct points or if they are all collinear.
+    pub fn convex_hull(points: impl IntoIterator<Item = Vec2>) -> Option<Self> {
+        let mut points: Vec<Vec2> = points.into_iter().collect();
+        points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+        points.dedup();
+        if points.len() < 3 {
+            return None;
+        }
+
+        // Builds the lower hull from left to right, then the upper hull from right to left,
+        // dropping every vertex that doesn't make a left turn.
+        let turns_left = |hull: &[Vec2], point: Vec2| match hull {
+            [.., a, b] => (*b - *a).perp_dot(point - *a) > 0.0,
+            _ => true,
+        };
+        let mut hull: Vec<Vec2> = Vec::with_capacity(points.len() + 1);
+        for &point in &points {
+            while !turns_left(&hull, point) {
+                hull.pop();
+            }
+            hull.push(point);
+        }
+        let lower_len = hull.len();
+        for &point in points.iter().rev().skip(1) {
+            while hull.len() > lower_len && !turns_left(&hull, point) {
+                hull.pop();
+            }
+            hull.push(point);
+        }
+        // The last point is the first point again.
+        hull.pop();
+
+        (hull.len() >= 3).then(|| Self::new(hull))
+    }
+}
+
+impl ConvexHull3d {
+    /// Computes the convex hull of a set of points with an incremental algorithm.
+    ///
+    /// Points closer to the surface of the hull than a small tolerance, relative to the size of
+    /// the point set, are considered to be inside of it. Returns `None` if there are fewer than
+    /// four points or if they are all coplanar.
+    pub fn convex_hull(points: impl IntoIterator<Item = Vec3>) -> Option<Self> {
+        let points: Vec<Vec3> = points.into_iter().collect();
+        let extent = points.iter().fold(0.0_f32, |extent, point| {
+            extent.max(point.abs().max_element())
+        });
+        let tolerance = extent.max(f32::MIN_POSITIVE) * 1e-5;
+
+        // Start from a tetrahedron of four points far apart from each other.
+        let farthest_from = |distance: &dyn Fn(Vec3) -> f32| {
+            points
+                .iter()
+                .enumerate()
+                .map(|(index, &point)| (index, distance(point)))
+                .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        };
+        let (a, _) = farthest_from(&|point| -point.x)?;
+        let (b, ab) = farthest_from(&|point| point.distance(points[a]))?;
+        let line = (points[b] - points[a]).normalize_or_zero();
+        let (c, abc) =
+            farthest_from(&|point| (point - points[a]).reject_from_normalized(line).length())?;
+        let normal = (points[b] - points[a])
+            .cross(points[c] - points[a])
+            .normalize_or_zero();
+        let (d, abcd) = farthest_from(&|point| ops::abs(normal.dot(point - points[a])))?;
+        if ab <= tolerance || abc <= tolerance || abcd <= tolerance {
+            return None;
+        }
+        let mut faces: Vec<[usize; 3]> = match normal.dot(points[d] - points[a]) > 0.0 {
+            true => vec![[a, c, b], [a, b, d], [b, c, d], [c, a, d]],
+            false => vec![[a, b, c], [a, d, b], [b, d, c], [c, d, a]],
+        };
+
+        let face_distance = |face: &[usize; 3], point: Vec3| {
+            let [a, b, c] = face.map(|index| points[index]);
+            (b - a).cross(c - a).normalize_or_zero().dot(point - a)
+        };
+        let mut horizon: Vec<(usize, usize)> = Vec::new();
+        for (index, &point) in points.iter().enumerate() {
+            if [a, b, c, d].contains(&index) {
+                continue;
+            }
+            let (visible, hidden): (Vec<[usize; 3]>, Vec<[usize; 3]>) = faces
+                .iter()
+                .partition(|face| face_distance(face, point) > tolerance);
+            if visible.is_empty() {
+                continue;
+            }
+
+            // The horizon is made of the edges of visible faces that aren't shared with another
+            // visible face. Each of them becomes a new face with the point.
+            horizon.clear();
+            for face in &visible {
+                for edge in [(face[0], face[1]), (face[1], face[2]), (face[2], face[0])] {
+                    let shared = visible.iter().any(|other| {
+                        [
+                            (other[0], other[1]),
+                            (other[1], other[2]),
+                            (other[2], other[0]),
+                        ]
+                        .contains(&(edge.1, edge.0))
+                    });
+                    if !shared {
+                        horizon.push(edge);
+                    }
+                }
+            }
+            faces = hidden;
+            faces.extend(horizon.iter().map(|&(start, end)| [start, end, index]));
+        }
+
+        // Keep only the vertices used by the faces.
+        let mut remap: Vec<Option<u32>> = vec![None; points.len()];
+        let mut vertices: Vec<Vec3> = Vec::new();
+        let faces: Vec<[u32; 3]> = faces
+            .iter()
+            .map(|face| {
+                face.map(|index| {
+                    *remap[index].get_or_insert_with(|| {
+                        vertices.push(points[index]);
+                        vertices.len() as u32 - 1
+                    })
+                })
+            })
+            .collect();
+        Some(Self {
+            vertices: vertices.into_boxed_slice(),
+            faces: faces.into_boxed_slice(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{Measured2d, Measured3d};
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn convex_hull_2d() {
+        let hull = BoxedPolygon::convex_hull([
+            Vec2::new(0.0, 0.0),
+            Vec2::new(0.5, 0.5),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(2.0, 2.0),
+            Vec2::new(0.0, 2.0),
+            Vec2::new(0.0, 2.0),
+            Vec2::new(1.0, 1.5),
+        ])
+        .unwrap();
+        assert_eq!(
+            &*hull.vertices,
+            &[
+                Vec2::new(0.0, 0.0),
+                Vec2::new(2.0, 0.0),
+                Vec2::new(2.0, 2.0),
+                Vec2::new(0.0, 2.0),
+            ]
+        );
+        assert_eq!(hull.area(), 4.0);
+
+        assert!(BoxedPolygon::convex_hull([Vec2::ZERO, Vec2::X, Vec2::X * 2.0]).is_none());
+    }
+
+    #[test]
+    fn convex_hull_3d() {
+        let mut points = Vec::new();
+        for x in [-1.0, 1.0] {
+            for y in [-1.0, 1.0] {
+                for z in [-1.0, 1.0] {
+                    points.push(Vec3::new(x, y, z));
+                }
+            }
+        }
+        // Points inside and on the faces of the cube are dropped.
+        points.extend([
+            Vec3::ZERO,
+            Vec3::new(0.5, -0.2, 0.1),
+            Vec3::new(1.0, 0.0, 0.0),
+        ]);
+
+        let hull = ConvexHull3d::convex_hull(points).unwrap();
+        assert_eq!(hull.vertices().len(), 8);
+        assert_eq!(hull.faces().len(), 12);
+        assert_abs_diff_eq!(hull.volume(), 8.0, epsilon = 1e-4);
+        assert_abs_diff_eq!(hull.area(), 24.0, epsilon = 1e-4);
+        for triangle in hull.triangles() {
+            let normal = triangle.normal().unwrap();
+            assert!(normal.dot(triangle.centroid()) > 0.0);
+        }
+
+        assert!(
+            ConvexHull3d::convex_hull([Vec3::ZERO, Vec3::X, Vec3::Y, Vec3::X + Vec3::Y]).is_none()
+        );
+    }
+}
@@ -6,7 +6,15 @@ mod dim2;
 pub use dim2::*;
 mod dim3;
 pub use dim3::*;
+#[cfg(feature = "alloc")]
+mod boolean;
+#[cfg(feature = "alloc")]
+mod hull;
 mod polygon;
+#[cfg(feature = "alloc")]
+mod triangulation;
+#[cfg(feature = "alloc")]
+pub use triangulation::TriangulationError;
 #[cfg(feature = "serialize")]
 mod serde;
 
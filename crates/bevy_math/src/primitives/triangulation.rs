@@ -0,0 +1,338 @@
+//! Triangulation of simple polygons with holes by ear clipping.
+
+use super::{dim2::signed_area, BoxedPolygon, BoxedPolygonWithHoles};
+use crate::Vec2;
+use alloc::vec::Vec;
+use thiserror::Error;
+
+/// An error that happens when triangulating a polygon.
+#[derive(Clone, Copy, Debug, Error, PartialEq, Eq)]
+pub enum TriangulationError {
+    /// The polygon has fewer than three vertices.
+    #[error("The polygon has fewer than three vertices")]
+    NotEnoughVertices,
+    /// The polygon isn't simple, or one of its holes crosses its exterior or another hole.
+    #[error("The polygon is not simple")]
+    NotSimple,
+}
+
+impl BoxedPolygon {
+    /// Triangulates the polygon, which must be simple.
+    ///
+    /// Returns the triangles as indices into the vertices of the polygon, wound counterclockwise.
+    pub fn triangulate(&self) -> Result<Vec<[u32; 3]>, TriangulationError> {
+        triangulate(&self.vertices, &[])
+    }
+}
+
+impl BoxedPolygonWithHoles {
+    /// Triangulates the polygon, whose exterior and holes must be simple and mustn't cross each
+    /// other.
+    ///
+    /// Returns the triangles as indices into the vertices of the exterior followed by the
+    /// vertices of each hole, as iterated by [`BoxedPolygonWithHoles::vertices`], wound
+    /// counterclockwise.
+    pub fn triangulate(&self) -> Result<Vec<[u32; 3]>, TriangulationError> {
+        let holes: Vec<&[Vec2]> = self.holes.iter().map(|hole| &*hole.vertices).collect();
+        triangulate(&self.exterior.vertices, &holes)
+    }
+}
+
+/// Triangulates the polygon with the given exterior and holes.
+///
+/// The holes are first connected to the exterior with bridges, giving a single weakly simple
+/// ring, which is then triangulated by ear clipping.
+fn triangulate(exterior: &[Vec2], holes: &[&[Vec2]]) -> Result<Vec<[u32; 3]>, TriangulationError> {
+    if exterior.len() < 3 {
+        return Err(TriangulationError::NotEnoughVertices);
+    }
+    let mut positions: Vec<Vec2> = exterior.to_vec();
+    let mut ring: Vec<u32> = oriented_ring(0, exterior, true);
+    let mut hole_rings: Vec<Vec<u32>> = Vec::with_capacity(holes.len());
+    for hole in holes {
+        // Degenerate holes are skipped, but their vertices still take up indices.
+        if hole.len() >= 3 {
+            hole_rings.push(oriented_ring(positions.len() as u32, hole, false));
+        }
+        positions.extend_from_slice(hole);
+    }
+    let position = |index: u32| positions[index as usize];
+
+    // Bridge the holes from right to left, so that the bridges of later holes can't cross them.
+    let rightmost = |hole: &[u32]| {
+        (0..hole.len())
+            .max_by(|&a, &b| position(hole[a]).x.total_cmp(&position(hole[b]).x))
+            .unwrap_or(0)
+    };
+    hole_rings.sort_by(|a, b| {
+        let (a, b) = (position(a[rightmost(a)]).x, position(b[rightmost(b)]).x);
+        b.total_cmp(&a)
+    });
+    for (hole_index, hole) in hole_rings.iter().enumerate() {
+        let start = rightmost(hole);
+        let from = position(hole[start]);
+        let crosses_ring = |ring: &[u32], to: Vec2| {
+            (0..ring.len()).any(|index| {
+                let (a, b) = (
+                    position(ring[index]),
+                    position(ring[(index + 1) % ring.len()]),
+                );
+                segments_cross(from, to, a, b)
+            })
+        };
+
+        // Bridge to the closest vertex of the exterior that can be seen from the hole.
+        let mut candidates: Vec<usize> = (0..ring.len()).collect();
+        candidates.sort_by(|&a, &b| {
+            from.distance_squared(position(ring[a]))
+                .total_cmp(&from.distance_squared(position(ring[b])))
+        });
+        let bridge = candidates.into_iter().find(|&index| {
+            let to = position(ring[index]);
+            let previous = position(ring[(index + ring.len() - 1) % ring.len()]);
+            let next = position(ring[(index + 1) % ring.len()]);
+            to != from
+                && in_cone(previous, to, next, from)
+                && !crosses_ring(&ring, to)
+                && !hole_rings[hole_index..]
+                    .iter()
+                    .any(|other| crosses_ring(other, to))
+        });
+        let Some(bridge) = bridge else {
+            return Err(TriangulationError::NotSimple);
+        };
+
+        let hole_loop = hole[start..]
+            .iter()
+            .chain(&hole[..=start])
+            .copied()
+            .chain([ring[bridge]]);
+        ring.splice(bridge + 1..bridge + 1, hole_loop.collect::<Vec<_>>());
+    }
+
+    // Only reflex vertices can lie inside an ear, so they are the only ones tested against each
+    // candidate. Clipping an ear can only make its neighbors convex, never reflex, so the list
+    // only shrinks.
+    let mut reflex: Vec<u32> = (0..ring.len())
+        .filter(|&index| is_reflex(&positions, &ring, index))
+        .map(|index| ring[index])
+        .collect();
+    reflex.sort_unstable();
+    reflex.dedup();
+
+    // Clip ears until a single triangle is left.
+    let mut triangles = Vec::with_capacity(ring.len().saturating_sub(2));
+    while ring.len() > 3 {
+        let len = ring.len();
+        let corner = |index: usize| {
+            [
+                ring[(index + len - 1) % len],
+                ring[index],
+                ring[(index + 1) % len],
+            ]
+        };
+        let ear = (0..len).find(|&index| {
+            let [a, b, c] = corner(index).map(position);
+            (b - a).perp_dot(c - b) > 0.0
+                && reflex.iter().all(|&other| {
+                    let point = position(other);
+                    point == a || point == b || point == c || !in_triangle(a, b, c, point)
+                })
+        });
+        let removed = match ear {
+            Some(index) => {
+                triangles.push(corner(index));
+                index
+            }
+            None => {
+                // Drop a degenerate vertex, such as a collinear one, or give up.
+                let degenerate = (0..len).find(|&index| {
+                    let [a, b, c] = corner(index).map(position);
+                    (b - a).perp_dot(c - b) == 0.0
+                });
+                match degenerate {
+                    Some(index) => index,
+                    None => return Err(TriangulationError::NotSimple),
+                }
+            }
+        };
+        ring.remove(removed);
+
+        // Vertices that appear twice in the ring, at the ends of bridges, stay reflex as long as
+        // one of their occurrences is.
+        let len = ring.len();
+        for neighbor in [ring[(removed + len - 1) % len], ring[removed % len]] {
+            let still_reflex = (0..len)
+                .any(|index| ring[index] == neighbor && is_reflex(&positions, &ring, index));
+            if !still_reflex {
+                reflex.retain(|&vertex| vertex != neighbor);
+            }
+        }
+    }
+    let [a, b, c] = [ring[0], ring[1], ring[2]];
+    if (position(b) - position(a)).perp_dot(position(c) - position(b)) > 0.0 {
+        triangles.push([a, b, c]);
+    }
+    Ok(triangles)
+}
+
+/// Returns true if the vertex at `index` in the counterclockwise `ring` isn't strictly convex.
+fn is_reflex(positions: &[Vec2], ring: &[u32], index: usize) -> bool {
+    let len = ring.len();
+    let [a, b, c] = [(index + len - 1) % len, index, (index + 1) % len]
+        .map(|index| positions[ring[index] as usize]);
+    (b - a).perp_dot(c - b) <= 0.0
+}
+
+/// Returns the indices of a ring of `vertices` starting at `offset`, counterclockwise if
+/// `counterclockwise` is true or clockwise otherwise.
+fn oriented_ring(offset: u32, vertices: &[Vec2], counterclockwise: bool) -> Vec<u32> {
+    let mut ring: Vec<u32> = (offset..offset + vertices.len() as u32).collect();
+    if (signed_area(vertices) > 0.0) != counterclockwise {
+        ring.reverse();
+    }
+    ring
+}
+
+/// Returns true if the segments `(a, b)` and `(c, d)` intersect, unless they only share an
+/// endpoint.
+fn segments_cross(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> bool {
+    if a == c || a == d || b == c || b == d {
+        return false;
+    }
+    let side = |p: Vec2, q: Vec2, r: Vec2| (q - p).perp_dot(r - p);
+    let (abc, abd) = (side(a, b, c), side(a, b, d));
+    let (cda, cdb) = (side(c, d, a), side(c, d, b));
+    if abc == 0.0 && abd == 0.0 {
+        // Collinear segments cross if they overlap.
+        let direction = b - a;
+        let project = |p: Vec2| (p - a).dot(direction);
+        let (c, d) = (project(c), project(d));
+        return c.max(d) > 0.0 && c.min(d) < direction.length_squared();
+    }
+    abc * abd <= 0.0 && cda * cdb <= 0.0
+}
+
+/// Returns true if `point` is strictly inside the interior angle of a counterclockwise ring at
+/// `vertex`, between the edges from `previous` and to `next`.
+fn in_cone(previous: Vec2, vertex: Vec2, next: Vec2, point: Vec2) -> bool {
+    let left = |p: Vec2, q: Vec2, r: Vec2| (q - p).perp_dot(r - p) > 0.0;
+    let left_on = |p: Vec2, q: Vec2, r: Vec2| (q - p).perp_dot(r - p) >= 0.0;
+    if left_on(vertex, next, previous) {
+        // The vertex is convex.
+        left(vertex, point, previous) && left(point, vertex, next)
+    } else {
+        !(left_on(vertex, point, next) && left_on(point, vertex, previous))
+    }
+}
+
+/// Returns true if `point` is inside or on the boundary of the counterclockwise triangle
+/// `(a, b, c)`.
+fn in_triangle(a: Vec2, b: Vec2, c: Vec2, point: Vec2) -> bool {
+    (b - a).perp_dot(point - a) >= 0.0
+        && (c - b).perp_dot(point - b) >= 0.0
+        && (a - c).perp_dot(point - c) >= 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::{Measured2d, Triangle2d};
+    use alloc::vec;
+
+    fn triangulated_area(vertices: &[Vec2], triangles: &[[u32; 3]]) -> f32 {
+        triangles
+            .iter()
+            .map(|triangle| {
+                let [a, b, c] = triangle.map(|index| vertices[index as usize]);
+                assert!((b - a).perp_dot(c - a) > 0.0);
+                Triangle2d::new(a, b, c).area()
+            })
+            .sum()
+    }
+
+    #[test]
+    fn triangulate_concave_polygon() {
+        // An L shape, wound clockwise.
+        let polygon = BoxedPolygon::new([
+            Vec2::new(0.0, 0.0),
+            Vec2::new(0.0, 2.0),
+            Vec2::new(1.0, 2.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(2.0, 1.0),
+            Vec2::new(2.0, 0.0),
+        ]);
+        let triangles = polygon.triangulate().unwrap();
+        assert_eq!(triangles.len(), 4);
+        assert_eq!(triangulated_area(&polygon.vertices, &triangles), 3.0);
+        assert_eq!(polygon.area(), 3.0);
+    }
+
+    #[test]
+    fn triangulate_polygon_with_holes() {
+        let square = |center: Vec2, half_size: f32| {
+            BoxedPolygon::new(
+                [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)]
+                    .map(|(x, y)| center + Vec2::new(x, y) * half_size),
+            )
+        };
+        let polygon = BoxedPolygonWithHoles::new(
+            square(Vec2::ZERO, 4.0),
+            [
+                square(Vec2::new(-2.0, 0.0), 1.0),
+                square(Vec2::new(2.0, 1.0), 1.0),
+            ],
+        );
+        let triangles = polygon.triangulate().unwrap();
+        let vertices: Vec<Vec2> = polygon.vertices().collect();
+        assert_eq!(triangles.len(), 14);
+        assert_eq!(triangulated_area(&vertices, &triangles), 56.0);
+        assert_eq!(polygon.area(), 56.0);
+
+        // A degenerate hole before a valid one doesn't shift the indices of the valid hole.
+        let polygon = BoxedPolygonWithHoles::new(
+            square(Vec2::ZERO, 4.0),
+            [
+                BoxedPolygon::new([Vec2::ZERO, Vec2::X]),
+                square(Vec2::new(2.0, 1.0), 1.0),
+            ],
+        );
+        let triangles = polygon.triangulate().unwrap();
+        let vertices: Vec<Vec2> = polygon.vertices().collect();
+        assert_eq!(vertices.len(), 10);
+        assert!(triangles
+            .iter()
+            .flatten()
+            .all(|&index| index != 4 && index != 5));
+        assert_eq!(triangulated_area(&vertices, &triangles), 60.0);
+
+        let degenerate = BoxedPolygon::new([Vec2::ZERO, Vec2::X]);
+        assert_eq!(
+            degenerate.triangulate(),
+            Err(TriangulationError::NotEnoughVertices)
+        );
+    }
+
+    #[test]
+    fn triangulate_comb() {
+        // A comb with many reflex vertices between its teeth.
+        let teeth = 16;
+        let mut vertices = vec![Vec2::new(teeth as f32 * 2.0, 0.0), Vec2::ZERO];
+        for tooth in 0..teeth {
+            let x = tooth as f32 * 2.0;
+            vertices.extend([
+                Vec2::new(x, 3.0),
+                Vec2::new(x + 1.0, 3.0),
+                Vec2::new(x + 1.0, 1.0),
+                Vec2::new(x + 2.0, 1.0),
+            ]);
+        }
+        let polygon = BoxedPolygon::new(vertices);
+        let triangles = polygon.triangulate().unwrap();
+        assert_eq!(
+            triangulated_area(&polygon.vertices, &triangles),
+            polygon.area()
+        );
+        assert_eq!(polygon.area(), teeth as f32 * 4.0);
+    }
+}
@@ -0,0 +1,186 @@
+//! The expanding polytope algorithm, which finds the penetration depth of intersecting shapes.
+
+use super::gjk::SupportPoint;
+use crate::{ops, Vec2, Vec3};
+use alloc::vec::Vec;
+
+/// The maximum number of iterations of the expanding polytope algorithm.
+const MAX_ITERATIONS: usize = 128;
+
+/// The relative tolerance on the penetration depth at which the algorithm stops.
+const RELATIVE_TOLERANCE: f32 = 1e-5;
+
+/// The penetration of two intersecting shapes found by the expanding polytope algorithm.
+pub(super) struct Penetration<V> {
+    /// The deepest point of the first shape inside of the second one.
+    pub point_a: V,
+    /// The deepest point of the second shape inside of the first one.
+    pub point_b: V,
+    /// The unit direction along which the first shape has to move back to separate the shapes,
+    /// pointing from the first shape toward the second one.
+    pub normal: V,
+    /// The distance that the shapes have to be moved apart to separate them.
+    pub depth: f32,
+}
+
+/// Expands a triangle of the Minkowski difference described by `support`, which contains the
+/// origin, until its edge closest to the origin is on the boundary of the Minkowski difference.
+pub(super) fn epa_2d(
+    support: &impl Fn(Vec2) -> SupportPoint<Vec2>,
+    simplex: &[SupportPoint<Vec2>],
+) -> Option<Penetration<Vec2>> {
+    // Wind the polygon counterclockwise, so that the outward normal of an edge is on its right.
+    let mut polygon = simplex.to_vec();
+    let [a, b, c] = [polygon[0].point, polygon[1].point, polygon[2].point];
+    if (b - a).perp_dot(c - a) < 0.0 {
+        polygon.swap(1, 2);
+    }
+
+    let mut closest = None;
+    for _ in 0..MAX_ITERATIONS {
+        let Some((index, normal, distance)) = (0..polygon.len())
+            .filter_map(|index| {
+                let (start, end) = (
+                    polygon[index].point,
+                    polygon[(index + 1) % polygon.len()].point,
+                );
+                let normal = -(end - start).perp().try_normalize()?;
+                Some((index, normal, normal.dot(start)))
+            })
+            .min_by(|(_, _, a), (_, _, b)| a.total_cmp(b))
+        else {
+            break;
+        };
+        closest = Some((index, normal, distance));
+
+        let vertex = support(normal);
+        let support_distance = vertex.point.dot(normal);
+        if support_distance - distance <= RELATIVE_TOLERANCE * ops::abs(support_distance).max(1.0)
+            || polygon.iter().any(|other| other.point == vertex.point)
+        {
+            break;
+        }
+        polygon.insert(index + 1, vertex);
+    }
+
+    let (index, normal, distance) = closest?;
+    let (start, end) = (polygon[index], polygon[(index + 1) % polygon.len()]);
+    let edge = end.point - start.point;
+    let t = ((normal * distance - start.point).dot(edge) / edge.length_squared()).clamp(0.0, 1.0);
+    Some(Penetration {
+        point_a: start.a.lerp(end.a, t),
+        point_b: start.b.lerp(end.b, t),
+        normal,
+        depth: distance,
+    })
+}
+
+/// A triangular face of the polytope of the 3D expanding polytope algorithm.
+struct Face {
+    vertices: [usize; 3],
+    normal: Vec3,
+    distance: f32,
+}
+
+impl Face {
+    fn new(polytope: &[SupportPoint<Vec3>], vertices: [usize; 3]) -> Option<Self> {
+        let [a, b, c] = vertices.map(|index| polytope[index].point);
+        let normal = (b - a).cross(c - a).try_normalize()?;
+        Some(Self {
+            vertices,
+            normal,
+            distance: normal.dot(a),
+        })
+    }
+}
+
+/// Expands a tetrahedron of the Minkowski difference described by `support`, which contains the
+/// origin, until its face closest to the origin is on the boundary of the Minkowski difference.
+pub(super) fn epa_3d(
+    support: &impl Fn(Vec3) -> SupportPoint<Vec3>,
+    simplex: &[SupportPoint<Vec3>],
+) -> Option<Penetration<Vec3>> {
+    let mut polytope = simplex.to_vec();
+    let [a, b, c, d] = [0, 1, 2, 3].map(|index| polytope[index].point);
+    let mut faces: Vec<Face> = match (b - a).cross(c - a).dot(d - a) < 0.0 {
+        true => [[0, 1, 2], [0, 3, 1], [1, 3, 2], [2, 3, 0]],
+        false => [[0, 2, 1], [0, 1, 3], [1, 2, 3], [2, 0, 3]],
+    }
+    .into_iter()
+    .filter_map(|vertices| Face::new(&polytope, vertices))
+    .collect();
+
+    let mut horizon: Vec<(usize, usize)> = Vec::new();
+    let mut closest = None;
+    for _ in 0..MAX_ITERATIONS {
+        let Some(face) = faces
+            .iter()
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+        else {
+            break;
+        };
+        closest = Some((face.vertices, face.normal, face.distance));
+
+        let vertex = support(face.normal);
+        let support_distance = vertex.point.dot(face.normal);
+        if support_distance - face.distance
+            <= RELATIVE_TOLERANCE * ops::abs(support_distance).max(1.0)
+            || polytope.iter().any(|other| other.point == vertex.point)
+        {
+            break;
+        }
+
+        // Replace the faces that can be seen from the new vertex with faces joining the edges
+        // of their horizon to it.
+        let index = polytope.len();
+        polytope.push(vertex);
+        let (visible, hidden): (Vec<Face>, Vec<Face>) = faces.into_iter().partition(|face| {
+            face.normal
+                .dot(vertex.point - polytope[face.vertices[0]].point)
+                > 0.0
+        });
+        horizon.clear();
+        for face in &visible {
+            let [a, b, c] = face.vertices;
+            for edge in [(a, b), (b, c), (c, a)] {
+                let shared = visible.iter().any(|other| {
+                    let [a, b, c] = other.vertices;
+                    [(a, b), (b, c), (c, a)].contains(&(edge.1, edge.0))
+                });
+                if !shared {
+                    horizon.push(edge);
+                }
+            }
+        }
+        faces = hidden;
+        faces.extend(
+            horizon
+                .iter()
+                .filter_map(|&(start, end)| Face::new(&polytope, [start, end, index])),
+        );
+    }
+
+    let (vertices, normal, distance) = closest?;
+    let [a, b, c] = vertices.map(|index| polytope[index]);
+    let [u, v, w] = barycentric(normal * distance, [a.point, b.point, c.point]);
+    Some(Penetration {
+        point_a: a.a * u + b.a * v + c.a * w,
+        point_b: a.b * u + b.b * v + c.b * w,
+        normal,
+        depth: distance,
+    })
+}
+
+/// Computes the barycentric coordinates of the projection of `point` on the plane of a triangle.
+fn barycentric(point: Vec3, [a, b, c]: [Vec3; 3]) -> [f32; 3] {
+    let (ab, ac, ap) = (b - a, c - a, point - a);
+    let (d00, d01, d11) = (ab.dot(ab), ab.dot(ac), ac.dot(ac));
+    let (d20, d21) = (ap.dot(ab), ap.dot(ac));
+    let denominator = d00 * d11 - d01 * d01;
+    if denominator == 0.0 {
+        return [1.0, 0.0, 0.0];
+    }
+    let v = (d11 * d20 - d01 * d21) / denominator;
+    let w = (d00 * d21 - d01 * d20) / denominator;
+    [1.0 - v - w, v, w]
+}
@@ -0,0 +1,276 @@
+//! The Gilbert–Johnson–Keerthi distance algorithm, generic over the dimension.
+
+use crate::{ops, Vec2, Vec3, VectorSpace};
+use alloc::vec::Vec;
+
+/// The maximum number of iterations of the GJK algorithm.
+const MAX_ITERATIONS: usize = 64;
+
+/// The relative tolerance on the distance at which the GJK algorithm stops.
+const RELATIVE_TOLERANCE: f32 = 1e-6;
+
+/// The squared distance to the origin, relative to the squared size of the simplex, below which
+/// the origin is considered to be inside of it.
+const INTERSECTION_TOLERANCE: f32 = 1e-10;
+
+/// A vector space in which shapes can be queried.
+pub(super) trait QueryVector: VectorSpace + PartialEq + 'static {
+    /// The unit vectors along the axes and their opposites.
+    const AXES: &'static [Self];
+
+    /// The dimension of the space, which is one less than the number of points of a full simplex.
+    const DIMENSION: usize;
+
+    /// Computes the dot product of `self` and `rhs`.
+    fn dot(self, rhs: Self) -> f32;
+}
+
+impl QueryVector for Vec2 {
+    const AXES: &'static [Self] = &[Vec2::X, Vec2::Y, Vec2::NEG_X, Vec2::NEG_Y];
+    const DIMENSION: usize = 2;
+
+    #[inline]
+    fn dot(self, rhs: Self) -> f32 {
+        Vec2::dot(self, rhs)
+    }
+}
+
+impl QueryVector for Vec3 {
+    const AXES: &'static [Self] = &[
+        Vec3::X,
+        Vec3::Y,
+        Vec3::Z,
+        Vec3::NEG_X,
+        Vec3::NEG_Y,
+        Vec3::NEG_Z,
+    ];
+    const DIMENSION: usize = 3;
+
+    #[inline]
+    fn dot(self, rhs: Self) -> f32 {
+        Vec3::dot(self, rhs)
+    }
+}
+
+/// A point of the Minkowski difference `A - B` of two shapes, along with the points of `A` and
+/// `B` it is made of.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct SupportPoint<V> {
+    /// The point of the Minkowski difference.
+    pub point: V,
+    /// The point of the first shape.
+    pub a: V,
+    /// The point of the second shape.
+    pub b: V,
+}
+
+impl<V: QueryVector> SupportPoint<V> {
+    pub fn new(a: V, b: V) -> Self {
+        Self { point: a - b, a, b }
+    }
+}
+
+/// The outcome of the GJK algorithm.
+pub(super) enum Gjk<V> {
+    /// The shapes are separated, with the given closest points on each of them.
+    Separated { point_a: V, point_b: V },
+    /// The shapes intersect at the given point, and the simplex contains the origin.
+    Intersecting {
+        simplex: Vec<SupportPoint<V>>,
+        point: V,
+    },
+}
+
+/// Runs the GJK algorithm on the Minkowski difference described by `support`, starting
+/// with the support point along `direction`.
+pub(super) fn gjk<V: QueryVector>(support: &impl Fn(V) -> SupportPoint<V>, direction: V) -> Gjk<V> {
+    let direction = if direction == V::ZERO {
+        V::AXES[0]
+    } else {
+        direction
+    };
+    let mut simplex = Vec::with_capacity(V::DIMENSION + 1);
+    simplex.push(support(direction));
+    let mut previous_distance_squared = f32::INFINITY;
+    let mut weights = Vec::new();
+
+    for _ in 0..MAX_ITERATIONS {
+        let closest = closest_on_simplex(&mut simplex, &mut weights);
+        let distance_squared = closest.dot(closest);
+        let scale = simplex
+            .iter()
+            .map(|vertex| vertex.point.dot(vertex.point))
+            .fold(0.0_f32, f32::max);
+        if distance_squared <= INTERSECTION_TOLERANCE * scale || simplex.len() > V::DIMENSION {
+            let point = simplex
+                .iter()
+                .zip(&weights)
+                .fold(V::ZERO, |point, (vertex, &weight)| {
+                    point + vertex.a * weight
+                });
+            return Gjk::Intersecting { simplex, point };
+        }
+
+        // Stop when the new support point doesn't bring the simplex closer to the origin.
+        let vertex = support(-closest);
+        let progress = distance_squared - closest.dot(vertex.point);
+        if progress <= RELATIVE_TOLERANCE * distance_squared
+            || distance_squared >= previous_distance_squared
+            || simplex.iter().any(|other| other.point == vertex.point)
+        {
+            break;
+        }
+        previous_distance_squared = distance_squared;
+        simplex.push(vertex);
+    }
+
+    let (point_a, point_b) = simplex.iter().zip(&weights).fold(
+        (V::ZERO, V::ZERO),
+        |(point_a, point_b), (vertex, &weight)| {
+            (point_a + vertex.a * weight, point_b + vertex.b * weight)
+        },
+    );
+    Gjk::Separated { point_a, point_b }
+}
+
+/// Finds the point of the simplex closest to the origin, and reduces the simplex to the smallest
+/// face that contains it.
+///
+/// The barycentric weights of the point on the vertices of the reduced simplex are written to
+/// `weights`.
+fn closest_on_simplex<V: QueryVector>(
+    simplex: &mut Vec<SupportPoint<V>>,
+    weights: &mut Vec<f32>,
+) -> V {
+    // Look for the closest point to the origin on the affine hull of each face of the simplex,
+    // keeping the closest one that lies inside of its face.
+    let mut best: Option<(u32, [f32; 4], f32)> = None;
+    for mask in 1..1_u32 << simplex.len() {
+        let face: Vec<usize> = (0..simplex.len())
+            .filter(|index| mask & (1 << index) != 0)
+            .collect();
+        let Some(face_weights) = affine_closest_weights(simplex, &face) else {
+            continue;
+        };
+        if face_weights[..face.len()]
+            .iter()
+            .any(|&weight| weight <= 0.0)
+        {
+            continue;
+        }
+        let point = face
+            .iter()
+            .zip(face_weights)
+            .fold(V::ZERO, |point, (&index, weight)| {
+                point + simplex[index].point * weight
+            });
+        let distance_squared = point.dot(point);
+        if best.is_none_or(|(_, _, best)| distance_squared < best) {
+            best = Some((mask, face_weights, distance_squared));
+        }
+    }
+
+    // A single vertex always has a valid weight, so `best` is only empty for an empty simplex.
+    let (mask, face_weights, _) = best.unwrap_or((1, [1.0, 0.0, 0.0, 0.0], 0.0));
+    let mut index = 0;
+    simplex.retain(|_| {
+        index += 1;
+        mask & (1 << (index - 1)) != 0
+    });
+    weights.clear();
+    weights.extend_from_slice(&face_weights[..simplex.len()]);
+    simplex
+        .iter()
+        .zip(weights.iter())
+        .fold(V::ZERO, |point, (vertex, &weight)| {
+            point + vertex.point * weight
+        })
+}
+
+/// Computes the barycentric weights of the point closest to the origin on the affine hull of the
+/// `face` of the `simplex`, or `None` if the face is degenerate.
+fn affine_closest_weights<V: QueryVector>(
+    simplex: &[SupportPoint<V>],
+    face: &[usize],
+) -> Option<[f32; 4]> {
+    let origin = simplex[face[0]].point;
+    let edges: Vec<V> = face[1..]
+        .iter()
+        .map(|&index| simplex[index].point - origin)
+        .collect();
+
+    // The closest point `origin + Σ μ_i e_i` is orthogonal to every edge `e_j`, which gives the
+    // linear system `Σ μ_i (e_i · e_j) = -origin · e_j`.
+    let mut matrix = [[0.0; 4]; 3];
+    for (row, &edge) in matrix.iter_mut().zip(&edges) {
+        for (entry, &other) in row.iter_mut().zip(&edges) {
+            *entry = edge.dot(other);
+        }
+        row[3] = -origin.dot(edge);
+    }
+    let mu = solve(&mut matrix[..edges.len()])?;
+
+    let mut weights = [0.0; 4];
+    weights[0] = 1.0 - mu[..edges.len()].iter().sum::<f32>();
+    weights[1..face.len()].copy_from_slice(&mu[..edges.len()]);
+    Some(weights)
+}
+
+/// Solves a linear system of up to three equations given as an augmented matrix by Gaussian
+/// elimination, or returns `None` if it is singular.
+fn solve(matrix: &mut [[f32; 4]]) -> Option<[f32; 3]> {
+    let size = matrix.len();
+    let scale = matrix
+        .iter()
+        .enumerate()
+        .map(|(index, row)| ops::abs(row[index]))
+        .fold(0.0_f32, f32::max);
+    for column in 0..size {
+        let pivot = (column..size)
+            .max_by(|&a, &b| ops::abs(matrix[a][column]).total_cmp(&ops::abs(matrix[b][column])))?;
+        if ops::abs(matrix[pivot][column]) <= 1e-6 * scale {
+            return None;
+        }
+        matrix.swap(column, pivot);
+        for row in column + 1..size {
+            let factor = matrix[row][column] / matrix[column][column];
+            for entry in column..4 {
+                matrix[row][entry] -= factor * matrix[column][entry];
+            }
+        }
+    }
+
+    let mut solution = [0.0; 3];
+    for row in (0..size).rev() {
+        let known: f32 = (row + 1..size)
+            .map(|column| matrix[row][column] * solution[column])
+            .sum();
+        solution[row] = (matrix[row][3] - known) / matrix[row][row];
+    }
+    Some(solution)
+}
+
+/// Extends a simplex that contains the origin to a full simplex, by adding support points along
+/// the axes that don't lie in the affine hull of the simplex.
+///
+/// Returns `false` if the Minkowski difference is flat, so that the simplex can't be extended.
+pub(super) fn complete_simplex<V: QueryVector>(
+    support: &impl Fn(V) -> SupportPoint<V>,
+    simplex: &mut Vec<SupportPoint<V>>,
+) -> bool {
+    while simplex.len() <= V::DIMENSION {
+        let extended = V::AXES.iter().find_map(|&axis| {
+            let vertex = support(axis);
+            simplex.push(vertex);
+            let indices: Vec<usize> = (0..simplex.len()).collect();
+            let independent = affine_closest_weights(simplex, &indices).is_some();
+            simplex.pop();
+            independent.then_some(vertex)
+        });
+        match extended {
+            Some(vertex) => simplex.push(vertex),
+            None => return false,
+        }
+    }
+    true
+}
@@ -0,0 +1,512 @@
+//! Intersection, distance, contact and shape cast queries between convex shapes.
+//!
+//! The queries work on any pair of shapes implementing [`SupportMap2d`] or [`SupportMap3d`],
+//! such as [`Circle`](crate::primitives::Circle) and [`BoxedPolygon`](crate::primitives::BoxedPolygon),
+//! or [`Capsule3d`](crate::primitives::Capsule3d) and [`Cuboid`](crate::primitives::Cuboid),
+//! each placed in the world with an isometry. They are based on the Gilbert–Johnson–Keerthi
+//! (GJK) algorithm for separated shapes, and on the expanding polytope algorithm (EPA) for the
+//! penetration of intersecting shapes.
+//!
+//! ```
+//! # use bevy_math::{prelude::*, query::*};
+//! let cuboid = Cuboid::new(2.0, 2.0, 2.0);
+//! let capsule = Capsule3d::new(0.5, 2.0);
+//!
+//! let contact = contact_3d(&cuboid, Vec3::ZERO, &capsule, Vec3::new(1.25, 0.0, 0.0)).unwrap();
+//! assert!((contact.depth - 0.25).abs() < 1e-4);
+//! assert!(contact.normal.dot(Vec3::X) > 0.999);
+//! ```
+
+mod epa;
+mod gjk;
+mod support;
+
+pub use support::*;
+
+use crate::{Dir2, Dir3, Isometry2d, Isometry3d, Vec2, Vec3, Vec3A};
+use epa::{epa_2d, epa_3d, Penetration};
+use gjk::{complete_simplex, gjk, Gjk, SupportPoint};
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::Reflect;
+
+/// The maximum number of steps taken by a shape cast.
+const MAX_CAST_ITERATIONS: usize = 64;
+
+/// The distance between the shapes at which a shape cast considers them to be touching.
+const CAST_TOLERANCE: f32 = 1e-4;
+
+/// The closest points of two separated 2D shapes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "bevy_reflect", derive(Reflect), reflect(Debug, PartialEq))]
+pub struct ClosestPoints2d {
+    /// The point of the first shape closest to the second one.
+    pub point_a: Vec2,
+    /// The point of the second shape closest to the first one.
+    pub point_b: Vec2,
+    /// The distance between the shapes.
+    pub distance: f32,
+}
+
+/// The contact between two intersecting 2D shapes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "bevy_reflect", derive(Reflect), reflect(Debug, PartialEq))]
+pub struct Contact2d {
+    /// The deepest point of the first shape inside of the second one.
+    pub point_a: Vec2,
+    /// The deepest point of the second shape inside of the first one.
+    pub point_b: Vec2,
+    /// The direction from the first shape toward the second one along which they penetrate the
+    /// least. Moving the second shape by `normal * depth` separates the shapes.
+    pub normal: Dir2,
+    /// The penetration depth of the shapes.
+    pub depth: f32,
+}
+
+/// The first contact of a 2D shape moving along a direction with another shape.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "bevy_reflect", derive(Reflect), reflect(Debug, PartialEq))]
+pub struct ShapeCastHit2d {
+    /// The distance traveled by the moving shape before the contact.
+    pub distance: f32,
+    /// The point of contact on the moving shape, at the position of the contact.
+    pub point_a: Vec2,
+    /// The point of contact on the other shape.
+    pub point_b: Vec2,
+    /// The direction from the moving shape toward the other one at the point of contact.
+    pub normal: Dir2,
+}
+
+/// The closest points of two separated 3D shapes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "bevy_reflect", derive(Reflect), reflect(Debug, PartialEq))]
+pub struct ClosestPoints3d {
+    /// The point of the first shape closest to the second one.
+    pub point_a: Vec3,
+    /// The point of the second shape closest to the first one.
+    pub point_b: Vec3,
+    /// The distance between the shapes.
+    pub distance: f32,
+}
+
+/// The contact between two intersecting 3D shapes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "bevy_reflect", derive(Reflect), reflect(Debug, PartialEq))]
+pub struct Contact3d {
+    /// The deepest point of the first shape inside of the second one.
+    pub point_a: Vec3,
+    /// The deepest point of the second shape inside of the first one.
+    pub point_b: Vec3,
+    /// The direction from the first shape toward the second one along which they penetrate the
+    /// least. Moving the second shape by `normal * depth` separates the shapes.
+    pub normal: Dir3,
+    /// The penetration depth of the shapes.
+    pub depth: f32,
+}
+
+/// The first contact of a 3D shape moving along a direction with another shape.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "bevy_reflect", derive(Reflect), reflect(Debug, PartialEq))]
+pub struct ShapeCastHit3d {
+    /// The distance traveled by the moving shape before the contact.
+    pub distance: f32,
+    /// The point of contact on the moving shape, at the position of the contact.
+    pub point_a: Vec3,
+    /// The point of contact on the other shape.
+    pub point_b: Vec3,
+    /// The direction from the moving shape toward the other one at the point of contact.
+    pub normal: Dir3,
+}
+
+/// Returns the support function of the Minkowski difference of two placed 2D shapes.
+fn minkowski_support_2d<'a>(
+    shape_a: &'a (impl SupportMap2d + ?Sized),
+    isometry_a: Isometry2d,
+    shape_b: &'a (impl SupportMap2d + ?Sized),
+    isometry_b: Isometry2d,
+) -> impl Fn(Vec2) -> SupportPoint<Vec2> + 'a {
+    move |direction| {
+        SupportPoint::new(
+            isometry_a * shape_a.support(isometry_a.rotation.inverse() * direction),
+            isometry_b * shape_b.support(isometry_b.rotation.inverse() * -direction),
+        )
+    }
+}
+
+/// Returns the support function of the Minkowski difference of two placed 3D shapes.
+fn minkowski_support_3d<'a>(
+    shape_a: &'a (impl SupportMap3d + ?Sized),
+    isometry_a: Isometry3d,
+    shape_b: &'a (impl SupportMap3d + ?Sized),
+    isometry_b: Isometry3d,
+) -> impl Fn(Vec3) -> SupportPoint<Vec3> + 'a {
+    move |direction| {
+        SupportPoint::new(
+            isometry_a * shape_a.support(isometry_a.rotation.inverse() * direction),
+            isometry_b * shape_b.support(isometry_b.rotation.inverse() * -direction),
+        )
+    }
+}
+
+/// Checks if two 2D shapes intersect.
+pub fn intersects_2d(
+    shape_a: &(impl SupportMap2d + ?Sized),
+    isometry_a: impl Into<Isometry2d>,
+    shape_b: &(impl SupportMap2d + ?Sized),
+    isometry_b: impl Into<Isometry2d>,
+) -> bool {
+    closest_points_2d(shape_a, isometry_a, shape_b, isometry_b).is_none()
+}
+
+/// Computes the closest points of two 2D shapes, or returns `None` if they intersect.
+pub fn closest_points_2d(
+    shape_a: &(impl SupportMap2d + ?Sized),
+    isometry_a: impl Into<Isometry2d>,
+    shape_b: &(impl SupportMap2d + ?Sized),
+    isometry_b: impl Into<Isometry2d>,
+) -> Option<ClosestPoints2d> {
+    let (isometry_a, isometry_b) = (isometry_a.into(), isometry_b.into());
+    let support = minkowski_support_2d(shape_a, isometry_a, shape_b, isometry_b);
+    match gjk(&support, isometry_a.translation - isometry_b.translation) {
+        Gjk::Separated { point_a, point_b } => Some(ClosestPoints2d {
+            point_a,
+            point_b,
+            distance: point_a.distance(point_b),
+        }),
+        Gjk::Intersecting { .. } => None,
+    }
+}
+
+/// Computes the contact between two 2D shapes, or returns `None` if they are separated.
+///
+/// If the shapes only touch, or if they are both flat, the depth is zero.
+pub fn contact_2d(
+    shape_a: &(impl SupportMap2d + ?Sized),
+    isometry_a: impl Into<Isometry2d>,
+    shape_b: &(impl SupportMap2d + ?Sized),
+    isometry_b: impl Into<Isometry2d>,
+) -> Option<Contact2d> {
+    let (isometry_a, isometry_b) = (isometry_a.into(), isometry_b.into());
+    let offset = isometry_b.translation - isometry_a.translation;
+    let support = minkowski_support_2d(shape_a, isometry_a, shape_b, isometry_b);
+    let Gjk::Intersecting { mut simplex, point } = gjk(&support, -offset) else {
+        return None;
+    };
+    let penetration = complete_simplex(&support, &mut simplex)
+        .then(|| epa_2d(&support, &simplex))
+        .flatten();
+    Some(match penetration {
+        Some(Penetration {
+            point_a,
+            point_b,
+            normal,
+            depth,
+        }) => Contact2d {
+            point_a,
+            point_b,
+            normal: Dir2::new(normal).unwrap_or(Dir2::Y),
+            depth,
+        },
+        None => Contact2d {
+            point_a: point,
+            point_b: point,
+            normal: Dir2::new(offset).unwrap_or(Dir2::Y),
+            depth: 0.0,
+        },
+    })
+}
+
+/// Casts the first 2D shape along the given `direction`, and returns its first contact with the
+/// second shape within `max_distance`.
+///
+/// If the shapes already intersect, the contact is at a distance of zero.
+pub fn shape_cast_2d(
+    shape_a: &(impl SupportMap2d + ?Sized),
+    isometry_a: impl Into<Isometry2d>,
+    direction: Dir2,
+    max_distance: f32,
+    shape_b: &(impl SupportMap2d + ?Sized),
+    isometry_b: impl Into<Isometry2d>,
+) -> Option<ShapeCastHit2d> {
+    let (isometry_a, isometry_b) = (isometry_a.into(), isometry_b.into());
+
+    // Advance the shape by the distance between the shapes projected on the direction, which
+    // can't make it go through the other shape.
+    let mut distance = 0.0;
+    for _ in 0..MAX_CAST_ITERATIONS {
+        let moved = Isometry2d {
+            translation: isometry_a.translation + direction * distance,
+            ..isometry_a
+        };
+        let Some(closest) = closest_points_2d(shape_a, moved, shape_b, isometry_b) else {
+            let contact = contact_2d(shape_a, moved, shape_b, isometry_b)?;
+            return Some(ShapeCastHit2d {
+                distance,
+                point_a: contact.point_a,
+                point_b: contact.point_b,
+                normal: contact.normal,
+            });
+        };
+        let normal = Dir2::new(closest.point_b - closest.point_a).unwrap_or(direction);
+        if closest.distance <= CAST_TOLERANCE {
+            return Some(ShapeCastHit2d {
+                distance,
+                point_a: closest.point_a,
+                point_b: closest.point_b,
+                normal,
+            });
+        }
+        let approach = direction.dot(*normal);
+        if approach <= 0.0 {
+            return None;
+        }
+        distance += closest.distance / approach;
+        if distance > max_distance {
+            return None;
+        }
+    }
+    None
+}
+
+/// Checks if two 3D shapes intersect.
+pub fn intersects_3d(
+    shape_a: &(impl SupportMap3d + ?Sized),
+    isometry_a: impl Into<Isometry3d>,
+    shape_b: &(impl SupportMap3d + ?Sized),
+    isometry_b: impl Into<Isometry3d>,
+) -> bool {
+    closest_points_3d(shape_a, isometry_a, shape_b, isometry_b).is_none()
+}
+
+/// Computes the closest points of two 3D shapes, or returns `None` if they intersect.
+pub fn closest_points_3d(
+    shape_a: &(impl SupportMap3d + ?Sized),
+    isometry_a: impl Into<Isometry3d>,
+    shape_b: &(impl SupportMap3d + ?Sized),
+    isometry_b: impl Into<Isometry3d>,
+) -> Option<ClosestPoints3d> {
+    let (isometry_a, isometry_b) = (isometry_a.into(), isometry_b.into());
+    let support = minkowski_support_3d(shape_a, isometry_a, shape_b, isometry_b);
+    match gjk(
+        &support,
+        (isometry_a.translation - isometry_b.translation).into(),
+    ) {
+        Gjk::Separated { point_a, point_b } => Some(ClosestPoints3d {
+            point_a,
+            point_b,
+            distance: point_a.distance(point_b),
+        }),
+        Gjk::Intersecting { .. } => None,
+    }
+}
+
+/// Computes the contact between two 3D shapes, or returns `None` if they are separated.
+///
+/// If the shapes only touch, or if they are both flat, the depth is zero.
+pub fn contact_3d(
+    shape_a: &(impl SupportMap3d + ?Sized),
+    isometry_a: impl Into<Isometry3d>,
+    shape_b: &(impl SupportMap3d + ?Sized),
+    isometry_b: impl Into<Isometry3d>,
+) -> Option<Contact3d> {
+    let (isometry_a, isometry_b) = (isometry_a.into(), isometry_b.into());
+    let offset = Vec3::from(isometry_b.translation - isometry_a.translation);
+    let support = minkowski_support_3d(shape_a, isometry_a, shape_b, isometry_b);
+    let Gjk::Intersecting { mut simplex, point } = gjk(&support, -offset) else {
+        return None;
+    };
+    let penetration = complete_simplex(&support, &mut simplex)
+        .then(|| epa_3d(&support, &simplex))
+        .flatten();
+    Some(match penetration {
+        Some(Penetration {
+            point_a,
+            point_b,
+            normal,
+            depth,
+        }) => Contact3d {
+            point_a,
+            point_b,
+            normal: Dir3::new(normal).unwrap_or(Dir3::Y),
+            depth,
+        },
+        None => Contact3d {
+            point_a: point,
+            point_b: point,
+            normal: Dir3::new(offset).unwrap_or(Dir3::Y),
+            depth: 0.0,
+        },
+    })
+}
+
+/// Casts the first 3D shape along the given `direction`, and returns its first contact with the
+/// second shape within `max_distance`.
+///
+/// If the shapes already intersect, the contact is at a distance of zero.
+pub fn shape_cast_3d(
+    shape_a: &(impl SupportMap3d + ?Sized),
+    isometry_a: impl Into<Isometry3d>,
+    direction: Dir3,
+    max_distance: f32,
+    shape_b: &(impl SupportMap3d + ?Sized),
+    isometry_b: impl Into<Isometry3d>,
+) -> Option<ShapeCastHit3d> {
+    let (isometry_a, isometry_b) = (isometry_a.into(), isometry_b.into());
+
+    // Advance the shape by the distance between the shapes projected on the direction, which
+    // can't make it go through the other shape.
+    let mut distance = 0.0;
+    for _ in 0..MAX_CAST_ITERATIONS {
+        let moved = Isometry3d {
+            translation: isometry_a.translation + Vec3A::from(direction * distance),
+            ..isometry_a
+        };
+        let Some(closest) = closest_points_3d(shape_a, moved, shape_b, isometry_b) else {
+            let contact = contact_3d(shape_a, moved, shape_b, isometry_b)?;
+            return Some(ShapeCastHit3d {
+                distance,
+                point_a: contact.point_a,
+                point_b: contact.point_b,
+                normal: contact.normal,
+            });
+        };
+        let normal = Dir3::new(closest.point_b - closest.point_a).unwrap_or(direction);
+        if closest.distance <= CAST_TOLERANCE {
+            return Some(ShapeCastHit3d {
+                distance,
+                point_a: closest.point_a,
+                point_b: closest.point_b,
+                normal,
+            });
+        }
+        let approach = direction.dot(*normal);
+        if approach <= 0.0 {
+            return None;
+        }
+        distance += closest.distance / approach;
+        if distance > max_distance {
+            return None;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ops,
+        primitives::{BoxedPolygon, Capsule3d, Circle, Cuboid, Rectangle, Sphere},
+        Quat, Rot2,
+    };
+    use approx::assert_abs_diff_eq;
+    use core::f32::consts::FRAC_PI_2;
+
+    #[test]
+    fn closest_points() {
+        let sphere = Sphere::new(1.0);
+        let closest =
+            closest_points_3d(&sphere, Vec3::ZERO, &sphere, Vec3::new(3.0, 0.0, 0.0)).unwrap();
+        assert_abs_diff_eq!(closest.distance, 1.0, epsilon = 1e-4);
+        assert_abs_diff_eq!(closest.point_a, Vec3::X, epsilon = 1e-3);
+        assert_abs_diff_eq!(closest.point_b, Vec3::new(2.0, 0.0, 0.0), epsilon = 1e-3);
+
+        let capsule = Capsule3d::new(0.5, 2.0);
+        let cuboid = Cuboid::new(2.0, 2.0, 2.0);
+        let isometry = Isometry3d::new(Vec3::new(0.0, 2.5, 0.0), Quat::from_rotation_z(FRAC_PI_2));
+        let closest = closest_points_3d(&cuboid, Vec3::ZERO, &capsule, isometry).unwrap();
+        assert_abs_diff_eq!(closest.distance, 1.0, epsilon = 1e-4);
+        assert_abs_diff_eq!(closest.point_a.y, 1.0, epsilon = 1e-4);
+        assert_abs_diff_eq!(closest.point_b.y, 2.0, epsilon = 1e-4);
+
+        let circle = Circle::new(1.0);
+        let square = BoxedPolygon::new([
+            Vec2::new(-1.0, -1.0),
+            Vec2::new(1.0, -1.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(-1.0, 1.0),
+        ]);
+        let closest = closest_points_2d(&circle, Vec2::ZERO, &square, Vec2::new(2.5, 0.5)).unwrap();
+        assert_abs_diff_eq!(closest.distance, 0.5, epsilon = 1e-4);
+        assert_abs_diff_eq!(closest.point_b, Vec2::new(1.5, 0.0), epsilon = 1e-3);
+        assert!(intersects_2d(
+            &circle,
+            Vec2::ZERO,
+            &square,
+            Isometry2d::new(Vec2::new(2.3, 0.0), Rot2::degrees(45.0))
+        ));
+    }
+
+    #[test]
+    fn contacts() {
+        let capsule = Capsule3d::new(0.5, 2.0);
+        let cuboid = Cuboid::new(2.0, 2.0, 2.0);
+        let contact = contact_3d(&cuboid, Vec3::ZERO, &capsule, Vec3::new(1.25, 0.3, 0.0)).unwrap();
+        assert_abs_diff_eq!(contact.depth, 0.25, epsilon = 1e-4);
+        assert_abs_diff_eq!(*contact.normal, Vec3::X, epsilon = 1e-3);
+        assert_abs_diff_eq!(contact.point_a.x, 1.0, epsilon = 1e-4);
+        assert_abs_diff_eq!(contact.point_b.x, 0.75, epsilon = 1e-4);
+        assert!(contact_3d(&cuboid, Vec3::ZERO, &capsule, Vec3::new(2.0, 0.0, 0.0)).is_none());
+
+        let circle = Circle::new(1.0);
+        let rectangle = Rectangle::new(2.0, 4.0);
+        let contact = contact_2d(&rectangle, Vec2::ZERO, &circle, Vec2::new(0.0, -2.5)).unwrap();
+        assert_abs_diff_eq!(contact.depth, 0.5, epsilon = 1e-4);
+        assert_abs_diff_eq!(*contact.normal, Vec2::NEG_Y, epsilon = 1e-3);
+        assert_abs_diff_eq!(contact.point_b, Vec2::new(0.0, -1.5), epsilon = 1e-3);
+    }
+
+    #[test]
+    fn shape_casts() {
+        let sphere = Sphere::new(1.0);
+        let cuboid = Cuboid::new(2.0, 2.0, 2.0);
+        let hit = shape_cast_3d(
+            &sphere,
+            Vec3::ZERO,
+            Dir3::X,
+            10.0,
+            &cuboid,
+            Vec3::new(5.0, 0.5, 0.0),
+        )
+        .unwrap();
+        assert_abs_diff_eq!(hit.distance, 3.0, epsilon = 1e-3);
+        assert_abs_diff_eq!(*hit.normal, Vec3::X, epsilon = 1e-2);
+        assert_abs_diff_eq!(hit.point_a, Vec3::new(4.0, 0.0, 0.0), epsilon = 1e-2);
+        assert!(shape_cast_3d(
+            &sphere,
+            Vec3::ZERO,
+            Dir3::Y,
+            10.0,
+            &cuboid,
+            Vec3::new(5.0, 0.0, 0.0)
+        )
+        .is_none());
+        assert!(shape_cast_3d(
+            &sphere,
+            Vec3::ZERO,
+            Dir3::X,
+            2.0,
+            &cuboid,
+            Vec3::new(5.0, 0.0, 0.0)
+        )
+        .is_none());
+
+        let circle = Circle::new(0.5);
+        let rectangle = Rectangle::new(2.0, 2.0);
+        let hit = shape_cast_2d(
+            &circle,
+            Vec2::new(-3.0, 3.0),
+            Dir2::new(Vec2::new(1.0, -1.0)).unwrap(),
+            10.0,
+            &rectangle,
+            Vec2::ZERO,
+        )
+        .unwrap();
+        let corner_distance = ops::sqrt(8.0) - 0.5;
+        assert_abs_diff_eq!(hit.distance, corner_distance, epsilon = 1e-3);
+        assert_abs_diff_eq!(hit.point_b, Vec2::new(-1.0, 1.0), epsilon = 1e-2);
+
+        let hit = shape_cast_2d(&circle, Vec2::ZERO, Dir2::X, 1.0, &rectangle, Vec2::ZERO).unwrap();
+        assert_eq!(hit.distance, 0.0);
+    }
+}
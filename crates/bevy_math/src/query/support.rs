@@ -0,0 +1,257 @@
+use crate::{
+    ops,
+    primitives::{
+        BoxedPolygon, Capsule2d, Capsule3d, Circle, Cone, ConicalFrustum, ConvexHull3d,
+        ConvexPolygon, Cuboid, Cylinder, Ellipse, Polygon, Rectangle, RegularPolygon, Rhombus,
+        Segment2d, Segment3d, Sphere, Tetrahedron, Triangle2d, Triangle3d,
+    },
+    Vec2, Vec3,
+};
+
+/// A convex 2D shape described by its support function, which can be used with the queries in
+/// the [`query`](super) module.
+///
+/// The support function returns the point of the shape that is farthest along a direction.
+/// Non-convex shapes, such as [`Polygon`], are treated as their convex hull.
+pub trait SupportMap2d {
+    /// Returns the point of the shape that is farthest along the given `direction`, in the local
+    /// space of the shape.
+    ///
+    /// The direction doesn't have to be normalized. If it is zero, any point of the shape
+    /// may be returned.
+    fn support(&self, direction: Vec2) -> Vec2;
+}
+
+/// A convex 3D shape described by its support function, which can be used with the queries in
+/// the [`query`](super) module.
+///
+/// The support function returns the point of the shape that is farthest along a direction.
+/// Non-convex shapes are treated as their convex hull.
+pub trait SupportMap3d {
+    /// Returns the point of the shape that is farthest along the given `direction`, in the local
+    /// space of the shape.
+    ///
+    /// The direction doesn't have to be normalized. If it is zero, any point of the shape
+    /// may be returned.
+    fn support(&self, direction: Vec3) -> Vec3;
+}
+
+/// Returns the point among `points` that is farthest along `direction`.
+fn farthest_point<V: Copy>(
+    points: impl IntoIterator<Item = V>,
+    direction: impl Fn(V) -> f32,
+) -> Option<V> {
+    points
+        .into_iter()
+        .map(|point| (point, direction(point)))
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(point, _)| point)
+}
+
+impl SupportMap2d for Circle {
+    fn support(&self, direction: Vec2) -> Vec2 {
+        direction.normalize_or_zero() * self.radius
+    }
+}
+
+impl SupportMap2d for Ellipse {
+    fn support(&self, direction: Vec2) -> Vec2 {
+        // The point where the normal of the ellipse is parallel to the direction.
+        let scaled = self.half_size * self.half_size * direction;
+        let length = ops::sqrt(scaled.dot(direction));
+        if length > 0.0 {
+            scaled / length
+        } else {
+            Vec2::new(self.half_size.x, 0.0)
+        }
+    }
+}
+
+impl SupportMap2d for Rectangle {
+    fn support(&self, direction: Vec2) -> Vec2 {
+        self.half_size.copysign(direction)
+    }
+}
+
+impl SupportMap2d for Rhombus {
+    fn support(&self, direction: Vec2) -> Vec2 {
+        let Vec2 { x, y } = self.half_diagonals;
+        if ops::abs(direction.x) * x >= ops::abs(direction.y) * y {
+            Vec2::new(ops::copysign(x, direction.x), 0.0)
+        } else {
+            Vec2::new(0.0, ops::copysign(y, direction.y))
+        }
+    }
+}
+
+impl SupportMap2d for Capsule2d {
+    fn support(&self, direction: Vec2) -> Vec2 {
+        let center = Vec2::new(0.0, ops::copysign(self.half_length, direction.y));
+        center + direction.normalize_or_zero() * self.radius
+    }
+}
+
+impl SupportMap2d for Segment2d {
+    fn support(&self, direction: Vec2) -> Vec2 {
+        let [a, b] = self.vertices;
+        if direction.dot(b - a) > 0.0 {
+            b
+        } else {
+            a
+        }
+    }
+}
+
+impl SupportMap2d for Triangle2d {
+    fn support(&self, direction: Vec2) -> Vec2 {
+        farthest_point(self.vertices, |point| point.dot(direction)).unwrap_or(Vec2::ZERO)
+    }
+}
+
+impl SupportMap2d for RegularPolygon {
+    fn support(&self, direction: Vec2) -> Vec2 {
+        farthest_point(self.vertices(0.0), |point| point.dot(direction)).unwrap_or(Vec2::ZERO)
+    }
+}
+
+impl<const N: usize> SupportMap2d for ConvexPolygon<N> {
+    fn support(&self, direction: Vec2) -> Vec2 {
+        farthest_point(*self.vertices(), |point| point.dot(direction)).unwrap_or(Vec2::ZERO)
+    }
+}
+
+impl<const N: usize> SupportMap2d for Polygon<N> {
+    fn support(&self, direction: Vec2) -> Vec2 {
+        farthest_point(self.vertices, |point| point.dot(direction)).unwrap_or(Vec2::ZERO)
+    }
+}
+
+impl SupportMap2d for BoxedPolygon {
+    fn support(&self, direction: Vec2) -> Vec2 {
+        farthest_point(self.vertices.iter().copied(), |point| point.dot(direction))
+            .unwrap_or(Vec2::ZERO)
+    }
+}
+
+impl SupportMap3d for Sphere {
+    fn support(&self, direction: Vec3) -> Vec3 {
+        direction.normalize_or_zero() * self.radius
+    }
+}
+
+impl SupportMap3d for Cuboid {
+    fn support(&self, direction: Vec3) -> Vec3 {
+        self.half_size.copysign(direction)
+    }
+}
+
+impl SupportMap3d for Capsule3d {
+    fn support(&self, direction: Vec3) -> Vec3 {
+        let center = Vec3::new(0.0, ops::copysign(self.half_length, direction.y), 0.0);
+        center + direction.normalize_or_zero() * self.radius
+    }
+}
+
+/// Returns the point farthest along `direction` on the circle of the given `radius` around the
+/// Y axis, at the given `height`.
+fn rim_support(direction: Vec3, radius: f32, height: f32) -> Vec3 {
+    let radial = Vec2::new(direction.x, direction.z).normalize_or_zero() * radius;
+    Vec3::new(radial.x, height, radial.y)
+}
+
+impl SupportMap3d for Cylinder {
+    fn support(&self, direction: Vec3) -> Vec3 {
+        rim_support(
+            direction,
+            self.radius,
+            ops::copysign(self.half_height, direction.y),
+        )
+    }
+}
+
+impl SupportMap3d for Cone {
+    fn support(&self, direction: Vec3) -> Vec3 {
+        let tip = Vec3::new(0.0, 0.5 * self.height, 0.0);
+        let base = rim_support(direction, self.radius, -0.5 * self.height);
+        if tip.dot(direction) >= base.dot(direction) {
+            tip
+        } else {
+            base
+        }
+    }
+}
+
+impl SupportMap3d for ConicalFrustum {
+    fn support(&self, direction: Vec3) -> Vec3 {
+        let top = rim_support(direction, self.radius_top, 0.5 * self.height);
+        let bottom = rim_support(direction, self.radius_bottom, -0.5 * self.height);
+        if top.dot(direction) >= bottom.dot(direction) {
+            top
+        } else {
+            bottom
+        }
+    }
+}
+
+impl SupportMap3d for Segment3d {
+    fn support(&self, direction: Vec3) -> Vec3 {
+        let [a, b] = self.vertices;
+        if direction.dot(b - a) > 0.0 {
+            b
+        } else {
+            a
+        }
+    }
+}
+
+impl SupportMap3d for Triangle3d {
+    fn support(&self, direction: Vec3) -> Vec3 {
+        farthest_point(self.vertices, |point| point.dot(direction)).unwrap_or(Vec3::ZERO)
+    }
+}
+
+impl SupportMap3d for Tetrahedron {
+    fn support(&self, direction: Vec3) -> Vec3 {
+        farthest_point(self.vertices, |point| point.dot(direction)).unwrap_or(Vec3::ZERO)
+    }
+}
+
+impl SupportMap3d for ConvexHull3d {
+    fn support(&self, direction: Vec3) -> Vec3 {
+        farthest_point(self.vertices().iter().copied(), |point| {
+            point.dot(direction)
+        })
+        .unwrap_or(Vec3::ZERO)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn support_points() {
+        let ellipse = Ellipse::new(2.0, 1.0);
+        assert_abs_diff_eq!(ellipse.support(Vec2::X), Vec2::new(2.0, 0.0));
+        assert_abs_diff_eq!(ellipse.support(-Vec2::Y), Vec2::new(0.0, -1.0));
+
+        let capsule = Capsule3d::new(0.5, 2.0);
+        assert_abs_diff_eq!(capsule.support(Vec3::Y), Vec3::new(0.0, 1.5, 0.0));
+        assert_abs_diff_eq!(
+            capsule.support(Vec3::new(1.0, -1.0, 0.0)),
+            Vec3::new(0.0, -1.0, 0.0) + Vec3::new(1.0, -1.0, 0.0).normalize() * 0.5,
+            epsilon = 1e-6
+        );
+
+        let cone = Cone::new(1.0, 2.0);
+        assert_eq!(cone.support(Vec3::Y), Vec3::new(0.0, 1.0, 0.0));
+        assert_eq!(cone.support(-Vec3::Y + Vec3::X), Vec3::new(1.0, -1.0, 0.0));
+
+        let cuboid = Cuboid::new(2.0, 4.0, 6.0);
+        assert_eq!(
+            cuboid.support(Vec3::new(1.0, -1.0, 1.0)),
+            Vec3::new(1.0, -2.0, 3.0)
+        );
+    }
+}
@@ -7,8 +7,9 @@ use super::{Extrudable, MeshBuilder, Meshable};
 use bevy_math::{
     ops,
     primitives::{
-        Annulus, Capsule2d, Circle, CircularSector, CircularSegment, ConvexPolygon, Ellipse,
-        Rectangle, RegularPolygon, Rhombus, Triangle2d, Triangle3d, WindingOrder,
+        Annulus, BoxedPolygon, BoxedPolygonWithHoles, Capsule2d, Circle, CircularSector,
+        CircularSegment, ConvexPolygon, Ellipse, Rectangle, RegularPolygon, Rhombus, Triangle2d,
+        Triangle3d, TriangulationError, WindingOrder,
     },
     FloatExt, Vec2,
 };
@@ -457,6 +458,156 @@ impl<const N: usize> From<ConvexPolygon<N>> for Mesh {
     }
 }
 
+/// A builder used for creating a [`Mesh`] with a [`BoxedPolygon`] or [`BoxedPolygonWithHoles`]
+/// shape.
+///
+/// The polygon is triangulated when the builder is created. If it can't be triangulated, for
+/// example because it isn't simple, [`PolygonMeshBuilder::try_build`] returns the
+/// [`TriangulationError`], and [`MeshBuilder::build`] panics.
+#[derive(Clone, Debug, Default, Reflect)]
+#[reflect(Default, Debug)]
+pub struct PolygonMeshBuilder {
+    /// The vertices of the exterior, followed by the vertices of each hole.
+    vertices: Vec<Vec2>,
+    /// The indices of the vertices of the exterior, wound counterclockwise, and of each hole,
+    /// wound clockwise.
+    rings: Vec<Vec<u32>>,
+    /// The triangles of the polygon, wound counterclockwise.
+    triangles: Vec<[u32; 3]>,
+    /// The error that prevented the polygon from being triangulated, if any.
+    #[reflect(ignore)]
+    error: Option<TriangulationError>,
+}
+
+impl PolygonMeshBuilder {
+    /// Creates a new [`PolygonMeshBuilder`] by triangulating the given polygon.
+    pub fn new(polygon: &BoxedPolygonWithHoles) -> Self {
+        let (triangles, error) = match polygon.triangulate() {
+            Ok(triangles) => (triangles, None),
+            Err(error) => (Vec::new(), Some(error)),
+        };
+
+        let mut rings = Vec::with_capacity(polygon.holes.len() + 1);
+        let mut offset = 0;
+        for (index, ring) in core::iter::once(&polygon.exterior)
+            .chain(&polygon.holes)
+            .enumerate()
+        {
+            let len = ring.vertices.len() as u32;
+            // Degenerate rings have no walls, but their vertices still take up indices.
+            if len >= 3 {
+                let mut indices: Vec<u32> = (offset..offset + len).collect();
+                if (ring.signed_area() > 0.0) != (index == 0) {
+                    indices.reverse();
+                }
+                rings.push(indices);
+            }
+            offset += len;
+        }
+
+        Self {
+            vertices: polygon.vertices().collect(),
+            rings,
+            triangles,
+            error,
+        }
+    }
+
+    /// Builds the [`Mesh`] of the polygon, or returns a [`TriangulationError`] if the polygon
+    /// couldn't be triangulated.
+    pub fn try_build(&self) -> Result<Mesh, TriangulationError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        let (min, max) = self.vertices.iter().fold(
+            (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+            |(min, max), &vertex| (min.min(vertex), max.max(vertex)),
+        );
+        let size = (max - min).max(Vec2::splat(f32::EPSILON));
+
+        let positions: Vec<[f32; 3]> = self
+            .vertices
+            .iter()
+            .map(|vertex| [vertex.x, vertex.y, 0.0])
+            .collect();
+        let normals = vec![[0.0, 0.0, 1.0]; self.vertices.len()];
+        let uvs: Vec<[f32; 2]> = self
+            .vertices
+            .iter()
+            .map(|&vertex| {
+                let uv = (vertex - min) / size;
+                [uv.x, 1.0 - uv.y]
+            })
+            .collect();
+        let indices = self.triangles.iter().flatten().copied().collect();
+
+        Ok(Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_indices(Indices::U32(indices))
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs))
+    }
+}
+
+impl Meshable for BoxedPolygon {
+    type Output = PolygonMeshBuilder;
+
+    fn mesh(&self) -> Self::Output {
+        PolygonMeshBuilder::new(&self.clone().into())
+    }
+}
+
+impl Meshable for BoxedPolygonWithHoles {
+    type Output = PolygonMeshBuilder;
+
+    fn mesh(&self) -> Self::Output {
+        PolygonMeshBuilder::new(self)
+    }
+}
+
+impl MeshBuilder for PolygonMeshBuilder {
+    /// Builds the [`Mesh`] of the polygon.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the polygon couldn't be triangulated. Use [`PolygonMeshBuilder::try_build`]
+    /// to handle the error instead.
+    fn build(&self) -> Mesh {
+        self.try_build().unwrap()
+    }
+}
+
+impl Extrudable for PolygonMeshBuilder {
+    fn perimeter(&self) -> Vec<PerimeterSegment> {
+        self.rings
+            .iter()
+            .map(|ring| PerimeterSegment::Flat {
+                indices: ring.iter().chain(ring.first()).copied().collect(),
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<BoxedPolygon> for Mesh {
+    type Error = TriangulationError;
+
+    fn try_from(polygon: BoxedPolygon) -> Result<Self, Self::Error> {
+        polygon.mesh().try_build()
+    }
+}
+
+impl TryFrom<BoxedPolygonWithHoles> for Mesh {
+    type Error = TriangulationError;
+
+    fn try_from(polygon: BoxedPolygonWithHoles) -> Result<Self, Self::Error> {
+        polygon.mesh().try_build()
+    }
+}
+
 /// A builder used for creating a [`Mesh`] with a [`RegularPolygon`] shape.
 #[derive(Clone, Copy, Debug, Reflect)]
 #[reflect(Default, Debug)]
@@ -1175,10 +1326,16 @@ impl From<Capsule2d> for Mesh {
 
 #[cfg(test)]
 mod tests {
-    use bevy_math::{prelude::Annulus, primitives::RegularPolygon, FloatOrd};
+    use bevy_math::{
+        prelude::Annulus,
+        primitives::{
+            BoxedPolygon, BoxedPolygonWithHoles, Extrusion, RegularPolygon, TriangulationError,
+        },
+        FloatOrd, Vec2,
+    };
     use bevy_platform_support::collections::HashSet;
 
-    use crate::{Mesh, MeshBuilder, Meshable, VertexAttributeValues};
+    use crate::{Extrudable, Indices, Mesh, MeshBuilder, Meshable, VertexAttributeValues};
 
     fn count_distinct_positions(points: &[[f32; 3]]) -> usize {
         let mut map = <HashSet<_>>::default();
@@ -1188,6 +1345,57 @@ mod tests {
         map.len()
     }
 
+    #[test]
+    fn test_polygon_with_hole() {
+        let square = |half_size: f32| {
+            BoxedPolygon::new([
+                Vec2::new(-half_size, -half_size),
+                Vec2::new(half_size, -half_size),
+                Vec2::new(half_size, half_size),
+                Vec2::new(-half_size, half_size),
+            ])
+        };
+        let polygon = BoxedPolygonWithHoles::new(square(2.0), [square(1.0)]);
+        let mesh = polygon.mesh().build();
+        assert_eq!(mesh.count_vertices(), 8);
+        assert_eq!(mesh.indices().unwrap().len(), 8 * 3);
+
+        // Both the exterior and the hole get walls when extruded.
+        let extrusion = Extrusion::new(polygon, 1.0).mesh().build();
+        assert_eq!(
+            16,
+            count_distinct_positions(
+                extrusion
+                    .attribute(Mesh::ATTRIBUTE_POSITION)
+                    .unwrap()
+                    .as_float3()
+                    .unwrap()
+            )
+        );
+
+        // Degenerate holes get no walls and don't shift the indices of later holes.
+        let polygon = BoxedPolygonWithHoles::new(
+            square(2.0),
+            [BoxedPolygon::new([Vec2::ZERO, Vec2::X]), square(1.0)],
+        );
+        let builder = polygon.mesh();
+        assert_eq!(builder.perimeter().len(), 2);
+        let mesh = builder.build();
+        let Some(Indices::U32(indices)) = mesh.indices() else {
+            panic!("expected u32 indices");
+        };
+        assert_eq!(indices.len(), 8 * 3);
+        assert!(indices.iter().all(|&index| index != 4 && index != 5));
+
+        // Polygons that can't be triangulated return an error instead of an empty mesh.
+        let segment = BoxedPolygon::new([Vec2::ZERO, Vec2::X]);
+        assert_eq!(
+            segment.mesh().try_build().err(),
+            Some(TriangulationError::NotEnoughVertices)
+        );
+        assert!(Mesh::try_from(segment).is_err());
+    }
+
     #[test]
     fn test_annulus() {
         let mesh = Annulus::new(1.0, 1.2).mesh().resolution(16).build();
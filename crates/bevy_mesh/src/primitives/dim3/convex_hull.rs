@@ -0,0 +1,68 @@
+use super::triangle3d;
+use crate::{Indices, Mesh, MeshBuilder, Meshable, PrimitiveTopology};
+use bevy_asset::RenderAssetUsages;
+use bevy_math::{
+    primitives::{ConvexHull3d, Triangle3d},
+    Vec3,
+};
+use bevy_reflect::prelude::*;
+
+/// A builder used for creating a [`Mesh`] with a [`ConvexHull3d`] shape.
+///
+/// Each face is shaded flat, so the faces don't share vertices.
+#[derive(Clone, Debug, Reflect)]
+#[reflect(Debug)]
+pub struct ConvexHull3dMeshBuilder {
+    vertices: Vec<Vec3>,
+    faces: Vec<[u32; 3]>,
+}
+
+impl MeshBuilder for ConvexHull3dMeshBuilder {
+    fn build(&self) -> Mesh {
+        let mut positions = Vec::with_capacity(self.faces.len() * 3);
+        let mut normals = Vec::with_capacity(self.faces.len() * 3);
+        let mut uvs = Vec::with_capacity(self.faces.len() * 3);
+
+        for &[a, b, c] in &self.faces {
+            let face = Triangle3d::new(
+                self.vertices[a as usize],
+                self.vertices[b as usize],
+                self.vertices[c as usize],
+            );
+            positions.extend(face.vertices);
+
+            let face_normal = triangle3d::normal_vec(&face);
+            normals.extend([face_normal; 3]);
+
+            uvs.extend(triangle3d::uv_coords(&face));
+        }
+
+        let indices = Indices::U32((0..positions.len() as u32).collect());
+
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_indices(indices)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+    }
+}
+
+impl Meshable for ConvexHull3d {
+    type Output = ConvexHull3dMeshBuilder;
+
+    fn mesh(&self) -> Self::Output {
+        ConvexHull3dMeshBuilder {
+            vertices: self.vertices().to_vec(),
+            faces: self.faces().to_vec(),
+        }
+    }
+}
+
+impl From<ConvexHull3d> for Mesh {
+    fn from(hull: ConvexHull3d) -> Self {
+        hull.mesh().build()
+    }
+}
@@ -1,6 +1,7 @@
 mod capsule;
 mod cone;
 mod conical_frustum;
+mod convex_hull;
 mod cuboid;
 mod cylinder;
 mod plane;
@@ -12,6 +13,7 @@ pub(crate) mod triangle3d;
 pub use capsule::*;
 pub use cone::*;
 pub use conical_frustum::*;
+pub use convex_hull::*;
 pub use cuboid::*;
 pub use cylinder::*;
 pub use plane::*;
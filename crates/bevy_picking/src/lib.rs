@@ -178,7 +178,9 @@ pub mod prelude {
     #[cfg(feature = "bevy_mesh_picking_backend")]
     #[doc(hidden)]
     pub use crate::mesh_picking::{
-        ray_cast::{MeshRayCast, MeshRayCastSettings, RayCastBackfaces, RayCastVisibility},
+        ray_cast::{
+            MeshRayCast, MeshRayCastSettings, RayCastBackfaces, RayCastBvh, RayCastVisibility,
+        },
         MeshPickingPlugin, MeshPickingSettings, RayCastPickable,
     };
     #[doc(hidden)]
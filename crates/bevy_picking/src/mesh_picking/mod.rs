@@ -24,7 +24,10 @@ use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
 use bevy_reflect::prelude::*;
 use bevy_render::{prelude::*, view::RenderLayers};
-use ray_cast::{MeshRayCast, MeshRayCastSettings, RayCastVisibility, SimplifiedMesh};
+use ray_cast::{
+    update_mesh_bvhs, MeshBvhCache, MeshRayCast, MeshRayCastSettings, RayCastBvh,
+    RayCastVisibility, SimplifiedMesh,
+};
 
 /// Runtime settings for the [`MeshPickingPlugin`].
 #[derive(Resource, Reflect)]
@@ -67,8 +70,19 @@ pub struct MeshPickingPlugin;
 impl Plugin for MeshPickingPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<MeshPickingSettings>()
-            .register_type::<(RayCastPickable, MeshPickingSettings, SimplifiedMesh)>()
-            .add_systems(PreUpdate, update_hits.in_set(PickSet::Backend));
+            .init_resource::<MeshBvhCache>()
+            .register_type::<(
+                RayCastPickable,
+                MeshPickingSettings,
+                SimplifiedMesh,
+                RayCastBvh,
+            )>()
+            .add_systems(
+                PreUpdate,
+                (update_mesh_bvhs, update_hits)
+                    .chain()
+                    .in_set(PickSet::Backend),
+            );
     }
 }
 
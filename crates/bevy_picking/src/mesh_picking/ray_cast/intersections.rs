@@ -1,4 +1,7 @@
-use bevy_math::{bounding::Aabb3d, Dir3, Mat4, Ray3d, Vec3, Vec3A};
+use bevy_math::{
+    bounding::{Aabb3d, Bvh, RayCast3d},
+    Dir3, Mat4, Ray3d, Vec3, Vec3A,
+};
 use bevy_reflect::Reflect;
 use bevy_render::mesh::{Indices, Mesh, PrimitiveTopology};
 
@@ -28,12 +31,57 @@ pub struct RayTriangleHit {
     pub barycentric_coords: (f32, f32),
 }
 
+/// A bounding volume hierarchy over the triangles of a mesh, in mesh space.
+///
+/// The [index](bevy_math::bounding::BvhHandle::index) of the handle of each item is the index of
+/// its triangle. Build it with [`mesh_triangle_bvh`].
+pub type TriangleBvh = Bvh<Aabb3d, ()>;
+
+/// Builds a [`TriangleBvh`] over the triangles of a mesh.
+///
+/// Returns `None` if the mesh isn't a triangle list with positions, or if its indices are
+/// malformed.
+pub fn mesh_triangle_bvh(mesh: &Mesh) -> Option<TriangleBvh> {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return None;
+    }
+    let positions = mesh.attribute(Mesh::ATTRIBUTE_POSITION)?.as_float3()?;
+    let triangles: Vec<[usize; 3]> = match mesh.indices() {
+        Some(indices) => {
+            if indices.len() % 3 != 0 {
+                return None;
+            }
+            let indices = indices.iter().collect::<Vec<_>>();
+            indices
+                .chunks_exact(3)
+                .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+                .collect()
+        }
+        None => (0..positions.len() / 3)
+            .map(|triangle| [0, 1, 2].map(|vertex| 3 * triangle + vertex))
+            .collect(),
+    };
+    let mut volumes = Vec::with_capacity(triangles.len());
+    for triangle in triangles {
+        let vertices = triangle
+            .map(|vertex| positions.get(vertex).copied())
+            .into_iter()
+            .collect::<Option<Vec<_>>>()?;
+        let aabb = Aabb3d::from_point_cloud(Vec3::ZERO, vertices.into_iter().map(Vec3::from));
+        volumes.push((aabb, ()));
+    }
+    Some(Bvh::build(volumes))
+}
+
 /// Casts a ray on a mesh, and returns the intersection.
+///
+/// The triangles are looked up in `bvh` if it is given, and tested one by one otherwise.
 pub(super) fn ray_intersection_over_mesh(
     mesh: &Mesh,
     transform: &Mat4,
     ray: Ray3d,
     culling: Backfaces,
+    bvh: Option<&TriangleBvh>,
 ) -> Option<RayMeshHit> {
     if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
         return None; // ray_mesh_intersection assumes vertices are laid out in a triangle list
@@ -46,6 +94,32 @@ pub(super) fn ray_intersection_over_mesh(
         .attribute(Mesh::ATTRIBUTE_NORMAL)
         .and_then(|normal_values| normal_values.as_float3());
 
+    if let Some(bvh) = bvh {
+        return match mesh.indices() {
+            Some(Indices::U16(indices)) => ray_mesh_bvh_intersection(
+                ray,
+                transform,
+                positions,
+                normals,
+                Some(indices),
+                bvh,
+                culling,
+            ),
+            Some(Indices::U32(indices)) => ray_mesh_bvh_intersection(
+                ray,
+                transform,
+                positions,
+                normals,
+                Some(indices),
+                bvh,
+                culling,
+            ),
+            None => ray_mesh_bvh_intersection::<usize>(
+                ray, transform, positions, normals, None, bvh, culling,
+            ),
+        };
+    }
+
     match mesh.indices() {
         Some(Indices::U16(indices)) => {
             ray_mesh_intersection(ray, transform, positions, normals, Some(indices), culling)
@@ -57,6 +131,85 @@ pub(super) fn ray_intersection_over_mesh(
     }
 }
 
+/// Checks if a ray intersects a mesh, and returns the nearest intersection if one exists.
+///
+/// Unlike [`ray_mesh_intersection`], only the triangles whose bounding boxes in `bvh` are hit by
+/// the ray are tested. The `bvh` must have been built from the same triangles, for example with
+/// [`mesh_triangle_bvh`].
+pub fn ray_mesh_bvh_intersection<I: TryInto<usize> + Clone + Copy>(
+    ray: Ray3d,
+    mesh_transform: &Mat4,
+    positions: &[[f32; 3]],
+    vertex_normals: Option<&[[f32; 3]]>,
+    indices: Option<&[I]>,
+    bvh: &TriangleBvh,
+    backface_culling: Backfaces,
+) -> Option<RayMeshHit> {
+    let world_to_mesh = mesh_transform.inverse();
+
+    let mesh_space_ray = Ray3d::new(
+        world_to_mesh.transform_point3(ray.origin),
+        Dir3::new(world_to_mesh.transform_vector3(*ray.direction)).ok()?,
+    );
+
+    // Returns the vertices of a triangle and the index reported for it, which is the index of its
+    // first vertex for indexed meshes, like in `ray_mesh_intersection`.
+    let triangle = |triangle: usize| -> Option<([usize; 3], usize)> {
+        match indices {
+            Some(indices) => {
+                let vertices = indices.get(3 * triangle..3 * triangle + 3)?;
+                let vertices = [
+                    vertices[0].try_into().ok()?,
+                    vertices[1].try_into().ok()?,
+                    vertices[2].try_into().ok()?,
+                ];
+                Some((vertices, vertices[0]))
+            }
+            None => Some(([0, 1, 2].map(|vertex| 3 * triangle + vertex), triangle)),
+        }
+    };
+    let hit_triangle = |index: usize| -> Option<RayMeshHit> {
+        let (vertices, triangle_index) = triangle(index)?;
+        let tri_vertex_positions = &vertices.map(|vertex| Vec3::from(positions[vertex]));
+        let tri_normals =
+            vertex_normals.map(|normals| vertices.map(|vertex| Vec3::from(normals[vertex])));
+        let hit = triangle_intersection(
+            tri_vertex_positions,
+            tri_normals.as_ref(),
+            f32::MAX,
+            &mesh_space_ray,
+            backface_culling,
+        )?;
+        Some(RayMeshHit {
+            triangle_index: Some(triangle_index),
+            ..hit
+        })
+    };
+
+    let (handle, _) = bvh.ray_cast(
+        &RayCast3d::from_ray(mesh_space_ray, f32::MAX),
+        |handle, _| hit_triangle(handle.index()).map(|hit| hit.distance),
+    )?;
+    let hit = hit_triangle(handle.index())?;
+
+    Some(RayMeshHit {
+        point: mesh_transform.transform_point3(hit.point),
+        normal: mesh_transform.transform_vector3(hit.normal),
+        barycentric_coords: hit.barycentric_coords,
+        distance: mesh_transform
+            .transform_vector3(mesh_space_ray.direction * hit.distance)
+            .length(),
+        triangle: hit.triangle.map(|tri| {
+            [
+                mesh_transform.transform_point3(tri[0]),
+                mesh_transform.transform_point3(tri[1]),
+                mesh_transform.transform_point3(tri[2]),
+            ]
+        }),
+        triangle_index: hit.triangle_index,
+    })
+}
+
 /// Checks if a ray intersects a mesh, and returns the nearest intersection if one exists.
 pub fn ray_mesh_intersection<I: TryInto<usize> + Clone + Copy>(
     ray: Ray3d,
@@ -143,9 +296,9 @@ pub fn ray_mesh_intersection<I: TryInto<usize> + Clone + Copy>(
             let tri_vertex_positions = &[Vec3::from(a), Vec3::from(b), Vec3::from(c)];
             let tri_normals = vertex_normals.map(|normals| {
                 [
-                    Vec3::from(normals[i]),
-                    Vec3::from(normals[i + 1]),
-                    Vec3::from(normals[i + 2]),
+                    Vec3::from(normals[3 * i]),
+                    Vec3::from(normals[3 * i + 1]),
+                    Vec3::from(normals[3 * i + 2]),
                 ]
             });
 
@@ -312,7 +465,8 @@ pub fn ray_aabb_intersection_3d(ray: Ray3d, aabb: &Aabb3d, model_to_world: &Mat4
 
 #[cfg(test)]
 mod tests {
-    use bevy_math::Vec3;
+    use bevy_math::{primitives::Sphere, Quat, Vec3};
+    use bevy_render::mesh::Meshable;
 
     use super::*;
 
@@ -336,4 +490,52 @@ mod tests {
         let result = ray_triangle_intersection(&ray, &triangle, Backfaces::Cull);
         assert!(result.is_none());
     }
+
+    #[test]
+    fn ray_cast_triangle_bvh() {
+        let mut mesh = Sphere::new(1.0).mesh().ico(3).unwrap();
+        let transform = Mat4::from_scale_rotation_translation(
+            Vec3::new(2.0, 1.0, 0.5),
+            Quat::from_rotation_y(0.7),
+            Vec3::new(1.0, -2.0, 3.0),
+        );
+        let rays = [
+            Vec3::new(1.0, 2.0, -3.0),
+            Vec3::new(-2.0, 0.5, 1.0),
+            Vec3::new(0.3, -1.0, 0.7),
+        ]
+        .map(|direction| {
+            let direction = Dir3::new(direction).unwrap();
+            Ray3d::new(Vec3::new(1.0, -2.0, 3.0) - 6.0 * direction, direction)
+        });
+
+        for indexed in [true, false] {
+            if !indexed {
+                mesh.duplicate_vertices();
+            }
+            let bvh = mesh_triangle_bvh(&mesh).unwrap();
+            let positions = mesh
+                .attribute(Mesh::ATTRIBUTE_POSITION)
+                .unwrap()
+                .as_float3()
+                .unwrap();
+            assert_eq!(
+                bvh.len(),
+                mesh.indices().map_or(positions.len(), Indices::len) / 3
+            );
+
+            for ray in rays {
+                let expected =
+                    ray_intersection_over_mesh(&mesh, &transform, ray, Backfaces::Cull, None)
+                        .unwrap();
+                let hit =
+                    ray_intersection_over_mesh(&mesh, &transform, ray, Backfaces::Cull, Some(&bvh))
+                        .unwrap();
+                assert!((hit.distance - expected.distance).abs() < 1e-5);
+                assert_eq!(hit.triangle, expected.triangle);
+                assert_eq!(hit.triangle_index, expected.triangle_index);
+                assert!(hit.normal.abs_diff_eq(expected.normal, 1e-5));
+            }
+        }
+    }
 }
@@ -11,11 +11,15 @@ use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render::mesh::Mesh;
 
 use intersections::*;
-pub use intersections::{ray_aabb_intersection_3d, ray_mesh_intersection, RayMeshHit};
+pub use intersections::{
+    mesh_triangle_bvh, ray_aabb_intersection_3d, ray_mesh_bvh_intersection, ray_mesh_intersection,
+    RayMeshHit, TriangleBvh,
+};
 
-use bevy_asset::{Assets, Handle};
+use bevy_asset::{AssetEvent, AssetId, Assets, Handle};
 use bevy_ecs::{prelude::*, system::lifetimeless::Read, system::SystemParam};
 use bevy_math::FloatOrd;
+use bevy_platform_support::collections::HashMap;
 use bevy_render::{prelude::*, primitives::Aabb};
 use bevy_transform::components::GlobalTransform;
 use tracing::*;
@@ -110,8 +114,74 @@ pub struct RayCastBackfaces;
 #[reflect(Component, Debug)]
 pub struct SimplifiedMesh(pub Handle<Mesh>);
 
+/// Accelerates [ray casts](MeshRayCast) on this entity with a [`TriangleBvh`] over the triangles
+/// of its mesh, instead of testing every triangle.
+///
+/// This is worth it for meshes with many triangles. The hierarchy is built once per mesh and
+/// stored in the [`MeshBvhCache`], so every entity using the mesh benefits from it.
+#[derive(Component, Copy, Clone, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct RayCastBvh;
+
+/// The [`TriangleBvh`]s of the meshes of entities with a [`RayCastBvh`], used by [`MeshRayCast`].
+///
+/// It is kept up to date by [`update_mesh_bvhs`], which the
+/// [`MeshPickingPlugin`](crate::mesh_picking::MeshPickingPlugin) runs before picking. The
+/// hierarchy of a mesh is dropped when the mesh is modified or removed, and built again if it is
+/// still used.
+#[derive(Resource, Default)]
+pub struct MeshBvhCache {
+    bvhs: HashMap<AssetId<Mesh>, TriangleBvh>,
+}
+
+impl MeshBvhCache {
+    /// Returns the hierarchy over the triangles of the given mesh, if it was built.
+    pub fn get(&self, id: impl Into<AssetId<Mesh>>) -> Option<&TriangleBvh> {
+        self.bvhs.get(&id.into())
+    }
+}
+
 type MeshFilter = Or<(With<Mesh3d>, With<Mesh2d>, With<SimplifiedMesh>)>;
 
+/// Builds the [`TriangleBvh`]s of the meshes used by entities with a [`RayCastBvh`], and drops the
+/// ones of modified or removed meshes.
+pub fn update_mesh_bvhs(
+    mut cache: ResMut<MeshBvhCache>,
+    mut mesh_events: EventReader<AssetEvent<Mesh>>,
+    meshes: Res<Assets<Mesh>>,
+    entities: Query<
+        (Option<&Mesh2d>, Option<&Mesh3d>, Option<&SimplifiedMesh>),
+        (With<RayCastBvh>, MeshFilter),
+    >,
+) {
+    for event in mesh_events.read() {
+        match event {
+            AssetEvent::Modified { id }
+            | AssetEvent::Removed { id }
+            | AssetEvent::Unused { id } => {
+                cache.bvhs.remove(id);
+            }
+            AssetEvent::Added { .. } | AssetEvent::LoadedWithDependencies { .. } => {}
+        }
+    }
+
+    for (mesh2d, mesh3d, simplified_mesh) in &entities {
+        // The same mesh is ray cast against as in `MeshRayCast::cast_ray`.
+        let Some(mesh_handle) = simplified_mesh
+            .map(|m| &m.0)
+            .or(mesh3d.map(|m| &m.0).or(mesh2d.map(|m| &m.0)))
+        else {
+            continue;
+        };
+        if cache.bvhs.contains_key(&mesh_handle.id()) {
+            continue;
+        }
+        if let Some(bvh) = meshes.get(mesh_handle).and_then(mesh_triangle_bvh) {
+            cache.bvhs.insert(mesh_handle.id(), bvh);
+        }
+    }
+}
+
 /// Add this ray casting [`SystemParam`] to your system to cast rays into the world with an
 /// immediate-mode API. Call `cast_ray` to immediately perform a ray cast and get a result.
 ///
@@ -170,6 +240,8 @@ pub struct MeshRayCast<'w, 's> {
     #[doc(hidden)]
     pub meshes: Res<'w, Assets<Mesh>>,
     #[doc(hidden)]
+    pub bvhs: Option<Res<'w, MeshBvhCache>>,
+    #[doc(hidden)]
     pub hits: Local<'s, Vec<(FloatOrd, (Entity, RayMeshHit))>>,
     #[doc(hidden)]
     pub output: Local<'s, Vec<(Entity, RayMeshHit)>>,
@@ -287,7 +359,9 @@ impl<'w, 's> MeshRayCast<'w, 's> {
                 // Perform the actual ray cast.
                 let _ray_cast_guard = ray_cast_guard.enter();
                 let transform = transform.compute_matrix();
-                let intersection = ray_intersection_over_mesh(mesh, &transform, ray, backfaces);
+                let bvh = self.bvhs.as_ref().and_then(|bvhs| bvhs.get(mesh_handle));
+                let intersection =
+                    ray_intersection_over_mesh(mesh, &transform, ray, backfaces, bvh);
 
                 if let Some(intersection) = intersection {
                     let distance = FloatOrd(intersection.distance);
@@ -310,3 +384,53 @@ impl<'w, 's> MeshRayCast<'w, 's> {
         self.output.as_ref()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+    use bevy_math::{primitives::Sphere, Dir3, Vec3};
+    use bevy_render::mesh::{MeshAabb, Meshable};
+
+    #[test]
+    fn cast_ray_through_mesh_bvh() {
+        let mut world = World::new();
+        world.init_resource::<MeshBvhCache>();
+        world.init_resource::<Events<AssetEvent<Mesh>>>();
+        let mut meshes = Assets::<Mesh>::default();
+        let mesh = Sphere::new(1.0).mesh().ico(3).unwrap();
+        let aabb = mesh.compute_aabb().unwrap();
+        let handle = meshes.add(mesh);
+        world.insert_resource(meshes);
+        let entity = world
+            .spawn((
+                Mesh3d(handle.clone()),
+                aabb,
+                GlobalTransform::from_xyz(0.0, 0.0, -5.0),
+                RayCastBvh,
+            ))
+            .id();
+
+        world.run_system_once(update_mesh_bvhs).unwrap();
+        assert!(world.resource::<MeshBvhCache>().get(&handle).is_some());
+
+        let hits = world
+            .run_system_once(|mut ray_cast: MeshRayCast| {
+                let settings =
+                    MeshRayCastSettings::default().with_visibility(RayCastVisibility::Any);
+                ray_cast
+                    .cast_ray(Ray3d::new(Vec3::ZERO, Dir3::NEG_Z), &settings)
+                    .to_vec()
+            })
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].0, entity);
+        assert!((hits[0].1.distance - 4.0).abs() < 0.05);
+
+        // The hierarchy of a modified mesh is dropped, and only built again while it is used.
+        world.entity_mut(entity).remove::<RayCastBvh>();
+        world.send_event(AssetEvent::Modified { id: handle.id() });
+        world.run_system_once(update_mesh_bvhs).unwrap();
+        assert!(world.resource::<MeshBvhCache>().get(&handle).is_none());
+    }
+}
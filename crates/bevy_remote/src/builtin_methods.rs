@@ -21,6 +21,10 @@ use bevy_reflect::{
     GetPath as _, NamedField, OpaqueInfo, PartialReflect, ReflectDeserialize, ReflectSerialize,
     TypeInfo, TypeRegistration, TypeRegistry, VariantInfo,
 };
+#[cfg(feature = "bevy_state")]
+use bevy_reflect::{serde::TypedReflectSerializer, ReflectFromReflect};
+#[cfg(feature = "bevy_state")]
+use bevy_state::reflect::{ReflectFreelyMutableState, ReflectState};
 use serde::{de::DeserializeSeed as _, Deserialize, Serialize};
 use serde_json::{json, Map, Value};
 
@@ -62,6 +66,18 @@ pub const BRP_LIST_AND_WATCH_METHOD: &str = "bevy/list+watch";
 /// The method path for a `bevy/registry/schema` request.
 pub const BRP_REGISTRY_SCHEMA_METHOD: &str = "bevy/registry/schema";
 
+#[cfg(feature = "bevy_state")]
+/// The method path for a `bevy/states/list` request.
+pub const BRP_LIST_STATES_METHOD: &str = "bevy/states/list";
+
+#[cfg(feature = "bevy_state")]
+/// The method path for a `bevy/states/get` request.
+pub const BRP_GET_STATES_METHOD: &str = "bevy/states/get";
+
+#[cfg(feature = "bevy_state")]
+/// The method path for a `bevy/states/set` request.
+pub const BRP_SET_STATE_METHOD: &str = "bevy/states/set";
+
 /// `bevy/get`: Retrieves one or more components from the entity with the given
 /// ID.
 ///
@@ -200,6 +216,33 @@ pub struct BrpListParams {
     pub entity: Entity,
 }
 
+#[cfg(feature = "bevy_state")]
+/// `bevy/states/get`: Retrieves the current value of one or more states.
+///
+/// The server responds with a [`BrpGetStatesResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpGetStatesParams {
+    /// The [full paths] of the state types that are to be requested.
+    ///
+    /// [full paths]: bevy_reflect::TypePath::type_path
+    pub states: Vec<String>,
+}
+
+#[cfg(feature = "bevy_state")]
+/// `bevy/states/set`: Requests a transition of a state to the given value.
+///
+/// The server responds with a null.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpSetStateParams {
+    /// The [full path] of the state type to set.
+    ///
+    /// [full path]: bevy_reflect::TypePath::type_path
+    pub state: String,
+
+    /// The serialized value of the state to transition to.
+    pub value: Value,
+}
+
 /// `bevy/mutate_component`:
 ///
 /// The server responds with a null.
@@ -323,6 +366,16 @@ pub enum BrpGetResponse {
     Strict(HashMap<String, Value>),
 }
 
+#[cfg(feature = "bevy_state")]
+/// The response to a `bevy/states/list` request.
+pub type BrpListStatesResponse = Vec<String>;
+
+#[cfg(feature = "bevy_state")]
+/// The response to a `bevy/states/get` request.
+///
+/// States that don't currently exist are mapped to null.
+pub type BrpGetStatesResponse = HashMap<String, Value>;
+
 /// A single response from a `bevy/get+watch` request.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(untagged)]
@@ -927,6 +980,92 @@ pub fn process_remote_list_watching_request(
     }
 }
 
+#[cfg(feature = "bevy_state")]
+/// Handles a `bevy/states/list` request (list all states) coming from a client.
+pub fn process_remote_list_states_request(In(_): In<Option<Value>>, world: &World) -> BrpResult {
+    let app_type_registry = world.resource::<AppTypeRegistry>();
+    let type_registry = app_type_registry.read();
+
+    let mut response: BrpListStatesResponse = type_registry
+        .iter()
+        .filter(|registration| registration.data::<ReflectState>().is_some())
+        .map(|registration| registration.type_info().type_path().to_owned())
+        .collect();
+    response.sort();
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+#[cfg(feature = "bevy_state")]
+/// Handles a `bevy/states/get` request coming from a client.
+pub fn process_remote_get_states_request(
+    In(params): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    let app_type_registry = world.resource::<AppTypeRegistry>();
+    let type_registry = app_type_registry.read();
+
+    let states = match params.map(parse::<BrpGetStatesParams>).transpose()? {
+        Some(BrpGetStatesParams { states }) => states,
+        None => type_registry
+            .iter()
+            .filter(|registration| registration.data::<ReflectState>().is_some())
+            .map(|registration| registration.type_info().type_path().to_owned())
+            .collect(),
+    };
+
+    let mut response = BrpGetStatesResponse::default();
+    for state_path in states {
+        let reflect_state = get_state_type_registration(&type_registry, &state_path)
+            .and_then(|registration| {
+                registration
+                    .data::<ReflectState>()
+                    .ok_or_else(|| anyhow!("State `{}` isn't reflectable", state_path))
+            })
+            .map_err(BrpError::state_error)?;
+        let value = match reflect_state.reflect(world) {
+            Some(reflected) => serde_json::to_value(TypedReflectSerializer::new(
+                reflected.as_partial_reflect(),
+                &type_registry,
+            ))
+            .map_err(BrpError::state_error)?,
+            None => Value::Null,
+        };
+        response.insert(state_path, value);
+    }
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+#[cfg(feature = "bevy_state")]
+/// Handles a `bevy/states/set` request coming from a client.
+pub fn process_remote_set_state_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpSetStateParams { state, value } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = app_type_registry.read();
+
+    let registration =
+        get_state_type_registration(&type_registry, &state).map_err(BrpError::state_error)?;
+    let reflect_state = registration
+        .data::<ReflectFreelyMutableState>()
+        .ok_or_else(|| BrpError::state_error(format!("State `{state}` can't be set")))?;
+    let reflected = TypedReflectDeserializer::new(registration, &type_registry)
+        .deserialize(&value)
+        .map_err(|err| BrpError::state_error(format!("{state} is invalid: {err}")))?;
+    let reflected = registration
+        .data::<ReflectFromReflect>()
+        .and_then(|from_reflect| from_reflect.from_reflect(&*reflected))
+        .ok_or_else(|| BrpError::state_error(format!("{state} is invalid: {value}")))?;
+
+    reflect_state.set_next_state(world, &*reflected, &type_registry);
+
+    Ok(Value::Null)
+}
+
 /// Handles a `bevy/registry/schema` request (list all registry types in form of schema) coming from a client.
 pub fn export_registry_types(In(params): In<Option<Value>>, world: &World) -> BrpResult {
     let filter: BrpJsonSchemaQueryFilter = match params {
@@ -1480,6 +1619,18 @@ fn get_reflect_component<'r>(
         .ok_or_else(|| anyhow!("Component `{}` isn't reflectable", component_path))
 }
 
+#[cfg(feature = "bevy_state")]
+/// Given a state's type path, return the associated [`TypeRegistration`] from the given
+/// `type_registry` if possible.
+fn get_state_type_registration<'r>(
+    type_registry: &'r TypeRegistry,
+    state_path: &str,
+) -> AnyhowResult<&'r TypeRegistration> {
+    type_registry
+        .get_with_type_path(state_path)
+        .ok_or_else(|| anyhow!("Unknown state type: `{}`", state_path))
+}
+
 /// Given a component's type path, return the associated [`TypeRegistration`] from the given
 /// `type_registry` if possible.
 fn get_component_type_registration<'r>(
@@ -1535,6 +1686,69 @@ mod tests {
         test_serialize_deserialize(BrpListParams {
             entity: Entity::from_raw(0),
         });
+        #[cfg(feature = "bevy_state")]
+        {
+            test_serialize_deserialize(BrpGetStatesParams {
+                states: vec!["my_game::Screen".to_owned()],
+            });
+            test_serialize_deserialize(BrpSetStateParams {
+                state: "my_game::Screen".to_owned(),
+                value: Value::String("Menu".to_owned()),
+            });
+        }
+    }
+
+    #[cfg(feature = "bevy_state")]
+    #[test]
+    fn get_and_set_states() {
+        use bevy_state::state::{NextState, State, States};
+
+        #[derive(States, Reflect, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+        enum Screen {
+            Menu,
+            Gameplay,
+        }
+
+        let mut world = World::new();
+        let atr = AppTypeRegistry::default();
+        {
+            let mut registry = atr.write();
+            registry.register::<Screen>();
+            registry.register_type_data::<Screen, ReflectState>();
+            registry.register_type_data::<Screen, ReflectFreelyMutableState>();
+        }
+        world.insert_resource(atr);
+        world.insert_resource(State::new(Screen::Menu));
+        world.init_resource::<NextState<Screen>>();
+
+        let path = <Screen as bevy_reflect::TypePath>::type_path();
+        assert_eq!(
+            process_remote_list_states_request(In(None), &world).unwrap(),
+            serde_json::json!([path])
+        );
+        assert_eq!(
+            process_remote_get_states_request(In(None), &world).unwrap(),
+            serde_json::json!({ path: "Menu" })
+        );
+
+        let params = serde_json::to_value(BrpSetStateParams {
+            state: path.to_owned(),
+            value: Value::String("Gameplay".to_owned()),
+        })
+        .unwrap();
+        process_remote_set_state_request(In(Some(params)), &mut world).unwrap();
+        assert!(matches!(
+            world.resource::<NextState<Screen>>(),
+            NextState::Pending(Screen::Gameplay)
+        ));
+
+        let params = serde_json::to_value(BrpSetStateParams {
+            state: path.to_owned(),
+            value: Value::String("Credits".to_owned()),
+        })
+        .unwrap();
+        let error = process_remote_set_state_request(In(Some(params)), &mut world).unwrap_err();
+        assert_eq!(error.code, error_codes::STATE_ERROR);
     }
 
     #[test]
@@ -230,6 +230,44 @@
 //!
 //! `result`: An array of fully-qualified type names of components.
 //!
+//! ### bevy/states/list
+//!
+//! The `bevy/states` methods are only available with the `bevy_state` feature. When depending on
+//! `bevy`, it is enabled along with the `bevy_state` feature of `bevy`.
+//!
+//! List all registered state types, that is the types with [`ReflectState`] type data.
+//!
+//! `params`: None.
+//!
+//! `result`: An array of fully-qualified type names of states.
+//!
+//! ### bevy/states/get
+//!
+//! Retrieve the current value of one or more states.
+//!
+//! When `params` is not provided, this retrieves all registered states.
+//!
+//! `params` (optional):
+//! - `states`: An array of fully-qualified type names of states to get.
+//!
+//! `result`: A map associating each type name to its current value, or to null if the state
+//! doesn't currently exist.
+//!
+//! ### bevy/states/set
+//!
+//! Request a transition of a state to the given value, as if set through `NextState`. The
+//! transition is applied during the next run of the `StateTransition` schedule.
+//!
+//! `params`:
+//! - `state`: The fully-qualified type name of the state to set. It must have
+//!   [`ReflectFreelyMutableState`] type data.
+//! - `value`: The serialized value of the state to transition to.
+//!
+//! `result`: null.
+//!
+//! [`ReflectState`]: bevy_state::reflect::ReflectState
+//! [`ReflectFreelyMutableState`]: bevy_state::reflect::ReflectFreelyMutableState
+//!
 //! ### bevy/get+watch
 //!
 //! Watch the values of one or more components from an entity.
@@ -440,6 +478,31 @@ impl Default for RemotePlugin {
                 builtin_methods::BRP_LIST_AND_WATCH_METHOD,
                 builtin_methods::process_remote_list_watching_request,
             )
+            .with_state_methods()
+    }
+}
+
+impl RemotePlugin {
+    /// Adds the `bevy/states` methods.
+    #[cfg(feature = "bevy_state")]
+    fn with_state_methods(self) -> Self {
+        self.with_method(
+            builtin_methods::BRP_LIST_STATES_METHOD,
+            builtin_methods::process_remote_list_states_request,
+        )
+        .with_method(
+            builtin_methods::BRP_GET_STATES_METHOD,
+            builtin_methods::process_remote_get_states_request,
+        )
+        .with_method(
+            builtin_methods::BRP_SET_STATE_METHOD,
+            builtin_methods::process_remote_set_state_request,
+        )
+    }
+
+    #[cfg(not(feature = "bevy_state"))]
+    fn with_state_methods(self) -> Self {
+        self
     }
 }
 
@@ -728,6 +791,16 @@ impl BrpError {
         }
     }
 
+    /// An arbitrary state error. Possibly related to reflection.
+    #[must_use]
+    pub fn state_error<E: ToString>(error: E) -> Self {
+        Self {
+            code: error_codes::STATE_ERROR,
+            message: error.to_string(),
+            data: None,
+        }
+    }
+
     /// Attempt to reparent an entity to itself.
     #[must_use]
     pub fn self_reparent(entity: Entity) -> Self {
@@ -772,6 +845,9 @@ pub mod error_codes {
 
     /// Cannot reparent an entity to itself.
     pub const SELF_REPARENT: i16 = -23404;
+
+    /// Could not reflect or find state.
+    pub const STATE_ERROR: i16 = -23405;
 }
 
 /// The result of a request.
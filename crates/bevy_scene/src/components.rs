@@ -28,3 +28,10 @@ pub struct SceneRoot(pub Handle<Scene>);
 #[require(Transform)]
 #[cfg_attr(feature = "bevy_render", require(Visibility))]
 pub struct DynamicSceneRoot(pub Handle<DynamicScene>);
+
+/// Adding this component next to a [`DynamicSceneRoot`] spawns the scene over several frames.
+///
+/// See [`SceneSpawner::spawn_dynamic_incremental`](crate::SceneSpawner::spawn_dynamic_incremental).
+#[derive(Component, Clone, Copy, Debug, Default, Reflect, PartialEq, Eq)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub struct IncrementalSceneSpawn;
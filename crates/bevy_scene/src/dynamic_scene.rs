@@ -9,7 +9,7 @@ use bevy_ecs::{
 use bevy_reflect::{PartialReflect, TypePath, TypeRegistry};
 
 #[cfg(feature = "serialize")]
-use crate::serde::SceneSerializer;
+use crate::serde::{ReadableSceneSerializer, SceneSerializer};
 use bevy_ecs::component::ComponentCloneBehavior;
 #[cfg(feature = "serialize")]
 use serde::Serialize;
@@ -86,50 +86,77 @@ impl DynamicScene {
 
             // Apply/ add each component to the given entity.
             for component in &scene_entity.components {
-                let component = component.clone_value();
-                let type_info = component.get_represented_type_info().ok_or_else(|| {
-                    SceneSpawnError::NoRepresentedType {
-                        type_path: component.reflect_type_path().to_string(),
-                    }
-                })?;
-                let registration = type_registry.get(type_info.type_id()).ok_or_else(|| {
-                    SceneSpawnError::UnregisteredButReflectedType {
-                        type_path: type_info.type_path().to_string(),
-                    }
-                })?;
-                let reflect_component =
-                    registration.data::<ReflectComponent>().ok_or_else(|| {
-                        SceneSpawnError::UnregisteredComponent {
-                            type_path: type_info.type_path().to_string(),
-                        }
-                    })?;
-
-                {
-                    let component_id = reflect_component.register_component(world);
-                    // SAFETY: we registered the component above. the info exists
-                    #[expect(unsafe_code, reason = "this is faster")]
-                    let component_info =
-                        unsafe { world.components().get_info_unchecked(component_id) };
-                    match component_info.clone_behavior() {
-                        ComponentCloneBehavior::Ignore
-                        | ComponentCloneBehavior::RelationshipTarget(_) => continue,
-                        _ => {}
-                    }
-                }
-
-                SceneEntityMapper::world_scope(entity_map, world, |world, mapper| {
-                    reflect_component.apply_or_insert_mapped(
-                        &mut world.entity_mut(entity),
-                        component.as_partial_reflect(),
-                        &type_registry,
-                        mapper,
-                    );
-                });
+                Self::write_component_to_world(
+                    component.as_partial_reflect(),
+                    entity,
+                    world,
+                    entity_map,
+                    &type_registry,
+                )?;
             }
         }
 
         // Insert resources after all entities have been added to the world.
         // This ensures the entities are available for the resources to reference during mapping.
+        self.write_resources_to_world(world, entity_map, &type_registry)
+    }
+
+    /// Applies or adds a single scene component to `entity`, mapping the entities it references with `entity_map`.
+    pub(crate) fn write_component_to_world(
+        component: &dyn PartialReflect,
+        entity: Entity,
+        world: &mut World,
+        entity_map: &mut EntityHashMap<Entity>,
+        type_registry: &TypeRegistry,
+    ) -> Result<(), SceneSpawnError> {
+        let component = component.clone_value();
+        let type_info = component.get_represented_type_info().ok_or_else(|| {
+            SceneSpawnError::NoRepresentedType {
+                type_path: component.reflect_type_path().to_string(),
+            }
+        })?;
+        let registration = type_registry.get(type_info.type_id()).ok_or_else(|| {
+            SceneSpawnError::UnregisteredButReflectedType {
+                type_path: type_info.type_path().to_string(),
+            }
+        })?;
+        let reflect_component = registration.data::<ReflectComponent>().ok_or_else(|| {
+            SceneSpawnError::UnregisteredComponent {
+                type_path: type_info.type_path().to_string(),
+            }
+        })?;
+
+        {
+            let component_id = reflect_component.register_component(world);
+            // SAFETY: we registered the component above. the info exists
+            #[expect(unsafe_code, reason = "this is faster")]
+            let component_info = unsafe { world.components().get_info_unchecked(component_id) };
+            match component_info.clone_behavior() {
+                ComponentCloneBehavior::Ignore | ComponentCloneBehavior::RelationshipTarget(_) => {
+                    return Ok(())
+                }
+                _ => {}
+            }
+        }
+
+        SceneEntityMapper::world_scope(entity_map, world, |world, mapper| {
+            reflect_component.apply_or_insert_mapped(
+                &mut world.entity_mut(entity),
+                component.as_partial_reflect(),
+                type_registry,
+                mapper,
+            );
+        });
+        Ok(())
+    }
+
+    /// Inserts or applies the resources of the scene, mapping the entities they reference with `entity_map`.
+    pub(crate) fn write_resources_to_world(
+        &self,
+        world: &mut World,
+        entity_map: &mut EntityHashMap<Entity>,
+        type_registry: &TypeRegistry,
+    ) -> Result<(), SceneSpawnError> {
         for resource in &self.resources {
             let mut resource = resource.clone_value();
             let type_info = resource.get_represented_type_info().ok_or_else(|| {
@@ -158,7 +185,7 @@ impl DynamicScene {
 
             // If the world already contains an instance of the given resource
             // just apply the (possibly) new value, otherwise insert the resource
-            reflect_resource.apply_or_insert(world, resource.as_partial_reflect(), &type_registry);
+            reflect_resource.apply_or_insert(world, resource.as_partial_reflect(), type_registry);
         }
 
         Ok(())
@@ -190,6 +217,21 @@ impl DynamicScene {
     pub fn serialize(&self, registry: &TypeRegistry) -> Result<String, ron::Error> {
         serialize_ron(SceneSerializer::new(self, registry))
     }
+
+    /// Serialize this dynamic scene into the readable Bevy scene format (`.rscn` / `.rscn.ron`).
+    ///
+    /// Unlike [`serialize`](Self::serialize), entities are keyed by their [`Name`] where possible,
+    /// types are written with their [`SceneTypeAlias`], and components that have their default value
+    /// are only listed by type. See [`ReadableSceneSerializer`] for details. To deserialize the scene,
+    /// use the [`ReadableSceneLoader`].
+    ///
+    /// [`Name`]: bevy_ecs::name::Name
+    /// [`SceneTypeAlias`]: crate::serde::SceneTypeAlias
+    /// [`ReadableSceneLoader`]: crate::ReadableSceneLoader
+    #[cfg(feature = "serialize")]
+    pub fn serialize_readable(&self, registry: &TypeRegistry) -> Result<String, ron::Error> {
+        serialize_ron(ReadableSceneSerializer::new(self, registry))
+    }
 }
 
 /// Serialize a given Rust data structure into rust object notation (ron).
@@ -203,6 +203,11 @@ impl<'w> DynamicSceneBuilder<'w> {
         self
     }
 
+    /// Returns the [`World`] entities and resources are extracted from.
+    pub(crate) fn world(&self) -> &'w World {
+        self.original_world
+    }
+
     /// Consume the builder, producing a [`DynamicScene`].
     ///
     /// To make sure the dynamic scene doesn't contain entities without any components, call
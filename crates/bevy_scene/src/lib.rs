@@ -15,6 +15,7 @@ extern crate alloc;
 mod components;
 mod dynamic_scene;
 mod dynamic_scene_builder;
+mod prefab;
 mod scene;
 mod scene_filter;
 mod scene_loader;
@@ -30,6 +31,7 @@ use bevy_ecs::schedule::IntoSystemConfigs;
 pub use components::*;
 pub use dynamic_scene::*;
 pub use dynamic_scene_builder::*;
+pub use prefab::*;
 pub use scene::*;
 pub use scene_filter::*;
 pub use scene_loader::*;
@@ -59,9 +61,12 @@ impl Plugin for ScenePlugin {
         app.init_asset::<DynamicScene>()
             .init_asset::<Scene>()
             .init_asset_loader::<SceneLoader>()
+            .init_asset_loader::<PrefabLoader>()
+            .init_asset_loader::<ReadableSceneLoader>()
             .init_resource::<SceneSpawner>()
             .register_type::<SceneRoot>()
             .register_type::<DynamicSceneRoot>()
+            .register_type::<IncrementalSceneSpawn>()
             .add_systems(SpawnScene, (scene_spawner, scene_spawner_system).chain());
 
         // Register component hooks for DynamicSceneRoot
@@ -0,0 +1,527 @@
+#[cfg(feature = "serialize")]
+use crate::serde::{PrefabDeserializer, PrefabSerializer};
+use crate::{ron, DynamicEntity, DynamicScene};
+#[cfg(feature = "serialize")]
+use bevy_asset::{io::Reader, AssetLoader, LoadContext};
+use bevy_asset::{LoadDirectError, ParseAssetPathError};
+use bevy_ecs::{
+    entity::Entity,
+    reflect::AppTypeRegistry,
+    world::{FromWorld, World},
+};
+#[cfg(feature = "serialize")]
+use bevy_reflect::TypeRegistry;
+use bevy_reflect::{PartialReflect, TypeInfo, TypeRegistryArc};
+#[cfg(feature = "serialize")]
+use serde::de::DeserializeSeed;
+use thiserror::Error;
+
+/// A set of changes that turns a base [`DynamicScene`] into a derived one.
+///
+/// Entities are matched with the base scene by their [`DynamicEntity::entity`] identifier.
+/// Patches of entities that aren't part of the base scene add those entities to it.
+#[derive(Default)]
+pub struct ScenePatch {
+    /// Resources added to the scene, replacing base resources of the same type.
+    pub resources: Vec<Box<dyn PartialReflect>>,
+    /// Per-entity changes, applied in order.
+    pub entities: Vec<EntityPatch>,
+    /// Base entities removed from the scene.
+    pub removed_entities: Vec<Entity>,
+}
+
+/// The changes a [`ScenePatch`] makes to a single entity.
+pub struct EntityPatch {
+    /// The identifier of the entity within the base scene.
+    pub entity: Entity,
+    /// Components added to the entity, replacing base components of the same type.
+    pub components: Vec<Box<dyn PartialReflect>>,
+    /// Type paths of the base components removed from the entity.
+    pub removed_components: Vec<String>,
+}
+
+impl EntityPatch {
+    /// Creates an empty patch for the given entity.
+    pub fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            components: Vec::new(),
+            removed_components: Vec::new(),
+        }
+    }
+}
+
+impl ScenePatch {
+    /// Returns `true` if applying this patch doesn't change any scene.
+    pub fn is_empty(&self) -> bool {
+        self.resources.is_empty() && self.entities.is_empty() && self.removed_entities.is_empty()
+    }
+
+    /// Creates the patch that turns `source` into `target`.
+    ///
+    /// Entities are matched by their [`DynamicEntity::entity`] identifier and components and resources by
+    /// type. Values are compared with [`PartialReflect::reflect_partial_eq`], and values that can't be
+    /// compared are considered changed. Resources missing from `target` are not recorded.
+    pub fn diff(source: &DynamicScene, target: &DynamicScene) -> Self {
+        let mut patch = ScenePatch {
+            resources: changed_values(&source.resources, &target.resources),
+            ..Default::default()
+        };
+
+        for source_entity in &source.entities {
+            if !target
+                .entities
+                .iter()
+                .any(|entity| entity.entity == source_entity.entity)
+            {
+                patch.removed_entities.push(source_entity.entity);
+            }
+        }
+
+        for target_entity in &target.entities {
+            let source_entity = source
+                .entities
+                .iter()
+                .find(|entity| entity.entity == target_entity.entity);
+            let source_components = source_entity
+                .map(|entity| entity.components.as_slice())
+                .unwrap_or_default();
+            let entity_patch = EntityPatch {
+                entity: target_entity.entity,
+                components: changed_values(source_components, &target_entity.components),
+                removed_components: source_components
+                    .iter()
+                    .map(|component| type_path(component.as_ref()))
+                    .filter(|path| {
+                        !target_entity
+                            .components
+                            .iter()
+                            .any(|component| type_path(component.as_ref()) == *path)
+                    })
+                    .map(ToString::to_string)
+                    .collect(),
+            };
+            if source_entity.is_none()
+                || !entity_patch.components.is_empty()
+                || !entity_patch.removed_components.is_empty()
+            {
+                patch.entities.push(entity_patch);
+            }
+        }
+
+        patch
+    }
+
+    /// Applies the changes of this patch to `scene`.
+    ///
+    /// Removed entities and components that are missing from `scene` are ignored, so a patch
+    /// remains applicable after its base scene has been edited.
+    pub fn apply(&self, scene: &mut DynamicScene) {
+        scene
+            .entities
+            .retain(|entity| !self.removed_entities.contains(&entity.entity));
+        replace_values(&mut scene.resources, &self.resources);
+
+        for patch in &self.entities {
+            let index = match scene
+                .entities
+                .iter()
+                .position(|entity| entity.entity == patch.entity)
+            {
+                Some(index) => index,
+                None => {
+                    scene.entities.push(DynamicEntity {
+                        entity: patch.entity,
+                        components: Vec::new(),
+                    });
+                    scene.entities.len() - 1
+                }
+            };
+            let components = &mut scene.entities[index].components;
+            components.retain(|component| {
+                !patch
+                    .removed_components
+                    .iter()
+                    .any(|removed| removed == type_path(component.as_ref()))
+            });
+            replace_values(components, &patch.components);
+        }
+    }
+}
+
+/// A scene defined as a base scene asset plus a [`ScenePatch`].
+///
+/// Prefabs are stored in `.prefab.ron` files and loaded by the [`PrefabLoader`], which resolves the
+/// chain of base scenes into a single [`DynamicScene`]. The base scene may itself be a prefab.
+///
+/// With the default [`DependentReloadMode::Reload`], hot reloading a base scene reloads every prefab
+/// derived from it, and the instances spawned from it are updated with the patch still applied on top.
+/// With [`DependentReloadMode::InPlace`] the prefabs are only notified and keep the scene they were
+/// loaded with until they are reloaded themselves.
+///
+/// [`DependentReloadMode::Reload`]: bevy_asset::DependentReloadMode::Reload
+/// [`DependentReloadMode::InPlace`]: bevy_asset::DependentReloadMode::InPlace
+pub struct ScenePrefab {
+    /// Path of the base scene, relative to the prefab.
+    pub base: String,
+    /// The changes made to the base scene.
+    pub patch: ScenePatch,
+}
+
+impl ScenePrefab {
+    /// Creates a prefab deriving from the scene at `base` without any changes.
+    pub fn new(base: impl Into<String>) -> Self {
+        Self {
+            base: base.into(),
+            patch: ScenePatch::default(),
+        }
+    }
+
+    /// Serialize this prefab into the `.prefab.ron` format read by the [`PrefabLoader`].
+    #[cfg(feature = "serialize")]
+    pub fn serialize(&self, registry: &TypeRegistry) -> Result<String, ron::Error> {
+        crate::serialize_ron(PrefabSerializer::new(self, registry))
+    }
+}
+
+fn type_path(value: &dyn PartialReflect) -> &str {
+    value
+        .get_represented_type_info()
+        .map(TypeInfo::type_path)
+        .unwrap_or_else(|| value.reflect_type_path())
+}
+
+/// Clones the values of `target` that are missing from `source` or differ from the value of the same type in it.
+fn changed_values(
+    source: &[Box<dyn PartialReflect>],
+    target: &[Box<dyn PartialReflect>],
+) -> Vec<Box<dyn PartialReflect>> {
+    target
+        .iter()
+        .filter(|value| {
+            let path = type_path(value.as_ref());
+            source
+                .iter()
+                .find(|source| type_path(source.as_ref()) == path)
+                .and_then(|source| source.reflect_partial_eq(value.as_partial_reflect()))
+                != Some(true)
+        })
+        .map(|value| value.clone_value())
+        .collect()
+}
+
+/// Replaces the values in `values` that have the same type as one of `replacements`, and appends the others.
+fn replace_values(
+    values: &mut Vec<Box<dyn PartialReflect>>,
+    replacements: &[Box<dyn PartialReflect>],
+) {
+    for replacement in replacements {
+        let replacement_path = type_path(replacement.as_ref());
+        match values
+            .iter_mut()
+            .find(|value| type_path(value.as_ref()) == replacement_path)
+        {
+            Some(value) => *value = replacement.clone_value(),
+            None => values.push(replacement.clone_value()),
+        }
+    }
+}
+
+/// Asset loader for scene prefabs (`.prefab` / `.prefab.ron`).
+///
+/// The loader reads a [`ScenePrefab`], loads its base scene and returns the base scene with the
+/// prefab's patch applied.
+#[derive(Debug)]
+pub struct PrefabLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for PrefabLoader {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        PrefabLoader {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+/// Possible errors that can be produced by [`PrefabLoader`]
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum PrefabLoaderError {
+    /// An [IO Error](std::io::Error)
+    #[error("Error while trying to read the prefab file: {0}")]
+    Io(#[from] std::io::Error),
+    /// A [RON Error](ron::error::SpannedError)
+    #[error("Could not parse RON: {0}")]
+    RonSpannedError(#[from] ron::error::SpannedError),
+    /// The path of the base scene is invalid.
+    #[error("Invalid base scene path '{path}': {error}")]
+    InvalidBasePath {
+        /// The path of the base scene, as written in the prefab.
+        path: String,
+        /// The error that occurred while parsing the path.
+        error: ParseAssetPathError,
+    },
+    /// The base scene could not be loaded.
+    #[error(transparent)]
+    LoadBase(#[from] Box<LoadDirectError>),
+}
+
+#[cfg(feature = "serialize")]
+impl AssetLoader for PrefabLoader {
+    type Asset = DynamicScene;
+    type Settings = ();
+    type Error = PrefabLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let prefab = {
+            let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+            let prefab_deserializer = PrefabDeserializer {
+                type_registry: &self.type_registry.read(),
+            };
+            prefab_deserializer
+                .deserialize(&mut deserializer)
+                .map_err(|e| deserializer.span_error(e))?
+        };
+
+        let base_path = load_context
+            .asset_path()
+            .resolve_embed(&prefab.base)
+            .map_err(|error| PrefabLoaderError::InvalidBasePath {
+                path: prefab.base.clone(),
+                error,
+            })?;
+        let mut scene = load_context
+            .loader()
+            .immediate()
+            .load::<DynamicScene>(base_path)
+            .await
+            .map_err(Box::new)?
+            .take();
+        prefab.patch.apply(&mut scene);
+        Ok(scene)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["prefab", "prefab.ron"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        DynamicScene, DynamicSceneBuilder, DynamicSceneRoot, EntityPatch, SceneInstance,
+        ScenePatch, ScenePlugin, ScenePrefab, SceneSpawner,
+    };
+    use alloc::sync::Arc;
+    use bevy_app::{App, TaskPoolPlugin};
+    use bevy_asset::{
+        io::{
+            memory::Dir, memory::MemoryAssetReader, AssetSource, AssetSourceEvent, AssetSourceId,
+            AssetWatcher,
+        },
+        AssetApp, AssetPlugin, AssetServer, Handle,
+    };
+    use bevy_ecs::{
+        component::Component,
+        entity::Entity,
+        reflect::{AppTypeRegistry, ReflectComponent},
+        world::World,
+    };
+    use bevy_reflect::Reflect;
+    use std::{path::Path, sync::Mutex};
+
+    #[derive(Component, Reflect, Default, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Enemy;
+
+    #[test]
+    fn patch_overrides_adds_and_removes() {
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<Health>();
+        registry.write().register::<Enemy>();
+
+        let mut world = World::new();
+        world.insert_resource(registry.clone());
+        let a = world.spawn((Health(10), Enemy)).id();
+        let b = world.spawn(Health(20)).id();
+        let c = world.spawn(Health(30)).id();
+        let mut scene = DynamicSceneBuilder::from_world(&world)
+            .extract_entities([a, b, c].into_iter())
+            .build();
+
+        let mut override_a = EntityPatch::new(a);
+        override_a.components.push(Box::new(Health(15)));
+        override_a
+            .removed_components
+            .push(core::any::type_name::<Enemy>().into());
+        let mut added = EntityPatch::new(world.spawn_empty().id());
+        added.components.push(Box::new(Enemy));
+        let patch = ScenePatch {
+            entities: vec![override_a, added],
+            removed_entities: vec![c],
+            ..Default::default()
+        };
+        patch.apply(&mut scene);
+
+        let mut spawned = World::new();
+        spawned.insert_resource(registry);
+        let mut entity_map = Default::default();
+        scene.write_to_world(&mut spawned, &mut entity_map).unwrap();
+        assert_eq!(spawned.entities().len(), 3);
+        let a = spawned.entity(entity_map[&a]);
+        assert_eq!(a.get::<Health>(), Some(&Health(15)));
+        assert!(!a.contains::<Enemy>());
+        assert_eq!(
+            spawned.get::<Health>(entity_map[&b]),
+            Some(&Health(20)),
+            "entities without a patch are kept as is"
+        );
+        assert!(!entity_map.contains_key(&c));
+
+        // Removing or patching entities the base no longer has is not an error.
+        patch.apply(&mut DynamicScene::default());
+    }
+
+    #[derive(Component, Reflect, Default, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Speed(u32);
+
+    fn write_scene(dir: &Dir, registry: &AppTypeRegistry, path: &str, speed: Option<Speed>) {
+        let mut world = World::new();
+        world.insert_resource(registry.clone());
+        let mut entity = world.spawn(Health(10));
+        if let Some(speed) = speed {
+            entity.insert(speed);
+        }
+        let scene = DynamicScene::from_world(&world);
+        dir.insert_asset_text(Path::new(path), &scene.serialize(&registry.read()).unwrap());
+    }
+
+    struct ManualWatcher;
+
+    impl AssetWatcher for ManualWatcher {}
+
+    type SendSourceEvent = Arc<Mutex<Option<Box<dyn Fn(AssetSourceEvent) + Send + Sync>>>>;
+
+    #[test]
+    fn load_prefab_chain_and_reload_base() {
+        let dir = Dir::default();
+        let source_dir = dir.clone();
+        let send_event = SendSourceEvent::default();
+        let watcher_send_event = send_event.clone();
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || {
+                    Box::new(MemoryAssetReader {
+                        root: source_dir.clone(),
+                    })
+                })
+                .with_watcher(move |sender| {
+                    *watcher_send_event.lock().unwrap() =
+                        Some(Box::new(move |event| sender.send(event).unwrap()));
+                    Some(Box::new(ManualWatcher))
+                }),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin {
+                watch_for_changes_override: Some(true),
+                ..Default::default()
+            },
+            ScenePlugin,
+        ))
+        .register_type::<Health>()
+        .register_type::<Enemy>()
+        .register_type::<Speed>();
+        let registry = app.world().resource::<AppTypeRegistry>().clone();
+
+        // The base scene has a single entity, which is the first one spawned in a new world.
+        write_scene(&dir, &registry, "base.scn.ron", None);
+        let base_entity = Entity::from_raw(0);
+        let added_entity = Entity::from_raw(1);
+
+        let mut derived = ScenePrefab::new("../base.scn.ron");
+        let mut patch = EntityPatch::new(base_entity);
+        patch.components.push(Box::new(Health(15)));
+        derived.patch.entities.push(patch);
+        let mut patch = EntityPatch::new(added_entity);
+        patch.components.push(Box::new(Enemy));
+        derived.patch.entities.push(patch);
+        dir.insert_asset_text(
+            Path::new("prefabs/derived.prefab.ron"),
+            &derived.serialize(&registry.read()).unwrap(),
+        );
+
+        let mut nested = ScenePrefab::new("derived.prefab.ron");
+        let mut patch = EntityPatch::new(added_entity);
+        patch.components.push(Box::new(Health(5)));
+        nested.patch.entities.push(patch);
+        dir.insert_asset_text(
+            Path::new("prefabs/nested.prefab.ron"),
+            &nested.serialize(&registry.read()).unwrap(),
+        );
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let handle: Handle<DynamicScene> = asset_server.load("prefabs/nested.prefab.ron");
+        let root = app.world_mut().spawn(DynamicSceneRoot(handle.clone())).id();
+        let entity_with = |app: &mut App, health: u32| {
+            app.world_mut()
+                .query::<(Entity, &Health)>()
+                .iter(app.world())
+                .find(|(_, h)| h.0 == health)
+                .map(|(entity, _)| entity)
+        };
+        for _ in 0..100 {
+            app.update();
+            if entity_with(&mut app, 5).is_some() {
+                break;
+            }
+        }
+        let overridden = entity_with(&mut app, 15).expect("base entity is overridden");
+        let added = entity_with(&mut app, 5).expect("entity is added and overridden again");
+        assert!(app.world().entity(added).contains::<Enemy>());
+        assert!(entity_with(&mut app, 10).is_none());
+        assert_eq!(
+            app.world()
+                .resource::<SceneSpawner>()
+                .iter_instance_entities(**app.world().get::<SceneInstance>(root).unwrap())
+                .count(),
+            2
+        );
+
+        // Changes made to the instance at runtime survive the reload.
+        app.world_mut().get_mut::<Health>(added).unwrap().0 = 6;
+
+        // Editing the base reloads the prefabs deriving from it, and reaches the spawned instance,
+        // which keeps the prefab's overrides.
+        write_scene(&dir, &registry, "base.scn.ron", Some(Speed(3)));
+        send_event.lock().unwrap().as_ref().unwrap()(AssetSourceEvent::ModifiedAsset(
+            "base.scn.ron".into(),
+        ));
+        for _ in 0..100 {
+            app.update();
+            if app.world().entity(overridden).contains::<Speed>() {
+                break;
+            }
+        }
+        let overridden = app.world().entity(overridden);
+        assert_eq!(overridden.get::<Speed>(), Some(&Speed(3)));
+        assert_eq!(overridden.get::<Health>(), Some(&Health(15)));
+        assert_eq!(app.world().get::<Health>(added), Some(&Health(6)));
+    }
+}
@@ -1,5 +1,5 @@
 #[cfg(feature = "serialize")]
-use crate::serde::SceneDeserializer;
+use crate::serde::{ReadableSceneDeserializer, SceneDeserializer};
 use crate::{ron, DynamicScene};
 use bevy_asset::{io::Reader, AssetLoader, LoadContext};
 use bevy_ecs::{
@@ -67,3 +67,48 @@ impl AssetLoader for SceneLoader {
         &["scn", "scn.ron"]
     }
 }
+
+/// Asset loader for a Bevy dynamic scene in the readable format (`.rscn` / `.rscn.ron`).
+///
+/// The loader handles assets serialized with [`DynamicScene::serialize_readable`].
+#[derive(Debug)]
+pub struct ReadableSceneLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for ReadableSceneLoader {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        ReadableSceneLoader {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl AssetLoader for ReadableSceneLoader {
+    type Asset = DynamicScene;
+    type Settings = ();
+    type Error = SceneLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+        let scene_deserializer = ReadableSceneDeserializer {
+            type_registry: &self.type_registry.read(),
+        };
+        Ok(scene_deserializer
+            .deserialize(&mut deserializer)
+            .map_err(|e| deserializer.span_error(e))?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["rscn", "rscn.ron"]
+    }
+}
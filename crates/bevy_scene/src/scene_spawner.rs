@@ -1,23 +1,27 @@
-use crate::{DynamicScene, Scene};
+use crate::{DynamicEntity, DynamicScene, DynamicSceneBuilder, Scene, ScenePatch};
 use bevy_asset::{AssetEvent, AssetId, Assets, Handle};
 use bevy_ecs::{
     entity::{hash_map::EntityHashMap, Entity},
     event::{Event, EventCursor, Events},
-    hierarchy::ChildOf,
-    reflect::AppTypeRegistry,
+    hierarchy::{ChildOf, Children},
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectMapEntities},
     resource::Resource,
     world::{Mut, World},
 };
-use bevy_platform_support::collections::{HashMap, HashSet};
-use bevy_reflect::Reflect;
+use bevy_platform_support::{
+    collections::{HashMap, HashSet},
+    time::Instant,
+};
+use bevy_reflect::{Reflect, TypeRegistry};
+use core::time::Duration;
 use thiserror::Error;
 use uuid::Uuid;
 
-use crate::{DynamicSceneRoot, SceneRoot};
+use crate::{DynamicSceneRoot, IncrementalSceneSpawn, SceneRoot};
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
     change_detection::ResMut,
-    prelude::{Changed, Component, Without},
+    prelude::{Changed, Component, Has, Without},
     system::{Commands, Query},
 };
 /// Triggered on a scene's parent entity when [`crate::SceneInstance`] becomes ready to use.
@@ -61,10 +65,13 @@ impl InstanceId {
 /// - [`spawn_queued_scenes`](Self::spawn_queued_scenes)
 /// - [`despawn_queued_scenes`](Self::despawn_queued_scenes)
 /// - [`despawn_queued_instances`](Self::despawn_queued_instances)
+/// - [`spawn_incremental_scenes`](Self::spawn_incremental_scenes)
 ///
 /// Deferred methods: (Scene operations will be processed when the [`scene_spawner_system`] is run)
 /// - [`spawn_dynamic`](Self::spawn_dynamic)
 /// - [`spawn_dynamic_as_child`](Self::spawn_dynamic_as_child)
+/// - [`spawn_dynamic_incremental`](Self::spawn_dynamic_incremental)
+/// - [`spawn_dynamic_incremental_as_child`](Self::spawn_dynamic_incremental_as_child)
 /// - [`spawn`](Self::spawn)
 /// - [`spawn_as_child`](Self::spawn_as_child)
 /// - [`despawn`](Self::despawn)
@@ -72,6 +79,8 @@ impl InstanceId {
 #[derive(Default, Resource)]
 pub struct SceneSpawner {
     pub(crate) spawned_dynamic_scenes: HashMap<AssetId<DynamicScene>, HashSet<InstanceId>>,
+    /// The version of each spawned dynamic scene that was last written to its instances.
+    spawned_scene_versions: HashMap<AssetId<DynamicScene>, DynamicScene>,
     pub(crate) spawned_instances: HashMap<InstanceId, InstanceInfo>,
     scene_asset_event_reader: EventCursor<AssetEvent<DynamicScene>>,
     dynamic_scenes_to_spawn: Vec<(Handle<DynamicScene>, InstanceId, Option<Entity>)>,
@@ -79,6 +88,147 @@ pub struct SceneSpawner {
     scenes_to_despawn: Vec<AssetId<DynamicScene>>,
     instances_to_despawn: Vec<InstanceId>,
     scenes_with_parent: Vec<(InstanceId, Entity)>,
+    incremental_spawns: Vec<IncrementalSpawn>,
+    incremental_spawn_budget: Option<Duration>,
+}
+
+/// Progress of a scene instance spawned over several frames.
+///
+/// See [`SceneSpawner::spawn_dynamic_incremental`] and [`SceneSpawner::spawn_progress`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SceneSpawnProgress {
+    /// Number of entities and components written to the world so far.
+    pub done: usize,
+    /// Total number of entities and components in the scene, or `0` while the scene is still loading.
+    pub total: usize,
+}
+
+impl SceneSpawnProgress {
+    /// Returns the fraction of the scene that was written to the world, between `0.0` and `1.0`.
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.done as f32 / self.total as f32
+        }
+    }
+}
+
+/// A dynamic scene instance being written to the world over several frames.
+struct IncrementalSpawn {
+    handle: Handle<DynamicScene>,
+    instance_id: InstanceId,
+    parent: Option<Entity>,
+    entity_map: EntityHashMap<Entity>,
+    progress: SceneSpawnProgress,
+    /// Index of the next scene entity to reserve a world entity for.
+    next_reserved: usize,
+    /// Index of the scene entity whose components are being written.
+    next_entity: usize,
+    /// Index of the next component of `next_entity` to write.
+    next_component: usize,
+}
+
+impl IncrementalSpawn {
+    fn new(handle: Handle<DynamicScene>, instance_id: InstanceId, parent: Option<Entity>) -> Self {
+        Self {
+            handle,
+            instance_id,
+            parent,
+            entity_map: EntityHashMap::default(),
+            progress: SceneSpawnProgress::default(),
+            next_reserved: 0,
+            next_entity: 0,
+            next_component: 0,
+        }
+    }
+
+    /// Despawns the entities created so far and starts the spawn over, for example because the scene changed.
+    fn restart(&mut self, world: &mut World) {
+        self.despawn_entities(world);
+        self.entity_map.clear();
+        self.progress = SceneSpawnProgress::default();
+        self.next_reserved = 0;
+        self.next_entity = 0;
+        self.next_component = 0;
+    }
+
+    /// Despawns the entities created so far.
+    fn despawn_entities(&self, world: &mut World) {
+        for &entity in self.entity_map.values() {
+            if let Ok(entity_mut) = world.get_entity_mut(entity) {
+                entity_mut.despawn();
+            }
+        }
+    }
+
+    /// Writes the scene to the world until `deadline`, returning `true` once the whole scene was written.
+    ///
+    /// A world entity is reserved for every scene entity before any component is written, so that
+    /// references to entities whose components haven't been written yet are mapped correctly.
+    fn step(
+        &mut self,
+        world: &mut World,
+        scene: &DynamicScene,
+        type_registry: &TypeRegistry,
+        deadline: Instant,
+    ) -> Result<bool, SceneSpawnError> {
+        let total = scene.entities.len()
+            + scene
+                .entities
+                .iter()
+                .map(|entity| entity.components.len())
+                .sum::<usize>();
+        if self.progress.total != total {
+            // The scene changed before its `AssetEvent::Modified` was received, so start over with the new scene.
+            if self.progress.total != 0 {
+                self.restart(world);
+            }
+            self.progress.total = total;
+        }
+
+        while let Some(scene_entity) = scene.entities.get(self.next_reserved) {
+            self.entity_map
+                .entry(scene_entity.entity)
+                .or_insert_with(|| world.spawn_empty().id());
+            self.next_reserved += 1;
+            self.progress.done += 1;
+            if Instant::now() >= deadline {
+                return Ok(false);
+            }
+        }
+
+        while let Some(scene_entity) = scene.entities.get(self.next_entity) {
+            let Some(component) = scene_entity.components.get(self.next_component) else {
+                self.next_entity += 1;
+                self.next_component = 0;
+                continue;
+            };
+            let Some(&entity) = self.entity_map.get(&scene_entity.entity) else {
+                // The scene changed since its entities were reserved, so start over with the new scene.
+                self.restart(world);
+                return Ok(false);
+            };
+            // The entity may have been despawned while the scene was being spawned.
+            if world.get_entity(entity).is_ok() {
+                DynamicScene::write_component_to_world(
+                    component.as_partial_reflect(),
+                    entity,
+                    world,
+                    &mut self.entity_map,
+                    type_registry,
+                )?;
+            }
+            self.next_component += 1;
+            self.progress.done += 1;
+            if Instant::now() >= deadline {
+                return Ok(false);
+            }
+        }
+
+        scene.write_resources_to_world(world, &mut self.entity_map, type_registry)?;
+        Ok(true)
+    }
 }
 
 /// Errors that can occur when spawning a scene.
@@ -133,9 +283,151 @@ pub enum SceneSpawnError {
         /// Id of the non-existent scene.
         id: AssetId<Scene>,
     },
+    /// Scene instance with the given id does not exist.
+    #[error("scene instance does not exist")]
+    NonExistentInstance {
+        /// Id of the non-existent scene instance.
+        id: InstanceId,
+    },
+    /// Scene instance with the given id wasn't spawned from a dynamic scene.
+    #[error("scene instance wasn't spawned from a dynamic scene")]
+    NotADynamicSceneInstance {
+        /// Id of the scene instance.
+        id: InstanceId,
+    },
 }
 
 impl SceneSpawner {
+    /// The time spent on incremental spawns per frame if no other budget was set.
+    pub const DEFAULT_INCREMENTAL_SPAWN_BUDGET: Duration = Duration::from_millis(2);
+
+    /// Returns the time spent writing incrementally spawned scenes to the world each frame.
+    pub fn incremental_spawn_budget(&self) -> Duration {
+        self.incremental_spawn_budget
+            .unwrap_or(Self::DEFAULT_INCREMENTAL_SPAWN_BUDGET)
+    }
+
+    /// Sets the time spent writing incrementally spawned scenes to the world each frame.
+    ///
+    /// The budget is shared by all incremental spawns. At least one entity or component is written each
+    /// frame, so spawns progress even with a budget of zero.
+    pub fn set_incremental_spawn_budget(&mut self, budget: Duration) {
+        self.incremental_spawn_budget = Some(budget);
+    }
+
+    /// Schedule the spawn of a new instance of the provided dynamic scene, spread over several frames.
+    ///
+    /// Unlike [`Self::spawn_dynamic`], which writes the whole scene to the world at once, the scene is
+    /// written a few entities and components at a time, within the
+    /// [incremental spawn budget](Self::set_incremental_spawn_budget) of each frame. The entities of the
+    /// instance exist and are partially built while it is being spawned. Use [`Self::spawn_progress`] to
+    /// follow the spawn; [`SceneInstanceReady`] is triggered once it is complete.
+    pub fn spawn_dynamic_incremental(&mut self, id: impl Into<Handle<DynamicScene>>) -> InstanceId {
+        let instance_id = InstanceId::new();
+        self.incremental_spawns
+            .push(IncrementalSpawn::new(id.into(), instance_id, None));
+        instance_id
+    }
+
+    /// Schedule the spawn of a new instance of the provided dynamic scene as a child of `parent`,
+    /// spread over several frames.
+    ///
+    /// See [`Self::spawn_dynamic_incremental`].
+    pub fn spawn_dynamic_incremental_as_child(
+        &mut self,
+        id: impl Into<Handle<DynamicScene>>,
+        parent: Entity,
+    ) -> InstanceId {
+        let instance_id = InstanceId::new();
+        self.incremental_spawns
+            .push(IncrementalSpawn::new(id.into(), instance_id, Some(parent)));
+        self.scenes_with_parent.push((instance_id, parent));
+        instance_id
+    }
+
+    /// Returns the progress of an instance scheduled with [`Self::spawn_dynamic_incremental`].
+    ///
+    /// Returns `None` once the instance is spawned, or if it isn't being spawned incrementally.
+    pub fn spawn_progress(&self, instance_id: InstanceId) -> Option<SceneSpawnProgress> {
+        self.incremental_spawns
+            .iter()
+            .find(|spawn| spawn.instance_id == instance_id)
+            .map(|spawn| spawn.progress)
+    }
+
+    /// Writes the incrementally spawned scenes to the world until the
+    /// [incremental spawn budget](Self::set_incremental_spawn_budget) is spent.
+    ///
+    /// Instances are spawned in the order they were scheduled in, and are recorded as spawned once
+    /// they are complete.
+    pub fn spawn_incremental_scenes(&mut self, world: &mut World) -> Result<(), SceneSpawnError> {
+        let deadline = Instant::now() + self.incremental_spawn_budget();
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+        let mut wrote = false;
+
+        let spawns = core::mem::take(&mut self.incremental_spawns);
+        for mut spawn in spawns {
+            if wrote && Instant::now() >= deadline {
+                self.incremental_spawns.push(spawn);
+                continue;
+            }
+
+            let done = world.resource_scope(|world, scenes: Mut<Assets<DynamicScene>>| {
+                let Some(scene) = scenes.get(spawn.handle.id()) else {
+                    return Ok(false);
+                };
+                wrote = true;
+                spawn.step(world, scene, &type_registry, deadline)
+            })?;
+            if !done {
+                self.incremental_spawns.push(spawn);
+                continue;
+            }
+
+            self.spawned_instances.insert(
+                spawn.instance_id,
+                InstanceInfo {
+                    entity_map: spawn.entity_map,
+                },
+            );
+            self.track_dynamic_instance(world, spawn.handle.id(), spawn.instance_id);
+            // Scenes with parents need more setup before they are ready.
+            // See `set_scene_instance_parent_sync()`.
+            if spawn.parent.is_none() {
+                world.commands().trigger(SceneInstanceReady {
+                    instance_id: spawn.instance_id,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Stops an incremental spawn, despawning the entities it already created.
+    fn cancel_incremental_spawn(&mut self, world: &mut World, instance_id: &InstanceId) {
+        let Some(index) = self
+            .incremental_spawns
+            .iter()
+            .position(|spawn| spawn.instance_id == *instance_id)
+        else {
+            return;
+        };
+        let spawn = self.incremental_spawns.remove(index);
+        spawn.despawn_entities(world);
+        self.scenes_with_parent
+            .retain(|(instance_id, _)| *instance_id != spawn.instance_id);
+    }
+
+    /// Restarts the incremental spawns of the dynamic scene `id`, because it was modified while they
+    /// were being written to the world.
+    fn restart_incremental_spawns(&mut self, world: &mut World, id: AssetId<DynamicScene>) {
+        for spawn in &mut self.incremental_spawns {
+            if spawn.handle.id() == id {
+                spawn.restart(world);
+            }
+        }
+    }
+
     /// Schedule the spawn of a new instance of the provided dynamic scene.
     pub fn spawn_dynamic(&mut self, id: impl Into<Handle<DynamicScene>>) -> InstanceId {
         let instance_id = InstanceId::new();
@@ -189,16 +481,28 @@ impl SceneSpawner {
         world: &mut World,
         id: impl Into<AssetId<DynamicScene>>,
     ) -> Result<(), SceneSpawnError> {
-        if let Some(instance_ids) = self.spawned_dynamic_scenes.remove(&id.into()) {
+        let id = id.into();
+        self.spawned_scene_versions.remove(&id);
+        if let Some(instance_ids) = self.spawned_dynamic_scenes.remove(&id) {
             for instance_id in instance_ids {
                 self.despawn_instance_sync(world, &instance_id);
             }
         }
+        let incremental_instance_ids = self
+            .incremental_spawns
+            .iter()
+            .filter(|spawn| spawn.handle.id() == id)
+            .map(|spawn| spawn.instance_id)
+            .collect::<Vec<_>>();
+        for instance_id in incremental_instance_ids {
+            self.cancel_incremental_spawn(world, &instance_id);
+        }
         Ok(())
     }
 
     /// Immediately despawns a scene instance, removing all its entities from the world.
     pub fn despawn_instance_sync(&mut self, world: &mut World, instance_id: &InstanceId) {
+        self.cancel_incremental_spawn(world, instance_id);
         if let Some(instance) = self.spawned_instances.remove(instance_id) {
             for &entity in instance.entity_map.values() {
                 if let Ok(entity_mut) = world.get_entity_mut(entity) {
@@ -220,11 +524,29 @@ impl SceneSpawner {
         let instance_id = InstanceId::new();
         self.spawned_instances
             .insert(instance_id, InstanceInfo { entity_map });
-        let spawned = self.spawned_dynamic_scenes.entry(id).or_default();
-        spawned.insert(instance_id);
+        self.track_dynamic_instance(world, id, instance_id);
         Ok(instance_id)
     }
 
+    /// Records `instance_id` as an instance of the dynamic scene `id`, keeping a copy of the scene so that
+    /// later modifications can be applied to the instance as a [`ScenePatch`].
+    fn track_dynamic_instance(
+        &mut self,
+        world: &World,
+        id: AssetId<DynamicScene>,
+        instance_id: InstanceId,
+    ) {
+        self.spawned_dynamic_scenes
+            .entry(id)
+            .or_default()
+            .insert(instance_id);
+        if !self.spawned_scene_versions.contains_key(&id) {
+            if let Some(scene) = world.resource::<Assets<DynamicScene>>().get(id) {
+                self.spawned_scene_versions.insert(id, clone_scene(scene));
+            }
+        }
+    }
+
     fn spawn_dynamic_internal(
         world: &mut World,
         id: AssetId<DynamicScene>,
@@ -275,19 +597,45 @@ impl SceneSpawner {
     /// Iterate through all instances of the provided scenes and update those immediately.
     ///
     /// Useful for updating already spawned scene instances after their corresponding scene has been modified.
+    /// Only the entities, components and resources that differ from the version of the scene the instances
+    /// were last updated with are written, so changes made to an instance at runtime are kept unless the
+    /// scene changed the same component.
     pub fn update_spawned_scenes(
         &mut self,
         world: &mut World,
         scene_ids: &[AssetId<DynamicScene>],
     ) -> Result<(), SceneSpawnError> {
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
         for id in scene_ids {
-            if let Some(spawned_instances) = self.spawned_dynamic_scenes.get(id) {
+            let Some(spawned_instances) = self.spawned_dynamic_scenes.get(id) else {
+                continue;
+            };
+            let Some(previous) = self.spawned_scene_versions.get(id) else {
                 for instance_id in spawned_instances {
                     if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
                         Self::spawn_dynamic_internal(world, *id, &mut instance_info.entity_map)?;
                     }
                 }
+                continue;
+            };
+            let scenes = world.resource::<Assets<DynamicScene>>();
+            let scene = scenes
+                .get(*id)
+                .ok_or(SceneSpawnError::NonExistentScene { id: *id })?;
+            let patch = ScenePatch::diff(previous, scene);
+            let scene = clone_scene(scene);
+            let type_registry = type_registry.read();
+            for instance_id in spawned_instances {
+                if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
+                    write_patch_to_instance(
+                        world,
+                        &patch,
+                        &mut instance_info.entity_map,
+                        &type_registry,
+                    )?;
+                }
             }
+            self.spawned_scene_versions.insert(*id, scene);
         }
         Ok(())
     }
@@ -322,11 +670,7 @@ impl SceneSpawner {
                 Ok(_) => {
                     self.spawned_instances
                         .insert(instance_id, InstanceInfo { entity_map });
-                    let spawned = self
-                        .spawned_dynamic_scenes
-                        .entry(handle.id())
-                        .or_insert_with(HashSet::default);
-                    spawned.insert(instance_id);
+                    self.track_dynamic_instance(world, handle.id(), instance_id);
 
                     // Scenes with parents need more setup before they are ready.
                     // See `set_scene_instance_parent_sync()`.
@@ -424,6 +768,205 @@ impl SceneSpawner {
     }
 }
 
+impl SceneSpawner {
+    /// Builds a [`DynamicScene`] from the current state of a spawned instance.
+    ///
+    /// The instance's entities are extracted with `builder`, whose filters decide which components are
+    /// included. Resources are only included if they were extracted with `builder` beforehand.
+    ///
+    /// Entities keep the identifier they have in the scene the instance was spawned from, and entity
+    /// references between them are mapped back accordingly. Entities that were added to the hierarchy of
+    /// the instance since it was spawned get new identifiers, and despawned entities are left out.
+    /// Components referencing entities outside of the instance, like the [`ChildOf`] of the root entities
+    /// of an instance spawned as a child, are left out as well.
+    pub fn instance_to_dynamic_scene(
+        &self,
+        builder: DynamicSceneBuilder,
+        instance_id: InstanceId,
+    ) -> Result<DynamicScene, SceneSpawnError> {
+        let instance = self
+            .spawned_instances
+            .get(&instance_id)
+            .ok_or(SceneSpawnError::NonExistentInstance { id: instance_id })?;
+        let world = builder.world();
+
+        // Map the living entities of the instance back to the scene.
+        let mut scene_entities = EntityHashMap::default();
+        let mut next_index = 0;
+        for (&scene_entity, &entity) in &instance.entity_map {
+            next_index = next_index.max(scene_entity.index() + 1);
+            if world.get_entity(entity).is_ok() {
+                scene_entities.insert(entity, scene_entity);
+            }
+        }
+        let mut to_visit = scene_entities.keys().copied().collect::<Vec<_>>();
+        to_visit.sort_unstable_by(|a, b| b.cmp(a));
+        while let Some(entity) = to_visit.pop() {
+            let Some(children) = world.get::<Children>(entity) else {
+                continue;
+            };
+            for &child in children {
+                if !scene_entities.contains_key(&child) {
+                    scene_entities.insert(child, Entity::from_raw(next_index));
+                    next_index += 1;
+                    to_visit.push(child);
+                }
+            }
+        }
+
+        let mut scene = builder
+            .extract_entities(scene_entities.keys().copied())
+            .build();
+        let type_registry = world.resource::<AppTypeRegistry>().read();
+        for entity in &mut scene.entities {
+            entity.entity = scene_entities[&entity.entity];
+            entity.components.retain_mut(|component| {
+                let Some(reflect_component) =
+                    component.get_represented_type_info().and_then(|info| {
+                        type_registry.get_type_data::<ReflectComponent>(info.type_id())
+                    })
+                else {
+                    return true;
+                };
+                let Some(component) = component.try_as_reflect_mut() else {
+                    return true;
+                };
+                let mut is_local = true;
+                reflect_component.visit_entities_mut(component, &mut |entity| match scene_entities
+                    .get(entity)
+                {
+                    Some(&scene_entity) => *entity = scene_entity,
+                    None => is_local = false,
+                });
+                is_local
+            });
+        }
+        scene.entities.sort_by_key(|entity| entity.entity);
+
+        for resource in &mut scene.resources {
+            if let Some(map_entities) = resource
+                .get_represented_type_info()
+                .and_then(|info| type_registry.get_type_data::<ReflectMapEntities>(info.type_id()))
+            {
+                map_entities.map_entities(resource.as_partial_reflect_mut(), &mut scene_entities);
+            }
+        }
+
+        Ok(scene)
+    }
+
+    /// Builds the [`ScenePatch`] that turns the dynamic scene an instance was spawned from into the
+    /// current state of the instance.
+    ///
+    /// The state of the instance is extracted with [`Self::instance_to_dynamic_scene`]. Applying the patch
+    /// to the source scene, for example by saving it as a [`ScenePrefab`](crate::ScenePrefab), recreates the
+    /// instance.
+    pub fn instance_to_patch(
+        &self,
+        builder: DynamicSceneBuilder,
+        instance_id: InstanceId,
+    ) -> Result<ScenePatch, SceneSpawnError> {
+        let world = builder.world();
+        let id = self
+            .spawned_dynamic_scenes
+            .iter()
+            .find(|(_, instances)| instances.contains(&instance_id))
+            .map(|(id, _)| *id)
+            .ok_or(SceneSpawnError::NotADynamicSceneInstance { id: instance_id })?;
+        let source = world
+            .resource::<Assets<DynamicScene>>()
+            .get(id)
+            .ok_or(SceneSpawnError::NonExistentScene { id })?;
+        let scene = self.instance_to_dynamic_scene(builder, instance_id)?;
+        Ok(ScenePatch::diff(source, &scene))
+    }
+}
+
+/// Copies a dynamic scene, cloning its components and resources.
+fn clone_scene(scene: &DynamicScene) -> DynamicScene {
+    DynamicScene {
+        resources: scene
+            .resources
+            .iter()
+            .map(|resource| resource.clone_value())
+            .collect(),
+        entities: scene
+            .entities
+            .iter()
+            .map(|entity| DynamicEntity {
+                entity: entity.entity,
+                components: entity
+                    .components
+                    .iter()
+                    .map(|component| component.clone_value())
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+/// Applies the changes of `patch` to a spawned instance of its source scene.
+///
+/// Entities the patch adds are spawned, and entities that were despawned since the instance was spawned
+/// are left alone.
+fn write_patch_to_instance(
+    world: &mut World,
+    patch: &ScenePatch,
+    entity_map: &mut EntityHashMap<Entity>,
+    type_registry: &TypeRegistry,
+) -> Result<(), SceneSpawnError> {
+    for scene_entity in &patch.removed_entities {
+        if let Some(entity) = entity_map.remove(scene_entity) {
+            if let Ok(entity_mut) = world.get_entity_mut(entity) {
+                entity_mut.despawn();
+            }
+        }
+    }
+
+    // Reserve the added entities first, so that components referencing them are mapped correctly.
+    for entity_patch in &patch.entities {
+        entity_map
+            .entry(entity_patch.entity)
+            .or_insert_with(|| world.spawn_empty().id());
+    }
+
+    for entity_patch in &patch.entities {
+        let entity = entity_map[&entity_patch.entity];
+        let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+            continue;
+        };
+        for type_path in &entity_patch.removed_components {
+            let registration = type_registry.get_with_type_path(type_path).ok_or_else(|| {
+                SceneSpawnError::UnregisteredType {
+                    std_type_name: type_path.clone(),
+                }
+            })?;
+            if let Some(reflect_component) = registration.data::<ReflectComponent>() {
+                reflect_component.remove(&mut entity_mut);
+            }
+        }
+        for component in &entity_patch.components {
+            DynamicScene::write_component_to_world(
+                component.as_partial_reflect(),
+                entity,
+                world,
+                entity_map,
+                type_registry,
+            )?;
+        }
+    }
+
+    let resources = DynamicScene {
+        resources: patch
+            .resources
+            .iter()
+            .map(|resource| resource.clone_value())
+            .collect(),
+        entities: Vec::new(),
+    };
+    resources.write_resources_to_world(world, entity_map, type_registry)
+}
+
 /// System that handles scheduled scene instance spawning and despawning through a [`SceneSpawner`].
 pub fn scene_spawner_system(world: &mut World) {
     world.resource_scope(|world, mut scene_spawner: Mut<SceneSpawner>| {
@@ -446,10 +989,14 @@ pub fn scene_spawner_system(world: &mut World) {
         scene_spawner
             .scenes_to_spawn
             .retain(|(_, instance, _)| !dead_instances.contains(instance));
+        for instance in &dead_instances {
+            scene_spawner.cancel_incremental_spawn(world, instance);
+        }
 
         let scene_asset_events = world.resource::<Events<AssetEvent<DynamicScene>>>();
 
         let mut updated_spawned_scenes = Vec::new();
+        let mut modified_scenes = Vec::new();
         let scene_spawner = &mut *scene_spawner;
         for event in scene_spawner
             .scene_asset_event_reader
@@ -459,14 +1006,21 @@ pub fn scene_spawner_system(world: &mut World) {
                 if scene_spawner.spawned_dynamic_scenes.contains_key(id) {
                     updated_spawned_scenes.push(*id);
                 }
+                modified_scenes.push(*id);
             }
         }
+        for id in modified_scenes {
+            scene_spawner.restart_incremental_spawns(world, id);
+        }
 
         scene_spawner.despawn_queued_scenes(world).unwrap();
         scene_spawner.despawn_queued_instances(world);
         scene_spawner
             .spawn_queued_scenes(world)
             .unwrap_or_else(|err| panic!("{}", err));
+        scene_spawner
+            .spawn_incremental_scenes(world)
+            .unwrap_or_else(|err| panic!("{}", err));
         scene_spawner
             .update_spawned_scenes(world, &updated_spawned_scenes)
             .unwrap();
@@ -487,7 +1041,12 @@ pub fn scene_spawner(
         (Changed<SceneRoot>, Without<DynamicSceneRoot>),
     >,
     mut dynamic_scene_to_spawn: Query<
-        (Entity, &DynamicSceneRoot, Option<&mut SceneInstance>),
+        (
+            Entity,
+            &DynamicSceneRoot,
+            Option<&mut SceneInstance>,
+            Has<IncrementalSceneSpawn>,
+        ),
         (Changed<DynamicSceneRoot>, Without<SceneRoot>),
     >,
     mut scene_spawner: ResMut<SceneSpawner>,
@@ -501,8 +1060,12 @@ pub fn scene_spawner(
             commands.entity(entity).insert(SceneInstance(new_instance));
         }
     }
-    for (entity, dynamic_scene, instance) in &mut dynamic_scene_to_spawn {
-        let new_instance = scene_spawner.spawn_dynamic_as_child(dynamic_scene.0.clone(), entity);
+    for (entity, dynamic_scene, instance, incremental) in &mut dynamic_scene_to_spawn {
+        let new_instance = if incremental {
+            scene_spawner.spawn_dynamic_incremental_as_child(dynamic_scene.0.clone(), entity)
+        } else {
+            scene_spawner.spawn_dynamic_as_child(dynamic_scene.0.clone(), entity)
+        };
         if let Some(mut old_instance) = instance {
             scene_spawner.despawn_instance(**old_instance);
             *old_instance = SceneInstance(new_instance);
@@ -861,4 +1424,258 @@ mod tests {
         app.update();
         check(app.world_mut(), 0);
     }
+
+    #[derive(Component, Reflect, PartialEq, Debug)]
+    #[reflect(Component, PartialEq)]
+    struct Target(#[entities] Entity);
+
+    #[test]
+    fn instance_to_dynamic_scene_and_patch() {
+        let mut app = App::new();
+        app.add_plugins((AssetPlugin::default(), ScenePlugin))
+            .register_type::<ComponentA>()
+            .register_type::<Target>();
+
+        let mut scene_world = World::new();
+        scene_world.insert_resource(app.world().resource::<AppTypeRegistry>().clone());
+        let a = scene_world.spawn(ComponentA { x: 1.0, y: 2.0 }).id();
+        let b = scene_world
+            .spawn((ComponentA { x: 3.0, y: 4.0 }, Target(a)))
+            .id();
+        let c = scene_world.spawn(ComponentA { x: 5.0, y: 6.0 }).id();
+        let scene = app
+            .world_mut()
+            .resource_mut::<Assets<DynamicScene>>()
+            .add(DynamicScene::from_world(&scene_world));
+
+        let root = app.world_mut().spawn(DynamicSceneRoot(scene)).id();
+        app.update();
+        let instance_id = **app.world().get::<SceneInstance>(root).unwrap();
+        let entity_map =
+            &app.world().resource::<SceneSpawner>().spawned_instances[&instance_id].entity_map;
+        let (a_instance, b_instance, c_instance) = (entity_map[&a], entity_map[&b], entity_map[&c]);
+
+        // Edit the instance at runtime.
+        app.world_mut().get_mut::<ComponentA>(a_instance).unwrap().x = 10.0;
+        app.world_mut().despawn(c_instance);
+        app.world_mut()
+            .spawn((ComponentA { x: 7.0, y: 8.0 }, ChildOf(b_instance)));
+
+        let world = app.world();
+        let scene_spawner = world.resource::<SceneSpawner>();
+        let builder = || DynamicSceneBuilder::from_world(world).deny_component::<Children>();
+        let dynamic_scene = scene_spawner
+            .instance_to_dynamic_scene(builder(), instance_id)
+            .unwrap();
+        let added = Entity::from_raw(3);
+        assert_eq!(
+            dynamic_scene
+                .entities
+                .iter()
+                .map(|entity| entity.entity)
+                .collect::<Vec<_>>(),
+            [a, b, added]
+        );
+        let components = |entity: usize| {
+            dynamic_scene.entities[entity]
+                .components
+                .iter()
+                .map(|component| component.reflect_type_path())
+                .collect::<Vec<_>>()
+        };
+        // The `ChildOf` of the root entities points outside of the instance.
+        assert!(!components(0).contains(&core::any::type_name::<ChildOf>()));
+        let target = dynamic_scene.entities[1]
+            .components
+            .iter()
+            .find_map(|component| component.try_downcast_ref::<Target>())
+            .unwrap();
+        assert_eq!(target, &Target(a));
+        let child_of = dynamic_scene.entities[2]
+            .components
+            .iter()
+            .find_map(|component| component.try_downcast_ref::<ChildOf>())
+            .unwrap();
+        assert_eq!(child_of.get(), b);
+
+        let patch = scene_spawner
+            .instance_to_patch(builder(), instance_id)
+            .unwrap();
+        assert_eq!(patch.removed_entities, [c]);
+        assert_eq!(
+            patch
+                .entities
+                .iter()
+                .map(|patch| (patch.entity, patch.components.len()))
+                .collect::<Vec<_>>(),
+            [(a, 1), (added, 2)]
+        );
+        assert!(patch.entities[0].components[0]
+            .reflect_partial_eq(&ComponentA { x: 10.0, y: 2.0 })
+            .unwrap());
+    }
+
+    #[test]
+    fn spawn_incrementally() {
+        let mut app = App::new();
+        app.add_plugins((AssetPlugin::default(), ScenePlugin))
+            .register_type::<ComponentA>()
+            .register_type::<Target>()
+            .init_resource::<TriggerCount>();
+        app.world_mut()
+            .resource_mut::<SceneSpawner>()
+            .set_incremental_spawn_budget(Duration::ZERO);
+
+        let mut scene_world = World::new();
+        scene_world.insert_resource(app.world().resource::<AppTypeRegistry>().clone());
+        let a = scene_world.spawn(ComponentA { x: 1.0, y: 2.0 }).id();
+        let b = scene_world.spawn(ComponentA { x: 3.0, y: 4.0 }).id();
+        let c = scene_world.spawn(ComponentA { x: 5.0, y: 6.0 }).id();
+        // Reference an entity whose components are written in a later batch.
+        scene_world.entity_mut(a).insert(Target(c));
+        let scene = app
+            .world_mut()
+            .resource_mut::<Assets<DynamicScene>>()
+            .add(DynamicScene::from_world(&scene_world));
+
+        let root = app
+            .world_mut()
+            .spawn((DynamicSceneRoot(scene), IncrementalSceneSpawn))
+            .id();
+        app.world_mut().add_observer(
+            |_: Trigger<SceneInstanceReady>, mut trigger_count: ResMut<TriggerCount>| {
+                trigger_count.0 += 1;
+            },
+        );
+        app.update();
+        let instance_id = **app.world().get::<SceneInstance>(root).unwrap();
+
+        let mut last_progress = SceneSpawnProgress::default();
+        let mut frames = 1;
+        while let Some(progress) = app
+            .world()
+            .resource::<SceneSpawner>()
+            .spawn_progress(instance_id)
+        {
+            // A zero budget writes a single entity or component each frame.
+            assert_eq!(progress.total, 7);
+            assert_eq!(progress.done, last_progress.done + 1);
+            assert_eq!(app.world().resource::<TriggerCount>().0, 0);
+            last_progress = progress;
+            app.update();
+            frames += 1;
+        }
+        assert_eq!(last_progress.done, 7);
+        assert_eq!(frames, 8);
+        assert_eq!(app.world().resource::<TriggerCount>().0, 1);
+
+        let scene_spawner = app.world().resource::<SceneSpawner>();
+        assert!(scene_spawner.instance_is_ready(instance_id));
+        let entity_map = &scene_spawner.spawned_instances[&instance_id].entity_map;
+        assert_eq!(
+            app.world().get::<Target>(entity_map[&a]),
+            Some(&Target(entity_map[&c]))
+        );
+        assert_eq!(
+            app.world().get::<ChildOf>(entity_map[&b]).unwrap().get(),
+            root
+        );
+    }
+
+    #[test]
+    fn incremental_spawn_restarts_when_scene_changes() {
+        let mut app = App::new();
+        app.add_plugins((AssetPlugin::default(), ScenePlugin))
+            .register_type::<ComponentA>();
+        app.world_mut()
+            .resource_mut::<SceneSpawner>()
+            .set_incremental_spawn_budget(Duration::ZERO);
+
+        let mut scene_world = World::new();
+        scene_world.insert_resource(app.world().resource::<AppTypeRegistry>().clone());
+        scene_world.spawn(ComponentA { x: 1.0, y: 2.0 });
+        scene_world.spawn(ComponentA { x: 3.0, y: 4.0 });
+        let scene = app
+            .world_mut()
+            .resource_mut::<Assets<DynamicScene>>()
+            .add(DynamicScene::from_world(&scene_world));
+        let parent = app.world_mut().spawn_empty().id();
+        let instance_id = app
+            .world_mut()
+            .resource_mut::<SceneSpawner>()
+            .spawn_dynamic_incremental_as_child(scene.clone(), parent);
+        for _ in 0..3 {
+            app.update();
+        }
+
+        // Replace the scene with a smaller one while its entities are being written.
+        let mut scene_world = World::new();
+        scene_world.insert_resource(app.world().resource::<AppTypeRegistry>().clone());
+        scene_world.spawn(ComponentA { x: 5.0, y: 6.0 });
+        app.world_mut()
+            .resource_mut::<Assets<DynamicScene>>()
+            .insert(&scene, DynamicScene::from_world(&scene_world));
+        app.update();
+        let progress = app
+            .world()
+            .resource::<SceneSpawner>()
+            .spawn_progress(instance_id)
+            .unwrap();
+        assert_eq!(progress.total, 2);
+
+        while app
+            .world()
+            .resource::<SceneSpawner>()
+            .spawn_progress(instance_id)
+            .is_some()
+        {
+            app.update();
+        }
+        let mut query = app.world_mut().query::<&ComponentA>();
+        let components = query.iter(app.world()).collect::<Vec<_>>();
+        assert_eq!(components.len(), 1);
+        assert_eq!((components[0].x, components[0].y), (5.0, 6.0));
+        assert!(app
+            .world()
+            .resource::<SceneSpawner>()
+            .instance_is_ready(instance_id));
+    }
+
+    #[test]
+    fn cancel_incremental_spawn_with_parent() {
+        let mut app = App::new();
+        app.add_plugins((AssetPlugin::default(), ScenePlugin))
+            .register_type::<ComponentA>();
+        app.world_mut()
+            .resource_mut::<SceneSpawner>()
+            .set_incremental_spawn_budget(Duration::ZERO);
+
+        let mut scene_world = World::new();
+        scene_world.insert_resource(app.world().resource::<AppTypeRegistry>().clone());
+        scene_world.spawn(ComponentA { x: 1.0, y: 2.0 });
+        let scene = app
+            .world_mut()
+            .resource_mut::<Assets<DynamicScene>>()
+            .add(DynamicScene::from_world(&scene_world));
+        let parent = app.world_mut().spawn_empty().id();
+        let instance_id = app
+            .world_mut()
+            .resource_mut::<SceneSpawner>()
+            .spawn_dynamic_incremental_as_child(scene, parent);
+        app.update();
+
+        app.world_mut()
+            .resource_scope(|world, mut scene_spawner: Mut<SceneSpawner>| {
+                scene_spawner.despawn_instance_sync(world, &instance_id);
+                assert!(scene_spawner.spawn_progress(instance_id).is_none());
+                assert!(scene_spawner.scenes_with_parent.is_empty());
+            });
+        assert_eq!(
+            app.world_mut()
+                .query::<&ComponentA>()
+                .iter(app.world())
+                .count(),
+            0
+        );
+    }
 }
@@ -1,6 +1,6 @@
 //! `serde` serialization and deserialization implementation for Bevy scenes.
 
-use crate::{DynamicEntity, DynamicScene};
+use crate::{DynamicEntity, DynamicScene, EntityPatch, ScenePatch, ScenePrefab};
 use bevy_ecs::entity::Entity;
 use bevy_platform_support::collections::HashSet;
 use bevy_reflect::{
@@ -17,6 +17,10 @@ use serde::{
     Deserialize, Deserializer, Serialize, Serializer,
 };
 
+mod readable;
+
+pub use readable::*;
+
 /// Name of the serialized scene struct type.
 pub const SCENE_STRUCT: &str = "Scene";
 /// Name of the serialized resources field in a scene struct.
@@ -29,6 +33,18 @@ pub const ENTITY_STRUCT: &str = "Entity";
 /// Name of the serialized component field in an entity struct.
 pub const ENTITY_FIELD_COMPONENTS: &str = "components";
 
+/// Name of the serialized prefab struct type.
+pub const PREFAB_STRUCT: &str = "Prefab";
+/// Name of the serialized base scene field in a prefab struct.
+pub const PREFAB_BASE: &str = "base";
+/// Name of the serialized removed entities field in a prefab struct.
+pub const PREFAB_REMOVED_ENTITIES: &str = "removed_entities";
+
+/// Name of the serialized entity patch struct type.
+pub const ENTITY_PATCH_STRUCT: &str = "EntityPatch";
+/// Name of the serialized removed components field in an entity patch struct.
+pub const ENTITY_PATCH_REMOVED_COMPONENTS: &str = "removed_components";
+
 /// Serializer for a [`DynamicScene`].
 ///
 /// Helper object defining Bevy's serialize format for a [`DynamicScene`] and implementing
@@ -507,6 +523,384 @@ impl<'a, 'de> Visitor<'de> for SceneMapVisitor<'a> {
     }
 }
 
+/// Serializer for a [`ScenePrefab`].
+///
+/// Resources and component overrides are written in the same format as in a [`SceneSerializer`].
+pub struct PrefabSerializer<'a> {
+    /// The prefab to serialize.
+    pub prefab: &'a ScenePrefab,
+    /// The type registry containing the types present in the prefab.
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> PrefabSerializer<'a> {
+    /// Create a new serializer from a [`ScenePrefab`] and an associated [`TypeRegistry`].
+    pub fn new(prefab: &'a ScenePrefab, registry: &'a TypeRegistry) -> Self {
+        PrefabSerializer { prefab, registry }
+    }
+}
+
+impl<'a> Serialize for PrefabSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let patch = &self.prefab.patch;
+        let mut state = serializer.serialize_struct(PREFAB_STRUCT, 4)?;
+        state.serialize_field(PREFAB_BASE, &self.prefab.base)?;
+        state.serialize_field(
+            SCENE_RESOURCES,
+            &SceneMapSerializer {
+                entries: &patch.resources,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(
+            SCENE_ENTITIES,
+            &EntityPatchesSerializer {
+                entities: &patch.entities,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(PREFAB_REMOVED_ENTITIES, &patch.removed_entities)?;
+        state.end()
+    }
+}
+
+/// Handles serialization of entity patches as a map of entity id to serialized patch.
+pub struct EntityPatchesSerializer<'a> {
+    /// The entity patches to serialize.
+    pub entities: &'a [EntityPatch],
+    /// Type registry in which the component types used by the patches are registered.
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for EntityPatchesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_map(Some(self.entities.len()))?;
+        for patch in self.entities {
+            state.serialize_entry(
+                &patch.entity,
+                &EntityPatchSerializer {
+                    patch,
+                    registry: self.registry,
+                },
+            )?;
+        }
+        state.end()
+    }
+}
+
+/// Handles serialization of the components and removed components of an entity patch.
+pub struct EntityPatchSerializer<'a> {
+    /// The entity patch to serialize.
+    pub patch: &'a EntityPatch,
+    /// Type registry in which the component types used by the patch are registered.
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for EntityPatchSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct(ENTITY_PATCH_STRUCT, 2)?;
+        state.serialize_field(
+            ENTITY_FIELD_COMPONENTS,
+            &SceneMapSerializer {
+                entries: &self.patch.components,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(
+            ENTITY_PATCH_REMOVED_COMPONENTS,
+            &self.patch.removed_components,
+        )?;
+        state.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "snake_case")]
+enum PrefabField {
+    Base,
+    Resources,
+    Entities,
+    RemovedEntities,
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "snake_case")]
+enum EntityPatchField {
+    Components,
+    RemovedComponents,
+}
+
+/// Handles prefab deserialization.
+///
+/// Apart from `base`, every field of a prefab may be omitted.
+pub struct PrefabDeserializer<'a> {
+    /// Type registry in which the components and resources types used in the prefab to deserialize are registered.
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for PrefabDeserializer<'a> {
+    type Value = ScenePrefab;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            PREFAB_STRUCT,
+            &[
+                PREFAB_BASE,
+                SCENE_RESOURCES,
+                SCENE_ENTITIES,
+                PREFAB_REMOVED_ENTITIES,
+            ],
+            PrefabVisitor {
+                type_registry: self.type_registry,
+            },
+        )
+    }
+}
+
+struct PrefabVisitor<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for PrefabVisitor<'a> {
+    type Value = ScenePrefab;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("prefab struct")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let base = seq
+            .next_element()?
+            .ok_or_else(|| Error::missing_field(PREFAB_BASE))?;
+        let resources = seq
+            .next_element_seed(SceneMapDeserializer {
+                registry: self.type_registry,
+            })?
+            .ok_or_else(|| Error::missing_field(SCENE_RESOURCES))?;
+        let entities = seq
+            .next_element_seed(EntityPatchesDeserializer {
+                type_registry: self.type_registry,
+            })?
+            .ok_or_else(|| Error::missing_field(SCENE_ENTITIES))?;
+        let removed_entities = seq
+            .next_element()?
+            .ok_or_else(|| Error::missing_field(PREFAB_REMOVED_ENTITIES))?;
+
+        Ok(ScenePrefab {
+            base,
+            patch: ScenePatch {
+                resources,
+                entities,
+                removed_entities,
+            },
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut base = None;
+        let mut resources = None;
+        let mut entities = None;
+        let mut removed_entities = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                PrefabField::Base => {
+                    if base.is_some() {
+                        return Err(Error::duplicate_field(PREFAB_BASE));
+                    }
+                    base = Some(map.next_value()?);
+                }
+                PrefabField::Resources => {
+                    if resources.is_some() {
+                        return Err(Error::duplicate_field(SCENE_RESOURCES));
+                    }
+                    resources = Some(map.next_value_seed(SceneMapDeserializer {
+                        registry: self.type_registry,
+                    })?);
+                }
+                PrefabField::Entities => {
+                    if entities.is_some() {
+                        return Err(Error::duplicate_field(SCENE_ENTITIES));
+                    }
+                    entities = Some(map.next_value_seed(EntityPatchesDeserializer {
+                        type_registry: self.type_registry,
+                    })?);
+                }
+                PrefabField::RemovedEntities => {
+                    if removed_entities.is_some() {
+                        return Err(Error::duplicate_field(PREFAB_REMOVED_ENTITIES));
+                    }
+                    removed_entities = Some(map.next_value()?);
+                }
+            }
+        }
+
+        Ok(ScenePrefab {
+            base: base.ok_or_else(|| Error::missing_field(PREFAB_BASE))?,
+            patch: ScenePatch {
+                resources: resources.unwrap_or_default(),
+                entities: entities.unwrap_or_default(),
+                removed_entities: removed_entities.unwrap_or_default(),
+            },
+        })
+    }
+}
+
+/// Handles deserialization for a collection of entity patches.
+pub struct EntityPatchesDeserializer<'a> {
+    /// Type registry in which the component types used by the patches to deserialize are registered.
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for EntityPatchesDeserializer<'a> {
+    type Value = Vec<EntityPatch>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(EntityPatchesVisitor {
+            type_registry: self.type_registry,
+        })
+    }
+}
+
+struct EntityPatchesVisitor<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for EntityPatchesVisitor<'a> {
+    type Value = Vec<EntityPatch>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("map of entity patches")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entities = Vec::new();
+        while let Some(entity) = map.next_key::<Entity>()? {
+            let patch = map.next_value_seed(EntityPatchDeserializer {
+                entity,
+                type_registry: self.type_registry,
+            })?;
+            entities.push(patch);
+        }
+
+        Ok(entities)
+    }
+}
+
+/// Handle deserialization of an entity patch.
+pub struct EntityPatchDeserializer<'a> {
+    /// Id of the patched entity.
+    pub entity: Entity,
+    /// Type registry in which the component types used by the patch to deserialize are registered.
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for EntityPatchDeserializer<'a> {
+    type Value = EntityPatch;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            ENTITY_PATCH_STRUCT,
+            &[ENTITY_FIELD_COMPONENTS, ENTITY_PATCH_REMOVED_COMPONENTS],
+            EntityPatchVisitor {
+                entity: self.entity,
+                registry: self.type_registry,
+            },
+        )
+    }
+}
+
+struct EntityPatchVisitor<'a> {
+    pub entity: Entity,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for EntityPatchVisitor<'a> {
+    type Value = EntityPatch;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("entity patch")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let components = seq
+            .next_element_seed(SceneMapDeserializer {
+                registry: self.registry,
+            })?
+            .ok_or_else(|| Error::missing_field(ENTITY_FIELD_COMPONENTS))?;
+        let removed_components = seq
+            .next_element()?
+            .ok_or_else(|| Error::missing_field(ENTITY_PATCH_REMOVED_COMPONENTS))?;
+
+        Ok(EntityPatch {
+            entity: self.entity,
+            components,
+            removed_components,
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut components = None;
+        let mut removed_components = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                EntityPatchField::Components => {
+                    if components.is_some() {
+                        return Err(Error::duplicate_field(ENTITY_FIELD_COMPONENTS));
+                    }
+                    components = Some(map.next_value_seed(SceneMapDeserializer {
+                        registry: self.registry,
+                    })?);
+                }
+                EntityPatchField::RemovedComponents => {
+                    if removed_components.is_some() {
+                        return Err(Error::duplicate_field(ENTITY_PATCH_REMOVED_COMPONENTS));
+                    }
+                    removed_components = Some(map.next_value()?);
+                }
+            }
+        }
+
+        Ok(EntityPatch {
+            entity: self.entity,
+            components: components.unwrap_or_default(),
+            removed_components: removed_components.unwrap_or_default(),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
@@ -0,0 +1,890 @@
+use super::{SCENE_ENTITIES, SCENE_RESOURCES, SCENE_STRUCT};
+use crate::{DynamicEntity, DynamicScene};
+use alloc::borrow::Cow;
+use bevy_ecs::{
+    entity::{hash_map::EntityHashMap, Entity},
+    name::Name,
+};
+use bevy_platform_support::collections::{HashMap, HashSet};
+use bevy_reflect::{
+    prelude::ReflectDefault,
+    serde::{
+        ReflectDeserializerProcessor, ReflectSerializerProcessor, TypedReflectDeserializer,
+        TypedReflectSerializer,
+    },
+    GetTypeRegistration, PartialReflect, ReflectFromReflect, TypeInfo, TypeRegistration,
+    TypeRegistry,
+};
+use core::{any::TypeId, fmt::Formatter};
+use serde::{
+    de::{DeserializeSeed, Error, MapAccess, SeqAccess, Visitor},
+    ser::{SerializeMap, SerializeStruct},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// Name of the serialized field listing the components left at their default value in a readable entity struct.
+pub const ENTITY_FIELD_DEFAULTS: &str = "defaults";
+
+/// Type data holding the short name a type is written with in readable scenes.
+///
+/// Register it with [`register_scene_type_alias`].
+#[derive(Clone, Debug)]
+pub struct SceneTypeAlias(pub Cow<'static, str>);
+
+/// Registers `T` and makes readable scenes refer to it as `alias` instead of its full type path.
+///
+/// Aliases must be unique, and shouldn't be the type path of another type.
+pub fn register_scene_type_alias<T: GetTypeRegistration>(
+    registry: &mut TypeRegistry,
+    alias: impl Into<Cow<'static, str>>,
+) {
+    registry.register::<T>();
+    registry
+        .get_mut(TypeId::of::<T>())
+        .expect("the type was registered above")
+        .insert(SceneTypeAlias(alias.into()));
+}
+
+/// Serializer for a [`DynamicScene`] in a format meant to be read and edited by people.
+///
+/// Compared to the [`SceneSerializer`](super::SceneSerializer):
+/// - Entities are keyed by their [`Name`] when it is unique within the scene, and by `#` followed
+///   by their index otherwise. Entity references within components and resources are written the
+///   same way.
+/// - Types are written with their [`SceneTypeAlias`] when they have one.
+/// - Components that have their default value are only listed by type in the `defaults` of their
+///   entity.
+/// - Entities, components and resources are sorted, so the output is stable.
+///
+/// Scenes written by this serializer are read by the [`ReadableSceneDeserializer`]. Named entities
+/// are identified by a hash of their name in the deserialized scene, so their ids don't change when
+/// other entities are added, removed or renamed, and all other data round-trips as is.
+pub struct ReadableSceneSerializer<'a> {
+    /// The scene to serialize.
+    pub scene: &'a DynamicScene,
+    /// The type registry containing the types present in the scene.
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> ReadableSceneSerializer<'a> {
+    /// Create a new serializer from a [`DynamicScene`] and an associated [`TypeRegistry`].
+    pub fn new(scene: &'a DynamicScene, registry: &'a TypeRegistry) -> Self {
+        ReadableSceneSerializer { scene, registry }
+    }
+}
+
+impl<'a> Serialize for ReadableSceneSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let keys = EntityKeys::new(self.scene);
+        let mut state = serializer.serialize_struct(SCENE_STRUCT, 2)?;
+        state.serialize_field(
+            SCENE_RESOURCES,
+            &ReadableMapSerializer {
+                entries: self.scene.resources.iter().map(AsRef::as_ref).collect(),
+                registry: self.registry,
+                keys: &keys,
+            },
+        )?;
+        state.serialize_field(
+            SCENE_ENTITIES,
+            &ReadableEntitiesSerializer {
+                scene: self.scene,
+                registry: self.registry,
+                keys: &keys,
+            },
+        )?;
+        state.end()
+    }
+}
+
+/// The keys entities are written with.
+struct EntityKeys {
+    names: EntityHashMap<String>,
+}
+
+impl EntityKeys {
+    fn new(scene: &DynamicScene) -> Self {
+        let mut name_counts = HashMap::<&str, usize>::default();
+        let entity_names = scene
+            .entities
+            .iter()
+            .filter_map(|entity| Some((entity.entity, entity_name(entity)?)))
+            .collect::<Vec<_>>();
+        for (_, name) in &entity_names {
+            *name_counts.entry(*name).or_default() += 1;
+        }
+        let names = entity_names
+            .into_iter()
+            .filter(|(_, name)| name_counts[name] == 1 && !name.starts_with('#'))
+            .map(|(entity, name)| (entity, name.to_string()))
+            .collect();
+        Self { names }
+    }
+
+    fn key(&self, entity: Entity) -> String {
+        match self.names.get(&entity) {
+            Some(name) => name.clone(),
+            None if entity.generation() == 1 => format!("#{}", entity.index()),
+            None => format!("#{}v{}", entity.index(), entity.generation()),
+        }
+    }
+}
+
+impl ReflectSerializerProcessor for EntityKeys {
+    fn try_serialize<S>(
+        &self,
+        value: &dyn PartialReflect,
+        _registry: &TypeRegistry,
+        serializer: S,
+    ) -> Result<Result<S::Ok, S>, S::Error>
+    where
+        S: Serializer,
+    {
+        match value.try_downcast_ref::<Entity>() {
+            Some(entity) => Ok(Ok(serializer.serialize_str(&self.key(*entity))?)),
+            None => Ok(Err(serializer)),
+        }
+    }
+}
+
+fn entity_name(entity: &DynamicEntity) -> Option<&str> {
+    entity
+        .components
+        .iter()
+        .find_map(|component| component.try_downcast_ref::<Name>())
+        .map(Name::as_str)
+}
+
+/// Returns the name `value` is written with.
+fn type_name<'a>(value: &'a dyn PartialReflect, registry: &'a TypeRegistry) -> &'a str {
+    let type_path = value
+        .get_represented_type_info()
+        .map(TypeInfo::type_path)
+        .unwrap_or_else(|| value.reflect_type_path());
+    registry
+        .get_with_type_path(type_path)
+        .and_then(|registration| registration.data::<SceneTypeAlias>())
+        .map(|alias| alias.0.as_ref())
+        .unwrap_or(type_path)
+}
+
+fn is_default(value: &dyn PartialReflect, registry: &TypeRegistry) -> bool {
+    value
+        .get_represented_type_info()
+        .and_then(|info| registry.get_type_data::<ReflectDefault>(info.type_id()))
+        .and_then(|default| value.reflect_partial_eq(default.default().as_partial_reflect()))
+        .unwrap_or(false)
+}
+
+struct ReadableEntitiesSerializer<'a> {
+    scene: &'a DynamicScene,
+    registry: &'a TypeRegistry,
+    keys: &'a EntityKeys,
+}
+
+impl<'a> Serialize for ReadableEntitiesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // Entities without a name first, by id, then the named entities by name.
+        let mut entities = self.scene.entities.iter().collect::<Vec<_>>();
+        entities.sort_by(|a, b| {
+            let a_name = self.keys.names.get(&a.entity);
+            let b_name = self.keys.names.get(&b.entity);
+            a_name.cmp(&b_name).then(a.entity.cmp(&b.entity))
+        });
+
+        let mut state = serializer.serialize_map(Some(entities.len()))?;
+        for entity in entities {
+            let is_named = self.keys.names.contains_key(&entity.entity);
+            let (defaults, components) = entity
+                .components
+                .iter()
+                .map(AsRef::as_ref)
+                // The name of named entities is their key.
+                .filter(|component| !(is_named && component.try_downcast_ref::<Name>().is_some()))
+                .partition::<Vec<_>, _>(|component| is_default(*component, self.registry));
+            let mut defaults = defaults
+                .into_iter()
+                .map(|component| type_name(component, self.registry))
+                .collect::<Vec<_>>();
+            defaults.sort_unstable();
+
+            state.serialize_entry(
+                &self.keys.key(entity.entity),
+                &ReadableEntitySerializer {
+                    components: ReadableMapSerializer {
+                        entries: components,
+                        registry: self.registry,
+                        keys: self.keys,
+                    },
+                    defaults,
+                },
+            )?;
+        }
+        state.end()
+    }
+}
+
+struct ReadableEntitySerializer<'a> {
+    components: ReadableMapSerializer<'a>,
+    defaults: Vec<&'a str>,
+}
+
+impl<'a> Serialize for ReadableEntitySerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct(super::ENTITY_STRUCT, 2)?;
+        state.serialize_field(super::ENTITY_FIELD_COMPONENTS, &self.components)?;
+        if self.defaults.is_empty() {
+            state.skip_field(ENTITY_FIELD_DEFAULTS)?;
+        } else {
+            state.serialize_field(ENTITY_FIELD_DEFAULTS, &self.defaults)?;
+        }
+        state.end()
+    }
+}
+
+struct ReadableMapSerializer<'a> {
+    entries: Vec<&'a dyn PartialReflect>,
+    registry: &'a TypeRegistry,
+    keys: &'a EntityKeys,
+}
+
+impl<'a> Serialize for ReadableMapSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut entries = self
+            .entries
+            .iter()
+            .map(|&entry| (type_name(entry, self.registry), entry))
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|(name, _)| *name);
+
+        let mut state = serializer.serialize_map(Some(entries.len()))?;
+        for (name, value) in entries {
+            state.serialize_entry(
+                name,
+                &TypedReflectSerializer::with_processor(value, self.registry, self.keys),
+            )?;
+        }
+        state.end()
+    }
+}
+
+/// Handles deserialization of scenes written by the [`ReadableSceneSerializer`].
+pub struct ReadableSceneDeserializer<'a> {
+    /// Type registry in which the components and resources types used in the scene to deserialize
+    /// are registered.
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for ReadableSceneDeserializer<'a> {
+    type Value = DynamicScene;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let aliases = self
+            .type_registry
+            .iter()
+            .filter_map(|registration| {
+                let alias = registration.data::<SceneTypeAlias>()?;
+                Some((alias.0.as_ref(), registration))
+            })
+            .collect();
+        deserializer.deserialize_struct(
+            SCENE_STRUCT,
+            &[SCENE_RESOURCES, SCENE_ENTITIES],
+            ReadableSceneVisitor {
+                context: &mut ReadableContext {
+                    registry: self.type_registry,
+                    aliases,
+                    entity_names: EntityHashMap::default(),
+                },
+            },
+        )
+    }
+}
+
+/// State shared by the deserializers of a readable scene.
+struct ReadableContext<'a> {
+    registry: &'a TypeRegistry,
+    aliases: HashMap<&'a str, &'a TypeRegistration>,
+    /// The names of the named entities seen so far, by the id derived from them.
+    entity_names: EntityHashMap<String>,
+}
+
+impl<'a> ReadableContext<'a> {
+    fn registration<E: Error>(&self, name: &str) -> Result<&'a TypeRegistration, E> {
+        self.aliases
+            .get(name)
+            .copied()
+            .or_else(|| self.registry.get_with_type_path(name))
+            .ok_or_else(|| Error::custom(format_args!("no registration found for type `{name}`")))
+    }
+
+    /// Returns the entity written as `key`, see [`ReadableSceneSerializer`].
+    ///
+    /// Named entities get ids derived from their names, starting at [`NAMED_ENTITY_START`], so ids
+    /// written with `#` must be below it.
+    fn entity<E: Error>(&mut self, key: &str) -> Result<Entity, E> {
+        if let Some(id) = key.strip_prefix('#') {
+            let (index, generation) = id.split_once('v').unwrap_or((id, "1"));
+            let entity = index
+                .parse::<u32>()
+                .ok()
+                .filter(|index| *index < NAMED_ENTITY_START)
+                .zip(generation.parse::<u32>().ok())
+                .and_then(|(index, generation)| {
+                    Entity::try_from_bits((u64::from(generation) << 32) | u64::from(index)).ok()
+                });
+            return entity.ok_or_else(|| Error::custom(format_args!("invalid entity id `{key}`")));
+        }
+
+        let entity = named_entity(key);
+        match self.entity_names.get(&entity) {
+            Some(name) if name != key => Err(Error::custom(format_args!(
+                "the entity names `{name}` and `{key}` have the same id, rename one of them"
+            ))),
+            Some(_) => Ok(entity),
+            None => {
+                self.entity_names.insert(entity, key.to_string());
+                Ok(entity)
+            }
+        }
+    }
+}
+
+/// The index of the first id given to named entities when deserializing a readable scene.
+///
+/// The ids of named entities are in the upper half of the index space so they don't collide with
+/// the ids of unnamed entities.
+const NAMED_ENTITY_START: u32 = 1 << 31;
+
+/// Returns the id of the entity named `name`, derived from a 32-bit FNV-1a hash of the name.
+fn named_entity(name: &str) -> Entity {
+    let hash = name.bytes().fold(0x811c9dc5_u32, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x01000193)
+    });
+    Entity::from_raw((hash | NAMED_ENTITY_START).min(u32::MAX - 1))
+}
+
+impl ReflectDeserializerProcessor for ReadableContext<'_> {
+    fn try_deserialize<'de, D>(
+        &mut self,
+        registration: &TypeRegistration,
+        _registry: &TypeRegistry,
+        deserializer: D,
+    ) -> Result<Result<Box<dyn PartialReflect>, D>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if registration.type_id() != TypeId::of::<Entity>() {
+            return Ok(Err(deserializer));
+        }
+        let key = String::deserialize(deserializer)?;
+        Ok(Ok(Box::new(self.entity::<D::Error>(&key)?)))
+    }
+}
+
+struct ReadableSceneVisitor<'a, 'r> {
+    context: &'a mut ReadableContext<'r>,
+}
+
+impl<'a, 'r, 'de> Visitor<'de> for ReadableSceneVisitor<'a, 'r> {
+    type Value = DynamicScene;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("scene struct")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut resources = None;
+        let mut entities = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                super::SceneField::Resources => {
+                    if resources.is_some() {
+                        return Err(Error::duplicate_field(SCENE_RESOURCES));
+                    }
+                    resources = Some(map.next_value_seed(ReadableMapDeserializer {
+                        context: self.context,
+                    })?);
+                }
+                super::SceneField::Entities => {
+                    if entities.is_some() {
+                        return Err(Error::duplicate_field(SCENE_ENTITIES));
+                    }
+                    entities = Some(map.next_value_seed(ReadableEntitiesDeserializer {
+                        context: self.context,
+                    })?);
+                }
+            }
+        }
+
+        Ok(DynamicScene {
+            resources: resources.ok_or_else(|| Error::missing_field(SCENE_RESOURCES))?,
+            entities: entities.ok_or_else(|| Error::missing_field(SCENE_ENTITIES))?,
+        })
+    }
+}
+
+struct ReadableEntitiesDeserializer<'a, 'r> {
+    context: &'a mut ReadableContext<'r>,
+}
+
+impl<'a, 'r, 'de> DeserializeSeed<'de> for ReadableEntitiesDeserializer<'a, 'r> {
+    type Value = Vec<DynamicEntity>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'a, 'r, 'de> Visitor<'de> for ReadableEntitiesDeserializer<'a, 'r> {
+    type Value = Vec<DynamicEntity>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("map of entities")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entities = Vec::new();
+        let mut added = EntityHashMap::default();
+        while let Some(key) = map.next_key::<String>()? {
+            let entity = self.context.entity(&key)?;
+            if added.insert(entity, ()).is_some() {
+                return Err(Error::custom(format_args!("duplicate entity: `{key}`")));
+            }
+            let mut components = map.next_value_seed(ReadableEntityDeserializer {
+                context: self.context,
+            })?;
+            if !key.starts_with('#') {
+                components.push(Box::new(Name::new(key)));
+            }
+            entities.push(DynamicEntity { entity, components });
+        }
+
+        Ok(entities)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum ReadableEntityField {
+    Components,
+    Defaults,
+}
+
+struct ReadableEntityDeserializer<'a, 'r> {
+    context: &'a mut ReadableContext<'r>,
+}
+
+impl<'a, 'r, 'de> DeserializeSeed<'de> for ReadableEntityDeserializer<'a, 'r> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            super::ENTITY_STRUCT,
+            &[super::ENTITY_FIELD_COMPONENTS, ENTITY_FIELD_DEFAULTS],
+            self,
+        )
+    }
+}
+
+impl<'a, 'r, 'de> Visitor<'de> for ReadableEntityDeserializer<'a, 'r> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("entity struct")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut components = None;
+        let mut defaults = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                ReadableEntityField::Components => {
+                    if components.is_some() {
+                        return Err(Error::duplicate_field(super::ENTITY_FIELD_COMPONENTS));
+                    }
+                    components = Some(map.next_value_seed(ReadableMapDeserializer {
+                        context: self.context,
+                    })?);
+                }
+                ReadableEntityField::Defaults => {
+                    if defaults.is_some() {
+                        return Err(Error::duplicate_field(ENTITY_FIELD_DEFAULTS));
+                    }
+                    defaults = Some(map.next_value::<Vec<String>>()?);
+                }
+            }
+        }
+
+        let mut components = components.unwrap_or_default();
+        for name in defaults.unwrap_or_default() {
+            let registration = self.context.registration::<A::Error>(&name)?;
+            let default = registration.data::<ReflectDefault>().ok_or_else(|| {
+                Error::custom(format_args!(
+                    "`{name}` is listed in `defaults` but doesn't reflect `Default`"
+                ))
+            })?;
+            components.push(default.default().into_partial_reflect());
+        }
+        Ok(components)
+    }
+}
+
+struct ReadableMapDeserializer<'a, 'r> {
+    context: &'a mut ReadableContext<'r>,
+}
+
+impl<'a, 'r, 'de> DeserializeSeed<'de> for ReadableMapDeserializer<'a, 'r> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'a, 'r, 'de> Visitor<'de> for ReadableMapDeserializer<'a, 'r> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("map of reflect types")
+    }
+
+    fn visit_seq<A>(self, _seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        Err(Error::custom("expected a map of reflect types"))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let registry = self.context.registry;
+        let mut added = <HashSet<_>>::default();
+        let mut entries = Vec::new();
+        while let Some(name) = map.next_key::<String>()? {
+            let registration = self.context.registration::<A::Error>(&name)?;
+            if !added.insert(registration.type_id()) {
+                return Err(Error::custom(format_args!(
+                    "duplicate reflect type: `{name}`"
+                )));
+            }
+
+            let value = map.next_value_seed(TypedReflectDeserializer::with_processor(
+                registration,
+                registry,
+                self.context,
+            ))?;
+
+            // Attempt to convert using FromReflect.
+            let value = registration
+                .data::<ReflectFromReflect>()
+                .and_then(|fr| fr.from_reflect(value.as_partial_reflect()))
+                .map(PartialReflect::into_partial_reflect)
+                .unwrap_or(value);
+
+            entries.push(value);
+        }
+
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        named_entity, register_scene_type_alias, ReadableSceneDeserializer, NAMED_ENTITY_START,
+    };
+    use crate::{ron, DynamicEntity, DynamicScene, DynamicSceneBuilder};
+    use bevy_ecs::{
+        entity::{hash_map::EntityHashMap, Entity},
+        name::Name,
+        prelude::{Component, ReflectComponent, ReflectResource, Resource, World},
+        reflect::AppTypeRegistry,
+    };
+    use bevy_reflect::{prelude::ReflectDefault, PartialReflect, Reflect};
+    use serde::de::DeserializeSeed;
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component, Default)]
+    struct Health(i32);
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Speed(f32);
+
+    #[derive(Component, Reflect)]
+    #[reflect(Component)]
+    struct Target(#[entities] Entity);
+
+    #[derive(Resource, Reflect, Default)]
+    #[reflect(Resource)]
+    struct Score(u32);
+
+    fn create_world() -> World {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            register_scene_type_alias::<Health>(&mut registry, "Health");
+            register_scene_type_alias::<Target>(&mut registry, "Target");
+            registry.register::<Speed>();
+            registry.register::<Score>();
+            registry.register::<Name>();
+            registry.register::<Entity>();
+        }
+        world.insert_resource(registry);
+        world
+    }
+
+    fn deserialize(world: &World, input: &str) -> DynamicScene {
+        let mut deserializer = ron::de::Deserializer::from_str(input).unwrap();
+        ReadableSceneDeserializer {
+            type_registry: &world.resource::<AppTypeRegistry>().read(),
+        }
+        .deserialize(&mut deserializer)
+        .unwrap()
+    }
+
+    #[test]
+    fn readable_round_trip() {
+        let mut world = create_world();
+        let enemy = world.spawn((Name::new("Enemy"), Health(5))).id();
+        let player = world
+            .spawn((Name::new("Player"), Health(0), Speed(2.5), Target(enemy)))
+            .id();
+        let unnamed = world.spawn((Health(0), Target(player))).id();
+        world.insert_resource(Score(7));
+
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .extract_entities([player, unnamed, enemy].into_iter())
+            .extract_resources()
+            .build();
+        let output = scene
+            .serialize_readable(&world.resource::<AppTypeRegistry>().read())
+            .unwrap();
+
+        let expected = r##"(
+  resources: {
+    "bevy_scene::serde::readable::tests::Score": (7),
+  },
+  entities: {
+    "#2": (
+      components: {
+        "Target": ("Player"),
+      },
+      defaults: [
+        "Health",
+      ],
+    ),
+    "Enemy": (
+      components: {
+        "Health": (5),
+      },
+    ),
+    "Player": (
+      components: {
+        "Target": ("Enemy"),
+        "bevy_scene::serde::readable::tests::Speed": (2.5),
+      },
+      defaults: [
+        "Health",
+      ],
+    ),
+  },
+)"##;
+        assert_eq!(expected, output);
+
+        let deserialized = deserialize(&world, &output);
+        let registry = world.resource::<AppTypeRegistry>().read();
+        assert_eq!(output, deserialized.serialize_readable(&registry).unwrap());
+        drop(registry);
+
+        let mut dst_world = create_world();
+        deserialized
+            .write_to_world(&mut dst_world, &mut EntityHashMap::default())
+            .unwrap();
+        assert_eq!(7, dst_world.resource::<Score>().0);
+        let mut query = dst_world.query::<(Entity, &Name, &Health)>();
+        let (enemy, _, _) = query
+            .iter(&dst_world)
+            .find(|(_, name, _)| name.as_str() == "Enemy")
+            .unwrap();
+        let (player, _, health) = query
+            .iter(&dst_world)
+            .find(|(_, name, _)| name.as_str() == "Player")
+            .unwrap();
+        assert_eq!(&Health(0), health);
+        assert_eq!(enemy, dst_world.get::<Target>(player).unwrap().0);
+        assert_eq!(3, dst_world.query::<&Health>().iter(&dst_world).count());
+    }
+
+    fn assert_entity(
+        entity: &DynamicEntity,
+        expected_entity: Entity,
+        expected_components: &[&dyn PartialReflect],
+    ) {
+        assert_eq!(expected_entity, entity.entity);
+        assert_eq!(expected_components.len(), entity.components.len());
+        for (component, expected) in entity.components.iter().zip(expected_components) {
+            assert_eq!(
+                Some(true),
+                component.reflect_partial_eq(*expected),
+                "{component:?} != {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn readable_named_entity_ids() {
+        let world = create_world();
+        let scene = deserialize(
+            &world,
+            r##"(
+  resources: {},
+  entities: {
+    "#4v2": (
+      components: {
+        "Target": ("Key"),
+      },
+    ),
+    "Door": (
+      components: {
+        "Target": ("#4v2"),
+      },
+    ),
+    "Key": (
+      defaults: ["Health"],
+    ),
+  },
+)"##,
+        );
+
+        // Named entities get their ids from their names, even when they are first seen in a
+        // reference to them.
+        let key = named_entity("Key");
+        let door = named_entity("Door");
+        assert!(key.index() >= NAMED_ENTITY_START && door.index() >= NAMED_ENTITY_START);
+        let unnamed = Entity::from_bits((2 << 32) | 4);
+        assert_eq!(3, scene.entities.len());
+        assert_entity(&scene.entities[0], unnamed, &[&Target(key)]);
+        assert_entity(
+            &scene.entities[1],
+            door,
+            &[&Target(unnamed), &Name::new("Door")],
+        );
+        assert_entity(&scene.entities[2], key, &[&Health(0), &Name::new("Key")]);
+    }
+
+    #[test]
+    fn readable_named_entity_ids_are_stable() {
+        let world = create_world();
+        let ids = |door: &str| {
+            let scene = deserialize(
+                &world,
+                &format!(
+                    r#"(resources: {{}}, entities: {{
+                        "Chest": (components: {{}}),
+                        "{door}": (components: {{ "Target": ("Key") }}),
+                        "Key": (components: {{}}),
+                    }})"#
+                ),
+            );
+            scene
+                .entities
+                .iter()
+                .map(|entity| entity.entity)
+                .collect::<Vec<_>>()
+        };
+
+        // Renaming one entity leaves the ids of the others unchanged.
+        let before = ids("Door");
+        let after = ids("Gate");
+        assert_eq!(before[0], after[0]);
+        assert_ne!(before[1], after[1]);
+        assert_eq!(before[2], after[2]);
+    }
+
+    #[test]
+    fn readable_invalid_entities() {
+        let world = create_world();
+        let error = |entities: &str| {
+            let input = format!("(resources: {{}}, entities: {{ {entities} }})");
+            let mut deserializer = ron::de::Deserializer::from_str(&input).unwrap();
+            ReadableSceneDeserializer {
+                type_registry: &world.resource::<AppTypeRegistry>().read(),
+            }
+            .deserialize(&mut deserializer)
+            .map(|_| ())
+            .unwrap_err()
+            .to_string()
+        };
+
+        assert_eq!(
+            "duplicate entity: `Door`",
+            error(r#""Door": (components: {}), "Door": (components: {})"#)
+        );
+        assert_eq!(
+            "invalid entity id `#2147483648`",
+            error(r##""#2147483648": (components: {})"##)
+        );
+        assert_eq!(
+            "the entity names `e188904` and `e558220` have the same id, rename one of them",
+            error(r#""e188904": (components: {}), "e558220": (components: {})"#)
+        );
+    }
+
+    #[test]
+    fn readable_duplicate_names_use_ids() {
+        let mut world = create_world();
+        world.spawn((Name::new("Crate"), Speed(1.0)));
+        world.spawn(Name::new("Crate"));
+        world.spawn(Name::new("#1"));
+
+        let output = DynamicScene::from_world(&world)
+            .serialize_readable(&world.resource::<AppTypeRegistry>().read())
+            .unwrap();
+        assert!(output.contains(r##""#0": ("##));
+        assert!(output.contains(r##""#1": ("##));
+        assert!(output.contains(r##""#2": ("##));
+        assert_eq!(
+            2,
+            output.matches(r#""bevy_ecs::name::Name": "Crate""#).count()
+        );
+        assert!(output.contains(r##""bevy_ecs::name::Name": "#1""##));
+    }
+}
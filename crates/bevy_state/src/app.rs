@@ -4,9 +4,12 @@ use bevy_utils::once;
 use log::warn;
 
 use crate::{
+    entity_state::{register_entity_state, ApplyEntityStateTransitions},
     state::{
-        setup_state_transitions_in_world, ComputedStates, FreelyMutableState, NextState, State,
-        StateTransition, StateTransitionEvent, StateTransitionSteps, States, SubStates,
+        register_state_stack, register_state_transition_history, setup_state_transitions_in_world,
+        ComputedStates, FreelyMutableState, NextState, NextStateStack, State, StateStack,
+        StateStackTransitionEvent, StateTransition, StateTransitionEvent, StateTransitionHistory,
+        StateTransitionSteps, States, SubStates,
     },
     state_scoped::clear_state_scoped_entities,
 };
@@ -47,6 +50,36 @@ pub trait AppExtStates {
     /// by triggering the [`StateTransition`](struct@StateTransition) schedule manually.
     fn insert_state<S: FreelyMutableState>(&mut self, state: S) -> &mut Self;
 
+    /// Initializes a [`State`] with standard starting values, managed as a [`StateStack`].
+    ///
+    /// This method is idempotent: it has no effect when called again using the same generic type.
+    ///
+    /// In addition to the resources added by [`init_state`](Self::init_state), adds the
+    /// [`StateStack<S>`] and [`NextStateStack<S>`] resources, and enables use of the
+    /// [`OnPause`](crate::state::OnPause) and [`OnResume`](crate::state::OnResume) schedules.
+    /// A state can't be set up both as a regular state and as a state stack.
+    fn init_state_stack<S: FreelyMutableState + FromWorld>(&mut self) -> &mut Self;
+
+    /// Inserts a specific [`State`] managed as a [`StateStack`] to the current [`App`], and
+    /// overrides any [`State`] and [`StateStack`] previously added of the same type.
+    ///
+    /// See [`init_state_stack`](Self::init_state_stack) for more details.
+    fn insert_state_stack<S: FreelyMutableState>(&mut self, state: S) -> &mut Self;
+
+    /// Sets up per-entity state machines of type `S`, stored in
+    /// [`EntityState<S>`](crate::entity_state::EntityState) components.
+    ///
+    /// This method is idempotent: it has no effect when called again using the same generic type.
+    fn add_entity_state<S: FreelyMutableState>(&mut self) -> &mut Self;
+
+    /// Records the transitions of `S` in a [`StateTransitionHistory<S>`] resource.
+    ///
+    /// The history doesn't know which system set [`NextState<S>`]. Each record only holds the source
+    /// location that set it, and only when the `track_location` feature of `bevy_ecs` is enabled.
+    ///
+    /// This method is idempotent: it has no effect when called again using the same generic type.
+    fn enable_state_transition_history<S: FreelyMutableState>(&mut self) -> &mut Self;
+
     /// Sets up a type implementing [`ComputedStates`].
     ///
     /// This method is idempotent: it has no effect when called again using the same generic type.
@@ -85,6 +118,26 @@ pub trait AppExtStates {
         S: FreelyMutableState + FromReflect + GetTypeRegistration + Typed;
 }
 
+/// Adds the resources, events and systems of a state stack whose [`State<S>`] was just inserted.
+fn install_state_stack<S: FreelyMutableState>(app: &mut SubApp, state: S) {
+    app.init_resource::<StateStack<S>>()
+        .init_resource::<NextStateStack<S>>()
+        .init_resource::<NextState<S>>()
+        .add_event::<StateTransitionEvent<S>>()
+        .add_event::<StateStackTransitionEvent<S>>();
+    let schedule = app.get_schedule_mut(StateTransition).expect(
+        "The `StateTransition` schedule is missing. Did you forget to add StatesPlugin or DefaultPlugins before adding a state stack?"
+    );
+    register_state_stack::<S>(schedule);
+    app.world_mut().send_event(StateTransitionEvent {
+        exited: None,
+        entered: Some(state),
+    });
+    if S::SCOPED_ENTITIES_ENABLED {
+        app.enable_state_scoped_entities::<S>();
+    }
+}
+
 /// Separate function to only warn once for all state installation methods.
 fn warn_if_no_states_plugin_installed(app: &SubApp) {
     if !app.is_plugin_added::<StatesPlugin>() {
@@ -153,6 +206,81 @@ impl AppExtStates for SubApp {
         self
     }
 
+    fn init_state_stack<S: FreelyMutableState + FromWorld>(&mut self) -> &mut Self {
+        warn_if_no_states_plugin_installed(self);
+        if !self.world().contains_resource::<State<S>>() {
+            self.init_resource::<State<S>>();
+            let state = self.world().resource::<State<S>>().get().clone();
+            install_state_stack(self, state);
+        } else {
+            let name = core::any::type_name::<S>();
+            warn!("State {} is already initialized.", name);
+        }
+
+        self
+    }
+
+    fn insert_state_stack<S: FreelyMutableState>(&mut self, state: S) -> &mut Self {
+        warn_if_no_states_plugin_installed(self);
+        if !self.world().contains_resource::<State<S>>() {
+            self.insert_resource::<State<S>>(State::new(state.clone()));
+            install_state_stack(self, state);
+        } else {
+            // Overwrite previous state, stack and initial event
+            self.insert_resource::<State<S>>(State::new(state.clone()))
+                .insert_resource(StateStack::<S>::default());
+            self.world_mut()
+                .resource_mut::<Events<StateTransitionEvent<S>>>()
+                .clear();
+            self.world_mut().send_event(StateTransitionEvent {
+                exited: None,
+                entered: Some(state),
+            });
+        }
+
+        self
+    }
+
+    fn add_entity_state<S: FreelyMutableState>(&mut self) -> &mut Self {
+        warn_if_no_states_plugin_installed(self);
+        let schedule = self.get_schedule_mut(StateTransition).expect(
+            "The `StateTransition` schedule is missing. Did you forget to add StatesPlugin or DefaultPlugins before calling add_entity_state?"
+        );
+        if !schedule
+            .graph()
+            .contains_set(ApplyEntityStateTransitions::<S>::default())
+        {
+            register_entity_state::<S>(schedule);
+        } else {
+            let name = core::any::type_name::<S>();
+            warn!("Entity state {} is already initialized.", name);
+        }
+
+        self
+    }
+
+    fn enable_state_transition_history<S: FreelyMutableState>(&mut self) -> &mut Self {
+        if self
+            .world()
+            .contains_resource::<StateTransitionHistory<S>>()
+        {
+            return self;
+        }
+        if !self
+            .world()
+            .contains_resource::<Events<StateTransitionEvent<S>>>()
+        {
+            let name = core::any::type_name::<S>();
+            warn!("State transition history is enabled for state `{}`, but the state isn't installed in the app!", name);
+        }
+        self.init_resource::<StateTransitionHistory<S>>();
+        let schedule = self.get_schedule_mut(StateTransition).expect(
+            "The `StateTransition` schedule is missing. Did you forget to add StatesPlugin or DefaultPlugins before enabling the state transition history?"
+        );
+        register_state_transition_history::<S>(schedule);
+        self
+    }
+
     fn add_computed_state<S: ComputedStates>(&mut self) -> &mut Self {
         warn_if_no_states_plugin_installed(self);
         if !self
@@ -266,6 +394,26 @@ impl AppExtStates for App {
         self
     }
 
+    fn init_state_stack<S: FreelyMutableState + FromWorld>(&mut self) -> &mut Self {
+        self.main_mut().init_state_stack::<S>();
+        self
+    }
+
+    fn insert_state_stack<S: FreelyMutableState>(&mut self, state: S) -> &mut Self {
+        self.main_mut().insert_state_stack::<S>(state);
+        self
+    }
+
+    fn add_entity_state<S: FreelyMutableState>(&mut self) -> &mut Self {
+        self.main_mut().add_entity_state::<S>();
+        self
+    }
+
+    fn enable_state_transition_history<S: FreelyMutableState>(&mut self) -> &mut Self {
+        self.main_mut().enable_state_transition_history::<S>();
+        self
+    }
+
     fn add_computed_state<S: ComputedStates>(&mut self) -> &mut Self {
         self.main_mut().add_computed_state::<S>();
         self
@@ -317,10 +465,20 @@ impl Plugin for StatesPlugin {
 mod tests {
     use crate::{
         app::StatesPlugin,
-        state::{State, StateTransition, StateTransitionEvent},
+        commands::CommandsStatesExt,
+        state::{
+            NextState, NextStateStack, OnEnter, OnExit, OnPause, OnResume, State, StateStack,
+            StateTransition, StateTransitionEvent, StateTransitionHistory,
+        },
+        state_scoped::StateScoped,
     };
+    use alloc::vec::Vec;
     use bevy_app::App;
-    use bevy_ecs::event::Events;
+    use bevy_ecs::{
+        event::Events,
+        resource::Resource,
+        system::{Commands, ResMut},
+    };
     use bevy_state_macros::States;
 
     use super::AppExtStates;
@@ -372,4 +530,128 @@ mod tests {
         assert_eq!(last.exited, None);
         assert_eq!(last.entered, Some(TestState::C));
     }
+
+    #[derive(Resource, Default)]
+    struct TransitionLog(Vec<&'static str>);
+
+    fn log(entry: &'static str) -> impl Fn(ResMut<TransitionLog>) {
+        move |mut log: ResMut<TransitionLog>| log.0.push(entry)
+    }
+
+    #[test]
+    fn state_stack_pauses_and_resumes() {
+        let mut app = App::new();
+        app.add_plugins(StatesPlugin)
+            .init_resource::<TransitionLog>()
+            .init_state_stack::<TestState>()
+            .enable_state_scoped_entities::<TestState>()
+            .add_systems(OnEnter(TestState::A), log("enter A"))
+            .add_systems(OnExit(TestState::A), log("exit A"))
+            .add_systems(OnPause(TestState::A), log("pause A"))
+            .add_systems(OnResume(TestState::A), log("resume A"))
+            .add_systems(OnEnter(TestState::B), log("enter B"))
+            .add_systems(OnExit(TestState::B), log("exit B"))
+            .add_systems(OnEnter(TestState::C), log("enter C"))
+            .add_systems(OnExit(TestState::C), log("exit C"));
+
+        let world = app.world_mut();
+        world.run_schedule(StateTransition);
+        let scoped = world.spawn(StateScoped(TestState::A)).id();
+
+        world
+            .resource_mut::<NextStateStack<TestState>>()
+            .push(TestState::B);
+        world.run_schedule(StateTransition);
+        assert_eq!(world.resource::<State<TestState>>().0, TestState::B);
+        assert_eq!(
+            world.resource::<StateStack<TestState>>().paused(),
+            [TestState::A]
+        );
+        assert!(world.get_entity(scoped).is_ok());
+
+        // `NextState` replaces the top of the stack.
+        world
+            .resource_mut::<NextState<TestState>>()
+            .set(TestState::C);
+        world.run_schedule(StateTransition);
+        assert_eq!(world.resource::<State<TestState>>().0, TestState::C);
+
+        world.commands().pop_state::<TestState>();
+        world.flush();
+        world.run_schedule(StateTransition);
+        assert_eq!(world.resource::<State<TestState>>().0, TestState::A);
+        assert!(world.resource::<StateStack<TestState>>().is_empty());
+        assert!(world.get_entity(scoped).is_ok());
+
+        // Popping the bottom of the stack does nothing.
+        world.resource_mut::<NextStateStack<TestState>>().pop();
+        world.run_schedule(StateTransition);
+        assert_eq!(world.resource::<State<TestState>>().0, TestState::A);
+
+        world
+            .run_system_cached(|mut commands: Commands| {
+                commands.replace_state(TestState::B);
+            })
+            .unwrap();
+        world.run_schedule(StateTransition);
+        assert_eq!(world.resource::<State<TestState>>().0, TestState::B);
+        assert!(world.get_entity(scoped).is_err());
+
+        assert_eq!(
+            world.resource::<TransitionLog>().0,
+            [
+                "enter A", "pause A", "enter B", "exit B", "enter C", "exit C", "resume A",
+                "exit A", "enter B",
+            ]
+        );
+    }
+
+    #[test]
+    fn state_transition_history_records_and_undoes() {
+        let mut app = App::new();
+        app.add_plugins(StatesPlugin)
+            .init_state::<TestState>()
+            .enable_state_transition_history::<TestState>();
+
+        let world = app.world_mut();
+        world.run_schedule(StateTransition);
+        world
+            .resource_mut::<NextState<TestState>>()
+            .set(TestState::B);
+        world.run_schedule(StateTransition);
+        world
+            .resource_mut::<NextState<TestState>>()
+            .set(TestState::C);
+        world.run_schedule(StateTransition);
+
+        let transitions = |world: &bevy_ecs::world::World| {
+            world
+                .resource::<StateTransitionHistory<TestState>>()
+                .records()
+                .map(|record| (record.exited.clone(), record.entered.clone()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            transitions(world),
+            [
+                (None, Some(TestState::A)),
+                (Some(TestState::A), Some(TestState::B)),
+                (Some(TestState::B), Some(TestState::C)),
+            ]
+        );
+
+        world.commands().undo_state_transition::<TestState>();
+        world.flush();
+        world.run_schedule(StateTransition);
+        assert_eq!(world.resource::<State<TestState>>().0, TestState::B);
+        assert_eq!(transitions(world).len(), 2);
+
+        world
+            .resource_mut::<StateTransitionHistory<TestState>>()
+            .set_capacity(1);
+        assert_eq!(
+            transitions(world),
+            [(Some(TestState::A), Some(TestState::B))]
+        );
+    }
 }
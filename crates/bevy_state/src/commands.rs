@@ -1,7 +1,7 @@
-use bevy_ecs::{system::Commands, world::World};
+use bevy_ecs::{change_detection::Mut, system::Commands, world::World};
 use log::debug;
 
-use crate::state::{FreelyMutableState, NextState};
+use crate::state::{FreelyMutableState, NextState, NextStateStack, StateTransitionHistory};
 
 /// Extension trait for [`Commands`] adding `bevy_state` helpers.
 pub trait CommandsStatesExt {
@@ -13,6 +13,32 @@ pub trait CommandsStatesExt {
     /// Note that commands introduce sync points to the ECS schedule, so modifying `NextState`
     /// directly may be more efficient depending on your use-case.
     fn set_state<S: FreelyMutableState>(&mut self, state: S);
+
+    /// Pushes `state` on top of the current state of a [`StateStack<S>`](crate::prelude::StateStack),
+    /// pausing the current state.
+    ///
+    /// Internally this schedules a command that updates the
+    /// [`NextStateStack<S>`](crate::prelude::NextStateStack) resource.
+    fn push_state<S: FreelyMutableState>(&mut self, state: S);
+
+    /// Pops the current state of a [`StateStack<S>`](crate::prelude::StateStack), resuming the
+    /// state below it.
+    ///
+    /// Internally this schedules a command that updates the
+    /// [`NextStateStack<S>`](crate::prelude::NextStateStack) resource.
+    fn pop_state<S: FreelyMutableState>(&mut self);
+
+    /// Replaces the current state of a [`StateStack<S>`](crate::prelude::StateStack) with `state`.
+    ///
+    /// Internally this schedules a command that updates the
+    /// [`NextStateStack<S>`](crate::prelude::NextStateStack) resource.
+    fn replace_state<S: FreelyMutableState>(&mut self, state: S);
+
+    /// Goes back to the state exited by the latest transition recorded in the
+    /// [`StateTransitionHistory<S>`](crate::prelude::StateTransitionHistory).
+    ///
+    /// Does nothing if the history isn't enabled for `S` or if there is nothing to undo.
+    fn undo_state_transition<S: FreelyMutableState>(&mut self);
 }
 
 impl CommandsStatesExt for Commands<'_, '_> {
@@ -27,4 +53,53 @@ impl CommandsStatesExt for Commands<'_, '_> {
             next.set(state);
         });
     }
+
+    fn push_state<S: FreelyMutableState>(&mut self, state: S) {
+        self.queue(move |w: &mut World| {
+            set_next_state_stack(w, NextStateStack::Push(state));
+        });
+    }
+
+    fn pop_state<S: FreelyMutableState>(&mut self) {
+        self.queue(|w: &mut World| {
+            set_next_state_stack::<S>(w, NextStateStack::Pop);
+        });
+    }
+
+    fn replace_state<S: FreelyMutableState>(&mut self, state: S) {
+        self.queue(move |w: &mut World| {
+            set_next_state_stack(w, NextStateStack::Replace(state));
+        });
+    }
+
+    fn undo_state_transition<S: FreelyMutableState>(&mut self) {
+        self.queue(|w: &mut World| {
+            w.resource_scope(|w, mut next: Mut<NextState<S>>| {
+                let Some(mut history) = w.get_resource_mut::<StateTransitionHistory<S>>() else {
+                    debug!(
+                        "no state transition history to undo for {}",
+                        core::any::type_name::<S>()
+                    );
+                    return;
+                };
+                if history.undo(&mut next).is_none() {
+                    debug!(
+                        "no state transition to undo for {}",
+                        core::any::type_name::<S>()
+                    );
+                }
+            });
+        });
+    }
+}
+
+fn set_next_state_stack<S: FreelyMutableState>(w: &mut World, next: NextStateStack<S>) {
+    let mut next_stack = w.resource_mut::<NextStateStack<S>>();
+    if !matches!(*next_stack, NextStateStack::Unchanged) {
+        debug!(
+            "overwriting next state stack change {:?} with {:?}",
+            *next_stack, next
+        );
+    }
+    *next_stack = next;
 }
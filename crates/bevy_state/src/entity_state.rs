@@ -0,0 +1,368 @@
+use core::{marker::PhantomData, mem};
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_ecs::reflect::ReflectComponent;
+use bevy_ecs::{
+    change_detection::DetectChangesMut,
+    component::{require, Component, HookContext},
+    entity::Entity,
+    event::Event,
+    query::Changed,
+    schedule::{IntoSystemConfigs, IntoSystemSetConfigs, Schedule, SystemSet},
+    system::{Commands, Query},
+    world::DeferredWorld,
+};
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::prelude::*;
+
+use crate::state::{FreelyMutableState, StateTransitionSteps};
+
+/// The state of a per-entity finite-state machine.
+///
+/// Unlike [`State<S>`](crate::state::State), which holds the single app-wide value of `S`, each
+/// entity with this component has its own value of `S`. To change it, set the
+/// [`NextEntityState<S>`] component of the same entity; the change is applied during the
+/// [`StateTransition`](crate::state::StateTransition) schedule, after the app-wide states.
+///
+/// Instead of schedules, per-entity transitions trigger the [`ExitEntityState<S>`],
+/// [`EntityStateTransition<S>`] and [`EnterEntityState<S>`] events, in that order, targeting the
+/// entity. [`EnterEntityState<S>`] is also triggered when this component is added, right away
+/// from its `on_add` hook: only observers that already exist at that point, such as global ones
+/// added with `App::add_observer`, see the initial state. Observers added to the entity after it
+/// is spawned, like the one below, only see later transitions.
+///
+/// Per-entity state machines are set up with
+/// [`add_entity_state`](crate::app::AppExtStates::add_entity_state).
+///
+/// ```
+/// use bevy_state::prelude::*;
+/// use bevy_ecs::prelude::*;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum Guard {
+///     #[default]
+///     Patrolling,
+///     Chasing,
+/// }
+///
+/// fn spawn_guard(mut commands: Commands) {
+///     commands
+///         .spawn(EntityState(Guard::Patrolling))
+///         .observe(|trigger: Trigger<EnterEntityState<Guard>>| {
+///             if trigger.0 == Guard::Chasing {
+///                 // Sound the alarm...
+///             }
+///         });
+/// }
+///
+/// fn spot_player(mut guards: Query<(&EntityState<Guard>, &mut NextEntityState<Guard>)>) {
+///     for (state, mut next) in &mut guards {
+///         if *state == Guard::Patrolling {
+///             next.set(Guard::Chasing);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Clone, PartialEq, Eq, Hash)]
+#[component(on_add = enter_initial_entity_state::<S>)]
+#[require(NextEntityState<S>)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Component, Debug, PartialEq)
+)]
+pub struct EntityState<S: FreelyMutableState>(pub S);
+
+impl<S: FreelyMutableState> EntityState<S> {
+    /// Get the current state.
+    pub fn get(&self) -> &S {
+        &self.0
+    }
+}
+
+impl<S: FreelyMutableState + Default> Default for EntityState<S> {
+    fn default() -> Self {
+        Self(S::default())
+    }
+}
+
+impl<S: FreelyMutableState> PartialEq<S> for EntityState<S> {
+    fn eq(&self, other: &S) -> bool {
+        self.get() == other
+    }
+}
+
+fn enter_initial_entity_state<S: FreelyMutableState>(
+    mut world: DeferredWorld,
+    HookContext { entity, .. }: HookContext,
+) {
+    let state = world.get::<EntityState<S>>(entity).unwrap().0.clone();
+    world.trigger_targets(EnterEntityState(state), entity);
+}
+
+/// The next state of the [`EntityState<S>`] of the same entity.
+///
+/// This component is added along with [`EntityState<S>`] and works like
+/// [`NextState<S>`](crate::state::NextState): only its value during the
+/// [`StateTransition`](crate::state::StateTransition) schedule matters.
+#[derive(Component, Debug, Default, Clone)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Component, Default, Debug)
+)]
+pub enum NextEntityState<S: FreelyMutableState> {
+    /// No state transition is pending
+    #[default]
+    Unchanged,
+    /// There is a pending transition for state `S`
+    Pending(S),
+}
+
+impl<S: FreelyMutableState> NextEntityState<S> {
+    /// Tentatively set a pending state transition to `Some(state)`.
+    pub fn set(&mut self, state: S) {
+        *self = Self::Pending(state);
+    }
+
+    /// Remove any pending changes to [`EntityState<S>`]
+    pub fn reset(&mut self) {
+        *self = Self::Unchanged;
+    }
+}
+
+/// Triggered on an entity when its [`EntityState<S>`] enters the provided state.
+///
+/// This event ignores identity transitions.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct EnterEntityState<S: FreelyMutableState>(pub S);
+
+/// Triggered on an entity when its [`EntityState<S>`] exits the provided state.
+///
+/// This event ignores identity transitions.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct ExitEntityState<S: FreelyMutableState>(pub S);
+
+/// Triggered on an entity when its [`EntityState<S>`] exits AND enters the provided `exited` and
+/// `entered` states.
+///
+/// It is always triggered *after* [`ExitEntityState`], and *before* [`EnterEntityState`].
+///
+/// This event is triggered on identity transitions.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct EntityStateTransition<S: FreelyMutableState> {
+    /// The state being exited.
+    pub exited: S,
+    /// The state being entered.
+    pub entered: S,
+}
+
+/// System set that applies the [`NextEntityState<S>`] of all entities.
+///
+/// It runs in the [`StateTransition`](crate::state::StateTransition) schedule, after
+/// [`StateTransitionSteps::EnterSchedules`].
+#[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ApplyEntityStateTransitions<S: FreelyMutableState>(PhantomData<S>);
+
+impl<S: FreelyMutableState> Default for ApplyEntityStateTransitions<S> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+/// Registers the system applying the [`NextEntityState<S>`] of all entities.
+///
+/// Runs automatically when using `App` to add entity states, but needs to be called manually in
+/// other situations.
+pub fn register_entity_state<S: FreelyMutableState>(schedule: &mut Schedule) {
+    schedule
+        .configure_sets(
+            ApplyEntityStateTransitions::<S>::default().after(StateTransitionSteps::EnterSchedules),
+        )
+        .add_systems(
+            apply_entity_state_transitions::<S>.in_set(ApplyEntityStateTransitions::<S>::default()),
+        );
+}
+
+fn apply_entity_state_transitions<S: FreelyMutableState>(
+    mut commands: Commands,
+    mut query: Query<
+        (Entity, &mut EntityState<S>, &mut NextEntityState<S>),
+        Changed<NextEntityState<S>>,
+    >,
+) {
+    for (entity, mut state, mut next_state) in &mut query {
+        let NextEntityState::Pending(entered) = mem::take(next_state.bypass_change_detection())
+        else {
+            continue;
+        };
+        let exited = match state.0 == entered {
+            true => entered.clone(),
+            false => mem::replace(&mut state.0, entered.clone()),
+        };
+
+        if exited != entered {
+            commands.trigger_targets(ExitEntityState(exited.clone()), entity);
+        }
+        commands.trigger_targets(
+            EntityStateTransition {
+                exited: exited.clone(),
+                entered: entered.clone(),
+            },
+            entity,
+        );
+        if exited != entered {
+            commands.trigger_targets(EnterEntityState(entered), entity);
+        }
+    }
+}
+
+/// Generates a [`Condition`](bevy_ecs::prelude::Condition)-satisfying closure that returns `true`
+/// if any entity's [`EntityState<S>`] is `state`.
+pub fn any_entity_in_state<S: FreelyMutableState>(
+    state: S,
+) -> impl FnMut(Query<&EntityState<S>>) -> bool + Clone {
+    move |query: Query<&EntityState<S>>| query.iter().any(|current| *current == state)
+}
+
+/// Generates a [`Condition`](bevy_ecs::prelude::Condition)-satisfying closure that returns `true`
+/// if the [`EntityState<S>`] of `entity` is `state`.
+///
+/// Returns `false` if the entity doesn't exist or has no [`EntityState<S>`].
+pub fn entity_in_state<S: FreelyMutableState>(
+    entity: Entity,
+    state: S,
+) -> impl FnMut(Query<&EntityState<S>>) -> bool + Clone {
+    move |query: Query<&EntityState<S>>| query.get(entity).is_ok_and(|current| *current == state)
+}
+
+#[cfg(all(test, feature = "bevy_app"))]
+mod tests {
+    use alloc::{format, string::String, vec::Vec};
+    use bevy_app::App;
+    use bevy_ecs::{
+        observer::Trigger,
+        resource::Resource,
+        system::{ResMut, RunSystemOnce},
+    };
+    use bevy_state_macros::States;
+
+    use super::*;
+    use crate::{
+        app::{AppExtStates, StatesPlugin},
+        state::StateTransition,
+    };
+
+    #[derive(States, Default, PartialEq, Eq, Hash, Debug, Clone)]
+    enum Guard {
+        #[default]
+        Patrolling,
+        Chasing,
+    }
+
+    #[derive(Resource, Default)]
+    struct TransitionLog(Vec<String>);
+
+    #[test]
+    fn entity_states_transition_independently() {
+        let mut app = App::new();
+        app.add_plugins(StatesPlugin)
+            .init_resource::<TransitionLog>()
+            .add_entity_state::<Guard>();
+        let world = app.world_mut();
+
+        let mut spawn_guard = |name: &'static str| {
+            let guard = world.spawn(EntityState(Guard::Patrolling)).id();
+            world
+                .entity_mut(guard)
+                .observe(
+                    move |trigger: Trigger<ExitEntityState<Guard>>,
+                          mut log: ResMut<TransitionLog>| {
+                        log.0.push(format!("{name} exit {:?}", trigger.0));
+                    },
+                )
+                .observe(
+                    move |trigger: Trigger<EntityStateTransition<Guard>>,
+                          mut log: ResMut<TransitionLog>| {
+                        log.0.push(format!(
+                            "{name} {:?} -> {:?}",
+                            trigger.exited, trigger.entered
+                        ));
+                    },
+                )
+                .observe(
+                    move |trigger: Trigger<EnterEntityState<Guard>>,
+                          mut log: ResMut<TransitionLog>| {
+                        log.0.push(format!("{name} enter {:?}", trigger.0));
+                    },
+                );
+            guard
+        };
+        let a = spawn_guard("a");
+        let b = spawn_guard("b");
+
+        world
+            .get_mut::<NextEntityState<Guard>>(a)
+            .unwrap()
+            .set(Guard::Chasing);
+        world
+            .get_mut::<NextEntityState<Guard>>(b)
+            .unwrap()
+            .set(Guard::Patrolling);
+        world.run_schedule(StateTransition);
+
+        assert_eq!(
+            world.get::<EntityState<Guard>>(a).unwrap().0,
+            Guard::Chasing
+        );
+        assert_eq!(
+            world.get::<EntityState<Guard>>(b).unwrap().0,
+            Guard::Patrolling
+        );
+        assert_eq!(
+            world.resource::<TransitionLog>().0,
+            [
+                "a exit Patrolling",
+                "a Patrolling -> Chasing",
+                "a enter Chasing",
+                "b Patrolling -> Patrolling",
+            ]
+        );
+
+        assert!(world
+            .run_system_once(any_entity_in_state(Guard::Chasing))
+            .unwrap());
+        assert!(world
+            .run_system_once(entity_in_state(a, Guard::Chasing))
+            .unwrap());
+        assert!(!world
+            .run_system_once(entity_in_state(b, Guard::Chasing))
+            .unwrap());
+    }
+
+    #[test]
+    fn initial_state_reaches_existing_observers_only() {
+        let mut app = App::new();
+        app.add_plugins(StatesPlugin)
+            .init_resource::<TransitionLog>()
+            .add_entity_state::<Guard>()
+            .add_observer(
+                |trigger: Trigger<EnterEntityState<Guard>>, mut log: ResMut<TransitionLog>| {
+                    log.0.push(format!("global enter {:?}", trigger.0));
+                },
+            );
+        let world = app.world_mut();
+
+        world.spawn(EntityState(Guard::Patrolling)).observe(
+            |trigger: Trigger<EnterEntityState<Guard>>, mut log: ResMut<TransitionLog>| {
+                log.0.push(format!("entity enter {:?}", trigger.0));
+            },
+        );
+        world.flush();
+
+        assert_eq!(
+            world.resource::<TransitionLog>().0,
+            ["global enter Patrolling"]
+        );
+    }
+}
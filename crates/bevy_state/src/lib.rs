@@ -26,6 +26,8 @@
 //! - 3 Transition Schedules - [`OnEnter<S>`](crate::state::OnEnter), [`OnExit<S>`](crate::state::OnExit) and [`OnTransition<S>`](crate::state::OnTransition) - which are used
 //!   to trigger systems specifically during matching transitions.
 //! - A [`StateTransitionEvent<S>`](crate::state::StateTransitionEvent) that gets fired when a given state changes.
+//! - A [`StateStack<S>`](crate::state::StateStack) that pushes and pops states, running [`OnPause<S>`](crate::state::OnPause)
+//!   and [`OnResume<S>`](crate::state::OnResume) for the states below the top instead of their exit and enter schedules.
 //! - The [`in_state<S>`](crate::condition::in_state) and [`state_changed<S>`](crate::condition::state_changed) run conditions - which are used
 //!   to determine whether a system should run based on the current state.
 
@@ -53,6 +55,9 @@ pub mod app;
 pub mod commands;
 /// Provides definitions for the runtime conditions that interact with the state system
 pub mod condition;
+/// Provides [`EntityState`](crate::entity_state::EntityState) and
+/// [`NextEntityState`](crate::entity_state::NextEntityState) for per-entity state machines.
+pub mod entity_state;
 /// Provides definitions for the basic traits required by the state system
 pub mod state;
 
@@ -84,10 +89,15 @@ pub mod prelude {
     pub use crate::{
         commands::CommandsStatesExt,
         condition::*,
+        entity_state::{
+            any_entity_in_state, entity_in_state, EnterEntityState, EntityState,
+            EntityStateTransition, ExitEntityState, NextEntityState,
+        },
         state::{
-            last_transition, ComputedStates, EnterSchedules, ExitSchedules, NextState, OnEnter,
-            OnExit, OnTransition, State, StateSet, StateTransition, StateTransitionEvent, States,
-            SubStates, TransitionSchedules,
+            last_transition, ComputedStates, EnterSchedules, ExitSchedules, NextState,
+            NextStateStack, OnEnter, OnExit, OnPause, OnResume, OnTransition, State, StateSet,
+            StateStack, StateStackTransitionEvent, StateTransition, StateTransitionEvent,
+            StateTransitionHistory, States, SubStates, TransitionSchedules,
         },
         state_scoped::StateScoped,
     };
@@ -0,0 +1,176 @@
+use alloc::collections::VecDeque;
+
+use bevy_ecs::{
+    change_detection::{DetectChanges, MaybeLocation},
+    event::EventReader,
+    resource::Resource,
+    schedule::{IntoSystemConfigs, Schedule},
+    system::{Res, ResMut},
+};
+use bevy_platform_support::time::Instant;
+
+use super::{
+    ApplyStateTransition, FreelyMutableState, NextState, StateTransitionEvent, StateTransitionSteps,
+};
+
+/// A transition of `S` recorded in a [`StateTransitionHistory<S>`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateTransitionRecord<S: FreelyMutableState> {
+    /// The state being exited.
+    pub exited: Option<S>,
+    /// The state being entered.
+    pub entered: Option<S>,
+    /// When the transition was applied.
+    pub time: Instant,
+    /// The source location of the code that last set the [`NextState<S>`] which caused the
+    /// transition.
+    ///
+    /// This is `None` for transitions that weren't caused by [`NextState<S>`], such as the initial
+    /// state. When the `track_location` feature of `bevy_ecs` is disabled, the [`MaybeLocation`]
+    /// doesn't hold a location.
+    pub set_location: Option<MaybeLocation>,
+}
+
+/// The latest transitions of `S`, oldest first.
+///
+/// This resource is only added and kept up to date for states that enabled it with
+/// [`enable_state_transition_history`](crate::app::AppExtStates::enable_state_transition_history).
+/// Once [`capacity`](Self::capacity) transitions are recorded, the oldest ones are dropped.
+///
+/// Transitions can be undone with
+/// [`undo_state_transition`](crate::commands::CommandsStatesExt::undo_state_transition), which
+/// removes the undone transition from the history instead of recording a new one.
+#[derive(Resource, Debug, Clone)]
+pub struct StateTransitionHistory<S: FreelyMutableState> {
+    records: VecDeque<StateTransitionRecord<S>>,
+    capacity: usize,
+    /// Where the pending [`NextState<S>`] was set.
+    pending_set_location: Option<MaybeLocation>,
+    /// The state a pending undo goes back to.
+    pending_undo: Option<S>,
+}
+
+impl<S: FreelyMutableState> Default for StateTransitionHistory<S> {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl<S: FreelyMutableState> StateTransitionHistory<S> {
+    /// The number of transitions recorded by default.
+    pub const DEFAULT_CAPACITY: usize = 64;
+
+    /// Creates an empty history keeping the latest `capacity` transitions.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            records: VecDeque::new(),
+            capacity,
+            pending_set_location: None,
+            pending_undo: None,
+        }
+    }
+
+    /// Returns the recorded transitions, oldest first.
+    pub fn records(&self) -> impl DoubleEndedIterator<Item = &StateTransitionRecord<S>> {
+        self.records.iter()
+    }
+
+    /// Returns the latest recorded transition.
+    pub fn last(&self) -> Option<&StateTransitionRecord<S>> {
+        self.records.back()
+    }
+
+    /// Returns the number of recorded transitions.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns `true` if no transition is recorded.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Returns the maximum number of recorded transitions.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Sets the maximum number of recorded transitions, dropping the oldest ones if needed.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.truncate();
+    }
+
+    /// Removes all recorded transitions.
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+
+    /// Queues a transition back to the state exited by the latest recorded transition, and
+    /// returns that state.
+    ///
+    /// Returns `None` and doesn't change `next_state` if there is nothing to undo.
+    pub fn undo(&mut self, next_state: &mut NextState<S>) -> Option<S> {
+        let previous = self.last()?.exited.clone()?;
+        next_state.set(previous.clone());
+        self.pending_undo = Some(previous.clone());
+        Some(previous)
+    }
+
+    fn truncate(&mut self) {
+        while self.records.len() > self.capacity {
+            self.records.pop_front();
+        }
+    }
+}
+
+/// Registers the systems keeping the [`StateTransitionHistory<S>`] up to date.
+///
+/// Runs automatically when using `App` to enable the history, but needs to be called manually in
+/// other situations.
+pub fn register_state_transition_history<S: FreelyMutableState>(schedule: &mut Schedule) {
+    schedule.add_systems(
+        (
+            track_next_state_setter::<S>.before(ApplyStateTransition::<S>::default()),
+            record_state_transitions::<S>.after(ApplyStateTransition::<S>::default()),
+        )
+            .in_set(StateTransitionSteps::DependentTransitions),
+    );
+}
+
+/// Remembers where the pending [`NextState<S>`] was set, before it is applied.
+fn track_next_state_setter<S: FreelyMutableState>(
+    next_state: Option<Res<NextState<S>>>,
+    mut history: ResMut<StateTransitionHistory<S>>,
+) {
+    let Some(next_state) = next_state else {
+        return;
+    };
+    if next_state.is_changed() && matches!(*next_state, NextState::Pending(_)) {
+        history.pending_set_location = Some(next_state.changed_by());
+    }
+}
+
+/// Records the transitions of `S` applied this frame.
+fn record_state_transitions<S: FreelyMutableState>(
+    mut transitions: EventReader<StateTransitionEvent<S>>,
+    mut history: ResMut<StateTransitionHistory<S>>,
+) {
+    let set_location = history.pending_set_location.take();
+    for transition in transitions.read() {
+        let pending_undo = history.pending_undo.take();
+        if pending_undo.is_some() && pending_undo == transition.entered {
+            history.records.pop_back();
+            continue;
+        }
+
+        let set_location = transition.exited.as_ref().and(set_location);
+        history.records.push_back(StateTransitionRecord {
+            exited: transition.exited.clone(),
+            entered: transition.entered.clone(),
+            time: Instant::now(),
+            set_location,
+        });
+    }
+    history.truncate();
+}
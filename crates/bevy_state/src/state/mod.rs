@@ -1,6 +1,8 @@
 mod computed_states;
 mod freely_mutable_state;
+mod history;
 mod resources;
+mod stack;
 mod state_set;
 mod states;
 mod sub_states;
@@ -9,7 +11,9 @@ mod transitions;
 pub use bevy_state_macros::*;
 pub use computed_states::*;
 pub use freely_mutable_state::*;
+pub use history::*;
 pub use resources::*;
+pub use stack::*;
 pub use state_set::*;
 pub use states::*;
 pub use sub_states::*;
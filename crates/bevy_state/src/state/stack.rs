@@ -0,0 +1,351 @@
+use alloc::vec::Vec;
+use core::mem;
+
+use bevy_ecs::{
+    change_detection::DetectChangesMut,
+    event::{Event, EventReader, EventWriter},
+    resource::Resource,
+    schedule::{IntoSystemConfigs, IntoSystemSetConfigs, Schedule, ScheduleLabel},
+    system::{In, IntoSystem, ResMut},
+    world::World,
+};
+use log::warn;
+
+use super::{
+    take_next_state, ApplyStateTransition, EnterSchedules, ExitSchedules, FreelyMutableState,
+    NextState, OnEnter, OnExit, OnTransition, State, StateTransitionEvent, StateTransitionSteps,
+    States, TransitionSchedules,
+};
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_ecs::prelude::ReflectResource;
+
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::prelude::ReflectDefault;
+
+/// The label of a [`Schedule`] that **only** runs whenever the provided state of a state stack
+/// is paused, because another state was pushed on top of it.
+///
+/// [`OnExit`] doesn't run for paused states.
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OnPause<S: FreelyMutableState>(pub S);
+
+/// The label of a [`Schedule`] that **only** runs whenever the provided state of a state stack
+/// is resumed, because the state on top of it was popped.
+///
+/// [`OnEnter`] doesn't run for resumed states.
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OnResume<S: FreelyMutableState>(pub S);
+
+/// The states paused below the current [`State<S>`] of a state stack, from bottom to top.
+///
+/// State stacks are set up with [`init_state_stack`](crate::app::AppExtStates::init_state_stack)
+/// or [`insert_state_stack`](crate::app::AppExtStates::insert_state_stack), and changed through the
+/// [`NextStateStack<S>`] resource. Pushing a state pauses the current one and popping resumes it,
+/// without running their [`OnExit`] and [`OnEnter`] schedules, so that a pause menu can be pushed
+/// on top of gameplay and popped again without tearing the game down.
+///
+/// The top of the stack is the regular [`State<S>`], so [`in_state`](crate::condition::in_state),
+/// computed states and sub states all follow it.
+///
+/// ```
+/// use bevy_state::prelude::*;
+/// use bevy_ecs::prelude::*;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum Screen {
+///     #[default]
+///     Gameplay,
+///     PauseMenu,
+/// }
+///
+/// fn toggle_pause(stack: Res<StateStack<Screen>>, mut next: ResMut<NextStateStack<Screen>>) {
+///     if stack.is_empty() {
+///         next.push(Screen::PauseMenu);
+///     } else {
+///         next.pop();
+///     }
+/// }
+/// ```
+#[derive(Resource, Debug, Clone)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(bevy_reflect::Reflect),
+    reflect(Resource, Default, Debug)
+)]
+pub struct StateStack<S: States> {
+    paused: Vec<S>,
+}
+
+impl<S: States> Default for StateStack<S> {
+    fn default() -> Self {
+        Self { paused: Vec::new() }
+    }
+}
+
+impl<S: States> StateStack<S> {
+    /// Returns the paused states, from bottom to top.
+    pub fn paused(&self) -> &[S] {
+        &self.paused
+    }
+
+    /// Returns `true` if `state` is paused below the current state.
+    pub fn is_paused(&self, state: &S) -> bool {
+        self.paused.contains(state)
+    }
+
+    /// Returns `true` if no state is paused, in which case the stack can't be popped.
+    pub fn is_empty(&self) -> bool {
+        self.paused.is_empty()
+    }
+
+    /// Returns the number of paused states.
+    pub fn len(&self) -> usize {
+        self.paused.len()
+    }
+}
+
+/// The next change of the [`StateStack<S>`].
+///
+/// Only the value of this resource during the [`StateTransition`](crate::state::StateTransition)
+/// schedule matters, so at most one change is applied per transition. When both this resource and
+/// [`NextState<S>`] are pending, this resource wins and the [`NextState<S>`] is discarded.
+/// A pending [`NextState<S>`] on its own replaces the current state.
+#[derive(Resource, Debug, Default, Clone)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(bevy_reflect::Reflect),
+    reflect(Resource, Default, Debug)
+)]
+pub enum NextStateStack<S: FreelyMutableState> {
+    /// No change is pending.
+    #[default]
+    Unchanged,
+    /// Pause the current state, and enter this one on top of it.
+    Push(S),
+    /// Exit the current state, and resume the state below it.
+    Pop,
+    /// Exit the current state, and enter this one in its place.
+    Replace(S),
+}
+
+impl<S: FreelyMutableState> NextStateStack<S> {
+    /// Tentatively push `state` on top of the current state.
+    pub fn push(&mut self, state: S) {
+        *self = Self::Push(state);
+    }
+
+    /// Tentatively pop the current state.
+    pub fn pop(&mut self) {
+        *self = Self::Pop;
+    }
+
+    /// Tentatively replace the current state with `state`.
+    pub fn replace(&mut self, state: S) {
+        *self = Self::Replace(state);
+    }
+
+    /// Remove any pending changes to the [`StateStack<S>`].
+    pub fn reset(&mut self) {
+        *self = Self::Unchanged;
+    }
+}
+
+/// Event sent when the [`StateStack<S>`] changes.
+///
+/// A [`StateTransitionEvent<S>`] between the previous and the new current state is sent alongside it.
+#[derive(Debug, Clone, PartialEq, Eq, Event)]
+pub enum StateStackTransitionEvent<S: FreelyMutableState> {
+    /// A state was pushed on top of the current state.
+    Pushed {
+        /// The state that was paused.
+        paused: S,
+        /// The state being entered.
+        entered: S,
+    },
+    /// The current state was popped.
+    Popped {
+        /// The state being exited.
+        exited: S,
+        /// The state that was resumed.
+        resumed: S,
+    },
+    /// The current state was replaced.
+    Replaced {
+        /// The state being exited.
+        exited: S,
+        /// The state being entered.
+        entered: S,
+    },
+}
+
+/// Registers the systems applying the changes of the [`StateStack<S>`] and running its transition
+/// schedules, in place of [`FreelyMutableState::register_state`].
+///
+/// Runs automatically when using `App` to insert state stacks, but needs to be called manually in
+/// other situations.
+pub fn register_state_stack<S: FreelyMutableState>(schedule: &mut Schedule) {
+    schedule.configure_sets((
+        ApplyStateTransition::<S>::default().in_set(StateTransitionSteps::DependentTransitions),
+        ExitSchedules::<S>::default().in_set(StateTransitionSteps::ExitSchedules),
+        TransitionSchedules::<S>::default().in_set(StateTransitionSteps::TransitionSchedules),
+        EnterSchedules::<S>::default().in_set(StateTransitionSteps::EnterSchedules),
+    ));
+
+    schedule
+        .add_systems(apply_state_stack_transition::<S>.in_set(ApplyStateTransition::<S>::default()))
+        .add_systems(
+            last_stack_transition::<S>
+                .pipe(run_stack_exit::<S>)
+                .in_set(ExitSchedules::<S>::default()),
+        )
+        .add_systems(
+            last_stack_transition::<S>
+                .pipe(run_stack_transition::<S>)
+                .in_set(TransitionSchedules::<S>::default()),
+        )
+        .add_systems(
+            last_stack_transition::<S>
+                .pipe(run_stack_enter::<S>)
+                .in_set(EnterSchedules::<S>::default()),
+        );
+}
+
+fn apply_state_stack_transition<S: FreelyMutableState>(
+    mut transition_events: EventWriter<StateTransitionEvent<S>>,
+    mut stack_events: EventWriter<StateStackTransitionEvent<S>>,
+    current_state: Option<ResMut<State<S>>>,
+    stack: Option<ResMut<StateStack<S>>>,
+    next_stack: Option<ResMut<NextStateStack<S>>>,
+    next_state: Option<ResMut<NextState<S>>>,
+) {
+    let next_state = take_next_state(next_state);
+    let next = match next_stack {
+        Some(mut next_stack) => match mem::take(next_stack.bypass_change_detection()) {
+            NextStateStack::Unchanged => next_state.map(NextStateStack::Replace),
+            next => {
+                next_stack.set_changed();
+                Some(next)
+            }
+        },
+        None => next_state.map(NextStateStack::Replace),
+    };
+    let (Some(next), Some(mut current_state), Some(mut stack)) = (next, current_state, stack)
+    else {
+        return;
+    };
+
+    let event = match next {
+        NextStateStack::Unchanged => return,
+        NextStateStack::Push(entered) => {
+            let paused = mem::replace(&mut current_state.0, entered.clone());
+            stack.paused.push(paused.clone());
+            StateStackTransitionEvent::Pushed { paused, entered }
+        }
+        NextStateStack::Pop => {
+            let Some(resumed) = stack.paused.pop() else {
+                warn!(
+                    "Tried to pop the state stack of {}, but no state is paused.",
+                    core::any::type_name::<S>()
+                );
+                return;
+            };
+            let exited = mem::replace(&mut current_state.0, resumed.clone());
+            StateStackTransitionEvent::Popped { exited, resumed }
+        }
+        NextStateStack::Replace(entered) => {
+            let exited = mem::replace(&mut current_state.0, entered.clone());
+            StateStackTransitionEvent::Replaced { exited, entered }
+        }
+    };
+
+    let (exited, entered) = match &event {
+        StateStackTransitionEvent::Pushed { paused, entered } => (paused, entered),
+        StateStackTransitionEvent::Popped { exited, resumed } => (exited, resumed),
+        StateStackTransitionEvent::Replaced { exited, entered } => (exited, entered),
+    };
+    transition_events.send(StateTransitionEvent {
+        exited: Some(exited.clone()),
+        entered: Some(entered.clone()),
+    });
+    stack_events.send(event);
+}
+
+/// The last change of a state stack, if any.
+///
+/// The [`StateTransitionEvent<S>`] is only used when no [`StateStackTransitionEvent<S>`] was sent,
+/// to enter the initial state.
+type LastStackTransition<S> = (
+    Option<StateTransitionEvent<S>>,
+    Option<StateStackTransitionEvent<S>>,
+);
+
+fn last_stack_transition<S: FreelyMutableState>(
+    mut transitions: EventReader<StateTransitionEvent<S>>,
+    mut stack_transitions: EventReader<StateStackTransitionEvent<S>>,
+) -> LastStackTransition<S> {
+    (
+        transitions.read().last().cloned(),
+        stack_transitions.read().last().cloned(),
+    )
+}
+
+fn run_stack_exit<S: FreelyMutableState>(
+    In((_, stack_transition)): In<LastStackTransition<S>>,
+    world: &mut World,
+) {
+    match stack_transition {
+        Some(StateStackTransitionEvent::Pushed { paused, entered }) if paused != entered => {
+            let _ = world.try_run_schedule(OnPause(paused));
+        }
+        Some(
+            StateStackTransitionEvent::Popped {
+                exited,
+                resumed: entered,
+            }
+            | StateStackTransitionEvent::Replaced { exited, entered },
+        ) if exited != entered => {
+            let _ = world.try_run_schedule(OnExit(exited));
+        }
+        _ => {}
+    }
+}
+
+fn run_stack_transition<S: FreelyMutableState>(
+    In((_, stack_transition)): In<LastStackTransition<S>>,
+    world: &mut World,
+) {
+    if let Some(StateStackTransitionEvent::Replaced { exited, entered }) = stack_transition {
+        let _ = world.try_run_schedule(OnTransition { exited, entered });
+    }
+}
+
+fn run_stack_enter<S: FreelyMutableState>(
+    In((transition, stack_transition)): In<LastStackTransition<S>>,
+    world: &mut World,
+) {
+    match stack_transition {
+        Some(
+            StateStackTransitionEvent::Pushed {
+                paused: exited,
+                entered,
+            }
+            | StateStackTransitionEvent::Replaced { exited, entered },
+        ) if exited != entered => {
+            let _ = world.try_run_schedule(OnEnter(entered));
+        }
+        Some(StateStackTransitionEvent::Popped { exited, resumed }) if exited != resumed => {
+            let _ = world.try_run_schedule(OnResume(resumed));
+        }
+        Some(_) => {}
+        None => {
+            if let Some(StateTransitionEvent {
+                exited: None,
+                entered: Some(entered),
+            }) = transition
+            {
+                let _ = world.try_run_schedule(OnEnter(entered));
+            }
+        }
+    }
+}
@@ -4,12 +4,12 @@ use bevy_ecs::{
     component::Component,
     entity::Entity,
     event::EventReader,
-    system::{Commands, Query},
+    system::{Commands, Query, Res},
 };
 #[cfg(feature = "bevy_reflect")]
 use bevy_reflect::prelude::*;
 
-use crate::state::{StateTransitionEvent, States};
+use crate::state::{StateStack, StateTransitionEvent, States};
 
 /// Entities marked with this component will be removed
 /// when the world's state of the matching type no longer matches the supplied value.
@@ -67,9 +67,12 @@ where
 
 /// Removes entities marked with [`StateScoped<S>`]
 /// when their state no longer matches the world state.
+///
+/// Entities whose state is paused in the [`StateStack<S>`] are kept until it is popped.
 pub fn clear_state_scoped_entities<S: States>(
     mut commands: Commands,
     mut transitions: EventReader<StateTransitionEvent<S>>,
+    stack: Option<Res<StateStack<S>>>,
     query: Query<(Entity, &StateScoped<S>)>,
 ) {
     // We use the latest event, because state machine internals generate at most 1
@@ -84,6 +87,9 @@ pub fn clear_state_scoped_entities<S: States>(
     let Some(exited) = &transition.exited else {
         return;
     };
+    if stack.is_some_and(|stack| stack.is_paused(exited)) {
+        return;
+    }
     for (entity, binding) in &query {
         if binding.0 == *exited {
             commands.entity(entity).despawn();